@@ -1,19 +1,38 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use crate::domain::model::{FrameworkEntity, SyncInfo, FRAMEWORK_CATEGORIES};
+use crate::domain::model::{EntityMatch, EntitySearchHit, EntitySearchResult, FrameworkEntity, ReleaseInfo, SyncInfo, SyncPreview, SyncProgress, FRAMEWORK_CATEGORIES};
+use crate::domain::AiddError;
 use crate::domain::ports::inbound::FrameworkPort;
-use crate::domain::ports::outbound::{FileSystemPort, ProjectRepository};
+use crate::domain::ports::outbound::{unix_ms_from_iso, Clock, FileSystemPort, ProjectRepository};
 use crate::infrastructure::integrations::adapter_trait::resolve_content_dir;
+use crate::infrastructure::sync::github_adapter::{sha256_hex, RELEASE_CATEGORIES};
 use crate::infrastructure::sync::GitHubAdapter;
 
 // FileSystemPort is used both as Arc<dyn ...> in the struct and as &dyn ... in free functions.
 
+/// Seconds between background update checks when the caller does not pick an interval.
+pub const DEFAULT_AUTO_SYNC_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// First retry delay after a failed background check; doubles per failure up to the interval.
+const AUTO_SYNC_RETRY_SECS: u64 = 60;
+
+/// Callback invoked when the scheduler finds (or installs) a newer framework release.
+pub type UpdateListener = Arc<dyn Fn(&SyncInfo) + Send + Sync>;
+
 pub struct FrameworkService {
     framework_path: PathBuf,
     repository: Arc<dyn ProjectRepository>,
     fs: Arc<dyn FileSystemPort>,
+    clock: Arc<dyn Clock>,
     github: GitHubAdapter,
+    /// Dropping the sender stops the running auto-sync thread.
+    auto_sync_task: Mutex<Option<Sender<()>>>,
+    /// Held for the whole of a sync so two never stage into `framework.tmp` at once.
+    sync_lock: tokio::sync::Mutex<()>,
 }
 
 impl FrameworkService {
@@ -21,27 +40,31 @@ impl FrameworkService {
         aidd_home: &Path,
         repository: Arc<dyn ProjectRepository>,
         fs: Arc<dyn FileSystemPort>,
-    ) -> Result<Self, String> {
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self, AiddError> {
         let framework_path = aidd_home.join("framework");
 
         // Ensure framework directory + category subdirs exist
-        fs.create_dir_all(&framework_path.to_string_lossy())?;
+        fs.create_dir_all(&framework_path.to_string_lossy()).map_err(AiddError::Io)?;
         for cat in FRAMEWORK_CATEGORIES {
-            fs.create_dir_all(&framework_path.join(cat).to_string_lossy())?;
+            fs.create_dir_all(&framework_path.join(cat).to_string_lossy()).map_err(AiddError::Io)?;
         }
 
         Ok(Self {
             framework_path,
             repository,
             fs,
+            clock,
             github: GitHubAdapter::new(),
+            auto_sync_task: Mutex::new(None),
+            sync_lock: tokio::sync::Mutex::new(()),
         })
     }
 
     // ── Sync methods (async, called from Tauri async commands) ──────────
 
     /// Check for framework updates without downloading.
-    pub async fn check_for_updates(&self) -> Result<SyncInfo, String> {
+    pub async fn check_for_updates(&self) -> Result<SyncInfo, AiddError> {
         let data = self.repository.load()?;
 
         let (latest_version, changelog) = self.github.fetch_latest_release().await?;
@@ -50,7 +73,7 @@ impl FrameworkService {
             None => true,
         };
 
-        let now = format!("{:?}", std::time::SystemTime::now());
+        let now = self.clock.now_iso();
 
         // Persist the check timestamp
         let mut data = data;
@@ -64,11 +87,40 @@ impl FrameworkService {
             auto_sync: data.auto_sync,
             last_check: Some(now),
             changelog,
+            pruned: Vec::new(),
         })
     }
 
-    /// Download and install a framework version (or latest if None).
-    pub async fn sync_framework(&self, version: Option<String>) -> Result<SyncInfo, String> {
+    /// Published framework versions, newest first, for choosing one to pin.
+    pub async fn list_available_versions(&self, include_prereleases: bool) -> Result<Vec<ReleaseInfo>, AiddError> {
+        self.github.list_releases(include_prereleases).await
+    }
+
+    /// Download and install a framework version (or latest if None), reporting
+    /// download and extraction progress to `on_progress`.
+    ///
+    /// With `prune`, files the previously installed release shipped but the new
+    /// one doesn't are moved to `framework.orphaned/`. Files no release shipped
+    /// (local additions) and `README.md` files are never pruned.
+    ///
+    /// Waits for any sync already in progress to finish first.
+    pub async fn sync_framework(
+        &self,
+        version: Option<String>,
+        prune: bool,
+        on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+    ) -> Result<SyncInfo, AiddError> {
+        let _guard = self.sync_lock.lock().await;
+        self.sync_framework_locked(version, prune, on_progress).await
+    }
+
+    /// `sync_framework` body; the caller must hold `sync_lock`.
+    async fn sync_framework_locked(
+        &self,
+        version: Option<String>,
+        prune: bool,
+        on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+    ) -> Result<SyncInfo, AiddError> {
         // Determine target version
         let (target_version, changelog) = match version {
             Some(v) => (v, None),
@@ -78,20 +130,23 @@ impl FrameworkService {
             }
         };
 
-        // Download and extract
-        self.github
-            .download_and_extract(&target_version, &self.framework_path)
-            .await?;
-
-        // Ensure category dirs still exist after extraction
-        for cat in FRAMEWORK_CATEGORIES {
-            self.fs
-                .create_dir_all(&self.framework_path.join(cat).to_string_lossy())?;
-        }
+        // Stage the release next to the live framework and only swap it in once complete
+        let staging = self.framework_path.with_extension("tmp");
+        let backup = self.framework_path.with_extension("bak");
+        remove_dir_if_exists(&staging)?;
+        let pruned = match self.stage_release(&target_version, &staging, prune, on_progress).await {
+            Ok(pruned) => pruned,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+        };
+        swap_dirs(&self.framework_path, &staging, &backup)?;
 
         // Update persisted version
         let mut data = self.repository.load()?;
-        let now = format!("{:?}", std::time::SystemTime::now());
+        let now = self.clock.now_iso();
+        data.previous_framework_version = data.framework_version.take();
         data.framework_version = Some(target_version.clone());
         data.last_sync_check = Some(now.clone());
         self.repository.save(&data)?;
@@ -103,18 +158,193 @@ impl FrameworkService {
             auto_sync: data.auto_sync,
             last_check: Some(now),
             changelog,
+            pruned,
         })
     }
 
+    /// Download a version (or latest if None) into a scratch directory and report
+    /// which framework files it would add, modify or no longer ship. Nothing
+    /// under the live framework or the persisted version is touched.
+    pub async fn preview_sync(&self, version: Option<String>) -> Result<SyncPreview, AiddError> {
+        let target_version = match version {
+            Some(v) => v,
+            None => self.github.fetch_latest_release().await?.0,
+        };
+
+        let scratch = self.framework_path.with_extension("preview");
+        remove_dir_if_exists(&scratch)?;
+        let extracted = self
+            .github
+            .download_and_extract(&target_version, &scratch, None, &|_| {})
+            .await;
+        let preview = extracted.and_then(|_| diff_trees(&self.framework_path, &scratch));
+        let _ = std::fs::remove_dir_all(&scratch);
+
+        Ok(SyncPreview { target_version, ..preview? })
+    }
+
+    /// Restore the framework from `framework.bak/`, replacing the current install.
+    pub fn rollback_framework(&self) -> Result<SyncInfo, AiddError> {
+        let backup = self.framework_path.with_extension("bak");
+        if !backup.is_dir() {
+            return Err(AiddError::Other("No framework backup available to roll back to".to_string()));
+        }
+
+        let staging = self.framework_path.with_extension("tmp");
+        remove_dir_if_exists(&staging)?;
+        if self.framework_path.exists() {
+            std::fs::rename(&self.framework_path, &staging)
+                .map_err(|e| AiddError::Io(format!("Failed to move current framework aside: {}", e)))?;
+        }
+        if let Err(e) = std::fs::rename(&backup, &self.framework_path) {
+            let _ = std::fs::rename(&staging, &self.framework_path);
+            return Err(AiddError::Io(format!("Failed to restore framework backup: {}", e)));
+        }
+        let _ = std::fs::remove_dir_all(&staging);
+
+        let mut data = self.repository.load()?;
+        data.framework_version = data.previous_framework_version.take();
+        self.repository.save(&data)?;
+
+        self.get_sync_status()
+    }
+
+    /// Build a complete framework tree in `staging`: the current install plus the
+    /// release on top, minus orphaned release files when `prune` is set. Returns
+    /// the pruned paths.
+    async fn stage_release(
+        &self,
+        version: &str,
+        staging: &Path,
+        prune: bool,
+        on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+    ) -> Result<Vec<String>, AiddError> {
+        // Extraction never deletes, so start from the live tree to keep local-only entities
+        copy_dir_recursive(&self.framework_path, staging, &*self.fs)?;
+        let previous = read_release_manifest(staging);
+
+        let shipped: BTreeSet<String> = self
+            .github
+            .download_and_extract(version, staging, None, on_progress)
+            .await?
+            .into_iter()
+            .collect();
+
+        // Without a manifest there's no telling upstream files from local ones, so skip
+        let pruned = match previous {
+            Some(previous) if prune => {
+                let orphans = orphaned_files(&previous, &shipped);
+                quarantine_files(staging, &self.framework_path.with_extension("orphaned"), &orphans)?;
+                orphans
+            }
+            _ => Vec::new(),
+        };
+        write_release_manifest(staging, &shipped)?;
+
+        for cat in FRAMEWORK_CATEGORIES {
+            self.fs
+                .create_dir_all(&staging.join(cat).to_string_lossy())
+                .map_err(AiddError::Io)?;
+        }
+        Ok(pruned)
+    }
+
     /// Set auto-sync preference.
-    pub fn set_auto_sync(&self, enabled: bool) -> Result<(), String> {
+    pub fn set_auto_sync(&self, enabled: bool) -> Result<(), AiddError> {
         let mut data = self.repository.load()?;
         data.auto_sync = enabled;
-        self.repository.save(&data)
+        self.repository.save(&data).map_err(AiddError::from)
+    }
+
+    /// Set whether the auto-sync scheduler installs updates it finds.
+    pub fn set_auto_install(&self, enabled: bool) -> Result<(), AiddError> {
+        let mut data = self.repository.load()?;
+        data.auto_install = enabled;
+        self.repository.save(&data).map_err(AiddError::from)
+    }
+
+    /// Check for updates every `interval_secs` in the background while the
+    /// persisted `auto_sync` flag is on, calling `on_update` when a newer release
+    /// is available and again after installing it when `auto_install` is set.
+    ///
+    /// The first check waits out whatever remains of the interval since
+    /// `last_sync_check`, so restarting the Hub doesn't re-check immediately.
+    /// Transient failures (offline, rate limited, ...) retry with exponential backoff. Restarting
+    /// replaces any running scheduler.
+    pub fn start_auto_sync(self: &Arc<Self>, interval_secs: u64, on_update: UpdateListener) -> Result<(), AiddError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| AiddError::Other(format!("Failed to start auto-sync runtime: {}", e)))?;
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        *self.auto_sync_task.lock().map_err(|e| AiddError::Other(e.to_string()))? = Some(stop_tx);
+
+        let interval = Duration::from_secs(interval_secs.max(AUTO_SYNC_RETRY_SECS));
+        let last_check = self.repository.load()?.last_sync_check;
+        let mut wait = auto_sync_delay(last_check.as_deref().and_then(unix_ms_from_iso), self.clock.now_unix_ms(), interval);
+        let service = Arc::downgrade(self);
+        std::thread::spawn(move || {
+            let mut failures = 0;
+            loop {
+                match stop_rx.recv_timeout(wait) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    _ => break,
+                }
+                let Some(service) = service.upgrade() else { break };
+                wait = match runtime.block_on(service.auto_sync_tick(on_update.as_ref())) {
+                    Ok(()) => {
+                        failures = 0;
+                        interval
+                    }
+                    Err(e) => {
+                        eprintln!("[framework] Background update check failed: {}", e);
+                        // Retrying sooner only helps when the failure was transient
+                        failures += 1;
+                        if e.is_transient() { failure_backoff(failures, interval) } else { interval }
+                    }
+                };
+            }
+        });
+        Ok(())
+    }
+
+    /// Stop the auto-sync scheduler. Returns `false` when none was running.
+    pub fn stop_auto_sync(&self) -> Result<bool, AiddError> {
+        Ok(self.auto_sync_task.lock().map_err(|e| AiddError::Other(e.to_string()))?.take().is_some())
+    }
+
+    /// One scheduler run. Re-reads the flags so toggling them applies without a restart.
+    /// Skipped entirely while another sync is in progress.
+    async fn auto_sync_tick(&self, on_update: &(dyn Fn(&SyncInfo) + Send + Sync)) -> Result<(), AiddError> {
+        let data = self.repository.load()?;
+        if !data.auto_sync {
+            return Ok(());
+        }
+        let Ok(_guard) = self.sync_lock.try_lock() else {
+            eprintln!("[framework] Sync already in progress; skipping background update check");
+            return Ok(());
+        };
+        let info = self.check_for_updates().await?;
+        if !info.update_available {
+            return Ok(());
+        }
+        on_update(&info);
+        if data.auto_install {
+            let installed = self.sync_framework_locked(None, false, &|_| {}).await?;
+            on_update(&installed);
+        }
+        Ok(())
+    }
+
+    /// `<framework>/<category>/<name>.md`, after validating both parts.
+    fn entity_path(&self, category: &str, name: &str) -> Result<PathBuf, AiddError> {
+        validate_category(category)?;
+        let name = sanitize_entity_name(name)?;
+        Ok(self.framework_path.join(category).join(format!("{}.md", name)))
     }
 
     /// Get current sync status without hitting the network.
-    pub fn get_sync_status(&self) -> Result<SyncInfo, String> {
+    pub fn get_sync_status(&self) -> Result<SyncInfo, AiddError> {
         let data = self.repository.load()?;
         Ok(SyncInfo {
             current_version: data.framework_version,
@@ -123,12 +353,13 @@ impl FrameworkService {
             auto_sync: data.auto_sync,
             last_check: data.last_sync_check,
             changelog: None,
+            pruned: Vec::new(),
         })
     }
 }
 
 impl FrameworkPort for FrameworkService {
-    fn get_version(&self) -> Result<Option<String>, String> {
+    fn get_version(&self) -> Result<Option<String>, AiddError> {
         let data = self.repository.load()?;
         Ok(data.framework_version)
     }
@@ -137,15 +368,16 @@ impl FrameworkPort for FrameworkService {
         self.framework_path.to_string_lossy().to_string()
     }
 
-    fn list_entities(&self, category: &str) -> Result<Vec<FrameworkEntity>, String> {
-        self.list_entities_with_project(category, None)
+    fn list_entities(&self, category: &str) -> Result<Vec<FrameworkEntity>, AiddError> {
+        self.list_entities_with_project(category, None, None)
     }
 
     fn list_entities_with_project(
         &self,
         category: &str,
         project_path: Option<&str>,
-    ) -> Result<Vec<FrameworkEntity>, String> {
+        filter: Option<&HashMap<String, String>>,
+    ) -> Result<Vec<FrameworkEntity>, AiddError> {
         validate_category(category)?;
 
         let mut result = Vec::new();
@@ -175,23 +407,45 @@ impl FrameworkPort for FrameworkService {
             );
         }
 
+        // Filter after the dedup so a project entity never resurfaces behind a filtered-out global one
+        if let Some(filter) = filter {
+            result.retain(|entity| frontmatter_matches(&entity.frontmatter, filter));
+        }
         result.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(result)
     }
 
-    fn read_entity(&self, category: &str, name: &str) -> Result<FrameworkEntity, String> {
-        validate_category(category)?;
+    fn search_entities(
+        &self,
+        query: &str,
+        categories: Option<&[String]>,
+        project_path: Option<&str>,
+    ) -> Result<EntitySearchResult, AiddError> {
+        if query.trim().is_empty() {
+            return Err(AiddError::Other("Search query must not be empty".to_string()));
+        }
 
-        let file_path = self.framework_path.join(category).join(format!("{}.md", name));
-        let content = self.fs.read_to_string(&file_path.to_string_lossy())?;
+        let categories: Vec<&str> = match categories {
+            Some(list) => list.iter().map(String::as_str).collect(),
+            None => FRAMEWORK_CATEGORIES.to_vec(),
+        };
+        let mut entities = Vec::new();
+        for category in categories {
+            entities.extend(self.list_entities_with_project(category, project_path, None)?);
+        }
+        Ok(search_in(&entities, query, MAX_SEARCH_HITS))
+    }
+
+    fn read_entity(&self, category: &str, name: &str) -> Result<FrameworkEntity, AiddError> {
+        let file_path = self.entity_path(category, name)?;
+        let content = self
+            .fs
+            .read_to_string(&file_path.to_string_lossy())
+            .map_err(AiddError::Io)?;
 
         let (frontmatter, body) = parse_frontmatter(&content);
 
-        let last_modified = std::fs::metadata(&file_path)
-            .ok()
-            .and_then(|m| m.modified().ok())
-            .map(|t| format!("{:?}", t))
-            .unwrap_or_default();
+        let last_modified = entity_stamp(&*self.fs, &file_path, &content);
 
         Ok(FrameworkEntity {
             name: name.to_string(),
@@ -204,19 +458,184 @@ impl FrameworkPort for FrameworkService {
         })
     }
 
-    fn write_entity(&self, category: &str, name: &str, content: &str) -> Result<(), String> {
-        validate_category(category)?;
+    fn write_entity(&self, category: &str, name: &str, content: &str) -> Result<(), AiddError> {
+        self.write_entity_if_unchanged(category, name, content, None)
+    }
 
-        let file_path = self.framework_path.join(category).join(format!("{}.md", name));
-        self.fs.write(&file_path.to_string_lossy(), content)
+    fn write_entity_if_unchanged(
+        &self,
+        category: &str,
+        name: &str,
+        content: &str,
+        expected_last_modified: Option<&str>,
+    ) -> Result<(), AiddError> {
+        let file_path = self.entity_path(category, name)?;
+        let path = file_path.to_string_lossy();
+        if let Some(expected) = expected_last_modified {
+            // A file deleted since it was loaded has an empty stamp, which also conflicts
+            let current = self.fs.read_to_string(&path).ok();
+            let stamp = current
+                .as_deref()
+                .map(|content| entity_stamp(&*self.fs, &file_path, content))
+                .unwrap_or_default();
+            if stamp != expected {
+                return Err(AiddError::Conflict {
+                    path: path.to_string(),
+                    current_content: current.unwrap_or_default(),
+                });
+            }
+        }
+        self.fs.write(&path, content).map_err(AiddError::Io)
+    }
+
+    fn write_entity_parts(
+        &self,
+        category: &str,
+        name: &str,
+        frontmatter: serde_json::Value,
+        body: &str,
+    ) -> Result<(), AiddError> {
+        let serde_json::Value::Object(frontmatter) = frontmatter else {
+            return Err(AiddError::Other("Frontmatter must be a JSON object".to_string()));
+        };
+        let file_path = self.entity_path(category, name)?;
+        let existing = self.fs.read_to_string(&file_path.to_string_lossy()).ok();
+        let content = render_entity(existing.as_deref(), &frontmatter, body);
+        self.write_entity(category, name, &content)
+    }
+
+    fn delete_entity(&self, category: &str, name: &str) -> Result<(), AiddError> {
+        self.delete_entity_with_project(category, name, None)
     }
 
-    fn delete_entity(&self, category: &str, name: &str) -> Result<(), String> {
+    fn delete_entity_with_project(
+        &self,
+        category: &str,
+        name: &str,
+        project_path: Option<&str>,
+    ) -> Result<(), AiddError> {
+        let file_path = match project_path {
+            Some(proj) => {
+                validate_category(category)?;
+                let name = sanitize_entity_name(name)?;
+                resolve_content_dir(Path::new(proj), category).join(format!("{}.md", name))
+            }
+            None => self.entity_path(category, name)?,
+        };
+        self.fs
+            .remove_file(&file_path.to_string_lossy())
+            .map_err(AiddError::Io)
+    }
+
+    fn duplicate_entity(
+        &self,
+        category: &str,
+        src_name: &str,
+        dst_name: &str,
+        overwrite: bool,
+    ) -> Result<(), AiddError> {
         validate_category(category)?;
+        let category_dir = self.framework_path.join(category);
+        duplicate_entity_in(&category_dir, category, src_name, dst_name, overwrite, &*self.fs)
+    }
+}
+
+/// Copy `src_name` to `dst_name` within one category directory. The copy's
+/// frontmatter `name:`, if it has one, is set to the new name.
+fn duplicate_entity_in(
+    category_dir: &Path,
+    category: &str,
+    src_name: &str,
+    dst_name: &str,
+    overwrite: bool,
+    fs: &dyn FileSystemPort,
+) -> Result<(), AiddError> {
+    let src_name = &sanitize_entity_name(src_name)?;
+    let dst_name = &sanitize_entity_name(dst_name)?;
+
+    let src = locate_entity(category_dir, src_name, fs)
+        .ok_or_else(|| AiddError::Io(format!("Entity not found: {}/{}", category, src_name)))?;
+    if let Some(existing) = locate_entity(category_dir, dst_name, fs) {
+        if !overwrite {
+            return Err(AiddError::Other(format!("Entity already exists: {}/{}", category, dst_name)));
+        }
+        let removed = match existing {
+            EntityLocation::File(path) => fs.remove_file(&path.to_string_lossy()),
+            EntityLocation::SkillDir(path) => fs.remove_dir_all(&path.to_string_lossy()),
+        };
+        removed.map_err(AiddError::Io)?;
+    }
+
+    // Nested knowledge names are paths; the frontmatter holds only the last segment
+    let new_name = dst_name.rsplit('/').next().unwrap_or(dst_name);
+    match src {
+        EntityLocation::File(path) => {
+            let content = fs.read_to_string(&path.to_string_lossy()).map_err(AiddError::Io)?;
+            let dst = category_dir.join(format!("{}.md", dst_name));
+            if let Some(parent) = dst.parent() {
+                fs.create_dir_all(&parent.to_string_lossy()).map_err(AiddError::Io)?;
+            }
+            fs.write(&dst.to_string_lossy(), &renamed_entity(&content, new_name)).map_err(AiddError::Io)
+        }
+        EntityLocation::SkillDir(path) => {
+            let dst = category_dir.join(dst_name);
+            copy_dir_recursive(&path, &dst, fs)?;
+            let skill = dst.join("SKILL.md").to_string_lossy().to_string();
+            let content = fs.read_to_string(&skill).map_err(AiddError::Io)?;
+            fs.write(&skill, &renamed_entity(&content, new_name)).map_err(AiddError::Io)
+        }
+    }
+}
+
+/// `content` with its frontmatter `name:` set to `name`. Content without a
+/// `name:` key is returned unchanged.
+fn renamed_entity(content: &str, name: &str) -> String {
+    let (frontmatter, body) = parse_frontmatter(content);
+    match frontmatter {
+        serde_json::Value::Object(mut map) if map.contains_key("name") => {
+            map.insert("name".to_string(), serde_json::Value::String(name.to_string()));
+            render_entity(Some(content), &map, &body)
+        }
+        _ => content.to_string(),
+    }
+}
 
-        let file_path = self.framework_path.join(category).join(format!("{}.md", name));
-        std::fs::remove_file(&file_path)
-            .map_err(|e| format!("Failed to delete {}: {}", file_path.display(), e))
+/// Where an entity lives on disk: a `<name>.md` file (possibly nested, as in
+/// knowledge), or a skill directory holding `SKILL.md` plus any assets.
+enum EntityLocation {
+    File(PathBuf),
+    SkillDir(PathBuf),
+}
+
+fn locate_entity(category_dir: &Path, name: &str, fs: &dyn FileSystemPort) -> Option<EntityLocation> {
+    let is_file = |path: &Path| {
+        let path = path.to_string_lossy();
+        fs.exists(&path) && !fs.is_dir(&path)
+    };
+    let file = category_dir.join(format!("{}.md", name));
+    if is_file(&file) {
+        return Some(EntityLocation::File(file));
+    }
+    let dir = category_dir.join(name);
+    is_file(&dir.join("SKILL.md")).then_some(EntityLocation::SkillDir(dir))
+}
+
+/// Entity names are relative paths (`a/b` for nested knowledge). Normalizes `\`
+/// to `/` and rejects anything that could escape the category directory: empty,
+/// `.` or `..` segments, and absolute or drive-prefixed paths.
+pub(crate) fn sanitize_entity_name(name: &str) -> Result<String, AiddError> {
+    let normalized = name.trim().replace('\\', "/");
+    let valid = !normalized.is_empty()
+        && normalized
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains(':'))
+        && Path::new(&normalized)
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if valid {
+        Ok(normalized)
+    } else {
+        Err(AiddError::Other(format!("Invalid entity name: {}", name)))
     }
 }
 
@@ -236,24 +655,21 @@ fn scan_directory(
         return;
     }
 
-    let entries = match std::fs::read_dir(dir) {
+    let entries = match fs.read_dir(&dir.to_string_lossy()) {
         Ok(e) => e,
         Err(_) => return,
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let file_name = match path.file_name() {
-            Some(n) => n.to_string_lossy().to_string(),
-            None => continue,
-        };
+    for entry in entries {
+        let path = PathBuf::from(&entry.path);
+        let file_name = entry.name;
 
         // Skip hidden files/dirs and README.md
         if file_name.starts_with('.') || file_name == "README.md" {
             continue;
         }
 
-        if path.is_file() && file_name.ends_with(".md") {
+        if !entry.is_dir && file_name.ends_with(".md") {
             // Flat .md file (rules, workflows, templates, spec, or a top-level skill file)
             let name = file_name.trim_end_matches(".md").to_string();
             if seen.contains(&name) {
@@ -263,12 +679,12 @@ fn scan_directory(
                 seen.insert(name);
                 result.push(entity);
             }
-        } else if path.is_dir() {
+        } else if entry.is_dir {
             match category {
                 "skills" => {
                     // Skills: look for SKILL.md inside the subdir
                     let skill_md = path.join("SKILL.md");
-                    if skill_md.exists() {
+                    if fs.exists(&skill_md.to_string_lossy()) {
                         let name = file_name.clone();
                         if seen.contains(&name) {
                             continue;
@@ -305,23 +721,20 @@ fn scan_knowledge_subdir(
     result: &mut Vec<FrameworkEntity>,
     seen: &mut std::collections::HashSet<String>,
 ) {
-    let entries = match std::fs::read_dir(dir) {
+    let entries = match fs.read_dir(&dir.to_string_lossy()) {
         Ok(e) => e,
         Err(_) => return,
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let file_name = match path.file_name() {
-            Some(n) => n.to_string_lossy().to_string(),
-            None => continue,
-        };
+    for entry in entries {
+        let path = PathBuf::from(&entry.path);
+        let file_name = entry.name;
 
         if file_name.starts_with('.') || file_name == "README.md" {
             continue;
         }
 
-        if path.is_file() && file_name.ends_with(".md") {
+        if !entry.is_dir && file_name.ends_with(".md") {
             let stem = file_name.trim_end_matches(".md");
             let name = format!("{}/{}", prefix, stem);
             if seen.contains(&name) {
@@ -331,7 +744,7 @@ fn scan_knowledge_subdir(
                 seen.insert(name);
                 result.push(entity);
             }
-        } else if path.is_dir() {
+        } else if entry.is_dir {
             let sub_prefix = format!("{}/{}", prefix, file_name);
             scan_knowledge_subdir(&path, &sub_prefix, category, source, fs, result, seen);
         }
@@ -348,23 +761,20 @@ fn scan_workflows_subdir(
     result: &mut Vec<FrameworkEntity>,
     seen: &mut std::collections::HashSet<String>,
 ) {
-    let entries = match std::fs::read_dir(dir) {
+    let entries = match fs.read_dir(&dir.to_string_lossy()) {
         Ok(e) => e,
         Err(_) => return,
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let file_name = match path.file_name() {
-            Some(n) => n.to_string_lossy().to_string(),
-            None => continue,
-        };
+    for entry in entries {
+        let path = PathBuf::from(&entry.path);
+        let file_name = entry.name;
 
         if file_name.starts_with('.') || file_name == "README.md" {
             continue;
         }
 
-        if path.is_file() && file_name.ends_with(".md") {
+        if !entry.is_dir && file_name.ends_with(".md") {
             let stem = file_name.trim_end_matches(".md");
             let name = format!("{}/{}", prefix, stem);
             if seen.contains(&name) {
@@ -378,6 +788,117 @@ fn scan_workflows_subdir(
     }
 }
 
+/// Whether `frontmatter` has every key in `filter` with a matching value
+/// (case-insensitive). List values — JSON arrays or inline `[a, b]` strings —
+/// match when any element does.
+fn frontmatter_matches(frontmatter: &serde_json::Value, filter: &HashMap<String, String>) -> bool {
+    filter.iter().all(|(key, expected)| {
+        let expected = expected.trim();
+        match frontmatter.get(key) {
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .any(|item| item.as_str().is_some_and(|s| s.trim().eq_ignore_ascii_case(expected))),
+            Some(serde_json::Value::String(value)) => {
+                let value = value.trim();
+                match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    Some(list) => list
+                        .split(',')
+                        .any(|item| item.trim().trim_matches(['"', '\'']).eq_ignore_ascii_case(expected)),
+                    None => value.eq_ignore_ascii_case(expected),
+                }
+            }
+            Some(other) => other.to_string().eq_ignore_ascii_case(expected),
+            None => false,
+        }
+    })
+}
+
+/// Most entities a search returns; large knowledge trees are cut off here.
+const MAX_SEARCH_HITS: usize = 200;
+/// Most matches reported per entity.
+const MAX_MATCHES_PER_ENTITY: usize = 5;
+/// Longest snippet, in characters.
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// Entities whose frontmatter values or body contain `query` (case-insensitive),
+/// keeping at most `limit` hits.
+fn search_in(entities: &[FrameworkEntity], query: &str, limit: usize) -> EntitySearchResult {
+    let needle = query.trim().to_lowercase();
+    let mut hits = Vec::new();
+    let mut truncated = false;
+
+    for entity in entities {
+        let mut matches = Vec::new();
+        if let Some(map) = entity.frontmatter.as_object() {
+            for (key, value) in map {
+                let text = value.as_str().map(String::from).unwrap_or_else(|| value.to_string());
+                if text.to_lowercase().contains(&needle) {
+                    matches.push(EntityMatch { field: Some(key.clone()), line: None, snippet: snippet(&text) });
+                }
+            }
+        }
+        for (idx, line) in entity.content.lines().enumerate() {
+            if line.to_lowercase().contains(&needle) {
+                matches.push(EntityMatch { field: None, line: Some(idx + 1), snippet: snippet(line) });
+            }
+        }
+        if matches.is_empty() {
+            continue;
+        }
+        if hits.len() == limit {
+            truncated = true;
+            break;
+        }
+        matches.truncate(MAX_MATCHES_PER_ENTITY);
+        hits.push(EntitySearchHit {
+            name: entity.name.clone(),
+            category: entity.category.clone(),
+            path: entity.path.clone(),
+            source: entity.source.clone(),
+            frontmatter: entity.frontmatter.clone(),
+            matches,
+        });
+    }
+
+    EntitySearchResult { hits, truncated }
+}
+
+fn snippet(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_SNIPPET_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+/// How long the scheduler waits before its first check: the rest of `interval`
+/// since the last recorded check, or nothing when there is none (or it's overdue).
+fn auto_sync_delay(last_check_ms: Option<i64>, now_ms: i64, interval: Duration) -> Duration {
+    let Some(last) = last_check_ms else { return Duration::ZERO };
+    let elapsed = Duration::from_millis(now_ms.saturating_sub(last).max(0) as u64);
+    interval.saturating_sub(elapsed)
+}
+
+/// Retry delay after `failures` consecutive failed checks, capped at `interval`.
+fn failure_backoff(failures: u32, interval: Duration) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    Duration::from_secs(AUTO_SYNC_RETRY_SECS.saturating_mul(1 << exponent)).min(interval)
+}
+
+/// The `last_modified` stamp of an entity file holding `content`: its mtime in
+/// epoch ms plus a content hash, so an edit that keeps the mtime (coarse
+/// timestamps, a restored backup) still changes the stamp.
+fn entity_stamp(fs: &dyn FileSystemPort, path: &Path, content: &str) -> String {
+    let modified_ms = fs
+        .metadata(&path.to_string_lossy())
+        .ok()
+        .and_then(|m| m.modified)
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    format!("{}-{}", modified_ms, &sha256_hex(content.as_bytes())[..16])
+}
+
 /// Read a single .md file into a FrameworkEntity.
 fn read_md_entity(
     path: &Path,
@@ -389,11 +910,7 @@ fn read_md_entity(
     let content = fs.read_to_string(&path.to_string_lossy()).ok()?;
     let (frontmatter, body) = parse_frontmatter(&content);
 
-    let last_modified = std::fs::metadata(path)
-        .ok()
-        .and_then(|m| m.modified().ok())
-        .map(|t| format!("{:?}", t))
-        .unwrap_or_default();
+    let last_modified = entity_stamp(fs, path, &content);
 
     Some(FrameworkEntity {
         name: name.to_string(),
@@ -406,50 +923,632 @@ fn read_md_entity(
     })
 }
 
-fn validate_category(category: &str) -> Result<(), String> {
+fn remove_dir_if_exists(dir: &Path) -> Result<(), AiddError> {
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)
+            .map_err(|e| AiddError::Io(format!("Failed to remove {}: {}", dir.display(), e)))?;
+    }
+    Ok(())
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path, fs: &dyn FileSystemPort) -> Result<(), AiddError> {
+    fs.create_dir_all(&dst.to_string_lossy()).map_err(AiddError::Io)?;
+    let src = src.to_string_lossy();
+    if !fs.is_dir(&src) {
+        return Ok(());
+    }
+
+    for entry in fs.read_dir(&src).map_err(AiddError::Io)? {
+        let to = dst.join(&entry.name);
+        if entry.is_dir {
+            copy_dir_recursive(Path::new(&entry.path), &to, fs)?;
+        } else {
+            fs.copy_file(&entry.path, &to.to_string_lossy()).map_err(AiddError::Io)?;
+        }
+    }
+    Ok(())
+}
+
+/// Written to the framework root after each sync: every file the installed
+/// release shipped, so the next sync can tell upstream deletions from local files.
+const RELEASE_MANIFEST: &str = ".aidd-release.json";
+
+fn read_release_manifest(root: &Path) -> Option<BTreeSet<String>> {
+    let raw = std::fs::read_to_string(root.join(RELEASE_MANIFEST)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_release_manifest(root: &Path, files: &BTreeSet<String>) -> Result<(), AiddError> {
+    let json = serde_json::to_string_pretty(files)
+        .map_err(|e| AiddError::Io(format!("Failed to serialize release manifest: {}", e)))?;
+    std::fs::write(root.join(RELEASE_MANIFEST), json)
+        .map_err(|e| AiddError::Io(format!("Failed to write release manifest: {}", e)))
+}
+
+/// Files a previous release shipped under a managed category that the new one
+/// doesn't. `README.md` files are left alone.
+fn orphaned_files(previous: &BTreeSet<String>, shipped: &BTreeSet<String>) -> Vec<String> {
+    previous
+        .iter()
+        .filter(|path| !shipped.contains(*path))
+        .filter(|path| RELEASE_CATEGORIES.iter().any(|cat| path.starts_with(cat)))
+        .filter(|path| path.rsplit('/').next() != Some("README.md"))
+        .cloned()
+        .collect()
+}
+
+/// Move `files` (relative to `root`) under `quarantine` at the same relative
+/// paths, then drop directories they leave empty.
+fn quarantine_files(root: &Path, quarantine: &Path, files: &[String]) -> Result<(), AiddError> {
+    for relative in files {
+        let from = root.join(relative);
+        if !from.is_file() {
+            continue;
+        }
+        let to = quarantine.join(relative);
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AiddError::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        let _ = std::fs::remove_file(&to);
+        std::fs::rename(&from, &to)
+            .map_err(|e| AiddError::Io(format!("Failed to move {} aside: {}", from.display(), e)))?;
+
+        // remove_dir only succeeds on empty directories, so this stops at the first one still in use
+        for dir in from.ancestors().skip(1).take_while(|dir| *dir != root) {
+            if std::fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// SHA256 of every file under `root`, keyed by `/`-separated relative path.
+fn file_hashes(root: &Path) -> Result<BTreeMap<String, String>, AiddError> {
+    fn walk(root: &Path, dir: &Path, out: &mut BTreeMap<String, String>) -> Result<(), AiddError> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| AiddError::Io(format!("Failed to read {}: {}", dir.display(), e)))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, &path, out)?;
+                continue;
+            }
+            if entry.file_name() == RELEASE_MANIFEST {
+                continue;
+            }
+            let bytes = std::fs::read(&path)
+                .map_err(|e| AiddError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let key: Vec<String> = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+            out.insert(key.join("/"), sha256_hex(&bytes));
+        }
+        Ok(())
+    }
+
+    let mut hashes = BTreeMap::new();
+    if root.is_dir() {
+        walk(root, root, &mut hashes)?;
+    }
+    Ok(hashes)
+}
+
+/// Compare the live framework tree against an extracted release.
+fn diff_trees(current: &Path, release: &Path) -> Result<SyncPreview, AiddError> {
+    let (current, release) = (file_hashes(current)?, file_hashes(release)?);
+    let mut preview = SyncPreview::default();
+    for (path, hash) in &release {
+        match current.get(path) {
+            None => preview.added.push(path.clone()),
+            Some(existing) if existing != hash => preview.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    preview.removed = current.keys().filter(|path| !release.contains_key(*path)).cloned().collect();
+    Ok(preview)
+}
+
+/// Replace `live` with `staging`, keeping the old `live` as `backup`.
+/// If the final rename fails, the previous `live` is put back.
+fn swap_dirs(live: &Path, staging: &Path, backup: &Path) -> Result<(), AiddError> {
+    remove_dir_if_exists(backup)?;
+    if live.exists() {
+        std::fs::rename(live, backup)
+            .map_err(|e| AiddError::Io(format!("Failed to back up current framework: {}", e)))?;
+    }
+    if let Err(e) = std::fs::rename(staging, live) {
+        let _ = std::fs::rename(backup, live);
+        let _ = std::fs::remove_dir_all(staging);
+        return Err(AiddError::Io(format!("Failed to install new framework: {}", e)));
+    }
+    Ok(())
+}
+
+fn validate_category(category: &str) -> Result<(), AiddError> {
     if FRAMEWORK_CATEGORIES.contains(&category) {
         Ok(())
     } else {
-        Err(format!(
-            "Invalid category '{}'. Valid: {:?}",
-            category, FRAMEWORK_CATEGORIES
-        ))
+        Err(AiddError::InvalidCategory {
+            got: category.to_string(),
+            valid: FRAMEWORK_CATEGORIES.iter().map(|c| c.to_string()).collect(),
+        })
     }
 }
 
 /// Parse YAML frontmatter from a markdown string.
-fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
-    let trimmed = content.trim_start();
-    if !trimmed.starts_with("---") {
+pub(crate) fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
+    let Some((yaml_str, _, body)) = split_frontmatter(content) else {
         return (
             serde_json::Value::Object(serde_json::Map::new()),
             content.to_string(),
         );
+    };
+
+    let mut map = serde_json::Map::new();
+    for line in yaml_str.lines() {
+        if let Some((key, value)) = frontmatter_entry(line) {
+            map.insert(key.to_string(), serde_json::Value::String(value.to_string()));
+        }
     }
 
-    let after_start = &trimmed[3..];
-    if let Some(end_idx) = after_start.find("\n---") {
-        let yaml_str = after_start[..end_idx].trim();
-        let body = after_start[end_idx + 4..].trim_start();
+    (serde_json::Value::Object(map), body.trim_start().to_string())
+}
 
-        let mut map = serde_json::Map::new();
-        for line in yaml_str.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
+/// The YAML between the `---` delimiters, the raw block up to and including
+/// the closing line, and the body after it. `None` when the content has no
+/// frontmatter block.
+pub(crate) fn split_frontmatter(content: &str) -> Option<(&str, &str, &str)> {
+    let start = content.len() - content.trim_start().len();
+    if !content[start..].starts_with("---") {
+        return None;
+    }
+    let after_open = start + 3;
+    let close = after_open + content[after_open..].find("\n---")?;
+    let close_end = close + 4;
+    let end = content[close_end..].find('\n').map_or(content.len(), |i| close_end + i + 1);
+    Some((content[after_open..close].trim(), &content[..end], &content[end..]))
+}
+
+/// A `key: value` frontmatter line as `parse_frontmatter` reads it, with quotes
+/// stripped. Blank lines, comments and lines without a colon yield `None`.
+fn frontmatter_entry(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (key, value) = line.split_once(':')?;
+    Some((key.trim(), value.trim().trim_matches('"').trim_matches('\'')))
+}
+
+/// Markdown for `frontmatter` + `body`. Lines of `existing`'s frontmatter are
+/// kept verbatim for keys whose value didn't change (along with comments and
+/// continuation lines such as `- item`), so key order and quoting survive;
+/// removed keys are dropped and new keys appended.
+fn render_entity(
+    existing: Option<&str>,
+    frontmatter: &serde_json::Map<String, serde_json::Value>,
+    body: &str,
+) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut written: BTreeSet<&str> = BTreeSet::new();
+    // Whether continuation lines belong to a key that is being kept verbatim
+    let mut keep_continuation = true;
+
+    for line in existing.and_then(split_frontmatter).map(|(yaml, _, _)| yaml).unwrap_or_default().lines() {
+        let Some((key, old_value)) = frontmatter_entry(line) else {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || keep_continuation {
+                lines.push(line.to_string());
             }
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim().to_string();
-                let value = value.trim().trim_matches('"').trim_matches('\'').to_string();
-                map.insert(key, serde_json::Value::String(value));
+            continue;
+        };
+        keep_continuation = false;
+        let Some((key, value)) = frontmatter.get_key_value(key) else { continue };
+        if !written.insert(key) {
+            continue;
+        }
+        if value.as_str() == Some(old_value) {
+            lines.push(line.to_string());
+            keep_continuation = true;
+        } else {
+            lines.push(yaml_entry(key, value));
+        }
+    }
+    for (key, value) in frontmatter {
+        if !written.contains(key.as_str()) {
+            lines.push(yaml_entry(key, value));
+        }
+    }
+
+    format!("---\n{}\n---\n\n{}", lines.join("\n"), body)
+}
+
+fn yaml_entry(key: &str, value: &serde_json::Value) -> String {
+    match yaml_value(value, false) {
+        rendered if rendered.is_empty() => format!("{}:", key),
+        rendered => format!("{}: {}", key, rendered),
+    }
+}
+
+/// A JSON value as a single-line YAML value. Strings are double-quoted only when
+/// plain YAML would misread them; arrays become `[a, b]` flow lists.
+fn yaml_value(value: &serde_json::Value, in_flow: bool) -> String {
+    match value {
+        serde_json::Value::Null if in_flow => "null".to_string(),
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => {
+            let special_start = s.starts_with(['-', '[', ']', '{', '}', '!', '&', '*', '>', '|', '@', '`', '#', '\'', '"', '%', '?', ',']);
+            let flow_special = in_flow && s.contains([',', '[', ']', '{', '}']);
+            if s.is_empty() || s.trim() != s || special_start || flow_special || s.contains(": ") || s.contains(" #") || s.ends_with(':') || s.contains('\n') {
+                format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+            } else {
+                s.clone()
             }
         }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|item| yaml_value(item, true)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        // JSON is valid YAML flow syntax
+        other => other.to_string(),
+    }
+}
 
-        (serde_json::Value::Object(map), body.to_string())
-    } else {
-        (
-            serde_json::Value::Object(serde_json::Map::new()),
-            content.to_string(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ports::outbound::HubData;
+    use crate::infrastructure::clock::SystemClock;
+    use crate::infrastructure::filesystem::{FileAdapter, MemoryFs};
+    use crate::infrastructure::persistence::MemoryStore;
+
+    fn listed_names(dir: &Path, category: &str) -> Vec<String> {
+        let mut entities = vec![];
+        scan_directory(dir, category, "global", &FileAdapter, &mut entities, &mut Default::default());
+        let mut names: Vec<String> = entities.into_iter().map(|e| e.name).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn duplicates_rules_and_skills_without_clobbering() {
+        let root = std::env::temp_dir().join(format!("aidd-duplicate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (rules, skills) = (root.join("rules"), root.join("skills"));
+        std::fs::create_dir_all(&rules).unwrap();
+        std::fs::write(rules.join("style.md"), "---\nname: style\n---\nUse tabs.").unwrap();
+        let review = skills.join("review");
+        std::fs::create_dir_all(review.join("assets")).unwrap();
+        std::fs::write(review.join("SKILL.md"), "---\nname: review\n---\nReview code.").unwrap();
+        std::fs::write(review.join("assets").join("checklist.txt"), "1. tests").unwrap();
+
+        duplicate_entity_in(&rules, "rules", "style", "style-strict", false, &FileAdapter).unwrap();
+        duplicate_entity_in(&skills, "skills", "review", "review-security", false, &FileAdapter).unwrap();
+
+        assert_eq!(listed_names(&rules, "rules"), ["style", "style-strict"]);
+        assert_eq!(listed_names(&skills, "skills"), ["review", "review-security"]);
+        assert!(skills.join("review-security/assets/checklist.txt").is_file());
+        let renamed = |path: PathBuf| parse_frontmatter(&std::fs::read_to_string(path).unwrap()).0["name"].clone();
+        assert_eq!(renamed(rules.join("style-strict.md")), "style-strict");
+        assert_eq!(renamed(skills.join("review-security/SKILL.md")), "review-security");
+        assert_eq!(renamed(rules.join("style.md")), "style");
+
+        assert!(duplicate_entity_in(&rules, "rules", "style", "style-strict", false, &FileAdapter).is_err());
+        std::fs::write(rules.join("style.md"), "Use spaces.").unwrap();
+        duplicate_entity_in(&rules, "rules", "style", "style-strict", true, &FileAdapter).unwrap();
+        assert_eq!(std::fs::read_to_string(rules.join("style-strict.md")).unwrap(), "Use spaces.");
+        assert!(duplicate_entity_in(&rules, "rules", "style", "../escape", false, &FileAdapter).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn duplicates_go_through_the_port() {
+        let skills = Path::new("/mem/home/framework/skills");
+        let fs = MemoryFs::new()
+            .with_file(skills.join("review/SKILL.md"), "---\nname: review\n---\nReview code.")
+            .with_file(skills.join("review/assets/checklist.txt"), "1. tests")
+            .with_file(skills.join("audit/SKILL.md"), "---\nname: audit\n---\nOld.")
+            .with_file(skills.join("audit/stale.txt"), "stale");
+
+        duplicate_entity_in(skills, "skills", "review", "audit", true, &fs).unwrap();
+
+        assert_eq!(fs.read_to_string(&skills.join("audit/assets/checklist.txt").to_string_lossy()).unwrap(), "1. tests");
+        assert!(!fs.exists(&skills.join("audit/stale.txt").to_string_lossy()));
+        let copied = fs.read_to_string(&skills.join("audit/SKILL.md").to_string_lossy()).unwrap();
+        assert_eq!(parse_frontmatter(&copied).0["name"], "audit");
+        assert!(fs.exists(&skills.join("review/SKILL.md").to_string_lossy()));
+    }
+
+    #[test]
+    fn deletes_go_through_the_port_and_honor_the_project_source() {
+        let (home, project) = (Path::new("/mem/home"), Path::new("/mem/project"));
+        let global_rule = home.join("framework/rules/style.md");
+        let project_rule = project.join(".aidd/content/rules/style.md");
+        let fs = Arc::new(MemoryFs::new().with_file(&global_rule, "Global.").with_file(&project_rule, "Project."));
+        let service = FrameworkService::new(home, Arc::new(MemoryStore::new(HubData::default())), fs.clone(), Arc::new(SystemClock)).unwrap();
+
+        service
+            .delete_entity_with_project("rules", "style", Some(&project.to_string_lossy()))
+            .unwrap();
+        assert!(!fs.exists(&project_rule.to_string_lossy()));
+        assert!(fs.exists(&global_rule.to_string_lossy()));
+
+        service.delete_entity("rules", "style").unwrap();
+        assert!(!fs.exists(&global_rule.to_string_lossy()));
+        assert!(service.delete_entity("rules", "style").is_err());
+        assert!(service.delete_entity_with_project("rules", "../x", Some("/p")).is_err());
+    }
+
+    #[test]
+    fn scans_flat_rules_skill_dirs_and_nested_knowledge() {
+        let root = Path::new("/mem/framework");
+        let fs = MemoryFs::new()
+            .with_file(root.join("rules/style.md"), "---\nname: style\n---\nUse tabs.")
+            .with_file(root.join("rules/README.md"), "Index.")
+            .with_file(root.join("rules/.draft.md"), "Hidden.")
+            .with_file(root.join("skills/review/SKILL.md"), "---\ndescription: Review code\n---\nSteps.")
+            .with_file(root.join("skills/review/assets/checklist.txt"), "1. tests")
+            .with_file(root.join("skills/empty/notes.txt"), "No SKILL.md here.")
+            .with_file(root.join("knowledge/lang/ownership.md"), "Borrowing.");
+        let scan = |category: &str| {
+            let mut entities = vec![];
+            scan_directory(&root.join(category), category, "global", &fs, &mut entities, &mut Default::default());
+            entities
+        };
+
+        let rules = scan("rules");
+        assert_eq!(rules.len(), 1);
+        assert_eq!((rules[0].name.as_str(), rules[0].content.as_str()), ("style", "Use tabs."));
+        assert!(!rules[0].last_modified.is_empty());
+
+        let skills = scan("skills");
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "review");
+        assert_eq!(skills[0].frontmatter["description"], "Review code");
+
+        let knowledge = scan("knowledge");
+        assert_eq!(knowledge.len(), 1);
+        assert_eq!(knowledge[0].name, "lang/ownership");
+    }
+
+    #[test]
+    fn global_entities_shadow_project_ones_with_the_same_name() {
+        let (home, project) = (Path::new("/mem/home"), Path::new("/mem/project"));
+        let fs = MemoryFs::new()
+            .with_file(home.join("framework/rules/style.md"), "Global style.")
+            .with_file(project.join(".aidd/content/rules/style.md"), "Project style.")
+            .with_file(project.join(".aidd/content/rules/lint.md"), "Project lint.");
+        let service = FrameworkService::new(home, Arc::new(MemoryStore::new(HubData::default())), Arc::new(fs), Arc::new(SystemClock)).unwrap();
+
+        let listed = service
+            .list_entities_with_project("rules", Some(&project.to_string_lossy()), None)
+            .unwrap();
+        let summary: Vec<(&str, &str, &str)> = listed
+            .iter()
+            .map(|e| (e.name.as_str(), e.source.as_str(), e.content.as_str()))
+            .collect();
+        assert_eq!(summary, [("lint", "project", "Project lint."), ("style", "global", "Global style.")]);
+    }
+
+    #[test]
+    fn entity_names_cannot_escape_the_category_dir() {
+        for name in ["../../evil", "/etc/passwd", "..\\evil", "rules/../../evil", "C:\\evil", "a//b", "./a", ""] {
+            assert!(sanitize_entity_name(name).is_err(), "{:?} should be rejected", name);
+        }
+        assert_eq!(sanitize_entity_name("knowledge/sub/topic").unwrap(), "knowledge/sub/topic");
+        assert_eq!(sanitize_entity_name("sub\\topic").unwrap(), "sub/topic");
+
+        let home = std::env::temp_dir().join(format!("aidd-traversal-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        let service = FrameworkService::new(&home, Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock)).unwrap();
+        let outside = home.join("outside.md");
+        std::fs::write(&outside, "keep").unwrap();
+        assert!(service.write_entity("rules", "../../outside", "pwned").is_err());
+        assert!(service.delete_entity("rules", "../../outside").is_err());
+        assert!(service.read_entity("rules", "../../outside").is_err());
+        assert_eq!(std::fs::read_to_string(&outside).unwrap(), "keep");
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn conditional_write_refuses_when_the_file_changed() {
+        let home = std::env::temp_dir().join(format!("aidd-write-conflict-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        let store = Arc::new(MemoryStore::new(HubData::default()));
+        let service = FrameworkService::new(&home, store, Arc::new(FileAdapter), Arc::new(SystemClock)).unwrap();
+        service.write_entity("rules", "style", "Use tabs.").unwrap();
+        let loaded = service.read_entity("rules", "style").unwrap().last_modified;
+
+        // Someone else edits the file; push its mtime forward so the change is visible
+        let path = home.join("framework/rules/style.md");
+        std::fs::write(&path, "Use spaces.").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+
+        let err = service.write_entity_if_unchanged("rules", "style", "Use tabs!", Some(&loaded)).unwrap_err();
+        assert!(matches!(&err, AiddError::Conflict { current_content, .. } if current_content == "Use spaces."));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Use spaces.");
+
+        let current = service.read_entity("rules", "style").unwrap().last_modified;
+        service.write_entity_if_unchanged("rules", "style", "Use tabs!", Some(&current)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Use tabs!");
+
+        // An edit that keeps the mtime still conflicts, through the content hash
+        let loaded = service.read_entity("rules", "style").unwrap().last_modified;
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        std::fs::write(&path, "Use both.").unwrap();
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+        assert!(service.write_entity_if_unchanged("rules", "style", "Use tabs?", Some(&loaded)).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Use both.");
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn frontmatter_filter_matches_tags_and_plain_values() {
+        let (frontmatter, _) = parse_frontmatter("---\nstatus: active\ntags: [security, \"style\"]\n---\nBody");
+        let filter = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        assert!(frontmatter_matches(&frontmatter, &filter(&[("tags", "security")])));
+        assert!(frontmatter_matches(&frontmatter, &filter(&[("tags", "Style"), ("status", "active")])));
+        assert!(frontmatter_matches(&serde_json::json!({ "tags": ["security"] }), &filter(&[("tags", "security")])));
+
+        assert!(!frontmatter_matches(&frontmatter, &filter(&[("tags", "testing")])));
+        assert!(!frontmatter_matches(&frontmatter, &filter(&[("tags", "security"), ("status", "draft")])));
+        assert!(!frontmatter_matches(&frontmatter, &filter(&[("model", "opus")])));
+    }
+
+    #[test]
+    fn search_returns_only_matching_entities_with_line_numbers() {
+        let root = std::env::temp_dir().join(format!("aidd-search-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("style.md"), "---\nname: style\n---\nIntro.\nAlways use TABS.\n").unwrap();
+        std::fs::write(root.join("testing.md"), "---\nname: testing\n---\nWrite tests first.\n").unwrap();
+        let mut entities = vec![];
+        scan_directory(&root, "rules", "global", &FileAdapter, &mut entities, &mut Default::default());
+
+        let result = search_in(&entities, "tabs", 10);
+        assert!(!result.truncated);
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.hits[0].name, "style");
+        assert_eq!(result.hits[0].matches[0].line, Some(2));
+        assert_eq!(result.hits[0].matches[0].snippet, "Always use TABS.");
+
+        // Frontmatter values match too, and the cap reports truncation
+        let result = search_in(&entities, "T", 1);
+        assert_eq!(result.hits.len(), 1);
+        assert!(result.truncated);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sync_preview_lists_added_modified_and_removed_files() {
+        let root = std::env::temp_dir().join(format!("aidd-sync-preview-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (live, release) = (root.join("framework"), root.join("framework.preview"));
+        for (dir, files) in [
+            (&live, vec![("AGENTS.md", "v1"), ("rules/style.md", "tabs"), ("rules/local.md", "mine")]),
+            (&release, vec![("AGENTS.md", "v2"), ("rules/style.md", "tabs"), ("skills/review/SKILL.md", "new")]),
+        ] {
+            for (path, content) in files {
+                let file = dir.join(path);
+                std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+                std::fs::write(file, content).unwrap();
+            }
+        }
+
+        let preview = diff_trees(&live, &release).unwrap();
+        assert_eq!(preview.added, ["skills/review/SKILL.md"]);
+        assert_eq!(preview.modified, ["AGENTS.md"]);
+        assert_eq!(preview.removed, ["rules/local.md"]);
+        // Comparing never writes to the live tree
+        assert_eq!(std::fs::read_to_string(live.join("AGENTS.md")).unwrap(), "v1");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn prune_quarantines_only_files_a_previous_release_shipped() {
+        let root = std::env::temp_dir().join(format!("aidd-sync-prune-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (staging, quarantine) = (root.join("framework.tmp"), root.join("framework.orphaned"));
+        for path in ["rules/style.md", "rules/old.md", "rules/local.md", "rules/README.md", "skills/gone/SKILL.md"] {
+            let file = staging.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, path).unwrap();
+        }
+        let set = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<BTreeSet<String>>();
+        write_release_manifest(&staging, &set(&["rules/style.md", "rules/old.md", "rules/README.md", "skills/gone/SKILL.md", "AGENTS.md"])).unwrap();
+        let previous = read_release_manifest(&staging).unwrap();
+
+        let orphans = orphaned_files(&previous, &set(&["rules/style.md"]));
+        assert_eq!(orphans, ["rules/old.md", "skills/gone/SKILL.md"]);
+        quarantine_files(&staging, &quarantine, &orphans).unwrap();
+
+        assert!(!staging.join("rules/old.md").exists());
+        assert!(!staging.join("skills/gone").exists());
+        assert!(staging.join("skills").is_dir());
+        assert!(staging.join("rules/local.md").is_file());
+        assert!(staging.join("rules/README.md").is_file());
+        assert_eq!(std::fs::read_to_string(quarantine.join("rules/old.md")).unwrap(), "rules/old.md");
+        assert!(quarantine.join("skills/gone/SKILL.md").is_file());
+        // The manifest is bookkeeping, not framework content
+        assert!(!file_hashes(&staging).unwrap().contains_key(RELEASE_MANIFEST));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn first_auto_sync_check_waits_out_the_remaining_interval() {
+        let day = Duration::from_secs(DEFAULT_AUTO_SYNC_INTERVAL_SECS);
+        let now = 1_769_904_000_000;
+        assert_eq!(auto_sync_delay(None, now, day), Duration::ZERO);
+        assert_eq!(auto_sync_delay(Some(now - 3_600_000), now, day), day - Duration::from_secs(3600));
+        assert_eq!(auto_sync_delay(Some(now - 2 * 86_400_000), now, day), Duration::ZERO);
+        // A check stamped in the future (clock skew) waits one interval, not longer
+        assert_eq!(auto_sync_delay(Some(now + 60_000), now, day), day);
+    }
+
+    #[test]
+    fn failed_checks_back_off_up_to_the_interval() {
+        let hour = Duration::from_secs(3600);
+        let delays: Vec<u64> = (1..=7).map(|n| failure_backoff(n, hour).as_secs()).collect();
+        assert_eq!(delays, [60, 120, 240, 480, 960, 1920, 3600]);
+        assert_eq!(failure_backoff(u32::MAX, hour), hour);
+    }
+
+    #[test]
+    fn writing_parts_changes_one_key_and_keeps_the_rest() {
+        let home = Path::new("/mem/home");
+        let rule = home.join("framework/rules/style.md");
+        let original = "---\nname: style\n# shown in the picker\ndescription: \"Tabs: always\"\ntags:\n  - format\n  - lint\nstatus: draft\n---\n\nUse tabs.";
+        let fs = Arc::new(MemoryFs::new().with_file(&rule, original));
+        let service = FrameworkService::new(home, Arc::new(MemoryStore::new(HubData::default())), fs.clone(), Arc::new(SystemClock)).unwrap();
+
+        let entity = service.read_entity("rules", "style").unwrap();
+        let mut frontmatter = entity.frontmatter.clone();
+        frontmatter["status"] = serde_json::json!("active");
+        frontmatter["globs"] = serde_json::json!(["*.rs", "src/{a,b}"]);
+        service.write_entity_parts("rules", "style", frontmatter, &entity.content).unwrap();
+
+        let reread = service.read_entity("rules", "style").unwrap();
+        assert_eq!(reread.frontmatter["name"], "style");
+        assert_eq!(reread.frontmatter["description"], "Tabs: always");
+        assert_eq!(reread.frontmatter["status"], "active");
+        assert_eq!(reread.content, "Use tabs.");
+        // Untouched lines, comments and list items come back verbatim, in order
+        assert_eq!(
+            fs.read_to_string(&rule.to_string_lossy()).unwrap(),
+            "---\nname: style\n# shown in the picker\ndescription: \"Tabs: always\"\ntags:\n  - format\n  - lint\nstatus: active\nglobs: [\"*.rs\", \"src/{a,b}\"]\n---\n\nUse tabs."
+        );
+
+        assert!(service.write_entity_parts("rules", "style", serde_json::json!("x"), "").is_err());
+    }
+
+    #[tokio::test]
+    async fn auto_sync_tick_skips_while_a_sync_is_running() {
+        let service = FrameworkService::new(
+            Path::new("/mem/home"),
+            Arc::new(MemoryStore::new(HubData::default())),
+            Arc::new(MemoryFs::new()),
+            Arc::new(SystemClock),
         )
+        .unwrap();
+        let notified = std::sync::atomic::AtomicBool::new(false);
+
+        let _running = service.sync_lock.lock().await;
+        // Returns before any network check, so no update is reported
+        service
+            .auto_sync_tick(&|_| notified.store(true, std::sync::atomic::Ordering::SeqCst))
+            .await
+            .unwrap();
+        assert!(!notified.load(std::sync::atomic::Ordering::SeqCst));
     }
 }
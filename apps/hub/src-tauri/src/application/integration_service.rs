@@ -1,8 +1,15 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use crate::domain::model::{IntegrationConfig, IntegrationResult, IntegrationType};
+use crate::domain::model::{
+    AgentsDocument, IntegrationBundle, IntegrationBundleEntry, IntegrationConfig, IntegrationRemovalFailure,
+    IntegrationRemovalSummary, IntegrationResult, IntegrationStatus, IntegrationType, PROJECT_PATH_PLACEHOLDER,
+};
 use crate::domain::ports::inbound::IntegrationPort;
-use crate::infrastructure::integrations::adapter_trait::ToolAdapter;
-use crate::infrastructure::integrations::{ClaudeAdapter, CursorAdapter, VscodeAdapter, GeminiAdapter, WindsurfAdapter};
+use crate::infrastructure::integrations::adapter_trait::{read_installed_mcp_entry, write_installed_mcp_entry, ToolAdapter};
+use crate::infrastructure::integrations::agents_md;
+use crate::infrastructure::integrations::{
+    ClaudeAdapter, ClineAdapter, ContinueAdapter, CursorAdapter, GeminiAdapter, VscodeAdapter, WindsurfAdapter,
+};
 
 pub struct IntegrationService {
     framework_path: PathBuf,
@@ -17,6 +24,8 @@ impl IntegrationService {
             Box::new(VscodeAdapter),
             Box::new(GeminiAdapter),
             Box::new(WindsurfAdapter::new()),
+            Box::new(ClineAdapter::new()),
+            Box::new(ContinueAdapter::new()),
         ];
         Self {
             framework_path: framework_path.to_path_buf(),
@@ -31,16 +40,98 @@ impl IntegrationService {
             .map(|a| a.as_ref())
             .ok_or_else(|| format!("No adapter for {:?}", tool))
     }
+
+    /// Integrate one bundled tool, then put its exported MCP entry (command,
+    /// args and env) in place of the default one.
+    fn import_entry(&self, project_path: &str, entry: &IntegrationBundleEntry) -> Result<IntegrationResult, String> {
+        let server = entry
+            .mcp_server
+            .clone()
+            .map(|server| replace_in_strings(server, PROJECT_PATH_PLACEHOLDER, project_path));
+        let env = server.as_ref().map(entry_env).unwrap_or_default();
+        let mut result = self.integrate(project_path, entry.tool.clone(), entry.dev_mode, &env)?;
+        let Some(server) = server else {
+            return Ok(result);
+        };
+
+        let adapter = self.adapter_for(&entry.tool)?;
+        for file in adapter.mcp_config_files(Path::new(project_path)) {
+            if read_installed_mcp_entry(&file)? == Some(file.entry_for(&server)) {
+                continue;
+            }
+            if write_installed_mcp_entry(&file, &server)? {
+                let path = file.path.to_string_lossy().to_string();
+                if !result.files_created.contains(&path) && !result.files_modified.contains(&path) {
+                    result.files_modified.push(path);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    #[cfg(test)]
+    fn with_adapters(framework_path: &Path, adapters: Vec<Box<dyn ToolAdapter>>) -> Self {
+        Self {
+            framework_path: framework_path.to_path_buf(),
+            adapters,
+        }
+    }
+}
+
+/// Current bundle format version written by `export_bundle`.
+const BUNDLE_VERSION: u32 = 1;
+
+/// Replace every occurrence of `from` in string values with `to`: the project
+/// root with the placeholder on export, and back on import.
+fn replace_in_strings(value: serde_json::Value, from: &str, to: &str) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(s.replace(from, to)),
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items.into_iter().map(|v| replace_in_strings(v, from, to)).collect(),
+        ),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, replace_in_strings(v, from, to)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// String-valued variables of an MCP entry's `env` block.
+fn entry_env(entry: &serde_json::Value) -> BTreeMap<String, String> {
+    entry
+        .get("env")
+        .and_then(|e| e.as_object())
+        .map(|env| {
+            env.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Project-relative, `/`-separated form of `file`; `None` for files outside the
+/// project (e.g. `~/.claude.json`), which are machine-specific.
+fn project_relative(project: &Path, file: &str) -> Option<String> {
+    let relative = Path::new(file).strip_prefix(project).ok()?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
 }
 
 impl IntegrationPort for IntegrationService {
-    fn integrate(&self, project_path: &str, tool: IntegrationType, dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn integrate(
+        &self,
+        project_path: &str,
+        tool: IntegrationType,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+    ) -> Result<IntegrationResult, String> {
         let project = Path::new(project_path);
         if !project.exists() {
             return Err(format!("Project path does not exist: {}", project_path));
         }
         let adapter = self.adapter_for(&tool)?;
-        adapter.integrate(project, &self.framework_path, dev_mode)
+        adapter.integrate(project, &self.framework_path, dev_mode, env)
     }
 
     fn remove_integration(&self, project_path: &str, tool: IntegrationType) -> Result<IntegrationResult, String> {
@@ -49,6 +140,39 @@ impl IntegrationPort for IntegrationService {
         adapter.remove(project)
     }
 
+    fn repair_integration(&self, project_path: &str, tool: IntegrationType) -> Result<IntegrationResult, String> {
+        let project = Path::new(project_path);
+        if !project.exists() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+        let adapter = self.adapter_for(&tool)?;
+        adapter.repair(project, &self.framework_path)
+    }
+
+    fn remove_all_integrations(&self, project_path: &str, purge: bool) -> Result<IntegrationRemovalSummary, String> {
+        let project = Path::new(project_path);
+        if !project.exists() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+        // One tool failing must not leave the rest integrated
+        let (mut removed, mut failed) = (Vec::new(), Vec::new());
+        for adapter in &self.adapters {
+            match adapter.remove(project) {
+                Ok(result) => removed.push(result),
+                Err(error) => failed.push(IntegrationRemovalFailure { tool: adapter.tool_type(), error }),
+            }
+        }
+
+        // Keep `.aidd/` while a tool may still point into it
+        let aidd_dir = project.join(".aidd");
+        let purged = purge && failed.is_empty() && aidd_dir.exists();
+        if purged {
+            std::fs::remove_dir_all(&aidd_dir)
+                .map_err(|e| format!("Failed to delete {}: {}", aidd_dir.display(), e))?;
+        }
+        Ok(IntegrationRemovalSummary { removed, failed, purged })
+    }
+
     fn check_status(&self, project_path: &str) -> Result<Vec<IntegrationConfig>, String> {
         let project = Path::new(project_path);
         let mut results = Vec::new();
@@ -61,4 +185,277 @@ impl IntegrationPort for IntegrationService {
     fn list_available(&self) -> Vec<IntegrationType> {
         IntegrationType::all()
     }
+
+    fn export_bundle(&self, project_path: &str) -> Result<IntegrationBundle, String> {
+        let project = Path::new(project_path);
+        if !project.exists() {
+            return Err(format!("Project path does not exist: {}", project_path));
+        }
+        let project_root = project.to_string_lossy();
+
+        let mut integrations = Vec::new();
+        for adapter in &self.adapters {
+            let config = adapter.check(project)?;
+            if matches!(config.status, IntegrationStatus::NotConfigured) {
+                continue;
+            }
+            let mut files: Vec<String> = config
+                .config_files
+                .iter()
+                .filter_map(|f| project_relative(project, f))
+                .collect();
+            files.sort();
+            // The entry as installed, so custom commands, args and env survive
+            let mut mcp_server = None;
+            for file in adapter.mcp_config_files(project) {
+                if let Some(entry) = read_installed_mcp_entry(&file)? {
+                    mcp_server = Some(replace_in_strings(entry, &project_root, PROJECT_PATH_PLACEHOLDER));
+                    break;
+                }
+            }
+            integrations.push(IntegrationBundleEntry {
+                tool: config.integration_type,
+                dev_mode: config.dev_mode,
+                mcp_server,
+                files,
+            });
+        }
+
+        Ok(IntegrationBundle { version: BUNDLE_VERSION, integrations })
+    }
+
+    fn import_bundle(&self, project_path: &str, bundle: &IntegrationBundle) -> Result<Vec<IntegrationResult>, String> {
+        if bundle.version > BUNDLE_VERSION {
+            return Err(format!(
+                "Unsupported integration bundle version {} (max {})",
+                bundle.version, BUNDLE_VERSION
+            ));
+        }
+        bundle
+            .integrations
+            .iter()
+            .map(|entry| self.import_entry(project_path, entry))
+            .collect()
+    }
+
+    fn parse_agents_md(&self, path: &str) -> Result<AgentsDocument, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        Ok(agents_md::parse_agents_md(&content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::integrations::{CursorAdapter, GeminiAdapter, VscodeAdapter};
+
+    fn clean_project(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aidd-bundle-{}-{}", label, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn project_scoped_service(framework: &Path) -> IntegrationService {
+        // Only adapters that write inside the project, so the test never touches $HOME.
+        IntegrationService::with_adapters(
+            framework,
+            vec![Box::new(CursorAdapter), Box::new(VscodeAdapter), Box::new(GeminiAdapter)],
+        )
+    }
+
+    #[test]
+    fn bundle_round_trips_onto_clean_project() {
+        let source = clean_project("source");
+        let target = clean_project("target");
+        let service = project_scoped_service(&source.join("no-framework"));
+        let source_path = source.to_string_lossy().to_string();
+        let target_path = target.to_string_lossy().to_string();
+
+        service.integrate(&source_path, IntegrationType::Cursor, true, &BTreeMap::new()).unwrap();
+        service.integrate(&source_path, IntegrationType::Vscode, false, &BTreeMap::new()).unwrap();
+        service.integrate(&source_path, IntegrationType::Gemini, false, &BTreeMap::new()).unwrap();
+
+        let bundle = service.export_bundle(&source_path).unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains(&source_path), "bundle leaks absolute path: {}", json);
+        assert!(json.contains(PROJECT_PATH_PLACEHOLDER));
+
+        let bundle: IntegrationBundle = serde_json::from_str(&json).unwrap();
+        let results = service.import_bundle(&target_path, &bundle).unwrap();
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(service.export_bundle(&target_path).unwrap(), bundle);
+        assert!(target.join(".cursor").join("rules").join("aidd.mdc").exists());
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn bundle_round_trips_a_customized_mcp_entry() {
+        let source = clean_project("custom-source");
+        let target = clean_project("custom-target");
+        let service = project_scoped_service(&source.join("no-framework"));
+        let source_path = source.to_string_lossy().to_string();
+        let target_path = target.to_string_lossy().to_string();
+
+        let env = BTreeMap::from([("AIDD_LOG".to_string(), "debug".to_string())]);
+        service.integrate(&source_path, IntegrationType::Cursor, false, &env).unwrap();
+        let mcp_path = source.join(".cursor").join("mcp.json");
+        let custom = serde_json::json!({ "mcpServers": { "aidd-engine": {
+            "command": "bunx",
+            "args": ["@aidd.md/mcp-engine@next", "--root", format!("{}/ai", source_path)],
+            "env": { "AIDD_LOG": "debug" }
+        }}});
+        std::fs::write(&mcp_path, serde_json::to_string_pretty(&custom).unwrap()).unwrap();
+
+        let bundle = service.export_bundle(&source_path).unwrap();
+        let exported = bundle.integrations[0].mcp_server.clone().unwrap();
+        assert_eq!(exported["command"], "bunx");
+        assert_eq!(exported["args"][2], format!("{}/ai", PROJECT_PATH_PLACEHOLDER));
+
+        service.import_bundle(&target_path, &bundle).unwrap();
+        let imported: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(target.join(".cursor").join("mcp.json")).unwrap(),
+        ).unwrap();
+        assert_eq!(imported["mcpServers"]["aidd-engine"], serde_json::json!({
+            "command": "bunx",
+            "args": ["@aidd.md/mcp-engine@next", "--root", format!("{}/ai", target_path)],
+            "env": { "AIDD_LOG": "debug" }
+        }));
+        assert_eq!(service.export_bundle(&target_path).unwrap(), bundle);
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn repeat_integrate_leaves_current_mcp_entries_untouched() {
+        let project = clean_project("idempotent");
+        let service = project_scoped_service(&project.join("no-framework"));
+        let path = project.to_string_lossy().to_string();
+
+        for tool in [IntegrationType::Cursor, IntegrationType::Vscode] {
+            let first = service.integrate(&path, tool.clone(), false, &BTreeMap::new()).unwrap();
+            assert!(!first.files_created.is_empty());
+
+            let second = service.integrate(&path, tool.clone(), false, &BTreeMap::new()).unwrap();
+            assert!(second.files_modified.is_empty(), "{:?}: {:?}", tool, second.files_modified);
+            assert!(second.files_created.is_empty(), "{:?}: {:?}", tool, second.files_created);
+            assert!(second.messages.iter().any(|m| m.starts_with("aidd entry already current")));
+
+            // A real change (dev mode toggled) is still written.
+            let third = service.integrate(&path, tool.clone(), true, &BTreeMap::new()).unwrap();
+            assert_eq!(third.files_modified.len(), 1, "{:?}", tool);
+        }
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn user_env_is_written_and_survives_reintegrate() {
+        let project = clean_project("env");
+        let service = project_scoped_service(&project.join("no-framework"));
+        let path = project.to_string_lossy().to_string();
+        let mcp_path = project.join(".cursor").join("mcp.json");
+        let env_of = || {
+            let config: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&mcp_path).unwrap()).unwrap();
+            config["mcpServers"]["aidd-engine"]["env"].clone()
+        };
+
+        let env = BTreeMap::from([("HTTPS_PROXY".to_string(), "http://proxy:3128".to_string())]);
+        service.integrate(&path, IntegrationType::Cursor, false, &env).unwrap();
+        assert_eq!(env_of(), serde_json::json!({ "HTTPS_PROXY": "http://proxy:3128" }));
+
+        // Without new env — and across a dev-mode switch — the existing block is kept.
+        let again = service.integrate(&path, IntegrationType::Cursor, false, &BTreeMap::new()).unwrap();
+        assert!(again.files_modified.is_empty());
+        service.integrate(&path, IntegrationType::Cursor, true, &BTreeMap::new()).unwrap();
+        assert_eq!(env_of(), serde_json::json!({ "HTTPS_PROXY": "http://proxy:3128" }));
+
+        let key = BTreeMap::from([("API_KEY".to_string(), "secret".to_string())]);
+        service.integrate(&path, IntegrationType::Cursor, true, &key).unwrap();
+        assert_eq!(
+            env_of(),
+            serde_json::json!({ "API_KEY": "secret", "HTTPS_PROXY": "http://proxy:3128" })
+        );
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn repair_only_recreates_missing_files() {
+        let project = clean_project("repair");
+        let service = project_scoped_service(&project.join("no-framework"));
+        let path = project.to_string_lossy().to_string();
+        service.integrate(&path, IntegrationType::Cursor, false, &BTreeMap::new()).unwrap();
+
+        // A hand-tuned MCP entry and a deleted AGENTS.md
+        let mcp_path = project.join(".cursor").join("mcp.json");
+        let custom = r#"{"mcpServers": {"aidd-engine": {"command": "npx", "args": ["-y", "@aidd.md/mcp-engine@next"]}}}"#;
+        std::fs::write(&mcp_path, custom).unwrap();
+        std::fs::remove_file(project.join("AGENTS.md")).unwrap();
+
+        let result = service.repair_integration(&path, IntegrationType::Cursor).unwrap();
+        assert_eq!(result.files_created, vec![project.join("AGENTS.md").to_string_lossy().to_string()]);
+        assert!(result.files_modified.is_empty());
+        assert_eq!(std::fs::read_to_string(&mcp_path).unwrap(), custom);
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn remove_all_clears_every_integration_and_purges_aidd() {
+        let project = clean_project("remove-all");
+        let service = project_scoped_service(&project.join("no-framework"));
+        let path = project.to_string_lossy().to_string();
+        service.integrate(&path, IntegrationType::Cursor, false, &BTreeMap::new()).unwrap();
+        service.integrate(&path, IntegrationType::Vscode, false, &BTreeMap::new()).unwrap();
+        assert!(project.join(".aidd").exists());
+
+        let summary = service.remove_all_integrations(&path, false).unwrap();
+        assert_eq!(summary.removed.len(), 3);
+        assert!(summary.failed.is_empty());
+        assert!(!summary.purged);
+        assert!(project.join(".aidd").exists());
+        let configured: Vec<_> = service
+            .check_status(&path)
+            .unwrap()
+            .into_iter()
+            .filter(|c| c.config_files.iter().any(|f| f.ends_with("mcp.json") || f.ends_with("aidd.mdc")))
+            .map(|c| c.integration_type)
+            .collect();
+        assert!(configured.is_empty(), "still configured: {:?}", configured);
+
+        let summary = service.remove_all_integrations(&path, true).unwrap();
+        assert!(summary.purged);
+        assert!(!project.join(".aidd").exists());
+        assert!(project.join("AGENTS.md").exists());
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn remove_all_keeps_going_past_a_failing_tool() {
+        let project = clean_project("remove-all-failure");
+        let service = project_scoped_service(&project.join("no-framework"));
+        let path = project.to_string_lossy().to_string();
+        service.integrate(&path, IntegrationType::Cursor, false, &BTreeMap::new()).unwrap();
+        std::fs::create_dir_all(project.join(".aidd")).unwrap();
+        // A directory where VS Code's mcp.json should be can't be read
+        std::fs::create_dir_all(project.join(".vscode").join("mcp.json")).unwrap();
+
+        let summary = service.remove_all_integrations(&path, true).unwrap();
+        let removed: Vec<_> = summary.removed.iter().map(|r| r.tool.clone()).collect();
+        assert_eq!(removed, vec![IntegrationType::Cursor, IntegrationType::Gemini]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].tool, IntegrationType::Vscode);
+        assert!(!summary.purged);
+        assert!(project.join(".aidd").exists());
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
 }
@@ -1,16 +1,27 @@
-use std::collections::HashSet;
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::domain::model::{
-    McpHealthReport, McpHealthSummary, McpServerStatus,
+    DiscoveredMcp, McpHealthReport, McpHealthSummary, McpNameConflict, McpServerStatus, McpValidation,
 };
 use crate::domain::ports::inbound::McpHealthPort;
 use crate::infrastructure::integrations::McpConfigScanner;
 use crate::infrastructure::process::McpProcessManager;
 
+/// Seconds between background scans when the caller does not pick an interval.
+pub const DEFAULT_HEALTH_INTERVAL_SECS: u64 = 30;
+
+/// Callback invoked with each report that differs from the previous scan.
+pub type HealthChangeListener = Arc<dyn Fn(&McpHealthReport) + Send + Sync>;
+
 pub struct McpHealthService {
     config_scanner: McpConfigScanner,
     process_manager: Arc<McpProcessManager>,
+    /// Dropping the sender stops the running monitor thread.
+    monitor: Mutex<Option<Sender<()>>>,
+    last_report: Mutex<Option<McpHealthReport>>,
 }
 
 impl McpHealthService {
@@ -18,8 +29,66 @@ impl McpHealthService {
         Self {
             config_scanner,
             process_manager,
+            monitor: Mutex::new(None),
+            last_report: Mutex::new(None),
         }
     }
+
+    /// Rescan every `interval_secs` in the background, calling `on_change` whenever
+    /// the discovered entries or Hub server statuses differ from the last scan.
+    /// The first scan always reports. Restarting replaces any running monitor.
+    pub fn start_health_monitor(
+        self: &Arc<Self>,
+        interval_secs: u64,
+        project_path: Option<String>,
+        on_change: HealthChangeListener,
+    ) -> Result<(), String> {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        *self.monitor.lock().map_err(|e| e.to_string())? = Some(stop_tx);
+        if let Ok(mut last) = self.last_report.lock() {
+            *last = None;
+        }
+
+        let interval = Duration::from_secs(interval_secs.max(1));
+        let service = Arc::downgrade(self);
+        std::thread::spawn(move || loop {
+            let Some(service) = service.upgrade() else { break };
+            service.poll_health(project_path.as_deref(), on_change.as_ref());
+            drop(service);
+            match stop_rx.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+        Ok(())
+    }
+
+    /// Stop the background monitor. Returns `false` when none was running.
+    pub fn stop_health_monitor(&self) -> Result<bool, String> {
+        Ok(self.monitor.lock().map_err(|e| e.to_string())?.take().is_some())
+    }
+
+    /// Forget the last report so a running monitor emits its next scan.
+    /// Returns whether a report was cached.
+    pub fn clear_cached_report(&self) -> bool {
+        self.last_report.lock().unwrap_or_else(|e| e.into_inner()).take().is_some()
+    }
+
+    fn poll_health(&self, project_path: Option<&str>, on_change: &(dyn Fn(&McpHealthReport) + Send + Sync)) {
+        let report = match self.scan_health(project_path) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("[mcp-health] Background scan failed: {}", e);
+                return;
+            }
+        };
+        let Ok(mut last) = self.last_report.lock() else { return };
+        if last.as_ref().is_some_and(|prev| !health_changed(prev, &report)) {
+            return;
+        }
+        on_change(&report);
+        *last = Some(report);
+    }
 }
 
 impl McpHealthPort for McpHealthService {
@@ -51,6 +120,15 @@ impl McpHealthPort for McpHealthService {
             .iter()
             .filter(|s| matches!(s.status, McpServerStatus::Error))
             .count();
+        let hub_unresponsive = hub_servers
+            .iter()
+            .filter(|s| matches!(s.status, McpServerStatus::Unresponsive))
+            .count();
+
+        let invalid_count = discovered
+            .iter()
+            .filter(|d| d.validation != McpValidation::Ok)
+            .count();
 
         let summary = McpHealthSummary {
             total_discovered: discovered.len(),
@@ -60,12 +138,148 @@ impl McpHealthPort for McpHealthService {
             hub_running,
             hub_stopped,
             hub_error,
+            hub_unresponsive,
+            invalid_count,
         };
 
+        let conflicts = detect_name_conflicts(&discovered);
+
         Ok(McpHealthReport {
             discovered,
             hub_servers,
             summary,
+            conflicts,
         })
     }
 }
+
+/// Identity of a report for change detection: each discovered entry by
+/// name + scope + tool, and each Hub server by id + status.
+fn health_fingerprint(report: &McpHealthReport) -> (BTreeSet<String>, BTreeSet<String>) {
+    let discovered = report
+        .discovered
+        .iter()
+        .map(|d| format!("{}|{:?}|{:?}", d.name, d.scope, d.tool))
+        .collect();
+    let servers = report
+        .hub_servers
+        .iter()
+        .map(|s| format!("{}|{:?}", s.id, s.status))
+        .collect();
+    (discovered, servers)
+}
+
+fn health_changed(prev: &McpHealthReport, next: &McpHealthReport) -> bool {
+    health_fingerprint(prev) != health_fingerprint(next)
+}
+
+/// Group aidd entries by server name and report names whose command/args/url
+/// differ between configs — typically a migration applied to only some tools.
+fn detect_name_conflicts(discovered: &[DiscoveredMcp]) -> Vec<McpNameConflict> {
+    let mut by_name: BTreeMap<&str, Vec<&DiscoveredMcp>> = BTreeMap::new();
+    for entry in discovered.iter().filter(|d| d.is_aidd) {
+        by_name.entry(entry.name.as_str()).or_default().push(entry);
+    }
+
+    by_name
+        .into_iter()
+        .filter_map(|(name, entries)| {
+            let launch_settings: HashSet<_> = entries
+                .iter()
+                .map(|e| (&e.command, &e.args, &e.url))
+                .collect();
+            if launch_settings.len() < 2 {
+                return None;
+            }
+            let tools: Vec<String> = entries
+                .iter()
+                .map(|e| format!("{:?}", e.tool).to_lowercase())
+                .collect();
+            Some(McpNameConflict {
+                name: name.to_string(),
+                suggestion: format!(
+                    "'{}' is launched differently across configs ({}). Re-run the integration for each tool so they share one command.",
+                    name,
+                    tools.join(", ")
+                ),
+                entries: entries.into_iter().cloned().collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::{McpConfigScope, McpToolSource};
+
+    fn aidd_entry(tool: McpToolSource, command: &str, args: &[&str]) -> DiscoveredMcp {
+        DiscoveredMcp {
+            name: "aidd".to_string(),
+            tool,
+            scope: McpConfigScope::Project,
+            config_path: "/tmp/mcp.json".to_string(),
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            url: None,
+            transport_type: Some("stdio".to_string()),
+            is_aidd: true,
+            installed_by_aidd: false,
+            also_in: vec![],
+            validation: McpValidation::Ok,
+        }
+    }
+
+    #[test]
+    fn differing_commands_for_same_name_are_flagged() {
+        let discovered = vec![
+            aidd_entry(McpToolSource::ClaudeCode, "npx", &["-y", "@aidd.md/mcp-engine"]),
+            aidd_entry(McpToolSource::Cursor, "node", &["mcps/mcp-aidd-engine/dist/index.js"]),
+            aidd_entry(McpToolSource::Vscode, "npx", &["-y", "@aidd.md/mcp-engine"]),
+        ];
+
+        let conflicts = detect_name_conflicts(&discovered);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].name, "aidd");
+        assert_eq!(conflicts[0].entries.len(), 3);
+        assert!(conflicts[0].suggestion.contains("claudecode, cursor, vscode"));
+
+        // Consistent entries are not reported.
+        assert!(detect_name_conflicts(&[discovered[0].clone(), discovered[2].clone()]).is_empty());
+    }
+
+    fn report(discovered: Vec<DiscoveredMcp>) -> McpHealthReport {
+        McpHealthReport {
+            summary: McpHealthSummary {
+                total_discovered: discovered.len(),
+                aidd_count: 0,
+                third_party_count: 0,
+                tools_with_config: vec![],
+                hub_running: 0,
+                hub_stopped: 0,
+                hub_error: 0,
+                hub_unresponsive: 0,
+                invalid_count: 0,
+            },
+            discovered,
+            hub_servers: vec![],
+            conflicts: vec![],
+        }
+    }
+
+    #[test]
+    fn health_change_ignores_launch_details_but_not_identity() {
+        let cursor = aidd_entry(McpToolSource::Cursor, "npx", &["-y", "@aidd.md/mcp-engine"]);
+        let base = report(vec![cursor.clone()]);
+
+        // Same name/scope/tool with a different command is not a change in the discovered set.
+        let relaunched = report(vec![aidd_entry(McpToolSource::Cursor, "node", &["dist/index.js"])]);
+        assert!(!health_changed(&base, &relaunched));
+
+        let mut global = cursor.clone();
+        global.scope = McpConfigScope::Global;
+        assert!(health_changed(&base, &report(vec![global])));
+        assert!(health_changed(&base, &report(vec![cursor, aidd_entry(McpToolSource::Vscode, "npx", &[])])));
+        assert!(health_changed(&base, &report(vec![])));
+    }
+}
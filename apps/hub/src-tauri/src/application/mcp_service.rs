@@ -1,46 +1,86 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
 use std::path::PathBuf;
 
-use crate::domain::model::{McpServer, McpServerMode};
+use crate::domain::model::{
+    McpConnectInfo, McpPackage, McpResetSummary, McpServer, McpServerMode, McpServerSpec, McpSpecValidation, RestartPolicy,
+};
+use crate::domain::AiddError;
 use crate::domain::ports::inbound::{McpPort, ProjectPort};
-use crate::infrastructure::mcp::McpClient;
-use crate::infrastructure::process::McpProcessManager;
+use crate::infrastructure::mcp::{McpClient, DEFAULT_REQUEST_TIMEOUT};
+use crate::infrastructure::process::{McpProcessManager, PING_TIMEOUT, RESET_REASON};
+use crate::infrastructure::process::command_path::resolve_command;
 use crate::application::ProjectService;
 use serde_json::Value;
+use std::time::Duration;
+
+/// Spawns a client process: `(command, args, cwd, env)`, as `McpClient::spawn_with_context`.
+pub type ClientSpawner =
+    Arc<dyn Fn(&str, &[&str], Option<&str>, Option<&[(&str, &str)]>) -> Result<McpClient, String> + Send + Sync>;
+
+/// How often a one-off client's spawn + initialize is retried after a
+/// transient failure (e.g. an `npx` cold start timing out).
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total tries, including the first.
+    pub attempts: u32,
+    /// Wait before the second try; doubled before each later one.
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { attempts: 2, backoff: Duration::from_millis(500) }
+    }
+}
 
 pub struct McpService {
     process_manager: Arc<McpProcessManager>,
     project_service: Arc<ProjectService>,
+    /// Per-request response timeout applied to every spawned client.
+    request_timeout: Duration,
+    /// One-off clients spawned for a call, so a reset can cancel them mid-request.
+    spawned_clients: Mutex<Vec<Weak<McpClient>>>,
+    /// Initialized clients reused across calls, keyed by package and the
+    /// project root they were spawned in.
+    pool: Mutex<HashMap<(String, Option<String>), Arc<McpClient>>>,
+    spawner: ClientSpawner,
+    retry: RetryPolicy,
 }
 
 impl McpService {
     pub fn new(process_manager: Arc<McpProcessManager>, project_service: Arc<ProjectService>) -> Self {
-        Self { process_manager, project_service }
+        Self {
+            process_manager,
+            project_service,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            spawned_clients: Mutex::new(Vec::new()),
+            pool: Mutex::new(HashMap::new()),
+            spawner: Arc::new(McpClient::spawn_with_context),
+            retry: RetryPolicy::default(),
+        }
     }
 
-    fn normalize_package(package: &str) -> Result<(&'static str, &'static str), String> {
-        match package {
-            "engine" | "mcp-aidd-engine" | "@aidd.md/mcp-engine" => {
-                Ok(("mcps/mcp-aidd-engine/dist/index.js", "@aidd.md/mcp-engine"))
-            }
-            "core" | "mcp-aidd-core" | "@aidd.md/mcp-core" => {
-                Ok(("mcps/mcp-aidd-core/dist/index.js", "@aidd.md/mcp-core"))
-            }
-            "memory" | "mcp-aidd-memory" | "@aidd.md/mcp-memory" => {
-                Ok(("mcps/mcp-aidd-memory/dist/index.js", "@aidd.md/mcp-memory"))
-            }
-            "tools" | "mcp-aidd-tools" | "@aidd.md/mcp-tools" => {
-                Ok(("mcps/mcp-aidd-tools/dist/index.js", "@aidd.md/mcp-tools"))
-            }
-            _ => Err(format!(
-                "Unknown package '{}'. Valid: engine, core, memory, tools",
-                package
-            )),
-        }
+    /// Override how one-off clients are spawned.
+    pub fn with_spawner(mut self, spawner: ClientSpawner) -> Self {
+        self.spawner = spawner;
+        self
+    }
+
+    /// Override the retry policy for spawning one-off clients.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn track_spawned(&self, client: &Arc<McpClient>) {
+        let mut clients = self.spawned_clients.lock().unwrap_or_else(|e| e.into_inner());
+        clients.retain(|c| c.strong_count() > 0);
+        clients.push(Arc::downgrade(client));
     }
 
     fn resolve_client_command(&self, package: &str) -> Result<(String, Vec<String>), String> {
-        let (local_rel, npm_name) = Self::normalize_package(package)?;
+        let package = McpPackage::from_str(package)?;
 
         let active_project = self
             .project_service
@@ -48,7 +88,7 @@ impl McpService {
             .map_err(|e| format!("Failed to resolve active project: {}", e))?;
 
         if let Some(root) = active_project {
-            let local_path = PathBuf::from(root).join(local_rel);
+            let local_path = PathBuf::from(root).join(package.local_entry());
             if local_path.exists() {
                 return Ok((
                     "node".to_string(),
@@ -60,36 +100,123 @@ impl McpService {
         // Fallback to npx if local dist bundle is unavailable.
         Ok((
             "npx".to_string(),
-            vec!["-y".to_string(), npm_name.to_string()],
+            vec!["-y".to_string(), package.npm_name().to_string()],
         ))
     }
 
+    /// Run `f` against the Hub-managed server for `package` when one is running,
+    /// otherwise against a pooled client for the active project.
     fn with_client<F, T>(&self, package: &str, f: F) -> Result<T, String>
     where
         F: FnOnce(&McpClient) -> Result<T, String>,
     {
+        // Hub-hosted packages run under their canonical id, whichever alias was passed
+        let server_id = match McpPackage::from_str(package) {
+            Ok(known) => known.id(),
+            Err(_) => package,
+        };
+        if let Some(client) = self.process_manager.client(server_id)? {
+            client
+                .initialize()
+                .map_err(|e| format!("Failed to initialize MCP client for '{}': {}", package, e))?;
+            return f(&*client);
+        }
+        let client = self.pooled_client(package)?;
+        f(&*client)
+    }
+
+    /// A live, initialized client for `package` in the active project, spawned
+    /// on first use. Aliases of one package share a client. Dead clients and
+    /// clients of other projects are dropped.
+    fn pooled_client(&self, package: &str) -> Result<Arc<McpClient>, String> {
+        let canonical = McpPackage::from_str(package)?.id();
+        let project = self
+            .project_service
+            .get_active_path()
+            .map_err(|e| format!("Failed to resolve active project: {}", e))?;
+        let key = (canonical.to_string(), project);
+        {
+            let mut pool = self.pool.lock().map_err(|e| e.to_string())?;
+            pool.retain(|(_, root), client| *root == key.1 && client.is_alive());
+            if let Some(client) = pool.get(&key) {
+                return Ok(client.clone());
+            }
+        }
+
+        // Spawn outside the lock; a concurrent call may race us, and the last insert wins
+        let (client, _) = self.spawn_initialized(package)?;
+        self.pool
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Like `with_client`, also passing the initialize result to `f`.
+    fn with_initialized_client<F, T>(&self, package: &str, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&McpClient, Value) -> Result<T, String>,
+    {
+        let (client, init) = self.spawn_initialized(package)?;
+        f(&*client, init)
+    }
+
+    /// Spawn and initialize a one-off client, retrying per `self.retry` while
+    /// the failure is transient. Errors the server answered with are final.
+    fn spawn_initialized(&self, package: &str) -> Result<(Arc<McpClient>, Value), String> {
         let (command, args) = self.resolve_client_command(package)?;
         let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
         let active_project = self
             .project_service
             .get_active_path()
             .map_err(|e| format!("Failed to resolve active project: {}", e))?;
-        let client = if let Some(root) = active_project.as_deref() {
-            let env_pairs = project_scope_env(&command, root);
-            let env = (!env_pairs.is_empty()).then_some(env_pairs.as_slice());
-            McpClient::spawn_with_context(&command, &arg_refs, Some(root), env)
-                .map_err(|e| format!("Failed to spawn MCP client for '{}': {}", package, e))?
-        } else {
-            McpClient::spawn(&command, &arg_refs)
-                .map_err(|e| format!("Failed to spawn MCP client for '{}': {}", package, e))?
-        };
-        client
-            .initialize()
-            .map_err(|e| format!("Failed to initialize MCP client for '{}': {}", package, e))?;
-        f(&client)
+        let env_pairs = active_project
+            .as_deref()
+            .map(|root| project_scope_env(&command, root))
+            .unwrap_or_default();
+        let env = (!env_pairs.is_empty()).then_some(env_pairs.as_slice());
+
+        let attempts = self.retry.attempts.max(1);
+        let mut backoff = self.retry.backoff;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let outcome = match (self.spawner)(&command, &arg_refs, active_project.as_deref(), env) {
+                Err(e) => Err((format!("Failed to spawn MCP client for '{}': {}", package, e), true)),
+                Ok(client) => {
+                    let client = Arc::new(client.with_request_timeout(self.request_timeout));
+                    self.track_spawned(&client);
+                    match client.initialize() {
+                        Ok(init) => Ok((client, init)),
+                        Err(e) => {
+                            let transient = is_transient_init_error(&e);
+                            Err((format!("Failed to initialize MCP client for '{}': {}", package, e), transient))
+                        }
+                    }
+                }
+            };
+            match outcome {
+                Ok(ready) => return Ok(ready),
+                Err((e, true)) if attempt < attempts => {
+                    eprintln!(
+                        "[mcp] Attempt {}/{} for '{}' failed: {}; retrying in {:?}",
+                        attempt, attempts, package, e, backoff
+                    );
+                    std::thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err((e, _)) => return Err(e),
+            }
+        }
     }
 }
 
+/// An initialize failure worth retrying: the server never answered in time
+/// (slow cold start). JSON-RPC errors it replied with are not.
+fn is_transient_init_error(error: &AiddError) -> bool {
+    matches!(error, AiddError::Timeout { .. })
+}
+
 /// Build optional env overrides for MCP subprocess scope.
 ///
 /// Local project-launched servers (`node <project>/mcps/...`) get scope from `cwd`.
@@ -102,9 +229,112 @@ fn project_scope_env<'a>(command: &str, project_root: &'a str) -> Vec<(&'static
     }
 }
 
+/// Error given to in-flight calls on pooled clients when they are shut down.
+const CLIENTS_SHUT_DOWN: &str = "MCP clients were shut down";
+
+/// Upper bound on a validation probe's initialize handshake.
+const SPEC_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn build_connect_info(label: &str, client: &McpClient, init: &Value) -> McpConnectInfo {
+    McpConnectInfo {
+        package: label.to_string(),
+        protocol_version: init
+            .get("protocolVersion")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string()),
+        server_info: init.get("serverInfo").cloned().unwrap_or(Value::Null),
+        init_duration_ms: client
+            .last_init_duration()
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default(),
+    }
+}
+
+/// Validate a custom MCP spec: static checks first, then (if they pass and
+/// `probe` is set) a real spawn + initialize bounded by `probe_timeout`.
+fn validate_spec(spec: &McpServerSpec, probe: bool, probe_timeout: Duration) -> McpSpecValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let cwd = spec.cwd.as_deref().map(std::path::Path::new);
+    if let Some(dir) = cwd {
+        if !dir.is_dir() {
+            errors.push(format!("Working directory does not exist: {}", dir.display()));
+        }
+    }
+
+    let command = spec.command.as_str();
+    let resolved = resolve_command(command, cwd);
+    if command.trim().is_empty() {
+        errors.push("Command is empty".to_string());
+    } else if command.contains('\0') {
+        errors.push("Command contains a NUL byte".to_string());
+    } else if command.trim() != command {
+        errors.push(format!("Command '{}' has leading or trailing whitespace", command));
+    } else if resolved.is_none() && command.contains(' ') {
+        errors.push(format!(
+            "Command '{}' not found; put its arguments in `args` instead of the command",
+            command
+        ));
+    } else if resolved.is_none() {
+        errors.push(format!("Command '{}' not found on PATH", command));
+    }
+
+    for (i, arg) in spec.args.iter().enumerate() {
+        if arg.contains('\0') {
+            errors.push(format!("Argument {} contains a NUL byte", i));
+        } else if arg.is_empty() {
+            warnings.push(format!("Argument {} is empty", i));
+        } else if arg.trim() != arg {
+            warnings.push(format!("Argument {} ('{}') has leading or trailing whitespace", i, arg));
+        } else if arg.starts_with('-') && arg.contains(' ') {
+            warnings.push(format!(
+                "Argument {} ('{}') contains a space; pass the flag and its value as separate args",
+                i, arg
+            ));
+        } else if arg.matches('"').count() % 2 == 1 || arg.matches('\'').count() % 2 == 1 {
+            warnings.push(format!(
+                "Argument {} ('{}') has an unmatched quote; args are passed verbatim, not shell-parsed",
+                i, arg
+            ));
+        }
+    }
+
+    let mut handshake = None;
+    if probe && errors.is_empty() {
+        // Spawn the resolved path: on Windows `npx` only launches as `npx.cmd`.
+        let program = resolved.as_ref().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+        let args: Vec<&str> = spec.args.iter().map(|s| s.as_str()).collect();
+        let env: Vec<(&str, &str)> = spec.env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        let probed = McpClient::spawn_with_context(&program, &args, spec.cwd.as_deref(), Some(&env))
+            .map(|client| client.with_request_timeout(probe_timeout))
+            .and_then(|client| {
+                let init = client.initialize()?;
+                Ok(build_connect_info(command, &client, &init))
+            });
+        match probed {
+            Ok(info) => handshake = Some(info),
+            Err(e) => errors.push(format!("Initialize handshake failed: {}", e)),
+        }
+    }
+
+    McpSpecValidation {
+        ok: errors.is_empty(),
+        resolved_command: resolved.map(|p| p.to_string_lossy().to_string()),
+        errors,
+        warnings,
+        handshake,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::project_scope_env;
+    use super::*;
+    use crate::domain::ports::outbound::HubData;
+    use crate::infrastructure::clock::SystemClock;
+    use crate::infrastructure::filesystem::FileAdapter;
+    use crate::infrastructure::persistence::MemoryStore;
+    use std::sync::atomic::{AtomicU32, Ordering};
 
     #[test]
     fn project_scope_env_is_empty_for_local_node_runtime() {
@@ -119,15 +349,141 @@ mod tests {
         assert_eq!(env[0].0, "AIDD_PROJECT_PATH");
         assert_eq!(env[0].1, "C:/repo/aidd.md");
     }
+
+    /// A service with no active project whose clients come from `spawner`.
+    #[cfg(unix)]
+    fn service_with_spawner(spawner: ClientSpawner) -> McpService {
+        let projects = Arc::new(ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock)));
+        McpService::new(Arc::new(McpProcessManager::new()), projects)
+            .with_spawner(spawner)
+            .with_retry_policy(RetryPolicy { attempts: 2, backoff: Duration::from_millis(10) })
+    }
+
+    /// A fake server answering `initialize` (id 1) with `reply`.
+    #[cfg(unix)]
+    fn fake_server(reply: &str) -> Result<McpClient, String> {
+        let script = format!(r#"printf '%s\n' '{{"jsonrpc":"2.0","id":1,{}}}'; cat > /dev/null"#, reply);
+        McpClient::spawn("sh", &["-c", &script])
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn transient_spawn_failure_is_retried() {
+        let spawns = Arc::new(AtomicU32::new(0));
+        let counter = spawns.clone();
+        let service = service_with_spawner(Arc::new(move |_: &str, _: &[&str], _: Option<&str>, _: Option<&[(&str, &str)]>| {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err("npx: cold start failed".to_string());
+            }
+            fake_server(r#""result":{"serverInfo":{"name":"fake"}}"#)
+        }));
+
+        let name = service
+            .with_initialized_client("engine", |_, init| Ok(init["serverInfo"]["name"].clone()))
+            .unwrap();
+        assert_eq!(name, "fake");
+        assert_eq!(spawns.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pooled_client_is_reused_until_shut_down() {
+        let spawns = Arc::new(AtomicU32::new(0));
+        let counter = spawns.clone();
+        let service = service_with_spawner(Arc::new(move |_: &str, _: &[&str], _: Option<&str>, _: Option<&[(&str, &str)]>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            fake_server(r#""result":{"serverInfo":{"name":"fake"}}"#)
+        }));
+
+        for _ in 0..3 {
+            service
+                .with_client("engine", |client| {
+                    assert!(client.is_alive());
+                    Ok(())
+                })
+                .unwrap();
+        }
+        assert_eq!(spawns.load(Ordering::SeqCst), 1);
+
+        assert_eq!(service.shutdown_clients(), 1);
+        service.with_client("engine", |_| Ok(())).unwrap();
+        assert_eq!(spawns.load(Ordering::SeqCst), 2);
+        service.shutdown_clients();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn package_aliases_share_one_pooled_client() {
+        let spawns = Arc::new(AtomicU32::new(0));
+        let counter = spawns.clone();
+        let service = service_with_spawner(Arc::new(move |_: &str, _: &[&str], _: Option<&str>, _: Option<&[(&str, &str)]>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            fake_server(r#""result":{"serverInfo":{"name":"fake"}}"#)
+        }));
+
+        for alias in ["engine", "@aidd.md/mcp-engine", "monolithic"] {
+            service.with_client(alias, |_| Ok(())).unwrap();
+        }
+        assert_eq!(spawns.load(Ordering::SeqCst), 1);
+        assert_eq!(service.shutdown_clients(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn server_errors_are_not_retried() {
+        let spawns = Arc::new(AtomicU32::new(0));
+        let counter = spawns.clone();
+        let service = service_with_spawner(Arc::new(move |_: &str, _: &[&str], _: Option<&str>, _: Option<&[(&str, &str)]>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            fake_server(r#""error":{"code":-32600,"message":"unsupported protocol"}"#)
+        }));
+
+        let err = service.with_initialized_client("engine", |_, init| Ok(init)).unwrap_err();
+        assert!(err.contains("JSON-RPC error -32600"), "{}", err);
+        assert_eq!(spawns.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn validate_spec_probes_good_command_and_rejects_missing_one() {
+        let script = r#"printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-11-05","serverInfo":{"name":"fake"}}}'; cat > /dev/null"#;
+        let good = McpServerSpec {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: Default::default(),
+            cwd: None,
+        };
+        let report = validate_spec(&good, true, Duration::from_secs(5));
+        assert!(report.ok, "{:?}", report.errors);
+        assert!(report.resolved_command.as_deref().is_some_and(|p| p.ends_with("/sh")));
+        assert_eq!(report.handshake.unwrap().server_info["name"], "fake");
+
+        let missing = McpServerSpec {
+            command: "aidd-no-such-command".to_string(),
+            args: vec!["-y @aidd.md/mcp-engine".to_string()],
+            ..good
+        };
+        let report = validate_spec(&missing, true, Duration::from_secs(5));
+        assert!(!report.ok);
+        assert_eq!(report.errors, vec!["Command 'aidd-no-such-command' not found on PATH"]);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.handshake.is_none());
+    }
 }
 
 impl McpPort for McpService {
-    fn start_server(&self, package: &str, mode: McpServerMode) -> Result<McpServer, String> {
-        self.process_manager.start(package, mode)
+    fn start_server(
+        &self,
+        package: &str,
+        mode: McpServerMode,
+        env: HashMap<String, String>,
+        extra_args: Vec<String>,
+    ) -> Result<McpServer, String> {
+        self.process_manager.start(package, mode, env, extra_args)
     }
 
-    fn stop_server(&self, server_id: &str) -> Result<(), String> {
-        self.process_manager.stop(server_id)
+    fn stop_server(&self, server_id: &str, grace: Duration) -> Result<(), String> {
+        self.process_manager.stop(server_id, grace)
     }
 
     fn stop_all(&self) -> Result<(), String> {
@@ -138,6 +494,19 @@ impl McpPort for McpService {
         self.process_manager.get_servers()
     }
 
+    fn get_server_logs(&self, server_id: &str) -> Result<String, String> {
+        self.process_manager.get_logs(server_id)
+    }
+
+    fn set_restart_policy(&self, server_id: &str, policy: RestartPolicy) -> Result<(), String> {
+        self.process_manager.set_restart_policy(server_id, policy)
+    }
+
+    fn ping_server(&self, server_id: &str) -> Result<u64, String> {
+        let latency = self.process_manager.ping(server_id, PING_TIMEOUT)?;
+        Ok(latency.as_millis() as u64)
+    }
+
     fn list_tools(&self, package: &str) -> Result<Vec<Value>, String> {
         self.with_client(package, |client| {
             let result = client.list_tools()?;
@@ -153,4 +522,38 @@ impl McpPort for McpService {
     fn call_tool(&self, package: &str, tool_name: &str, arguments: Value) -> Result<Value, String> {
         self.with_client(package, |client| client.call_tool(tool_name, arguments))
     }
+
+    fn connect_info(&self, package: &str) -> Result<McpConnectInfo, String> {
+        self.with_initialized_client(package, |client, init| Ok(build_connect_info(package, client, &init)))
+    }
+
+    fn validate_spec(&self, spec: &McpServerSpec, probe: bool) -> McpSpecValidation {
+        validate_spec(spec, probe, SPEC_PROBE_TIMEOUT)
+    }
+
+    fn reset_state(&self) -> Result<McpResetSummary, String> {
+        let mut summary = self.process_manager.reset();
+        let spawned = std::mem::take(&mut *self.spawned_clients.lock().map_err(|e| e.to_string())?);
+        for client in spawned.iter().filter_map(Weak::upgrade) {
+            summary.cancelled_requests += client.shutdown(RESET_REASON);
+            summary.clients_closed += 1;
+        }
+        // Pooled clients are tracked as spawned too, so they were shut down above
+        self.pool.lock().map_err(|e| e.to_string())?.clear();
+        Ok(summary)
+    }
+
+    fn shutdown_clients(&self) -> usize {
+        let pooled: Vec<Arc<McpClient>> = self
+            .pool
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain()
+            .map(|(_, client)| client)
+            .collect();
+        for client in &pooled {
+            client.shutdown(CLIENTS_SHUT_DOWN);
+        }
+        pooled.len()
+    }
 }
@@ -1,5 +1,7 @@
+use crate::domain::AiddError;
 use crate::domain::ports::inbound::{
-    MemoryPort, SessionSummary, ObservationEntry, EvolutionStatus, PatternStats,
+    MemoryPort, SessionSummary, SessionStats, ObservationEntry, EvolutionStatus, PatternStats, StorageInfo, MemoryPage, PermanentMemoryPage, ActivityEntry,
+    ConfigChange, DailyCount, ImportMode, ImportSummary, MemorySource,
 };
 
 /// Application Service for Memory queries.
@@ -14,14 +16,44 @@ impl MemoryService {
         Self { memory_port }
     }
 
+    /// Same use cases, scoped to one active project instead of the primary one.
+    pub fn for_project(&self, project_path: &str) -> Result<MemoryService, AiddError> {
+        Ok(Self::new(self.memory_port.for_project(project_path)?))
+    }
+
+    /// Same use cases, but reads report their errors instead of empty results.
+    pub fn strict(&self) -> MemoryService {
+        Self::new(self.memory_port.strict())
+    }
+
+    /// Which backend serves these reads (SQLite file or MCP engine)
+    pub fn source(&self) -> MemorySource {
+        self.memory_port.source()
+    }
+
     /// Use case: Get session summary with recent sessions
-    pub fn get_session_summary(&self) -> Result<SessionSummary, String> {
+    pub fn get_session_summary(&self) -> Result<SessionSummary, AiddError> {
         self.memory_port.get_session_summary()
     }
 
+    /// Use case: Session durations, discovery tokens and observation type counts
+    pub fn get_session_stats(&self) -> Result<SessionStats, AiddError> {
+        self.memory_port.get_session_stats()
+    }
+
     /// Use case: List all observations
-    pub fn list_all_observations(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        self.memory_port.list_all_observations(limit)
+    pub fn list_all_observations(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        types: Option<&[String]>,
+    ) -> Result<MemoryPage, AiddError> {
+        self.memory_port.list_all_observations(limit, cursor, types)
+    }
+
+    /// Use case: Observation counts per type, for filter facets
+    pub fn get_observation_type_counts(&self) -> Result<Vec<(String, usize)>, AiddError> {
+        self.memory_port.get_observation_type_counts()
     }
 
     /// Use case: List observations for a single session
@@ -29,7 +61,7 @@ impl MemoryService {
         &self,
         session_id: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
         self.memory_port.list_observations_by_session(session_id, limit)
     }
 
@@ -38,47 +70,78 @@ impl MemoryService {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<ObservationEntry>, String> {
+    ) -> Result<Vec<ObservationEntry>, AiddError> {
         self.memory_port.search_observations(query, limit)
     }
 
     /// Use case: Get evolution status
-    pub fn get_evolution_status(&self) -> Result<EvolutionStatus, String> {
+    pub fn get_evolution_status(&self) -> Result<EvolutionStatus, AiddError> {
         self.memory_port.get_evolution_status()
     }
 
     /// Use case: Get pattern statistics
-    pub fn get_pattern_stats(&self) -> Result<PatternStats, String> {
+    pub fn get_pattern_stats(&self) -> Result<PatternStats, AiddError> {
         self.memory_port.get_pattern_stats()
     }
 
+    /// Use case: Unified recent-activity feed across memory sources
+    pub fn get_activity_feed(&self, limit: Option<usize>) -> Result<Vec<ActivityEntry>, AiddError> {
+        self.memory_port.get_activity_feed(limit)
+    }
+
+    /// Use case: Per-day observation counts for timeline/heatmap views
+    pub fn observation_daily_counts(&self, since_ts: Option<i64>, until_ts: Option<i64>) -> Result<Vec<DailyCount>, AiddError> {
+        self.memory_port.observation_daily_counts(since_ts, until_ts)
+    }
+
+    /// Use case: Inspect database storage (journal mode, WAL sidecar files)
+    pub fn get_storage_info(&self) -> Result<StorageInfo, AiddError> {
+        self.memory_port.get_storage_info()
+    }
+
     /// Use case: List all sessions with full data
-    pub fn list_all_sessions(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        self.memory_port.list_all_sessions(limit)
+    pub fn list_all_sessions(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<MemoryPage, AiddError> {
+        self.memory_port.list_all_sessions(limit, cursor, from_ms, to_ms)
     }
 
     /// Use case: List evolution candidates with full data
-    pub fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, String> {
+    pub fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, AiddError> {
         self.memory_port.list_evolution_candidates()
     }
 
     /// Use case: List evolution log entries
-    pub fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+    pub fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
         self.memory_port.list_evolution_log(limit)
     }
 
     /// Use case: List permanent memory by type
-    pub fn list_permanent_memory(&self, memory_type: &str) -> Result<Vec<serde_json::Value>, String> {
-        self.memory_port.list_permanent_memory(memory_type)
+    pub fn list_permanent_memory(
+        &self,
+        memory_type: &str,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<PermanentMemoryPage, AiddError> {
+        self.memory_port.list_permanent_memory(memory_type, limit, offset)
+    }
+
+    /// Use case: Search permanent memory
+    pub fn search_permanent_memory(&self, query: &str, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        self.memory_port.search_permanent_memory(query, limit)
     }
 
     /// Use case: Delete a permanent memory entry
-    pub fn delete_permanent_memory(&self, memory_type: &str, id: &str) -> Result<(), String> {
+    pub fn delete_permanent_memory(&self, memory_type: &str, id: &str) -> Result<(), AiddError> {
         self.memory_port.delete_permanent_memory(memory_type, id)
     }
 
     /// Use case: List all drafts
-    pub fn list_drafts(&self) -> Result<Vec<serde_json::Value>, String> {
+    pub fn list_drafts(&self) -> Result<Vec<serde_json::Value>, AiddError> {
         self.memory_port.list_drafts()
     }
 
@@ -88,79 +151,100 @@ impl MemoryService {
         artifact_type: Option<&str>,
         status: Option<&str>,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
-        self.memory_port.list_artifacts(artifact_type, status, limit)
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
+        self.memory_port.list_artifacts(artifact_type, status, limit, from_ms, to_ms)
     }
 
     /// Use case: List recent pattern audit scores.
-    pub fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+    pub fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
         self.memory_port.list_audit_scores(limit)
     }
 
+    /// Use case: List recent pattern detections with their patterns.
+    pub fn list_pattern_detections(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        self.memory_port.list_pattern_detections(limit)
+    }
+
+    /// Use case: Export the whole memory database as a portable JSON bundle, streamed to `out`
+    pub fn export_bundle(&self, out: &mut dyn std::io::Write) -> Result<(), AiddError> {
+        self.memory_port.export_bundle(out)
+    }
+
+    /// Use case: Restore an exported bundle, replacing or merging with existing rows
+    pub fn import_bundle(&self, bundle_json: &str, mode: ImportMode) -> Result<ImportSummary, AiddError> {
+        self.memory_port.import_bundle(bundle_json, mode)
+    }
+
     /// Use case: Read governance config from project-local data.db
-    pub fn get_governance_config(&self) -> Result<serde_json::Value, String> {
+    pub fn get_governance_config(&self) -> Result<serde_json::Value, AiddError> {
         self.memory_port.get_governance_config()
     }
 
     /// Use case: Persist governance config in project-local data.db
-    pub fn upsert_governance_config(&self, config_json: &str) -> Result<(), String> {
+    pub fn upsert_governance_config(&self, config_json: &str) -> Result<Vec<ConfigChange>, AiddError> {
         self.memory_port.upsert_governance_config(config_json)
     }
 
     // --- Write operations ---
 
-    pub fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, String> {
+    pub fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, AiddError> {
         self.memory_port.create_permanent_memory(memory_type, title, content)
     }
 
-    pub fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), String> {
+    pub fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), AiddError> {
         self.memory_port.update_permanent_memory(id, title, content)
     }
 
-    pub fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, String> {
+    pub fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, AiddError> {
         self.memory_port.create_artifact(artifact_type, feature, title, description, content)
     }
 
-    pub fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), String> {
+    pub fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), AiddError> {
         self.memory_port.update_artifact(id, artifact_type, feature, title, description, content, status)
     }
 
-    pub fn archive_artifact(&self, id: &str) -> Result<(), String> {
+    pub fn archive_artifact(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.archive_artifact(id)
     }
 
-    pub fn delete_artifact(&self, id: &str) -> Result<(), String> {
+    pub fn delete_artifact(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.delete_artifact(id)
     }
 
-    pub fn approve_evolution_candidate(&self, id: &str) -> Result<(), String> {
+    pub fn approve_evolution_candidate(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.approve_evolution_candidate(id)
     }
 
-    pub fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), String> {
+    pub fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), AiddError> {
         self.memory_port.reject_evolution_candidate(id, reason)
     }
 
-    pub fn approve_draft(&self, id: &str) -> Result<(), String> {
+    pub fn approve_draft(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.approve_draft(id)
     }
 
-    pub fn reject_draft(&self, id: &str, reason: &str) -> Result<(), String> {
+    pub fn reject_draft(&self, id: &str, reason: &str) -> Result<(), AiddError> {
         self.memory_port.reject_draft(id, reason)
     }
 
-    pub fn delete_session(&self, id: &str) -> Result<(), String> {
+    pub fn delete_session(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.delete_session(id)
     }
 
-    pub fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), String> {
+    pub fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), AiddError> {
         self.memory_port.update_session(id, branch, input, output)
     }
 
-    pub fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), String> {
+    pub fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), AiddError> {
         self.memory_port.update_session_full(id, updates_json)
     }
 
+    pub fn reopen_session(&self, id: &str) -> Result<(), AiddError> {
+        self.memory_port.reopen_session(id)
+    }
+
     // --- Observation CRUD ---
 
     pub fn create_observation(
@@ -174,8 +258,9 @@ impl MemoryService {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<String, String> {
-        self.memory_port.create_observation(session_id, obs_type, title, narrative, facts, concepts, files_read, files_modified, discovery_tokens)
+        created_at: Option<&str>,
+    ) -> Result<String, AiddError> {
+        self.memory_port.create_observation(session_id, obs_type, title, narrative, facts, concepts, files_read, files_modified, discovery_tokens, created_at)
     }
 
     pub fn update_observation(
@@ -189,11 +274,11 @@ impl MemoryService {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<(), String> {
+    ) -> Result<(), AiddError> {
         self.memory_port.update_observation(id, obs_type, title, narrative, facts, concepts, files_read, files_modified, discovery_tokens)
     }
 
-    pub fn delete_observation(&self, id: &str) -> Result<(), String> {
+    pub fn delete_observation(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.delete_observation(id)
     }
 
@@ -205,7 +290,7 @@ impl MemoryService {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, AiddError> {
         self.memory_port.create_evolution_candidate(evo_type, title, confidence, data)
     }
 
@@ -216,12 +301,12 @@ impl MemoryService {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AiddError> {
         self.memory_port.update_evolution_candidate_entry(id, evo_type, title, confidence, data)
     }
 
-    pub fn delete_evolution_candidate(&self, id: &str) -> Result<(), String> {
-        self.memory_port.delete_evolution_candidate(id)
+    pub fn delete_evolution_candidate(&self, id: &str, preserve_log: bool) -> Result<(), AiddError> {
+        self.memory_port.delete_evolution_candidate(id, preserve_log)
     }
 
     // --- Draft CRUD ---
@@ -234,21 +319,50 @@ impl MemoryService {
         content: &str,
         confidence: f64,
         source: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, AiddError> {
         self.memory_port.create_draft(category, title, filename, content, confidence, source)
     }
 
-    pub fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), String> {
+    pub fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), AiddError> {
         self.memory_port.update_draft(id, title, content, category, confidence, filename)
     }
 
-    pub fn delete_draft(&self, id: &str) -> Result<(), String> {
+    pub fn delete_draft(&self, id: &str) -> Result<(), AiddError> {
         self.memory_port.delete_draft(id)
     }
 
+    pub fn delete_drafts_by_status(&self, status: &str) -> Result<usize, AiddError> {
+        self.memory_port.delete_drafts_by_status(status)
+    }
+
+    pub fn create_banned_pattern(&self, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>) -> Result<String, AiddError> {
+        self.memory_port.create_banned_pattern(category, pattern, pattern_type, severity, hint)
+    }
+
+    pub fn update_banned_pattern(&self, id: &str, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>, active: bool) -> Result<(), AiddError> {
+        self.memory_port.update_banned_pattern(id, category, pattern, pattern_type, severity, hint, active)
+    }
+
+    pub fn mark_detection_false_positive(&self, id: i64) -> Result<(), AiddError> {
+        self.memory_port.mark_detection_false_positive(id)
+    }
+
+    pub fn delete_drafts(&self, ids: &[String]) -> Result<usize, AiddError> {
+        self.memory_port.delete_drafts(ids)
+    }
+
+    pub fn reject_drafts(&self, ids: &[String], reason: &str) -> Result<usize, AiddError> {
+        self.memory_port.reject_drafts(ids, reason)
+    }
+
+    pub fn archive_artifacts(&self, ids: &[String]) -> Result<usize, AiddError> {
+        self.memory_port.archive_artifacts(ids)
+    }
+
     /// Use case: Get complete memory snapshot (all data)
-    pub fn get_memory_snapshot(&self) -> Result<MemorySnapshot, String> {
+    pub fn get_memory_snapshot(&self) -> Result<MemorySnapshot, AiddError> {
         Ok(MemorySnapshot {
+            source: self.source(),
             sessions: self.get_session_summary()?,
             observations: vec![],
             evolution: self.get_evolution_status()?,
@@ -257,10 +371,39 @@ impl MemoryService {
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct MemorySnapshot {
+    /// Backend the snapshot was read from.
+    pub source: MemorySource,
     pub sessions: SessionSummary,
     pub observations: Vec<ObservationEntry>,
     pub evolution: EvolutionStatus,
     pub patterns: PatternStats,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::application::ProjectService;
+    use crate::domain::ports::outbound::HubData;
+    use crate::infrastructure::adapters::{McpMemoryAdapter, SqliteMemoryAdapter};
+    use crate::infrastructure::clock::SystemClock;
+    use crate::infrastructure::filesystem::FileAdapter;
+    use crate::infrastructure::mcp::McpClient;
+    use crate::infrastructure::persistence::MemoryStore;
+
+    #[cfg(unix)]
+    #[test]
+    fn source_reflects_the_backing_adapter() {
+        let projects = Arc::new(ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock)));
+        let sqlite = MemoryService::new(Box::new(SqliteMemoryAdapter::new(projects)));
+        assert_eq!(sqlite.source(), MemorySource::Sqlite);
+
+        // The engine adapter is tagged without issuing any request
+        let client = Arc::new(McpClient::spawn("sleep", &["30"]).unwrap());
+        let engine = MemoryService::new(Box::new(McpMemoryAdapter::new(client)));
+        assert_eq!(engine.source(), MemorySource::Engine);
+        assert_eq!(serde_json::to_value(engine.source()).unwrap(), "engine");
+    }
+}
@@ -7,9 +7,9 @@ mod override_service;
 mod memory_service;
 
 pub use project_service::ProjectService;
-pub use framework_service::FrameworkService;
+pub use framework_service::{FrameworkService, DEFAULT_AUTO_SYNC_INTERVAL_SECS};
 pub use integration_service::IntegrationService;
 pub use mcp_service::McpService;
-pub use mcp_health_service::McpHealthService;
+pub use mcp_health_service::{McpHealthService, DEFAULT_HEALTH_INTERVAL_SECS};
 pub use override_service::OverrideService;
 pub use memory_service::{MemoryService, MemorySnapshot};
@@ -2,8 +2,10 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::domain::model::{
-    AgentOverrides, EffectiveEntity, FrameworkEntity, ProjectOverrides, FRAMEWORK_CATEGORIES,
+    AgentOverrides, BudgetReport, CategoryBudget, DiffStatus, EffectiveEntity, EntityDiff, FrameworkEntity, MergeMode,
+    ProjectOverrides, FRAMEWORK_CATEGORIES,
 };
+use super::framework_service::{parse_frontmatter, sanitize_entity_name, split_frontmatter};
 use crate::domain::ports::outbound::FileSystemPort;
 
 pub struct OverrideService {
@@ -43,13 +45,23 @@ impl OverrideService {
         enabled: bool,
     ) -> Result<(), String> {
         let mut overrides = self.load_agent_overrides(project_path)?;
+        overrides.migrate_skill_overrides();
+        toggle_disabled(&mut overrides.disabled, agent, enabled);
+        self.save_agent_overrides(project_path, &overrides)
+    }
 
-        if enabled {
-            overrides.disabled.retain(|a| a != agent);
-        } else if !overrides.disabled.contains(&agent.to_string()) {
-            overrides.disabled.push(agent.to_string());
+    /// Enable or disable a skill for a project, independently of agent routing.
+    pub fn set_skill_override(
+        &self,
+        project_path: &str,
+        skill: &str,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let mut overrides = self.load_agent_overrides(project_path)?;
+        overrides.migrate_skill_overrides();
+        if let Some(skills) = overrides.disabled_skills.as_mut() {
+            toggle_disabled(skills, skill, enabled);
         }
-
         self.save_agent_overrides(project_path, &overrides)
     }
 
@@ -62,6 +74,7 @@ impl OverrideService {
         name: &str,
         content: &str,
     ) -> Result<(), String> {
+        let name = sanitize_entity_name(name)?;
         let rules_dir = self.overrides_dir(project_path).join("rules");
         self.fs.create_dir_all(&rules_dir.to_string_lossy())?;
 
@@ -71,6 +84,7 @@ impl OverrideService {
 
     /// Remove a project-specific rule.
     pub fn remove_project_rule(&self, project_path: &str, name: &str) -> Result<(), String> {
+        let name = sanitize_entity_name(name)?;
         let file_path = self
             .overrides_dir(project_path)
             .join("rules")
@@ -106,12 +120,7 @@ impl OverrideService {
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default();
 
-                let enabled = if category == "skills" {
-                    // For skills, check if the agent (skill name) is disabled
-                    !agents.disabled.iter().any(|a| a == &name)
-                } else {
-                    true
-                };
+                let enabled = category != "skills" || !agents.is_skill_disabled(&name);
 
                 let content = self.fs.read_to_string(&entry.to_string_lossy()).ok();
 
@@ -121,6 +130,7 @@ impl OverrideService {
                     source: "global".to_string(),
                     enabled,
                     content,
+                    merge_mode: None,
                 });
             }
         }
@@ -135,12 +145,22 @@ impl OverrideService {
                     .unwrap_or_default();
 
                 let content = self.fs.read_to_string(&entry.to_string_lossy()).ok();
+                let mode = content
+                    .as_deref()
+                    .map(|c| {
+                        let (frontmatter, _) = parse_frontmatter(c);
+                        MergeMode::parse(frontmatter.get("merge").and_then(|v| v.as_str()))
+                    })
+                    .unwrap_or_default();
 
                 // Check if we already have a global entity with same name
                 if let Some(existing) = result.iter_mut().find(|e| e.name == name) {
-                    // Override replaces the global content
                     existing.source = "override".to_string();
-                    existing.content = content;
+                    existing.content = match (mode, existing.content.as_deref(), content.as_deref()) {
+                        (MergeMode::Replace, _, _) | (_, None, _) | (_, _, None) => content,
+                        (mode, Some(global), Some(local)) => Some(merge_content(global, local, mode)),
+                    };
+                    existing.merge_mode = Some(mode);
                 } else {
                     // New entity from project
                     result.push(EffectiveEntity {
@@ -149,6 +169,7 @@ impl OverrideService {
                         source: "override".to_string(),
                         enabled: true,
                         content,
+                        merge_mode: Some(mode),
                     });
                 }
             }
@@ -158,6 +179,76 @@ impl OverrideService {
         Ok(result)
     }
 
+    /// Compare a project override with the global entity it customizes, as raw
+    /// file contents (before any `merge:` is applied).
+    pub fn diff_override(&self, project_path: &str, category: &str, name: &str) -> Result<EntityDiff, String> {
+        validate_category(category)?;
+        let name = sanitize_entity_name(name)?;
+        let file_name = format!("{}.md", name);
+        let read = |path: PathBuf| {
+            let path = path.to_string_lossy();
+            if self.fs.exists(&path) { self.fs.read_to_string(&path).map(Some) } else { Ok(None) }
+        };
+        let global_content = read(self.framework_path.join(category).join(&file_name))?;
+        let override_content = read(self.overrides_dir(project_path).join(category).join(&file_name))?;
+
+        let status = match (&global_content, &override_content) {
+            (Some(global), Some(local)) if global == local => DiffStatus::Unchanged,
+            (Some(_), Some(_)) => DiffStatus::Modified,
+            (None, Some(_)) => DiffStatus::Added,
+            (Some(_), None) => DiffStatus::Removed,
+            (None, None) => return Err(format!("No global or override entity '{}' in {}", name, category)),
+        };
+        let unified_diff = if status == DiffStatus::Unchanged {
+            String::new()
+        } else {
+            similar::TextDiff::from_lines(
+                global_content.as_deref().unwrap_or_default(),
+                override_content.as_deref().unwrap_or_default(),
+            )
+            .unified_diff()
+            .header(&format!("global/{}/{}", category, file_name), &format!("override/{}/{}", category, file_name))
+            .to_string()
+        };
+
+        Ok(EntityDiff {
+            name,
+            category: category.to_string(),
+            status,
+            global_content,
+            override_content,
+            unified_diff,
+        })
+    }
+
+    /// Sum the size of every enabled effective entity, per category and overall.
+    pub fn content_budget(&self, project_path: &str) -> Result<BudgetReport, String> {
+        let mut report = BudgetReport {
+            project_path: project_path.to_string(),
+            ..BudgetReport::default()
+        };
+
+        for category in FRAMEWORK_CATEGORIES {
+            let mut budget = CategoryBudget {
+                category: category.to_string(),
+                ..CategoryBudget::default()
+            };
+            for entity in self.get_effective_entities(project_path, category)? {
+                if !entity.enabled {
+                    continue;
+                }
+                budget.entity_count += 1;
+                budget.bytes += entity.content.as_deref().map_or(0, |c| c.len() as u64);
+            }
+            budget.estimated_tokens = estimate_tokens(budget.bytes);
+            report.total_bytes += budget.bytes;
+            report.categories.push(budget);
+        }
+
+        report.total_estimated_tokens = estimate_tokens(report.total_bytes);
+        Ok(report)
+    }
+
     // ── Private helpers ──────────────────────────────────────────────────
 
     fn overrides_dir(&self, project_path: &str) -> PathBuf {
@@ -242,6 +333,35 @@ impl OverrideService {
     }
 }
 
+/// Marks where project content joins global content in a merged entity.
+const MERGE_SEPARATOR: &str = "\n\n<!-- project override -->\n\n";
+
+/// Combine a global entity with an append/prepend override. The override's own
+/// frontmatter is dropped; the global frontmatter, if any, stays on top.
+fn merge_content(global: &str, local: &str, mode: MergeMode) -> String {
+    let (_, local_body) = parse_frontmatter(local);
+    let local_body = local_body.trim();
+    let (head, global_body) = split_frontmatter(global).map_or(("", global), |(_, head, body)| (head, body));
+    let global_body = global_body.trim();
+    match mode {
+        MergeMode::Prepend => format!("{}{}{}{}", head, local_body, MERGE_SEPARATOR, global_body),
+        _ => format!("{}{}{}{}", head, global_body, MERGE_SEPARATOR, local_body),
+    }
+}
+
+/// Rough token count: about four bytes per token for English markdown.
+fn estimate_tokens(bytes: u64) -> u64 {
+    bytes.div_ceil(4)
+}
+
+fn toggle_disabled(disabled: &mut Vec<String>, name: &str, enabled: bool) {
+    if enabled {
+        disabled.retain(|n| n != name);
+    } else if !disabled.iter().any(|n| n == name) {
+        disabled.push(name.to_string());
+    }
+}
+
 /// List .md files in a directory (non-recursive, skip hidden).
 fn list_md_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
     let entries = std::fs::read_dir(dir)
@@ -279,3 +399,159 @@ fn validate_category(category: &str) -> Result<(), String> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::filesystem::FileAdapter;
+
+    #[test]
+    fn skill_toggles_leave_same_named_agents_alone() {
+        let root = std::env::temp_dir().join(format!("aidd-skill-overrides-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let (framework, project) = (root.join("framework"), root.join("project"));
+        std::fs::create_dir_all(framework.join("skills")).unwrap();
+        std::fs::write(framework.join("skills").join("reviewer.md"), "Review code.").unwrap();
+        std::fs::write(framework.join("skills").join("planner.md"), "Plan work.").unwrap();
+        let project = project.to_string_lossy().to_string();
+        let service = OverrideService::new(&framework, Arc::new(FileAdapter));
+        let skill_enabled = |name: &str| {
+            service.get_effective_entities(&project, "skills").unwrap()
+                .into_iter().find(|e| e.name == name).unwrap().enabled
+        };
+
+        // A legacy agents.json disables both the agent and the skill named `planner`
+        std::fs::create_dir_all(service.overrides_dir(&project)).unwrap();
+        std::fs::write(service.overrides_dir(&project).join("agents.json"), r#"{"disabled": ["planner"]}"#).unwrap();
+        assert!(!skill_enabled("planner"));
+
+        service.set_skill_override(&project, "reviewer", false).unwrap();
+        let agents = service.get_overrides(&project).unwrap().agents;
+        assert_eq!(agents.disabled, ["planner"]);
+        assert_eq!(agents.disabled_skills.as_deref(), Some(&["planner".to_string(), "reviewer".to_string()][..]));
+        assert!(!skill_enabled("reviewer"));
+
+        // Re-enabling the migrated skill keeps the agent disabled, and vice versa
+        service.set_skill_override(&project, "planner", true).unwrap();
+        assert!(skill_enabled("planner"));
+        service.set_agent_override(&project, "planner", true).unwrap();
+        service.set_agent_override(&project, "reviewer", false).unwrap();
+        let agents = service.get_overrides(&project).unwrap().agents;
+        assert_eq!(agents.disabled, ["reviewer"]);
+        assert_eq!(agents.disabled_skills.as_deref(), Some(&["reviewer".to_string()][..]));
+        assert!(skill_enabled("planner"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_rule_names_cannot_escape_the_rules_dir() {
+        let root = std::env::temp_dir().join(format!("aidd-rule-traversal-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let project = root.join("project").to_string_lossy().to_string();
+        let service = OverrideService::new(&root.join("framework"), Arc::new(FileAdapter));
+
+        assert!(service.add_project_rule(&project, "../../../escaped", "pwned").is_err());
+        assert!(!root.join("escaped.md").exists());
+        assert!(service.remove_project_rule(&project, "/etc/hosts").is_err());
+
+        service.add_project_rule(&project, "style", "Use tabs.").unwrap();
+        service.remove_project_rule(&project, "style").unwrap();
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn override_merge_modes_combine_with_global_rules() {
+        let root = std::env::temp_dir().join(format!("aidd-merge-overrides-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let framework = root.join("framework");
+        let project = root.join("project").to_string_lossy().to_string();
+        std::fs::create_dir_all(framework.join("rules")).unwrap();
+        for name in ["style", "tests", "docs"] {
+            std::fs::write(framework.join("rules").join(format!("{}.md", name)), format!("---\nname: {}\n---\nGlobal {}.", name, name)).unwrap();
+        }
+        let service = OverrideService::new(&framework, Arc::new(FileAdapter));
+        service.add_project_rule(&project, "style", "---\nmerge: append\n---\nAlso lint.").unwrap();
+        service.add_project_rule(&project, "tests", "---\nmerge: prepend\n---\nRun e2e first.").unwrap();
+        service.add_project_rule(&project, "docs", "Project docs only.").unwrap();
+
+        let entities = service.get_effective_entities(&project, "rules").unwrap();
+        let entity = |name: &str| entities.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(
+            entity("style").content.as_deref(),
+            Some("---\nname: style\n---\nGlobal style.\n\n<!-- project override -->\n\nAlso lint.")
+        );
+        assert_eq!(entity("style").merge_mode, Some(MergeMode::Append));
+        assert_eq!(
+            entity("tests").content.as_deref(),
+            Some("---\nname: tests\n---\nRun e2e first.\n\n<!-- project override -->\n\nGlobal tests.")
+        );
+        // No `merge:` keeps the old full replacement
+        assert_eq!(entity("docs").content.as_deref(), Some("Project docs only."));
+        assert_eq!(entity("docs").merge_mode, Some(MergeMode::Replace));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn content_budget_sums_enabled_effective_entities() {
+        let root = std::env::temp_dir().join(format!("aidd-content-budget-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let framework = root.join("framework");
+        let project = root.join("project").to_string_lossy().to_string();
+        std::fs::create_dir_all(framework.join("rules")).unwrap();
+        std::fs::create_dir_all(framework.join("skills")).unwrap();
+        std::fs::write(framework.join("rules").join("style.md"), "a".repeat(40)).unwrap();
+        std::fs::write(framework.join("rules").join("tests.md"), "b".repeat(10)).unwrap();
+        std::fs::write(framework.join("skills").join("reviewer.md"), "c".repeat(100)).unwrap();
+        std::fs::write(framework.join("skills").join("planner.md"), "d".repeat(1000)).unwrap();
+        let service = OverrideService::new(&framework, Arc::new(FileAdapter));
+        // The override replaces the 10-byte global rule; the disabled skill is not injected
+        service.add_project_rule(&project, "tests", &"e".repeat(20)).unwrap();
+        service.set_skill_override(&project, "planner", false).unwrap();
+
+        let report = service.content_budget(&project).unwrap();
+        let category = |name: &str| report.categories.iter().find(|c| c.category == name).unwrap();
+        assert_eq!(report.categories.len(), FRAMEWORK_CATEGORIES.len());
+        assert_eq!((category("rules").entity_count, category("rules").bytes), (2, 60));
+        assert_eq!(category("rules").estimated_tokens, 15);
+        assert_eq!((category("skills").entity_count, category("skills").bytes), (1, 100));
+        assert_eq!(category("agents").bytes, 0);
+        assert_eq!(report.total_bytes, 160);
+        assert_eq!(report.total_estimated_tokens, 40);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn diff_override_reports_changed_lines_against_the_global_rule() {
+        let root = std::env::temp_dir().join(format!("aidd-override-diff-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let framework = root.join("framework");
+        let project = root.join("project").to_string_lossy().to_string();
+        std::fs::create_dir_all(framework.join("rules")).unwrap();
+        std::fs::write(framework.join("rules").join("style.md"), "# Style\nUse tabs.\nWrap at 80.\nNo emoji.\n").unwrap();
+        std::fs::write(framework.join("rules").join("docs.md"), "Document APIs.\n").unwrap();
+        let service = OverrideService::new(&framework, Arc::new(FileAdapter));
+        service.add_project_rule(&project, "style", "# Style\nUse spaces.\nWrap at 100.\nNo emoji.\n").unwrap();
+        service.add_project_rule(&project, "local", "Project only.\n").unwrap();
+
+        let diff = service.diff_override(&project, "rules", "style").unwrap();
+        assert_eq!(diff.status, DiffStatus::Modified);
+        let changed = |prefix: char, header: &str| {
+            diff.unified_diff.lines().filter(|l| l.starts_with(prefix) && !l.starts_with(header)).collect::<Vec<_>>()
+        };
+        assert_eq!(changed('-', "---"), ["-Use tabs.", "-Wrap at 80."]);
+        assert_eq!(changed('+', "+++"), ["+Use spaces.", "+Wrap at 100."]);
+        assert!(diff.unified_diff.starts_with("--- global/rules/style.md\n+++ override/rules/style.md\n"));
+
+        assert_eq!(service.diff_override(&project, "rules", "local").unwrap().status, DiffStatus::Added);
+        let removed = service.diff_override(&project, "rules", "docs").unwrap();
+        assert_eq!((removed.status, removed.override_content), (DiffStatus::Removed, None));
+        assert!(service.diff_override(&project, "rules", "missing").is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,21 +1,35 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::domain::model::{AiddMarkers, Project, ProjectEntry};
+use crate::domain::model::{AiddMarkers, DetectionPolicy, Project, ProjectDiagnostics, ProjectEntry};
 use crate::domain::ports::inbound::ProjectPort;
-use crate::domain::ports::outbound::{FileSystemPort, ProjectRepository};
+use crate::domain::ports::outbound::{Clock, FileSystemPort, ProjectRepository};
+
+/// Subfolder some projects keep their AIDD files in instead of the repo root.
+const AI_SUBFOLDER: &str = "ai";
 
 pub struct ProjectService {
     repository: Arc<dyn ProjectRepository>,
     fs: Arc<dyn FileSystemPort>,
+    clock: Arc<dyn Clock>,
+    /// Where the ancestor walk in `find_aidd_root` gives up: `~/.aidd/` is the
+    /// Hub's own data dir, not a project marker.
+    home_dir: Option<PathBuf>,
 }
 
 impl ProjectService {
     pub fn new(
         repository: Arc<dyn ProjectRepository>,
         fs: Arc<dyn FileSystemPort>,
+        clock: Arc<dyn Clock>,
     ) -> Self {
-        Self { repository, fs }
+        Self { repository, fs, clock, home_dir: dirs::home_dir() }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_home_dir(mut self, home_dir: &Path) -> Self {
+        self.home_dir = Some(home_dir.to_path_buf());
+        self
     }
 
     /// Resolve the content directory for a category, checking config overrides first.
@@ -48,6 +62,47 @@ impl ProjectService {
         project_root.join("content").join(category)
     }
 
+    /// Markers for `root` treated as the project root (its own `.aidd/` and content dirs).
+    fn markers_at(&self, root: &Path) -> AiddMarkers {
+        let aidd_dir = root.join(".aidd");
+        let config_paths = self.read_config_paths(&aidd_dir);
+        let has = |category: &str| {
+            self.fs.is_dir(&self.resolve_content_path(root, &aidd_dir, category, &config_paths).to_string_lossy())
+        };
+
+        AiddMarkers {
+            agents: has("agents"),
+            rules: has("rules"),
+            skills: has("skills"),
+            workflows: has("workflows"),
+            specs: has("specs"),
+            knowledge: has("knowledge"),
+            templates: has("templates"),
+            aidd_dir: self.fs.is_dir(&aidd_dir.to_string_lossy()),
+            memory: self.fs.is_dir(&aidd_dir.join("memory").to_string_lossy()),
+        }
+    }
+
+    /// The directory holding the project's AIDD files, with its markers. Falls back
+    /// to the `ai/` subfolder (the legacy layout) when the top level has no content
+    /// dirs or `AGENTS.md` but `ai/` does.
+    fn resolve_content_root(&self, project: &Path) -> (PathBuf, AiddMarkers) {
+        let markers = self.markers_at(project);
+        let has_agents_md = |dir: &Path| self.fs.exists(&dir.join("AGENTS.md").to_string_lossy());
+        if markers.has_content_dir() || has_agents_md(project) {
+            return (project.to_path_buf(), markers);
+        }
+
+        let ai = project.join(AI_SUBFOLDER);
+        if self.fs.is_dir(&ai.to_string_lossy()) {
+            let ai_markers = self.markers_at(&ai);
+            if ai_markers.has_content_dir() || has_agents_md(&ai) {
+                return (ai, ai_markers);
+            }
+        }
+        (project.to_path_buf(), markers)
+    }
+
     /// Try to read content.paths from .aidd/config.json
     fn read_config_paths(&self, aidd_dir: &Path) -> Option<serde_json::Value> {
         let config_path = aidd_dir.join("config.json");
@@ -57,6 +112,107 @@ impl ProjectService {
             .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
             .and_then(|v| v.get("content")?.get("paths").cloned())
     }
+
+    fn has_aidd_root_marker(&self, dir: &Path) -> bool {
+        self.fs.is_dir(&dir.join(".aidd").to_string_lossy())
+            || self.fs.exists(&dir.join("AGENTS.md").to_string_lossy())
+    }
+
+    /// Walk up from `project` to the nearest ancestor carrying `.aidd/` or `AGENTS.md`.
+    /// `None` when the project has its own markers or no ancestor within the same
+    /// repository (up to the first `.git`) does. The home directory ends the walk.
+    fn find_aidd_root(&self, project: &Path) -> Option<String> {
+        if self.has_aidd_root_marker(project) || self.is_repo_root(project) {
+            return None;
+        }
+        for dir in project.ancestors().skip(1) {
+            if self.home_dir.as_deref() == Some(dir) {
+                return None;
+            }
+            if self.has_aidd_root_marker(dir) {
+                return Some(dir.to_string_lossy().to_string());
+            }
+            if self.is_repo_root(dir) {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// `.git` is a directory in a clone and a file in worktrees and submodules.
+    fn is_repo_root(&self, dir: &Path) -> bool {
+        self.fs.exists(&dir.join(".git").to_string_lossy())
+    }
+
+    /// Expand the workspace patterns declared at `project` into member directories
+    /// that contain a `package.json`.
+    fn find_workspaces(&self, project: &Path, package_json: Option<&serde_json::Value>) -> Vec<String> {
+        let mut patterns = self
+            .fs
+            .read_to_string(&project.join("pnpm-workspace.yaml").to_string_lossy())
+            .map(|yaml| pnpm_workspace_patterns(&yaml))
+            .unwrap_or_default();
+        if let Some(workspaces) = package_json.and_then(|v| v.get("workspaces")) {
+            let list = workspaces.get("packages").unwrap_or(workspaces);
+            patterns.extend(
+                list.as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|p| p.as_str().map(String::from)),
+            );
+        }
+
+        // `!pattern` entries exclude what they match, whatever their position in the list
+        let (excludes, includes): (Vec<&String>, Vec<&String>) = patterns.iter().partition(|p| p.starts_with('!'));
+        let excluded: Vec<String> = excludes
+            .iter()
+            .flat_map(|p| self.expand_workspace_pattern(project, &p[1..]))
+            .collect();
+
+        let mut members = Vec::new();
+        for dir in includes.iter().flat_map(|p| self.expand_workspace_pattern(project, p)) {
+            if self.fs.exists(&Path::new(&dir).join("package.json").to_string_lossy())
+                && !excluded.contains(&dir)
+                && !members.contains(&dir)
+            {
+                members.push(dir);
+            }
+        }
+        members.sort();
+        members
+    }
+
+    /// Directories a single workspace pattern names: `dir/*` and `dir/**` list the
+    /// children of `dir`, anything else is taken as a literal path.
+    fn expand_workspace_pattern(&self, project: &Path, pattern: &str) -> Vec<String> {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        match pattern.strip_suffix("/*").or_else(|| pattern.strip_suffix("/**")) {
+            Some(parent) => self.fs.list_dirs(&project.join(parent).to_string_lossy()).unwrap_or_default(),
+            None => vec![project.join(pattern).to_string_lossy().to_string()],
+        }
+    }
+}
+
+/// Entries of the `packages:` list in a `pnpm-workspace.yaml`.
+fn pnpm_workspace_patterns(yaml: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !trimmed.starts_with('-') {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if in_packages {
+            if let Some(item) = trimmed.strip_prefix('-') {
+                patterns.push(item.trim().trim_matches(|c| c == '\'' || c == '"').to_string());
+            }
+        }
+    }
+    patterns
 }
 
 impl ProjectPort for ProjectService {
@@ -66,41 +222,34 @@ impl ProjectPort for ProjectService {
         }
 
         let p = Path::new(path);
-        let aidd_dir = p.join(".aidd");
-        let config_paths = self.read_config_paths(&aidd_dir);
-
-        let markers = AiddMarkers {
-            agents: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "agents", &config_paths).to_string_lossy()),
-            rules: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "rules", &config_paths).to_string_lossy()),
-            skills: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "skills", &config_paths).to_string_lossy()),
-            workflows: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "workflows", &config_paths).to_string_lossy()),
-            specs: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "specs", &config_paths).to_string_lossy()),
-            knowledge: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "knowledge", &config_paths).to_string_lossy()),
-            templates: self.fs.is_dir(&self.resolve_content_path(p, &aidd_dir, "templates", &config_paths).to_string_lossy()),
-            aidd_dir: self.fs.is_dir(&aidd_dir.to_string_lossy()),
-            memory: self.fs.is_dir(&aidd_dir.join("memory").to_string_lossy()),
-        };
+        let (content_root, markers) = self.resolve_content_root(p);
 
-        let detected = markers.agents || markers.rules || markers.skills;
+        // An unreadable store should not break browsing; fall back to the default policy
+        let policy = self.repository.load().map(|d| d.detection_policy).unwrap_or_default();
+        let has_agents_md = self.fs.exists(&content_root.join("AGENTS.md").to_string_lossy());
+        let detected = policy.is_detected(&markers, has_agents_md);
 
         // Try to read name from package.json
         let pkg_path = p.join("package.json");
-        let name = if self.fs.exists(&pkg_path.to_string_lossy()) {
-            self.fs
-                .read_to_string(&pkg_path.to_string_lossy())
-                .ok()
-                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
-                .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
-                .unwrap_or_else(|| dir_name(p))
-        } else {
-            dir_name(p)
-        };
+        let package_json = self
+            .fs
+            .read_to_string(&pkg_path.to_string_lossy())
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+        let name = package_json
+            .as_ref()
+            .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+            .unwrap_or_else(|| dir_name(p));
 
         Ok(Project {
             name,
             path: path.to_string(),
             detected,
             markers,
+            // Files under `ai/` belong to this project, not to some ancestor
+            aidd_root: if content_root == p { self.find_aidd_root(p) } else { None },
+            workspaces: self.find_workspaces(p, package_json.as_ref()),
+            content_root: content_root.to_string_lossy().to_string(),
         })
     }
 
@@ -125,6 +274,7 @@ impl ProjectPort for ProjectService {
     fn remove(&self, path: &str) -> Result<(), String> {
         let mut data = self.repository.load()?;
         data.projects.retain(|p| p.path != path);
+        data.active_projects.retain(|p| p != path);
 
         if data.active_project.as_deref() == Some(path) {
             data.active_project = data.projects.first().map(|p| p.path.clone());
@@ -139,13 +289,28 @@ impl ProjectPort for ProjectService {
         Ok(data.projects)
     }
 
+    fn list_by_recent(&self) -> Result<Vec<ProjectEntry>, String> {
+        let mut projects = self.repository.load()?.projects;
+        // Stable sort: never-opened projects keep registration order.
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_accessed));
+        Ok(projects)
+    }
+
     fn switch(&self, path: &str) -> Result<(), String> {
         let mut data = self.repository.load()?;
 
-        if !data.projects.iter().any(|p| p.path == path) {
-            return Err(format!("Project not found: {}", path));
-        }
+        // Strictly after every existing stamp, so back-to-back switches within
+        // one millisecond still order correctly.
+        let latest = data.projects.iter().map(|p| p.last_accessed).max().unwrap_or(0);
+        let now = self.clock.now_unix_ms() as u64;
+        let entry = data
+            .projects
+            .iter_mut()
+            .find(|p| p.path == path)
+            .ok_or_else(|| format!("Project not found: {}", path))?;
+        entry.last_accessed = now.max(latest + 1);
 
+        data.active_projects.retain(|p| p != path);
         data.active_project = Some(path.to_string());
         self.repository.save(&data)?;
         Ok(())
@@ -155,6 +320,97 @@ impl ProjectPort for ProjectService {
         let data = self.repository.load()?;
         Ok(data.active_project)
     }
+
+    fn list_active_projects(&self) -> Result<Vec<String>, String> {
+        Ok(self.repository.load()?.active_set())
+    }
+
+    fn add_active_project(&self, path: &str) -> Result<(), String> {
+        let mut data = self.repository.load()?;
+        if !data.is_registered(path) {
+            return Err(format!("Project not found: {}", path));
+        }
+        if data.active_project.is_none() {
+            data.active_project = Some(path.to_string());
+        } else if !data.active_set().iter().any(|p| p == path) {
+            data.active_projects.push(path.to_string());
+        }
+        self.repository.save(&data)
+    }
+
+    fn remove_active_project(&self, path: &str) -> Result<(), String> {
+        let mut data = self.repository.load()?;
+        data.active_projects.retain(|p| p != path);
+        if data.active_project.as_deref() == Some(path) {
+            data.active_project = if data.active_projects.is_empty() {
+                None
+            } else {
+                Some(data.active_projects.remove(0))
+            };
+        }
+        self.repository.save(&data)
+    }
+
+    fn detection_policy(&self) -> Result<DetectionPolicy, String> {
+        Ok(self.repository.load()?.detection_policy)
+    }
+
+    fn set_detection_policy(&self, policy: DetectionPolicy) -> Result<(), String> {
+        let mut data = self.repository.load()?;
+        data.detection_policy = policy;
+        self.repository.save(&data)
+    }
+
+    fn diagnose(&self, path: &str) -> ProjectDiagnostics {
+        let project = match self.detect(path) {
+            Ok(project) => project,
+            Err(_) => {
+                return ProjectDiagnostics {
+                    path: path.to_string(),
+                    name: dir_name(Path::new(path)),
+                    exists: false,
+                    detected: false,
+                    markers: None,
+                    issues: vec![format!("Directory not found: {}", path)],
+                }
+            }
+        };
+
+        let mut issues = Vec::new();
+        if !project.detected {
+            issues.push("Not detected as an aidd project under the current detection policy".to_string());
+        }
+        if !project.markers.aidd_dir {
+            issues.push("Missing .aidd/ directory".to_string());
+        } else if !self.fs.exists(&Path::new(path).join(".aidd").join("data.db").to_string_lossy()) {
+            issues.push("No memory database at .aidd/data.db".to_string());
+        }
+
+        ProjectDiagnostics {
+            path: project.path,
+            name: project.name,
+            exists: true,
+            detected: project.detected,
+            markers: Some(project.markers),
+            issues,
+        }
+    }
+
+    fn verify_all(&self) -> Result<Vec<ProjectDiagnostics>, String> {
+        let projects = self.repository.load()?.projects;
+        // Projects are independent, so check them side by side; joining in spawn order
+        // keeps the result in registration order.
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = projects
+                .iter()
+                .map(|entry| scope.spawn(move || self.diagnose(&entry.path)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().map_err(|_| "Project diagnostics thread panicked".to_string()))
+                .collect()
+        })
+    }
 }
 
 fn dir_name(p: &Path) -> String {
@@ -162,3 +418,216 @@ fn dir_name(p: &Path) -> String {
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "unknown".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ports::outbound::HubData;
+    use crate::infrastructure::clock::{FixedClock, SystemClock};
+    use crate::infrastructure::filesystem::FileAdapter;
+    use crate::infrastructure::persistence::{JsonStore, MemoryStore};
+
+    fn entry(path: &str) -> ProjectEntry {
+        ProjectEntry { name: path.to_string(), path: path.to_string(), detected: true, last_accessed: 0 }
+    }
+
+    #[test]
+    fn switching_projects_orders_them_by_recency() {
+        let data = HubData {
+            projects: vec![entry("/a"), entry("/b"), entry("/c")],
+            ..HubData::default()
+        };
+        // A frozen clock: every switch lands in the same millisecond
+        let clock = Arc::new(FixedClock::at_unix_ms(1_769_904_000_000));
+        let service = ProjectService::new(Arc::new(MemoryStore::new(data)), Arc::new(FileAdapter), clock);
+
+        service.switch("/b").unwrap();
+        service.switch("/a").unwrap();
+        service.switch("/b").unwrap();
+
+        let order: Vec<String> = service.list_by_recent().unwrap().into_iter().map(|p| p.path).collect();
+        assert_eq!(order, vec!["/b", "/a", "/c"]);
+        // Registration order is untouched.
+        let registered: Vec<String> = service.list().unwrap().into_iter().map(|p| p.path).collect();
+        assert_eq!(registered, vec!["/a", "/b", "/c"]);
+        assert!(service.switch("/missing").is_err());
+    }
+
+    #[test]
+    fn strict_detection_rejects_bare_content_dirs() {
+        let root = std::env::temp_dir().join(format!("aidd-detect-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let template = root.join("template");
+        std::fs::create_dir_all(template.join("content").join("rules")).unwrap();
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".aidd").join("content").join("rules")).unwrap();
+        std::fs::write(project.join("AGENTS.md"), "# Agents").unwrap();
+        let detected = |service: &ProjectService, dir: &Path| service.detect(&dir.to_string_lossy()).unwrap().detected;
+
+        let service = ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock));
+        assert_eq!(service.detection_policy().unwrap(), DetectionPolicy::AnyContentDir);
+        assert!(detected(&service, &template));
+
+        for policy in [DetectionPolicy::RequireAiddDir, DetectionPolicy::RequireAgentsMd] {
+            service.set_detection_policy(policy).unwrap();
+            assert!(!detected(&service, &template), "{:?}", policy);
+            assert!(detected(&service, &project), "{:?}", policy);
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn registered_project_survives_reload() {
+        let root = std::env::temp_dir().join(format!("aidd-registry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".aidd")).unwrap();
+        let project_path = project.to_string_lossy().to_string();
+        let open = || ProjectService::new(Arc::new(JsonStore::at(root.join("hub.json"))), Arc::new(FileAdapter), Arc::new(SystemClock));
+
+        open().register(&project_path).unwrap();
+
+        // A fresh service over the same file sees the same registry and active project
+        let reloaded = open();
+        let paths: Vec<String> = reloaded.list().unwrap().into_iter().map(|p| p.path).collect();
+        assert_eq!(paths, vec![project_path.clone()]);
+        assert_eq!(reloaded.get_active_path().unwrap(), Some(project_path));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn detect_previews_markers_without_registering() {
+        let root = std::env::temp_dir().join(format!("aidd-preview-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd").join("content").join("agents")).unwrap();
+        std::fs::create_dir_all(root.join(".aidd").join("content").join("specs")).unwrap();
+        let service = ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock));
+
+        let project = service.detect(&root.to_string_lossy()).unwrap();
+        assert!(project.detected);
+        // Field names are shared with the frontend's `ProjectInfo['markers']`
+        let markers = serde_json::to_value(&project.markers).unwrap();
+        assert_eq!(markers["agents"], true);
+        assert_eq!(markers["specs"], true);
+        assert!(markers.get("agents_md").is_none() && markers.get("spec").is_none());
+        assert!(service.list().unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn monorepo_reports_workspace_members_and_root() {
+        let root = std::env::temp_dir().join(format!("aidd-monorepo-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        std::fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'packages/*'\n  - \"!packages/ignored\"\n\ncatalog:\n  - not-a-package\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("package.json"), r#"{ "name": "mono", "workspaces": ["apps/web"] }"#).unwrap();
+        for member in ["packages/a", "packages/ignored", "apps/web"] {
+            std::fs::create_dir_all(root.join(member)).unwrap();
+            std::fs::write(root.join(member).join("package.json"), "{}").unwrap();
+        }
+        std::fs::create_dir_all(root.join("packages").join("not-a-package")).unwrap();
+        let service = ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock));
+        let member = |p: &str| root.join(p).to_string_lossy().to_string();
+
+        let project = service.detect(&root.to_string_lossy()).unwrap();
+        assert_eq!(project.name, "mono");
+        assert_eq!(project.workspaces, vec![member("apps/web"), member("packages/a")]);
+        assert_eq!(project.aidd_root, None);
+
+        let package = service.detect(&member("packages/a")).unwrap();
+        assert!(package.workspaces.is_empty());
+        assert_eq!(package.aidd_root, Some(root.to_string_lossy().to_string()));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn aidd_root_walk_stops_at_the_repository_and_the_home_dir() {
+        let root = std::env::temp_dir().join(format!("aidd-root-walk-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        // `home/.aidd` stands in for the Hub's data dir; `home/outer` is an
+        // unrelated marker above the repository
+        let home = root.join("home");
+        let repo = home.join("outer").join("repo");
+        std::fs::create_dir_all(home.join(".aidd")).unwrap();
+        std::fs::write(home.join("outer").join("AGENTS.md"), "# Agents").unwrap();
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("packages").join("a")).unwrap();
+        let loose = home.join("scratch").join("tool");
+        std::fs::create_dir_all(&loose).unwrap();
+        let service = ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock))
+            .with_home_dir(&home);
+        let aidd_root = |dir: &Path| service.detect(&dir.to_string_lossy()).unwrap().aidd_root;
+
+        assert_eq!(aidd_root(&repo.join("packages").join("a")), None);
+        assert_eq!(aidd_root(&loose), None);
+
+        std::fs::create_dir_all(repo.join(".aidd")).unwrap();
+        assert_eq!(aidd_root(&repo.join("packages").join("a")), Some(repo.to_string_lossy().to_string()));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn verify_all_flags_missing_projects_alongside_healthy_ones() {
+        let root = std::env::temp_dir().join(format!("aidd-verify-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let healthy = root.join("healthy");
+        std::fs::create_dir_all(healthy.join(".aidd").join("content").join("rules")).unwrap();
+        std::fs::write(healthy.join(".aidd").join("data.db"), "").unwrap();
+        let healthy = healthy.to_string_lossy().to_string();
+        let missing = root.join("gone").to_string_lossy().to_string();
+        let data = HubData {
+            projects: vec![entry(&healthy), entry(&missing)],
+            ..HubData::default()
+        };
+        let service = ProjectService::new(Arc::new(MemoryStore::new(data)), Arc::new(FileAdapter), Arc::new(SystemClock));
+
+        let report = service.verify_all().unwrap();
+        let paths: Vec<&str> = report.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec![healthy.as_str(), missing.as_str()]);
+        assert!(report[0].exists && report[0].detected && report[0].issues.is_empty(), "{:?}", report[0].issues);
+        assert!(!report[1].exists);
+        assert!(report[1].markers.is_none());
+        assert!(!report[1].issues.is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn detects_root_and_ai_subfolder_layouts() {
+        let root = std::env::temp_dir().join(format!("aidd-ai-layout-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let top_level = root.join("top-level");
+        std::fs::create_dir_all(top_level.join(".aidd").join("content").join("rules")).unwrap();
+        std::fs::create_dir_all(top_level.join("ai").join("content").join("skills")).unwrap();
+        let nested = root.join("nested");
+        std::fs::create_dir_all(nested.join("ai").join("content").join("rules")).unwrap();
+        std::fs::write(nested.join("ai").join("AGENTS.md"), "# Agents").unwrap();
+        let service = ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock));
+        let detect = |dir: &Path| service.detect(&dir.to_string_lossy()).unwrap();
+
+        // Top-level markers win over an `ai/` folder
+        let project = detect(&top_level);
+        assert!(project.detected && project.markers.rules && !project.markers.skills);
+        assert_eq!(project.content_root, top_level.to_string_lossy());
+
+        let project = detect(&nested);
+        assert!(project.detected && project.markers.rules);
+        assert_eq!(project.content_root, nested.join("ai").to_string_lossy());
+        assert_eq!(project.aidd_root, None);
+
+        service.set_detection_policy(DetectionPolicy::RequireAgentsMd).unwrap();
+        assert!(detect(&nested).detected);
+        assert!(!detect(&top_level).detected);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
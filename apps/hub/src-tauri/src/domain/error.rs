@@ -0,0 +1,142 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Crate-level error type.
+///
+/// Serializes as `{ "code": "...", "message": "..." }` so the frontend can branch on
+/// a stable `code` instead of matching message text. Services still exposed through
+/// `Result<T, String>` convert via `From<AiddError> for String`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AiddError {
+    #[error("No active project configured")]
+    NoActiveProject,
+
+    #[error("Database not found: {path}")]
+    DbNotFound { path: String },
+
+    #[error("Invalid category '{got}'. Valid: {valid:?}")]
+    InvalidCategory { got: String, valid: Vec<String> },
+
+    #[error("Missing required table: {table}")]
+    SchemaMissing { table: String },
+
+    /// The engine that created the database is older than the Hub can query.
+    #[error("Memory database schema is version {found}, but version {expected} or newer is required; update the AIDD engine")]
+    SchemaOutdated { found: u32, expected: u32 },
+
+    #[error("{0}")]
+    Database(String),
+
+    /// The database stayed locked by another process (e.g. the engine) past the busy timeout.
+    #[error("{0}")]
+    DatabaseBusy(String),
+
+    #[error("{0}")]
+    Io(String),
+
+    #[error("{0}")]
+    Network(String),
+
+    #[error("{operation} timed out after {after_ms}ms")]
+    Timeout { operation: String, after_ms: u64 },
+
+    /// No connection could be made at all (no network, DNS failure, refused).
+    #[error("Can't reach {host}; check your internet connection")]
+    Offline { host: String },
+
+    /// The remote API refused the request until its rate limit resets.
+    #[error("{service} rate limit exceeded{}", .reset_at.as_ref().map(|t| format!(" (resets at {})", t)).unwrap_or_default())]
+    RateLimited { service: String, reset_at: Option<String> },
+
+    /// The remote API answered with a 5xx status.
+    #[error("{service} returned server error {status}")]
+    ServerError { service: String, status: u16 },
+
+    /// A write was refused because the file changed after the caller loaded it.
+    #[error("{path} was modified since it was loaded")]
+    Conflict { path: String, current_content: String },
+
+    /// Anything not yet given its own variant (mostly legacy `String` errors).
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AiddError {
+    /// Stable machine-readable code, suitable for i18n lookups.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AiddError::NoActiveProject => "no_active_project",
+            AiddError::DbNotFound { .. } => "db_not_found",
+            AiddError::InvalidCategory { .. } => "invalid_category",
+            AiddError::SchemaMissing { .. } => "schema_missing",
+            AiddError::SchemaOutdated { .. } => "schema_outdated",
+            AiddError::Database(_) => "database",
+            AiddError::DatabaseBusy(_) => "database_busy",
+            AiddError::Io(_) => "io",
+            AiddError::Network(_) => "network",
+            AiddError::Timeout { .. } => "timeout",
+            AiddError::Offline { .. } => "offline",
+            AiddError::RateLimited { .. } => "rate_limited",
+            AiddError::ServerError { .. } => "server_error",
+            AiddError::Conflict { .. } => "conflict",
+            AiddError::Other(_) => "other",
+        }
+    }
+
+    /// Whether the same request may succeed if simply tried again later.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            AiddError::DatabaseBusy(_) | AiddError::Timeout { .. } | AiddError::Offline { .. } | AiddError::RateLimited { .. } | AiddError::ServerError { .. }
+        )
+    }
+}
+
+impl Serialize for AiddError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let conflict_content = match self {
+            AiddError::Conflict { current_content, .. } => Some(current_content),
+            _ => None,
+        };
+        let mut state = serializer.serialize_struct("AiddError", 2 + conflict_content.is_some() as usize)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        // Let the frontend offer a merge without another read
+        if let Some(content) = conflict_content {
+            state.serialize_field("current_content", content)?;
+        }
+        state.end()
+    }
+}
+
+impl From<AiddError> for String {
+    fn from(err: AiddError) -> Self {
+        err.to_string()
+    }
+}
+
+impl From<String> for AiddError {
+    fn from(message: String) -> Self {
+        AiddError::Other(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_and_message() {
+        let err = AiddError::InvalidCategory {
+            got: "widgets".to_string(),
+            valid: vec!["rules".to_string(), "skills".to_string()],
+        };
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "code": "invalid_category",
+                "message": "Invalid category 'widgets'. Valid: [\"rules\", \"skills\"]"
+            })
+        );
+        assert_eq!(String::from(AiddError::NoActiveProject), "No active project configured");
+    }
+}
@@ -1,2 +1,5 @@
+pub mod error;
 pub mod model;
 pub mod ports;
+
+pub use error::AiddError;
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// One agent defined in an AGENTS.md (or agents `routing.md`) document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentDefinition {
+    pub name: String,
+    /// Tagline after a dash in the heading, e.g. `**Name** — TKB Curator`.
+    pub role: Option<String>,
+    pub purpose: Option<String>,
+    /// Skill reference as written (usually a `content/skills/...` path).
+    pub skills: Option<String>,
+    /// Activation commands or trigger descriptions.
+    pub triggers: Vec<String>,
+    pub capabilities: Vec<String>,
+}
+
+/// Maps a trigger (command, task kind) to the agent that handles it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingRule {
+    pub trigger: String,
+    pub agent: String,
+}
+
+/// Structured view of an AGENTS.md document. Every part is optional: a thin
+/// redirect file parses to an empty roster rather than an error.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentsDocument {
+    pub title: Option<String>,
+    pub orchestrator: Option<AgentDefinition>,
+    pub agents: Vec<AgentDefinition>,
+    /// Explicit routing entries; derived from agent triggers when the document has none.
+    pub routing: Vec<RoutingRule>,
+}
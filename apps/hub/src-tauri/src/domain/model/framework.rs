@@ -20,6 +20,33 @@ pub struct FrameworkEntity {
     pub source: String,
 }
 
+/// One place an entity matched a search query: a frontmatter value (`field`)
+/// or a body line (`line`, 1-based within the body).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMatch {
+    pub field: Option<String>,
+    pub line: Option<usize>,
+    pub snippet: String,
+}
+
+/// An entity matching a search, without its body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitySearchHit {
+    pub name: String,
+    pub category: String,
+    pub path: String,
+    pub source: String,
+    pub frontmatter: serde_json::Value,
+    pub matches: Vec<EntityMatch>,
+}
+
+/// Entities matching a search. `truncated` is set when the hit cap cut the list short.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitySearchResult {
+    pub hits: Vec<EntitySearchHit>,
+    pub truncated: bool,
+}
+
 /// Valid framework categories.
 pub const FRAMEWORK_CATEGORIES: &[&str] = &[
     "agents", "rules", "skills", "knowledge", "workflows", "templates", "specs",
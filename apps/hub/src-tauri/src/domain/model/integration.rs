@@ -9,6 +9,8 @@ pub enum IntegrationType {
     Vscode,
     Gemini,
     Windsurf,
+    Cline,
+    Continue,
 }
 
 impl IntegrationType {
@@ -19,8 +21,10 @@ impl IntegrationType {
             "vscode" => Ok(Self::Vscode),
             "gemini" => Ok(Self::Gemini),
             "windsurf" => Ok(Self::Windsurf),
+            "cline" => Ok(Self::Cline),
+            "continue" => Ok(Self::Continue),
             _ => Err(format!(
-                "Unknown integration type '{}'. Valid: claude_code, cursor, vscode, gemini, windsurf",
+                "Unknown integration type '{}'. Valid: claude_code, cursor, vscode, gemini, windsurf, cline, continue",
                 s
             )),
         }
@@ -33,11 +37,21 @@ impl IntegrationType {
             Self::Vscode => "VS Code / Copilot",
             Self::Gemini => "Gemini",
             Self::Windsurf => "Windsurf",
+            Self::Cline => "Cline",
+            Self::Continue => "Continue",
         }
     }
 
     pub fn all() -> Vec<Self> {
-        vec![Self::ClaudeCode, Self::Cursor, Self::Vscode, Self::Gemini, Self::Windsurf]
+        vec![
+            Self::ClaudeCode,
+            Self::Cursor,
+            Self::Vscode,
+            Self::Gemini,
+            Self::Windsurf,
+            Self::Cline,
+            Self::Continue,
+        ]
     }
 }
 
@@ -67,3 +81,43 @@ pub struct IntegrationResult {
     pub files_modified: Vec<String>,
     pub messages: Vec<String>,
 }
+
+/// What `remove_all_integrations` tore down across every tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationRemovalSummary {
+    /// Tools whose integration was removed, in registration order.
+    pub removed: Vec<IntegrationResult>,
+    /// Tools whose removal failed; the others are still attempted.
+    pub failed: Vec<IntegrationRemovalFailure>,
+    /// Whether the project's `.aidd/` directory was deleted.
+    pub purged: bool,
+}
+
+/// One tool `remove_all_integrations` could not clean up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationRemovalFailure {
+    pub tool: IntegrationType,
+    pub error: String,
+}
+
+/// Placeholder substituted for the project root in exported bundles.
+pub const PROJECT_PATH_PLACEHOLDER: &str = "${projectPath}";
+
+/// Portable snapshot of a project's aidd integrations, for sharing with a team.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrationBundle {
+    pub version: u32,
+    pub integrations: Vec<IntegrationBundleEntry>,
+}
+
+/// One configured tool within an `IntegrationBundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrationBundleEntry {
+    pub tool: IntegrationType,
+    pub dev_mode: bool,
+    /// MCP server entry (command/args/env) with the project root replaced by
+    /// `PROJECT_PATH_PLACEHOLDER`; `None` for tools without MCP config.
+    pub mcp_server: Option<serde_json::Value>,
+    /// Project-relative instruction/config files present when exported.
+    pub files: Vec<String>,
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use super::mcp_server::McpServer;
 
 /// Which AI tool owns this MCP config entry.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum McpToolSource {
     ClaudeCode,
@@ -10,6 +10,8 @@ pub enum McpToolSource {
     Vscode,
     Gemini,
     Windsurf,
+    Cline,
+    Continue,
 }
 
 /// Whether the config was found at global or project scope.
@@ -20,6 +22,20 @@ pub enum McpConfigScope {
     Project,
 }
 
+/// Whether a discovered entry looks launchable, checked without starting it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpValidation {
+    #[default]
+    Ok,
+    /// A stdio entry with no `command`.
+    MissingCommand,
+    /// `command` is not an executable path and not found on `PATH`.
+    CommandNotFound,
+    /// An http/sse entry whose `url` is missing or not an http(s) URL.
+    InvalidUrl,
+}
+
 /// A single MCP server entry discovered in a config file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoveredMcp {
@@ -34,6 +50,16 @@ pub struct DiscoveredMcp {
     /// Transport type: "stdio", "http", "sse", or None if unknown.
     pub transport_type: Option<String>,
     pub is_aidd: bool,
+    /// Whether the Hub itself wrote this entry (canonical name and launch
+    /// command), as opposed to a user's own `@aidd.md/...` server.
+    #[serde(default)]
+    pub installed_by_aidd: bool,
+    /// Tools whose configs define the same server (same url, or same command
+    /// and args) in another file. Empty when this entry is the only one.
+    #[serde(default)]
+    pub also_in: Vec<McpToolSource>,
+    #[serde(default)]
+    pub validation: McpValidation,
 }
 
 /// Summary statistics for the health report.
@@ -46,6 +72,21 @@ pub struct McpHealthSummary {
     pub hub_running: usize,
     pub hub_stopped: usize,
     pub hub_error: usize,
+    /// Hub servers whose process is alive but failed their last ping.
+    #[serde(default)]
+    pub hub_unresponsive: usize,
+    /// Discovered entries whose `validation` is not `ok`.
+    #[serde(default)]
+    pub invalid_count: usize,
+}
+
+/// An aidd server name registered with different launch settings in different configs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpNameConflict {
+    pub name: String,
+    /// Every discovered entry with this name, one per config file.
+    pub entries: Vec<DiscoveredMcp>,
+    pub suggestion: String,
 }
 
 /// Aggregated health report returned to frontend.
@@ -54,4 +95,5 @@ pub struct McpHealthReport {
     pub discovered: Vec<DiscoveredMcp>,
     pub hub_servers: Vec<McpServer>,
     pub summary: McpHealthSummary,
+    pub conflicts: Vec<McpNameConflict>,
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// MCP server hosting mode.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,15 +24,83 @@ impl McpServerMode {
     }
 }
 
+/// An AIDD MCP package the Hub can host or call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpPackage {
+    Engine,
+    Core,
+    Memory,
+    Tools,
+}
+
+impl McpPackage {
+    /// Accepts the short name, the workspace directory name and the npm name
+    /// ("engine", "mcp-aidd-engine", "@aidd.md/mcp-engine"); "monolithic" is
+    /// the engine's older name.
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "engine" | "monolithic" | "mcp-aidd-engine" | "@aidd.md/mcp-engine" => Ok(Self::Engine),
+            "core" | "mcp-aidd-core" | "@aidd.md/mcp-core" => Ok(Self::Core),
+            "memory" | "mcp-aidd-memory" | "@aidd.md/mcp-memory" => Ok(Self::Memory),
+            "tools" | "mcp-aidd-tools" | "@aidd.md/mcp-tools" => Ok(Self::Tools),
+            _ => Err(format!(
+                "Unknown package '{}'. Valid: engine, core, memory, tools",
+                s
+            )),
+        }
+    }
+
+    /// Canonical short name, also used as the Hub-hosted server id.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Engine => "engine",
+            Self::Core => "core",
+            Self::Memory => "memory",
+            Self::Tools => "tools",
+        }
+    }
+
+    pub fn npm_name(&self) -> &'static str {
+        match self {
+            Self::Engine => "@aidd.md/mcp-engine",
+            Self::Core => "@aidd.md/mcp-core",
+            Self::Memory => "@aidd.md/mcp-memory",
+            Self::Tools => "@aidd.md/mcp-tools",
+        }
+    }
+
+    /// Built entry point relative to a checkout of the aidd.md monorepo.
+    pub fn local_entry(&self) -> &'static str {
+        match self {
+            Self::Engine => "mcps/mcp-aidd-engine/dist/index.js",
+            Self::Core => "mcps/mcp-aidd-core/dist/index.js",
+            Self::Memory => "mcps/mcp-aidd-memory/dist/index.js",
+            Self::Tools => "mcps/mcp-aidd-tools/dist/index.js",
+        }
+    }
+}
+
 /// MCP server runtime status.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum McpServerStatus {
     Stopped,
     Running,
+    /// The process is alive but did not answer its last ping.
+    Unresponsive,
     Error,
 }
 
+/// What to do when a Hub-hosted server exits on its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RestartPolicy {
+    #[default]
+    Off,
+    /// Respawn after a non-zero exit, waiting `backoff_ms * 2^attempt` between tries.
+    OnFailure { max_retries: u32, backoff_ms: u64 },
+}
+
 /// MCP server entity — returned to frontend.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpServer {
@@ -42,4 +111,102 @@ pub struct McpServer {
     pub pid: Option<u32>,
     pub started_at: Option<String>,
     pub error: Option<String>,
+    /// Automatic restarts performed since the server was started.
+    #[serde(default)]
+    pub restart_count: u32,
+    /// Exit code of the most recent crash, if any.
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+}
+
+/// Result of connecting to an MCP package and completing the handshake.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpConnectInfo {
+    pub package: String,
+    pub protocol_version: Option<String>,
+    /// `serverInfo` from the initialize result (name, version).
+    pub server_info: serde_json::Value,
+    /// Time spent in the initialize handshake.
+    pub init_duration_ms: u64,
+}
+
+/// A custom MCP server launch command, in the shape tool configs store it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerSpec {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+}
+
+/// Outcome of checking an `McpServerSpec` before it is written to a config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpSpecValidation {
+    /// `true` when there are no `errors`.
+    pub ok: bool,
+    /// Executable `command` resolved to, if found.
+    pub resolved_command: Option<String>,
+    pub errors: Vec<String>,
+    /// Suspicious but launchable (e.g. a flag and its value in one arg).
+    pub warnings: Vec<String>,
+    /// Handshake details when the spec was probed successfully.
+    pub handshake: Option<McpConnectInfo>,
+}
+
+/// What `reset_mcp_state` tore down.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpResetSummary {
+    /// Ids of the Hub-managed servers that were stopped.
+    pub stopped_servers: Vec<String>,
+    /// Pooled and one-off clients that were shut down.
+    pub clients_closed: usize,
+    /// Requests that were still waiting on a response and were failed.
+    pub cancelled_requests: usize,
+    /// Whether a cached health report was dropped.
+    pub health_cache_cleared: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_package_alias_resolves_to_the_same_package() {
+        let aliases = [
+            ("engine", McpPackage::Engine),
+            ("monolithic", McpPackage::Engine),
+            ("mcp-aidd-engine", McpPackage::Engine),
+            ("@aidd.md/mcp-engine", McpPackage::Engine),
+            ("core", McpPackage::Core),
+            ("mcp-aidd-core", McpPackage::Core),
+            ("@aidd.md/mcp-core", McpPackage::Core),
+            ("memory", McpPackage::Memory),
+            ("mcp-aidd-memory", McpPackage::Memory),
+            ("@aidd.md/mcp-memory", McpPackage::Memory),
+            ("tools", McpPackage::Tools),
+            ("mcp-aidd-tools", McpPackage::Tools),
+            ("@aidd.md/mcp-tools", McpPackage::Tools),
+        ];
+        for (alias, expected) in aliases {
+            assert_eq!(McpPackage::from_str(alias), Ok(expected), "alias {}", alias);
+        }
+        for package in [McpPackage::Engine, McpPackage::Core, McpPackage::Memory, McpPackage::Tools] {
+            assert_eq!(McpPackage::from_str(package.id()), Ok(package));
+            assert_eq!(McpPackage::from_str(package.npm_name()), Ok(package));
+        }
+    }
+
+    #[test]
+    fn unknown_package_and_mode_names_are_rejected() {
+        let err = McpPackage::from_str("Engine").unwrap_err();
+        assert!(err.contains("Unknown package 'Engine'"), "{}", err);
+        assert!(McpPackage::from_str("").is_err());
+
+        assert_eq!(McpServerMode::from_str("tool_launched"), Ok(McpServerMode::ToolLaunched));
+        assert_eq!(McpServerMode::from_str("hub_hosted"), Ok(McpServerMode::HubHosted));
+        assert!(McpServerMode::from_str("hub-hosted").is_err());
+    }
 }
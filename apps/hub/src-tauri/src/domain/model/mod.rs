@@ -5,11 +5,21 @@ mod mcp_server;
 mod mcp_health;
 mod sync;
 mod overrides;
+mod agents;
 
-pub use project::{AiddMarkers, Project, ProjectEntry};
-pub use framework::{Framework, FrameworkEntity, FRAMEWORK_CATEGORIES};
-pub use integration::{IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType};
-pub use mcp_server::{McpServer, McpServerMode, McpServerStatus};
-pub use mcp_health::{DiscoveredMcp, McpToolSource, McpConfigScope, McpHealthSummary, McpHealthReport};
-pub use sync::SyncInfo;
-pub use overrides::{AgentOverrides, EffectiveEntity, ProjectOverrides};
+pub use project::{AiddMarkers, DetectionPolicy, Project, ProjectDiagnostics, ProjectEntry};
+pub use framework::{EntityMatch, EntitySearchHit, EntitySearchResult, Framework, FrameworkEntity, FRAMEWORK_CATEGORIES};
+pub use integration::{
+    IntegrationBundle, IntegrationBundleEntry, IntegrationConfig, IntegrationRemovalFailure, IntegrationRemovalSummary,
+    IntegrationResult, IntegrationStatus, IntegrationType, PROJECT_PATH_PLACEHOLDER,
+};
+pub use mcp_server::{
+    McpConnectInfo, McpPackage, McpResetSummary, McpServer, McpServerMode, McpServerSpec, McpServerStatus, McpSpecValidation,
+    RestartPolicy,
+};
+pub use mcp_health::{DiscoveredMcp, McpToolSource, McpConfigScope, McpHealthSummary, McpHealthReport, McpNameConflict, McpValidation};
+pub use sync::{ReleaseInfo, SyncInfo, SyncPhase, SyncPreview, SyncProgress};
+pub use overrides::{
+    AgentOverrides, BudgetReport, CategoryBudget, DiffStatus, EffectiveEntity, EntityDiff, MergeMode, ProjectOverrides,
+};
+pub use agents::{AgentDefinition, AgentsDocument, RoutingRule};
@@ -7,6 +7,28 @@ pub struct AgentOverrides {
     /// Agent names that are disabled for this project.
     #[serde(default)]
     pub disabled: Vec<String>,
+    /// Skill names that are disabled for this project. Absent in files written
+    /// before skills had their own list; those still apply `disabled` to skills.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_skills: Option<Vec<String>>,
+}
+
+impl AgentOverrides {
+    pub fn is_skill_disabled(&self, skill: &str) -> bool {
+        self.disabled_skills
+            .as_ref()
+            .unwrap_or(&self.disabled)
+            .iter()
+            .any(|s| s == skill)
+    }
+
+    /// Give legacy files their own skill list, seeded from `disabled` so every
+    /// skill that was off stays off. A no-op once the list exists.
+    pub fn migrate_skill_overrides(&mut self) {
+        if self.disabled_skills.is_none() {
+            self.disabled_skills = Some(self.disabled.clone());
+        }
+    }
 }
 
 /// Summary of project overrides for a single project.
@@ -20,6 +42,50 @@ pub struct ProjectOverrides {
     pub skill_count: usize,
 }
 
+/// How a project override combines with the global entity of the same name,
+/// set by `merge:` in the override's frontmatter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMode {
+    /// The override shadows the global entity entirely.
+    #[default]
+    Replace,
+    /// Global content first, then the override body.
+    Append,
+    /// Override body first, then the global content.
+    Prepend,
+}
+
+impl MergeMode {
+    /// Unknown or missing values fall back to `Replace`.
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("append") => MergeMode::Append,
+            Some("prepend") => MergeMode::Prepend,
+            _ => MergeMode::Replace,
+        }
+    }
+}
+
+/// Size of the enabled effective entities in one category.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CategoryBudget {
+    pub category: String,
+    pub entity_count: usize,
+    pub bytes: u64,
+    pub estimated_tokens: u64,
+}
+
+/// Size of the framework content that would be injected for a project.
+/// Token figures use a bytes/4 heuristic, not a real tokenizer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetReport {
+    pub project_path: String,
+    pub categories: Vec<CategoryBudget>,
+    pub total_bytes: u64,
+    pub total_estimated_tokens: u64,
+}
+
 /// A framework entity with override/source info applied.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EffectiveEntity {
@@ -27,7 +93,37 @@ pub struct EffectiveEntity {
     pub category: String,
     /// `"global"` or `"override"`
     pub source: String,
-    /// Whether this entity is enabled (agents can be disabled).
+    /// Whether this entity is enabled (skills can be disabled per project).
     pub enabled: bool,
     pub content: Option<String>,
+    /// How the project override was applied; `None` for global-only entities.
+    #[serde(default)]
+    pub merge_mode: Option<MergeMode>,
+}
+
+/// Where an entity differs between the global framework and a project override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    /// Both exist and their content differs.
+    Modified,
+    /// Both exist with identical content.
+    Unchanged,
+    /// Only the project override exists.
+    Added,
+    /// Only the global entity exists.
+    Removed,
+}
+
+/// A project override compared with its global base.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDiff {
+    pub name: String,
+    pub category: String,
+    pub status: DiffStatus,
+    pub global_content: Option<String>,
+    pub override_content: Option<String>,
+    /// Line-level unified diff from the global content to the override (a missing
+    /// side counts as empty). Empty when the two are identical.
+    pub unified_diff: String,
 }
@@ -14,6 +14,38 @@ pub struct AiddMarkers {
     pub memory: bool,
 }
 
+impl AiddMarkers {
+    /// Any content directory beyond the bare `.aidd/` and memory markers.
+    pub fn has_content_dir(&self) -> bool {
+        self.agents || self.rules || self.skills || self.workflows || self.specs || self.knowledge || self.templates
+    }
+}
+
+/// How strictly `detect` decides that a directory is an aidd project.
+/// Template repos often ship `rules/` or `skills/` alone, so stricter
+/// policies cut false positives while browsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionPolicy {
+    /// Any of `agents/`, `rules/` or `skills/`.
+    #[default]
+    AnyContentDir,
+    /// `.aidd/` plus at least one content directory.
+    RequireAiddDir,
+    /// An `AGENTS.md` at the project root.
+    RequireAgentsMd,
+}
+
+impl DetectionPolicy {
+    pub fn is_detected(&self, markers: &AiddMarkers, has_agents_md: bool) -> bool {
+        match self {
+            DetectionPolicy::AnyContentDir => markers.agents || markers.rules || markers.skills,
+            DetectionPolicy::RequireAiddDir => markers.aidd_dir && markers.has_content_dir(),
+            DetectionPolicy::RequireAgentsMd => has_agents_md,
+        }
+    }
+}
+
 /// Full project information with detection results.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -21,6 +53,31 @@ pub struct Project {
     pub path: String,
     pub detected: bool,
     pub markers: AiddMarkers,
+    /// Nearest ancestor holding `.aidd/` or `AGENTS.md`, when the project itself has neither
+    /// (e.g. a package inside a monorepo whose AIDD content lives at the repo root).
+    #[serde(default)]
+    pub aidd_root: Option<String>,
+    /// Workspace member directories declared by `pnpm-workspace.yaml` or the
+    /// `workspaces` field of `package.json`. Empty for single-package repos.
+    #[serde(default)]
+    pub workspaces: Vec<String>,
+    /// Directory the markers were read from: the project itself, or its `ai/`
+    /// subfolder for projects that keep their AIDD files there.
+    #[serde(default)]
+    pub content_root: String,
+}
+
+/// Health of one registered project, as reported by `diagnose`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDiagnostics {
+    pub path: String,
+    pub name: String,
+    /// False when the registered directory no longer exists; nothing else is checked then.
+    pub exists: bool,
+    pub detected: bool,
+    pub markers: Option<AiddMarkers>,
+    /// Human-readable problems; empty for a healthy project.
+    pub issues: Vec<String>,
 }
 
 /// Lightweight project entry for lists and persistence.
@@ -29,6 +86,9 @@ pub struct ProjectEntry {
     pub name: String,
     pub path: String,
     pub detected: bool,
+    /// Unix ms of the last switch to this project; 0 if never opened.
+    #[serde(default)]
+    pub last_accessed: u64,
 }
 
 impl From<&Project> for ProjectEntry {
@@ -37,6 +97,7 @@ impl From<&Project> for ProjectEntry {
             name: p.name.clone(),
             path: p.path.clone(),
             detected: p.detected,
+            last_accessed: 0,
         }
     }
 }
@@ -9,4 +9,52 @@ pub struct SyncInfo {
     pub auto_sync: bool,
     pub last_check: Option<String>,
     pub changelog: Option<String>,
+    /// Files the last sync moved to `framework.orphaned/` because the new
+    /// release no longer ships them (only with `prune`).
+    #[serde(default)]
+    pub pruned: Vec<String>,
+}
+
+/// What installing `target_version` would change in the framework directory.
+/// Paths are relative to the framework root, `/`-separated and sorted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncPreview {
+    pub target_version: String,
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    /// Present locally but not shipped by the release. A plain sync leaves
+    /// them in place; with `prune`, the ones an earlier release shipped are
+    /// moved to `framework.orphaned/` and local additions stay.
+    pub removed: Vec<String>,
+}
+
+/// Which stage of a framework sync a [`SyncProgress`] event describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncPhase {
+    Download,
+    Extract,
+}
+
+/// Progress of a running framework sync, emitted as `SYNC_PROGRESS`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncProgress {
+    pub phase: SyncPhase,
+    /// Bytes received while downloading; archive entries processed while extracting.
+    pub current: u64,
+    /// `Content-Length` while downloading (absent if the server omits it);
+    /// archive entry count while extracting.
+    pub total: Option<u64>,
+}
+
+/// A published framework release, for picking a version to install.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    /// Git tag as published (e.g. `v1.2.0`).
+    pub tag: String,
+    /// Tag without the leading `v`, as `sync_framework` and `SyncInfo` use it.
+    pub version: String,
+    pub published_at: Option<String>,
+    pub prerelease: bool,
+    pub changelog: Option<String>,
 }
@@ -1,31 +1,86 @@
-use crate::domain::model::FrameworkEntity;
+use std::collections::HashMap;
+
+use crate::domain::model::{EntitySearchResult, FrameworkEntity};
+use crate::domain::AiddError;
 
 /// Inbound port for framework management use cases.
 pub trait FrameworkPort: Send + Sync {
     /// Get the current framework version.
-    fn get_version(&self) -> Result<Option<String>, String>;
+    fn get_version(&self) -> Result<Option<String>, AiddError>;
 
     /// Get the resolved framework directory path.
     fn get_path(&self) -> String;
 
     /// List all entities in a framework category (e.g. "rules", "skills").
-    fn list_entities(&self, category: &str) -> Result<Vec<FrameworkEntity>, String>;
+    fn list_entities(&self, category: &str) -> Result<Vec<FrameworkEntity>, AiddError>;
 
     /// List entities from both global framework and project directories.
     /// Handles special directory structures: skills (subdirs with SKILL.md),
-    /// knowledge (nested subdirs with .md files).
+    /// knowledge (nested subdirs with .md files). With a `filter`, only entities
+    /// whose frontmatter matches every key/value pair are kept.
     fn list_entities_with_project(
         &self,
         category: &str,
         project_path: Option<&str>,
-    ) -> Result<Vec<FrameworkEntity>, String>;
+        filter: Option<&HashMap<String, String>>,
+    ) -> Result<Vec<FrameworkEntity>, AiddError>;
+
+    /// Case-insensitive search of entity frontmatter values and bodies across
+    /// `categories` (all when `None`), global and project, with match snippets.
+    fn search_entities(
+        &self,
+        query: &str,
+        categories: Option<&[String]>,
+        project_path: Option<&str>,
+    ) -> Result<EntitySearchResult, AiddError>;
 
     /// Read a specific entity by category and name.
-    fn read_entity(&self, category: &str, name: &str) -> Result<FrameworkEntity, String>;
+    fn read_entity(&self, category: &str, name: &str) -> Result<FrameworkEntity, AiddError>;
 
     /// Write (create or update) a framework entity.
-    fn write_entity(&self, category: &str, name: &str, content: &str) -> Result<(), String>;
+    fn write_entity(&self, category: &str, name: &str, content: &str) -> Result<(), AiddError>;
+
+    /// Write an entity only if its file still has `expected_last_modified`
+    /// (as reported in `FrameworkEntity::last_modified`); otherwise fail with
+    /// `AiddError::Conflict` carrying the current content. `None` always writes.
+    fn write_entity_if_unchanged(
+        &self,
+        category: &str,
+        name: &str,
+        content: &str,
+        expected_last_modified: Option<&str>,
+    ) -> Result<(), AiddError>;
+
+    /// Write an entity from `read_entity`'s split form: `frontmatter` (a JSON
+    /// object) is re-emitted as the `---` YAML block, followed by `body`.
+    /// Unchanged keys keep their original lines and order.
+    fn write_entity_parts(
+        &self,
+        category: &str,
+        name: &str,
+        frontmatter: serde_json::Value,
+        body: &str,
+    ) -> Result<(), AiddError>;
 
     /// Delete a framework entity.
-    fn delete_entity(&self, category: &str, name: &str) -> Result<(), String>;
+    fn delete_entity(&self, category: &str, name: &str) -> Result<(), AiddError>;
+
+    /// Delete an entity from the project's content directory when `project_path`
+    /// is given (a `source: "project"` entity), otherwise from the global framework.
+    fn delete_entity_with_project(
+        &self,
+        category: &str,
+        name: &str,
+        project_path: Option<&str>,
+    ) -> Result<(), AiddError>;
+
+    /// Copy an entity to `dst_name` (a skill's whole directory, a knowledge entry's
+    /// nested path). Fails if `dst_name` exists unless `overwrite` is set.
+    fn duplicate_entity(
+        &self,
+        category: &str,
+        src_name: &str,
+        dst_name: &str,
+        overwrite: bool,
+    ) -> Result<(), AiddError>;
 }
@@ -1,9 +1,32 @@
-use crate::domain::model::{IntegrationConfig, IntegrationResult, IntegrationType};
+use std::collections::BTreeMap;
+use crate::domain::model::{
+    AgentsDocument, IntegrationBundle, IntegrationConfig, IntegrationRemovalSummary, IntegrationResult, IntegrationType,
+};
 
 /// Inbound port for AI tool integration management.
 pub trait IntegrationPort: Send + Sync {
-    fn integrate(&self, project_path: &str, tool: IntegrationType, dev_mode: bool) -> Result<IntegrationResult, String>;
+    /// `env` holds extra variables for the aidd-engine MCP entry; variables already
+    /// present in the tool's config are kept.
+    fn integrate(
+        &self,
+        project_path: &str,
+        tool: IntegrationType,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+    ) -> Result<IntegrationResult, String>;
     fn remove_integration(&self, project_path: &str, tool: IntegrationType) -> Result<IntegrationResult, String>;
+    /// Recreate the integration's missing files and MCP entry without touching
+    /// anything already present (for a `NeedsUpdate` integration).
+    fn repair_integration(&self, project_path: &str, tool: IntegrationType) -> Result<IntegrationResult, String>;
+    /// Remove every tool's integration; with `purge`, also delete the project's
+    /// `.aidd/` directory. The shared AGENTS.md is kept either way.
+    fn remove_all_integrations(&self, project_path: &str, purge: bool) -> Result<IntegrationRemovalSummary, String>;
     fn check_status(&self, project_path: &str) -> Result<Vec<IntegrationConfig>, String>;
     fn list_available(&self) -> Vec<IntegrationType>;
+    /// Collect the project's configured integrations into a machine-independent bundle.
+    fn export_bundle(&self, project_path: &str) -> Result<IntegrationBundle, String>;
+    /// Apply every integration in `bundle` to the project via its adapter.
+    fn import_bundle(&self, project_path: &str, bundle: &IntegrationBundle) -> Result<Vec<IntegrationResult>, String>;
+    /// Parse the agent roster and routing out of an AGENTS.md-style file.
+    fn parse_agents_md(&self, path: &str) -> Result<AgentsDocument, String>;
 }
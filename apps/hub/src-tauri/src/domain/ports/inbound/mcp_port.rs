@@ -1,12 +1,42 @@
-use crate::domain::model::{McpServer, McpServerMode};
+use crate::domain::model::{
+    McpConnectInfo, McpResetSummary, McpServer, McpServerMode, McpServerSpec, McpSpecValidation, RestartPolicy,
+};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Inbound port for MCP server lifecycle management.
 pub trait McpPort: Send + Sync {
-    fn start_server(&self, package: &str, mode: McpServerMode) -> Result<McpServer, String>;
-    fn stop_server(&self, server_id: &str) -> Result<(), String>;
+    /// Start a Hub server with `env` added to its environment and `extra_args`
+    /// appended to its command line.
+    fn start_server(
+        &self,
+        package: &str,
+        mode: McpServerMode,
+        env: HashMap<String, String>,
+        extra_args: Vec<String>,
+    ) -> Result<McpServer, String>;
+    /// Stop a Hub server, killing it if it hasn't exited `grace` after SIGTERM.
+    fn stop_server(&self, server_id: &str, grace: Duration) -> Result<(), String>;
     fn stop_all(&self) -> Result<(), String>;
     fn get_servers(&self) -> Vec<McpServer>;
+    /// Recent stderr output of a Hub-started server, including after it exits.
+    fn get_server_logs(&self, server_id: &str) -> Result<String, String>;
+    /// Choose whether a running server is respawned after crashing.
+    fn set_restart_policy(&self, server_id: &str, policy: RestartPolicy) -> Result<(), String>;
+    /// Round-trip latency in ms of an MCP `ping` to a running Hub server. A
+    /// server that misses it is reported `Unresponsive` until one succeeds.
+    fn ping_server(&self, server_id: &str) -> Result<u64, String>;
     fn list_tools(&self, package: &str) -> Result<Vec<Value>, String>;
     fn call_tool(&self, package: &str, tool_name: &str, arguments: Value) -> Result<Value, String>;
+    /// Spawn and initialize a client for `package`, reporting handshake details.
+    fn connect_info(&self, package: &str) -> Result<McpConnectInfo, String>;
+    /// Check a custom server spec (command on PATH, args shape) and, with
+    /// `probe`, spawn it once to confirm the initialize handshake.
+    fn validate_spec(&self, spec: &McpServerSpec, probe: bool) -> McpSpecValidation;
+    /// Stop every Hub server and shut down pooled and one-off clients, failing
+    /// their in-flight calls. Safe when nothing is running.
+    fn reset_state(&self) -> Result<McpResetSummary, String>;
+    /// Shut down the clients kept alive between tool calls. Returns how many were closed.
+    fn shutdown_clients(&self) -> usize;
 }
@@ -1,111 +1,182 @@
+use crate::domain::AiddError;
+
 /// Memory port for querying AIDD memory data from the engine
 pub trait MemoryPort: Send + Sync {
+    /// A port bound to `project_path` (any registered project) rather than the
+    /// primary active project. Projects that aren't active are read-only.
+    fn for_project(&self, project_path: &str) -> Result<Box<dyn MemoryPort>, AiddError>;
+
+    /// Which backend answers this port's queries, for diagnosing mismatched data.
+    fn source(&self) -> MemorySource;
+
+    /// A view of this port whose reads return the underlying error (missing
+    /// table, corrupt row, ...) instead of falling back to an empty result.
+    fn strict(&self) -> Box<dyn MemoryPort>;
+
     /// Get summary of all sessions
-    fn get_session_summary(&self) -> Result<SessionSummary, String>;
+    fn get_session_summary(&self) -> Result<SessionSummary, AiddError>;
 
-    /// List all sessions with full detail (data JSON blob from SQLite)
-    fn list_all_sessions(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String>;
+    /// Aggregate session durations, discovery tokens and observation types.
+    fn get_session_stats(&self) -> Result<SessionStats, AiddError>;
+
+    /// List sessions with full detail (data JSON blob from SQLite), newest first.
+    /// Pass the previous page's `next_cursor` to continue past it. `from_ms`/`to_ms`
+    /// bound the start time (epoch ms, inclusive); either may be omitted.
+    fn list_all_sessions(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<MemoryPage, AiddError>;
+
+    /// List observations, newest first, keyset-paginated like sessions. `types`
+    /// keeps only observations of those types; `None` or empty lists all.
+    fn list_all_observations(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        types: Option<&[String]>,
+    ) -> Result<MemoryPage, AiddError>;
 
-    /// List all observations (no filter)
-    fn list_all_observations(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String>;
+    /// Observation count per type, most frequent first (for filter facets).
+    fn get_observation_type_counts(&self) -> Result<Vec<(String, usize)>, AiddError>;
 
     /// List observations for a specific session.
     fn list_observations_by_session(
         &self,
         session_id: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String>;
+    ) -> Result<Vec<serde_json::Value>, AiddError>;
 
     /// Search observations by query
     fn search_observations(
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<ObservationEntry>, String>;
+    ) -> Result<Vec<ObservationEntry>, AiddError>;
 
     /// Get evolution status
-    fn get_evolution_status(&self) -> Result<EvolutionStatus, String>;
+    fn get_evolution_status(&self) -> Result<EvolutionStatus, AiddError>;
 
     /// List evolution candidates with full detail
-    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, String>;
+    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, AiddError>;
 
     /// List evolution log entries
-    fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String>;
+    fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError>;
 
     /// Get pattern statistics
-    fn get_pattern_stats(&self) -> Result<PatternStats, String>;
+    fn get_pattern_stats(&self) -> Result<PatternStats, AiddError>;
+
+    /// List permanent memory entries by type (decision, mistake, convention),
+    /// newest first, one `limit`/`offset` page at a time.
+    fn list_permanent_memory(
+        &self,
+        memory_type: &str,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<PermanentMemoryPage, AiddError>;
 
-    /// List permanent memory entries by type (decision, mistake, convention)
-    fn list_permanent_memory(&self, memory_type: &str) -> Result<Vec<serde_json::Value>, String>;
+    /// Search permanent memory titles and content, best matches first.
+    fn search_permanent_memory(&self, query: &str, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError>;
 
     /// Delete a permanent memory entry by type and id
-    fn delete_permanent_memory(&self, memory_type: &str, id: &str) -> Result<(), String>;
+    fn delete_permanent_memory(&self, memory_type: &str, id: &str) -> Result<(), AiddError>;
 
     /// List draft entries
-    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, String>;
+    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, AiddError>;
 
-    /// List artifacts with optional filters
+    /// List artifacts with optional filters; `from_ms`/`to_ms` bound the artifact date.
     fn list_artifacts(
         &self,
         artifact_type: Option<&str>,
         status: Option<&str>,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String>;
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>, AiddError>;
 
     /// List recent pattern audit scores.
-    fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String>;
+    fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError>;
+
+    /// List recent pattern detections, newest first, each joined with its banned pattern.
+    fn list_pattern_detections(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError>;
+
+    /// Recent observations, artifacts, evolution log entries and audit scores,
+    /// merged into one feed ordered newest first.
+    fn get_activity_feed(&self, limit: Option<usize>) -> Result<Vec<ActivityEntry>, AiddError>;
+
+    /// Observation counts per UTC calendar day, oldest day first. `since_ts`/`until_ts`
+    /// bound the creation time (epoch ms, inclusive); either may be omitted.
+    fn observation_daily_counts(&self, since_ts: Option<i64>, until_ts: Option<i64>) -> Result<Vec<DailyCount>, AiddError>;
+
+    /// Storage diagnostics for the project database (journal mode, WAL sidecars).
+    fn get_storage_info(&self) -> Result<StorageInfo, AiddError>;
+
+    /// Write every table of the project database to `out` as one versioned JSON bundle,
+    /// for backup or sharing. Rows are streamed as stored, keyed by column name.
+    fn export_bundle(&self, out: &mut dyn std::io::Write) -> Result<(), AiddError>;
+
+    /// Restore a bundle produced by `export_bundle` in a single transaction.
+    /// Bundles with an unknown `schemaVersion` or columns the tables lack are rejected.
+    fn import_bundle(&self, bundle_json: &str, mode: ImportMode) -> Result<ImportSummary, AiddError>;
 
     /// Get governance configuration persisted in project-local data.db.
-    fn get_governance_config(&self) -> Result<serde_json::Value, String>;
+    fn get_governance_config(&self) -> Result<serde_json::Value, AiddError>;
 
-    /// Upsert governance configuration in project-local data.db.
-    fn upsert_governance_config(&self, config_json: &str) -> Result<(), String>;
+    /// Upsert governance configuration in project-local data.db, returning
+    /// what changed versus the previously stored config.
+    fn upsert_governance_config(&self, config_json: &str) -> Result<Vec<ConfigChange>, AiddError>;
 
     // --- Write operations ---
 
     /// Create a permanent memory entry. Returns the new entry ID.
-    fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, String>;
+    fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, AiddError>;
 
     /// Update a permanent memory entry by ID.
-    fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), String>;
+    fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), AiddError>;
 
     /// Create an artifact. Returns the new artifact ID.
-    fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, String>;
+    fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, AiddError>;
 
     /// Update an artifact by ID.
-    fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), String>;
+    fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), AiddError>;
 
     /// Archive an artifact (set status to 'done').
-    fn archive_artifact(&self, id: &str) -> Result<(), String>;
+    fn archive_artifact(&self, id: &str) -> Result<(), AiddError>;
 
     /// Delete an artifact by ID.
-    fn delete_artifact(&self, id: &str) -> Result<(), String>;
+    fn delete_artifact(&self, id: &str) -> Result<(), AiddError>;
 
     /// Approve an evolution candidate.
-    fn approve_evolution_candidate(&self, id: &str) -> Result<(), String>;
+    fn approve_evolution_candidate(&self, id: &str) -> Result<(), AiddError>;
 
     /// Reject an evolution candidate with a reason.
-    fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), String>;
+    fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), AiddError>;
 
     /// Approve a draft entry.
-    fn approve_draft(&self, id: &str) -> Result<(), String>;
+    fn approve_draft(&self, id: &str) -> Result<(), AiddError>;
 
     /// Reject a draft entry with a reason.
-    fn reject_draft(&self, id: &str, reason: &str) -> Result<(), String>;
+    fn reject_draft(&self, id: &str, reason: &str) -> Result<(), AiddError>;
 
     /// Delete a session and its associated observations.
-    fn delete_session(&self, id: &str) -> Result<(), String>;
+    fn delete_session(&self, id: &str) -> Result<(), AiddError>;
 
     /// Update a session's editable fields in its JSON data blob.
-    fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), String>;
+    fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), AiddError>;
 
     /// Update a session's full data via JSON merge. Reads existing data blob,
     /// deep-merges the provided JSON fields, and writes back.
-    fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), String>;
+    fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), AiddError>;
+
+    /// Reopen an ended session: clears `ended_at` and the blob's `endedAt`.
+    fn reopen_session(&self, id: &str) -> Result<(), AiddError>;
 
     // --- Observation CRUD ---
 
     /// Create an observation. Returns the new observation ID.
+    /// `created_at` overrides the timestamp (e.g. for imports); defaults to now.
     fn create_observation(
         &self,
         session_id: &str,
@@ -117,7 +188,8 @@ pub trait MemoryPort: Send + Sync {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<String, String>;
+        created_at: Option<&str>,
+    ) -> Result<String, AiddError>;
 
     /// Update an observation by ID.
     fn update_observation(
@@ -131,10 +203,10 @@ pub trait MemoryPort: Send + Sync {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<(), String>;
+    ) -> Result<(), AiddError>;
 
     /// Delete an observation by ID.
-    fn delete_observation(&self, id: &str) -> Result<(), String>;
+    fn delete_observation(&self, id: &str) -> Result<(), AiddError>;
 
     // --- Evolution Candidate CRUD ---
 
@@ -145,7 +217,7 @@ pub trait MemoryPort: Send + Sync {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<String, String>;
+    ) -> Result<String, AiddError>;
 
     /// Update an evolution candidate by ID.
     fn update_evolution_candidate_entry(
@@ -155,10 +227,11 @@ pub trait MemoryPort: Send + Sync {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<(), String>;
+    ) -> Result<(), AiddError>;
 
-    /// Delete an evolution candidate by ID.
-    fn delete_evolution_candidate(&self, id: &str) -> Result<(), String>;
+    /// Delete an evolution candidate by ID, along with its `evolution_log`
+    /// entries unless `preserve_log` keeps them for auditing.
+    fn delete_evolution_candidate(&self, id: &str, preserve_log: bool) -> Result<(), AiddError>;
 
     // --- Draft CRUD ---
 
@@ -171,16 +244,57 @@ pub trait MemoryPort: Send + Sync {
         content: &str,
         confidence: f64,
         source: &str,
-    ) -> Result<String, String>;
+    ) -> Result<String, AiddError>;
 
     /// Update a draft by ID.
-    fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), String>;
+    fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), AiddError>;
 
     /// Delete a draft by ID.
-    fn delete_draft(&self, id: &str) -> Result<(), String>;
+    fn delete_draft(&self, id: &str) -> Result<(), AiddError>;
+
+    /// Delete every draft with `status` (`pending`, `approved` or `rejected`)
+    /// in one transaction, returning how many were removed.
+    fn delete_drafts_by_status(&self, status: &str) -> Result<usize, AiddError>;
+
+    /// Create a banned pattern (`pattern_type` is `exact` or `regex`). Returns the new ID.
+    fn create_banned_pattern(&self, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>) -> Result<String, AiddError>;
+
+    /// Update a banned pattern's definition and active flag by ID.
+    fn update_banned_pattern(&self, id: &str, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>, active: bool) -> Result<(), AiddError>;
+
+    /// Reclassify a pattern detection as a false positive.
+    fn mark_detection_false_positive(&self, id: i64) -> Result<(), AiddError>;
+
+    // --- Bulk operations (one transaction each; return rows affected) ---
+
+    /// Delete the drafts with the given IDs.
+    fn delete_drafts(&self, ids: &[String]) -> Result<usize, AiddError>;
+
+    /// Reject the drafts with the given IDs, recording `reason` on each.
+    fn reject_drafts(&self, ids: &[String], reason: &str) -> Result<usize, AiddError>;
+
+    /// Archive the artifacts with the given IDs (set status to 'done').
+    fn archive_artifacts(&self, ids: &[String]) -> Result<usize, AiddError>;
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// A read result that keeps "failed to load" apart from "nothing here": on
+/// failure `data` is the empty value and `error` says why.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Loaded<T> {
+    pub data: T,
+    pub error: Option<AiddError>,
+}
+
+impl<T: Default> From<Result<T, AiddError>> for Loaded<T> {
+    fn from(result: Result<T, AiddError>) -> Self {
+        match result {
+            Ok(data) => Self { data, error: None },
+            Err(error) => Self { data: T::default(), error: Some(error) },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct SessionSummary {
     pub total: usize,
     pub active: usize,
@@ -188,6 +302,21 @@ pub struct SessionSummary {
     pub recent_sessions: Vec<SessionInfo>,
 }
 
+/// Rollup of session and observation metrics for the memory dashboard.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionStats {
+    pub total: usize,
+    pub active: usize,
+    pub completed: usize,
+    /// Over sessions with an end time, in ms; `None` when none has ended.
+    pub avg_duration_ms: Option<f64>,
+    pub median_duration_ms: Option<f64>,
+    /// Sum of `discovery_tokens` over every observation.
+    pub total_discovery_tokens: i64,
+    /// Observation count per `type`.
+    pub observations_by_type: std::collections::BTreeMap<String, usize>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SessionInfo {
     pub id: String,
@@ -204,9 +333,16 @@ pub struct ObservationEntry {
     #[serde(rename = "type")]
     pub observation_type: String,
     pub created_at: String,
+    /// Search relevance (higher is better); `None` when the match was not ranked.
+    #[serde(default)]
+    pub score: Option<f64>,
+    /// Excerpt around the match with terms wrapped in `<mark>`/`</mark>`;
+    /// only full-text search produces one.
+    #[serde(default)]
+    pub snippet: Option<String>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct EvolutionStatus {
     pub pending_count: usize,
     pub approved_count: usize,
@@ -214,10 +350,92 @@ pub struct EvolutionStatus {
     pub auto_applied_count: usize,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct PatternStats {
     pub total_patterns: usize,
     pub active_patterns: usize,
     pub total_detections: usize,
     pub false_positives: usize,
 }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StorageInfo {
+    pub db_path: String,
+    pub size_bytes: u64,
+    /// Value of `PRAGMA journal_mode` (e.g. `wal`, `delete`, `memory`).
+    pub journal_mode: String,
+    pub wal_file_exists: bool,
+    pub shm_file_exists: bool,
+}
+
+/// Backend behind a `MemoryPort`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemorySource {
+    /// The project's `.aidd/data.db`, read directly.
+    #[default]
+    Sqlite,
+    /// An AIDD engine process queried over MCP.
+    Engine,
+}
+
+/// One page of a keyset-paginated listing.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryPage {
+    pub items: Vec<serde_json::Value>,
+    /// Opaque cursor for the next page; `None` when this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// One offset page of permanent memory entries.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct PermanentMemoryPage {
+    pub items: Vec<serde_json::Value>,
+    /// Entries of this type across all pages.
+    pub total: usize,
+}
+
+/// How `import_bundle` treats rows that are already in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Wipe the memory tables, then insert every bundled row.
+    Replace,
+    /// Insert only rows whose `id` is not present yet.
+    Merge,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ImportSummary {
+    pub imported: usize,
+    /// Rows left alone in merge mode because their `id` already existed.
+    pub skipped: usize,
+}
+
+/// One governance setting that differs between two configs.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfigChange {
+    /// Dotted path, e.g. `evolution.killSwitch`.
+    pub key: String,
+    /// Prior value (`null` when the key was absent).
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DailyCount {
+    /// `YYYY-MM-DD` (UTC).
+    pub date: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ActivityEntry {
+    /// Source of the entry: `observation`, `artifact`, `evolution` or `audit`.
+    pub kind: String,
+    pub id: String,
+    pub title: String,
+    /// Unix timestamp in milliseconds.
+    pub timestamp: i64,
+}
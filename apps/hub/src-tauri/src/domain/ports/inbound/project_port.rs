@@ -1,4 +1,4 @@
-use crate::domain::model::{Project, ProjectEntry};
+use crate::domain::model::{DetectionPolicy, Project, ProjectDiagnostics, ProjectEntry};
 
 /// Inbound port for project management use cases.
 pub trait ProjectPort: Send + Sync {
@@ -14,9 +14,35 @@ pub trait ProjectPort: Send + Sync {
     /// List all registered projects.
     fn list(&self) -> Result<Vec<ProjectEntry>, String>;
 
+    /// List registered projects, most recently switched-to first.
+    fn list_by_recent(&self) -> Result<Vec<ProjectEntry>, String>;
+
     /// Set the active project by path.
     fn switch(&self, path: &str) -> Result<(), String>;
 
     /// Get the active project path.
     fn get_active_path(&self) -> Result<Option<String>, String>;
+
+    /// Every active project: the primary one first, then workspace-mode extras.
+    fn list_active_projects(&self) -> Result<Vec<String>, String>;
+
+    /// Add a registered project to the active set. Becomes the primary active
+    /// project when none is set.
+    fn add_active_project(&self, path: &str) -> Result<(), String>;
+
+    /// Drop a project from the active set. Removing the primary promotes the
+    /// next active project, if any.
+    fn remove_active_project(&self, path: &str) -> Result<(), String>;
+
+    /// The user's policy for what `detect` counts as an aidd project.
+    fn detection_policy(&self) -> Result<DetectionPolicy, String>;
+
+    /// Persist a new detection policy; applies to subsequent `detect` calls.
+    fn set_detection_policy(&self, policy: DetectionPolicy) -> Result<(), String>;
+
+    /// Check a single project directory for common setup problems.
+    fn diagnose(&self, path: &str) -> ProjectDiagnostics;
+
+    /// `diagnose` every registered project, in registration order.
+    fn verify_all(&self) -> Result<Vec<ProjectDiagnostics>, String>;
 }
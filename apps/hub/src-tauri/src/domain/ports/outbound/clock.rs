@@ -0,0 +1,93 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Outbound port for the current time, so timestamped output can be tested
+/// against a fixed instant.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+
+    /// Milliseconds since the Unix epoch.
+    fn now_unix_ms(&self) -> i64 {
+        self.now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as i64
+    }
+
+    /// ISO 8601 timestamp with millisecond precision, the format every stored timestamp uses.
+    fn now_iso(&self) -> String {
+        iso_from_unix_ms(self.now_unix_ms())
+    }
+}
+
+/// Format a Unix timestamp (ms) as UTC ISO 8601 — matches JS `new Date().toISOString()`.
+pub fn iso_from_unix_ms(ms: i64) -> String {
+    let ms = ms.max(0) as u64;
+    let secs = ms / 1000;
+    let days_since_epoch = secs / 86400;
+    let time_of_day = secs % 86400;
+    let hours = time_of_day / 3600;
+    let minutes = (time_of_day % 3600) / 60;
+    let seconds = time_of_day % 60;
+
+    // Compute year/month/day from days since epoch (1970-01-01)
+    let (year, month, day) = days_to_ymd(days_since_epoch);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year, month, day, hours, minutes, seconds, ms % 1000
+    )
+}
+
+/// Parse a UTC ISO 8601 timestamp (`YYYY-MM-DDTHH:MM:SS[.fff]Z`, as written by
+/// `iso_from_unix_ms`) back to Unix ms. `None` for anything else.
+pub fn unix_ms_from_iso(iso: &str) -> Option<i64> {
+    let rest = iso.strip_suffix('Z')?;
+    let (date, time) = rest.split_once('T')?;
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+    let (hms, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let mut time_parts = hms.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hours, minutes, seconds) = (time_parts.next()??, time_parts.next()??, time_parts.next()??);
+    let millis: i64 = format!("{:0<3}", fraction).get(..3)?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    // Inverse of `days_to_ymd` (same algorithm source)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    Some(((days * 86400 + hours * 3600 + minutes * 60 + seconds) * 1000) + millis)
+}
+
+/// Convert days since Unix epoch to (year, month, day)
+fn days_to_ymd(days: u64) -> (u64, u64, u64) {
+    // Algorithm from http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_round_trips_through_unix_ms() {
+        for ms in [0, 1_769_904_000_123, 951_782_400_000] {
+            assert_eq!(unix_ms_from_iso(&iso_from_unix_ms(ms)), Some(ms));
+        }
+        assert_eq!(unix_ms_from_iso("2026-02-01T00:00:00Z"), Some(1_769_904_000_000));
+        assert_eq!(unix_ms_from_iso("2026-02-01 00:00:00"), None);
+        assert_eq!(unix_ms_from_iso("SystemTime { tv_sec: 1 }"), None);
+    }
+}
@@ -1,3 +1,20 @@
+use std::time::SystemTime;
+
+/// One entry of a directory listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsEntry {
+    /// Final path component.
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+}
+
+/// What the services need from a file's metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsMetadata {
+    pub modified: Option<SystemTime>,
+}
+
 /// Outbound port for generic file system operations.
 pub trait FileSystemPort: Send + Sync {
     fn read_to_string(&self, path: &str) -> Result<String, String>;
@@ -5,4 +22,14 @@ pub trait FileSystemPort: Send + Sync {
     fn exists(&self, path: &str) -> bool;
     fn is_dir(&self, path: &str) -> bool;
     fn create_dir_all(&self, path: &str) -> Result<(), String>;
+    fn remove_file(&self, path: &str) -> Result<(), String>;
+    /// Remove a directory and everything below it.
+    fn remove_dir_all(&self, path: &str) -> Result<(), String>;
+    /// Copy a file byte for byte, so binary assets survive.
+    fn copy_file(&self, from: &str, to: &str) -> Result<(), String>;
+    /// Immediate subdirectories of `path`, sorted by name.
+    fn list_dirs(&self, path: &str) -> Result<Vec<String>, String>;
+    /// Immediate entries (files and directories) of `path`, sorted by name.
+    fn read_dir(&self, path: &str) -> Result<Vec<FsEntry>, String>;
+    fn metadata(&self, path: &str) -> Result<FsMetadata, String>;
 }
@@ -1,5 +1,7 @@
 mod project_repository;
 mod filesystem_port;
+mod clock;
 
 pub use project_repository::{HubData, ProjectRepository};
-pub use filesystem_port::FileSystemPort;
+pub use filesystem_port::{FileSystemPort, FsEntry, FsMetadata};
+pub use clock::{iso_from_unix_ms, unix_ms_from_iso, Clock};
@@ -1,19 +1,40 @@
-use crate::domain::model::ProjectEntry;
+use crate::domain::model::{DetectionPolicy, ProjectEntry};
 use serde::{Deserialize, Serialize};
 
+/// Current `hub.json` layout. Bump it with a matching step in `HubData::migrate`.
+///
+/// - 0: original layout (no `schema_version` field)
+/// - 1: `ProjectEntry.last_accessed`
+/// - 2: `active_projects`
+pub const HUB_SCHEMA_VERSION: u32 = 2;
+
 /// Persistent storage format for `~/.aidd/hub.json`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HubData {
+    #[serde(default)]
+    pub schema_version: u32,
     #[serde(default)]
     pub projects: Vec<ProjectEntry>,
     #[serde(default)]
     pub active_project: Option<String>,
+    /// Projects active alongside `active_project` in workspace mode; empty
+    /// in the default single-project mode.
+    #[serde(default)]
+    pub active_projects: Vec<String>,
     #[serde(default)]
     pub framework_version: Option<String>,
+    /// Version held in `framework.bak/`, restored by a rollback.
+    #[serde(default)]
+    pub previous_framework_version: Option<String>,
     #[serde(default = "default_true")]
     pub auto_sync: bool,
     #[serde(default)]
     pub last_sync_check: Option<String>,
+    /// Install updates the auto-sync scheduler finds instead of only announcing them.
+    #[serde(default)]
+    pub auto_install: bool,
+    #[serde(default)]
+    pub detection_policy: DetectionPolicy,
 }
 
 fn default_true() -> bool {
@@ -23,12 +44,60 @@ fn default_true() -> bool {
 impl Default for HubData {
     fn default() -> Self {
         Self {
+            schema_version: HUB_SCHEMA_VERSION,
             projects: Vec::new(),
             active_project: None,
+            active_projects: Vec::new(),
             framework_version: None,
+            previous_framework_version: None,
             auto_sync: true,
             last_sync_check: None,
+            auto_install: false,
+            detection_policy: DetectionPolicy::default(),
+        }
+    }
+}
+
+impl HubData {
+    /// Bring data loaded from an older `hub.json` up to `HUB_SCHEMA_VERSION`.
+    pub fn migrate(&mut self) {
+        // 0 -> 1: `last_accessed` comes in through its serde default (0, so
+        // projects never switched to since the upgrade sort last).
+        // 1 -> 2: `active_projects` defaults to empty, i.e. single-project mode.
+        self.schema_version = HUB_SCHEMA_VERSION;
+    }
+
+    /// Point `active_project` back at a registered project when it names a path
+    /// missing from `projects` (manual edit, interrupted removal): the first
+    /// project, or `None` when there are none. Unregistered entries are also
+    /// dropped from `active_projects`. Returns whether anything changed.
+    pub fn repair_active_project(&mut self) -> bool {
+        let dangling = match &self.active_project {
+            Some(active) => !self.is_registered(active),
+            None => false,
+        };
+        if dangling {
+            self.active_project = self.projects.first().map(|p| p.path.clone());
         }
+
+        let before = self.active_projects.len();
+        let active_projects = std::mem::take(&mut self.active_projects);
+        self.active_projects = active_projects.into_iter().filter(|p| self.is_registered(p)).collect();
+
+        dangling || self.active_projects.len() != before
+    }
+
+    /// The primary active project followed by any workspace-mode extras.
+    pub fn active_set(&self) -> Vec<String> {
+        self.active_project
+            .iter()
+            .chain(self.active_projects.iter().filter(|p| Some(*p) != self.active_project.as_ref()))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_registered(&self, path: &str) -> bool {
+        self.projects.iter().any(|p| p.path == path)
     }
 }
 
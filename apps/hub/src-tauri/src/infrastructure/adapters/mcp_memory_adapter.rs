@@ -1,10 +1,13 @@
 use serde_json::json;
 use std::sync::Arc;
 
+use crate::domain::AiddError;
 use crate::domain::ports::inbound::{
-    EvolutionStatus, MemoryPort, ObservationEntry, PatternStats, SessionInfo, SessionSummary,
+    ActivityEntry, ConfigChange, DailyCount, EvolutionStatus, ImportMode, ImportSummary, MemoryPage, MemoryPort, MemorySource, ObservationEntry, PatternStats, PermanentMemoryPage, SessionInfo, SessionStats,
+    SessionSummary, StorageInfo,
 };
-use crate::infrastructure::mcp::McpClient;
+use super::sqlite_memory_adapter::parse_artifact_date_text;
+use crate::infrastructure::mcp::{tool_result_json, McpClient};
 
 /// MCP Adapter for Memory Port.
 /// Connects to a running AIDD engine via JSON-RPC 2.0 over stdio.
@@ -23,29 +26,114 @@ impl McpMemoryAdapter {
         Self { client }
     }
 
-    /// Extract text content from MCP tool result.
-    /// MCP tools return `{ content: [{ type: "text", text: "..." }] }`.
-    fn extract_text_content(result: &serde_json::Value) -> Option<String> {
-        result
-            .get("content")
-            .and_then(|c| c.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|item| item.get("text"))
-            .and_then(|t| t.as_str())
-            .map(|s| s.to_string())
+    /// Call `tool` and return its JSON payload.
+    fn call_json(&self, tool: &str, arguments: serde_json::Value) -> Result<serde_json::Value, AiddError> {
+        let result = self
+            .client
+            .call_tool(tool, arguments)
+            .map_err(|e| format!("{} failed: {}", tool, e))?;
+        tool_result_json(&result).ok_or_else(|| AiddError::Other(format!("{} returned no JSON payload", tool)))
     }
 }
 
+/// `aidd_draft_list` defaults to 20; the SQLite adapter returns every draft.
+const DRAFT_LIST_LIMIT: usize = 1000;
+
+fn json_array(data: &serde_json::Value, key: &str) -> Vec<serde_json::Value> {
+    data.get(key).and_then(|v| v.as_array()).cloned().unwrap_or_default()
+}
+
+/// Whether `entry[key]` falls within `[from_ms, to_ms]`. Entries without a
+/// parseable timestamp only pass when no bound is set.
+fn in_range(entry: &serde_json::Value, key: &str, from_ms: Option<i64>, to_ms: Option<i64>) -> bool {
+    if from_ms.is_none() && to_ms.is_none() {
+        return true;
+    }
+    let ts = match entry.get(key) {
+        Some(serde_json::Value::Number(n)) => n.as_i64(),
+        Some(serde_json::Value::String(s)) => parse_artifact_date_text(s),
+        _ => None,
+    };
+    ts.is_some_and(|ts| from_ms.is_none_or(|from| ts >= from) && to_ms.is_none_or(|to| ts <= to))
+}
+
+/// Most entries a ranged listing asks the engine for before giving up.
+const MAX_RANGED_FETCH: usize = 5000;
+
+/// Up to `limit` entries whose `date_key` falls in `[from_ms, to_ms]`. Engine
+/// list tools take a `limit` but no date filter or offset, so with a range set
+/// `fetch` is re-run with a growing limit until enough entries match or the
+/// engine returns fewer than asked for (nothing older is left).
+fn fetch_in_range(
+    limit: usize,
+    date_key: &str,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+    mut fetch: impl FnMut(usize) -> Result<Vec<serde_json::Value>, AiddError>,
+) -> Result<Vec<serde_json::Value>, AiddError> {
+    let ranged = from_ms.is_some() || to_ms.is_some();
+    let mut asked = limit;
+    loop {
+        let entries = fetch(asked)?;
+        let exhausted = entries.len() < asked;
+        let mut matched: Vec<serde_json::Value> = entries
+            .into_iter()
+            .filter(|entry| in_range(entry, date_key, from_ms, to_ms))
+            .collect();
+        if !ranged || exhausted || matched.len() >= limit || asked >= MAX_RANGED_FETCH {
+            matched.truncate(limit);
+            return Ok(matched);
+        }
+        asked = (asked * 4).min(MAX_RANGED_FETCH);
+    }
+}
+
+/// Flatten the confidence tiers of an `aidd_evolution_status` payload, highest
+/// confidence first, marking each candidate pending like the SQLite adapter.
+fn pending_candidates(data: &serde_json::Value) -> Vec<serde_json::Value> {
+    let Some(tiers) = data.get("candidates") else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<serde_json::Value> = ["highConfidence", "mediumConfidence", "lowConfidence"]
+        .iter()
+        .flat_map(|tier| json_array(tiers, tier))
+        .map(|mut candidate| {
+            if let Some(obj) = candidate.as_object_mut() {
+                obj.insert("status".into(), json!("pending"));
+            }
+            candidate
+        })
+        .collect();
+    let confidence = |c: &serde_json::Value| c.get("confidence").and_then(|v| v.as_f64()).unwrap_or(0.0);
+    candidates.sort_by(|a, b| confidence(b).total_cmp(&confidence(a)));
+    candidates
+}
+
 impl MemoryPort for McpMemoryAdapter {
-    fn get_session_summary(&self) -> Result<SessionSummary, String> {
+    fn for_project(&self, project_path: &str) -> Result<Box<dyn MemoryPort>, AiddError> {
+        // The engine serves whichever project it was started for.
+        Err(AiddError::Other(format!(
+            "Project-scoped memory queries are not supported over MCP: {}",
+            project_path
+        )))
+    }
+
+    fn source(&self) -> MemorySource {
+        MemorySource::Engine
+    }
+
+    fn strict(&self) -> Box<dyn MemoryPort> {
+        // Engine reads already return their errors
+        Box::new(Self::new(self.client.clone()))
+    }
+
+    fn get_session_summary(&self) -> Result<SessionSummary, AiddError> {
         let result = self
             .client
             .call_tool("aidd_session", json!({ "action": "list", "limit": 50 }))
             .map_err(|e| format!("aidd_session failed: {}", e))?;
 
-        let text = Self::extract_text_content(&result).unwrap_or_default();
-        let data: serde_json::Value =
-            serde_json::from_str(&text).unwrap_or(serde_json::Value::Null);
+        let data = tool_result_json(&result).unwrap_or(serde_json::Value::Null);
 
         let sessions = data
             .get("sessions")
@@ -97,7 +185,7 @@ impl MemoryPort for McpMemoryAdapter {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<ObservationEntry>, String> {
+    ) -> Result<Vec<ObservationEntry>, AiddError> {
         let limit = limit.unwrap_or(10);
 
         let result = self
@@ -111,9 +199,7 @@ impl MemoryPort for McpMemoryAdapter {
             )
             .map_err(|e| format!("aidd_memory_search failed: {}", e))?;
 
-        let text = Self::extract_text_content(&result).unwrap_or_default();
-        let data: serde_json::Value =
-            serde_json::from_str(&text).unwrap_or(serde_json::Value::Null);
+        let data = tool_result_json(&result).unwrap_or(serde_json::Value::Null);
 
         let entries = data
             .get("entries")
@@ -146,20 +232,20 @@ impl MemoryPort for McpMemoryAdapter {
                         .and_then(|d| d.as_str())
                         .unwrap_or("")
                         .to_string(),
+                    score: e.get("relevanceScore").and_then(|s| s.as_f64()),
+                    snippet: None,
                 })
             })
             .collect())
     }
 
-    fn get_evolution_status(&self) -> Result<EvolutionStatus, String> {
+    fn get_evolution_status(&self) -> Result<EvolutionStatus, AiddError> {
         let result = self
             .client
             .call_tool("aidd_evolution_status", json!({}))
             .map_err(|e| format!("aidd_evolution_status failed: {}", e))?;
 
-        let text = Self::extract_text_content(&result).unwrap_or_default();
-        let data: serde_json::Value =
-            serde_json::from_str(&text).unwrap_or(serde_json::Value::Null);
+        let data = tool_result_json(&result).unwrap_or(serde_json::Value::Null);
 
         Ok(EvolutionStatus {
             pending_count: data
@@ -181,141 +267,246 @@ impl MemoryPort for McpMemoryAdapter {
         })
     }
 
-    fn list_all_observations(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn list_all_observations(
+        &self,
+        _limit: Option<usize>,
+        _cursor: Option<&str>,
+        _types: Option<&[String]>,
+    ) -> Result<MemoryPage, AiddError> {
+        Err(AiddError::Other("Observation listing is not available via MCP adapter (the engine has no list tool)".to_string()))
+    }
+
+    fn get_observation_type_counts(&self) -> Result<Vec<(String, usize)>, AiddError> {
+        Err(AiddError::Other("Observation type counts are not available via MCP adapter (the engine has no list tool)".to_string()))
     }
 
     fn list_observations_by_session(
         &self,
         _session_id: &str,
         _limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
+        Err(AiddError::Other("Observation listing is not available via MCP adapter (the engine has no list tool)".to_string()))
     }
 
-    fn list_all_sessions(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        // MCP adapter: not yet implemented for detailed session listing
-        Ok(vec![])
+    fn list_all_sessions(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<MemoryPage, AiddError> {
+        // `aidd_session list` has no offset, so everything fits on the first page
+        if cursor.is_some() {
+            return Err(AiddError::Other("Session paging is not supported via MCP adapter".to_string()));
+        }
+        let items = fetch_in_range(limit.unwrap_or(100), "startedAt", from_ms, to_ms, |count| {
+            let data = self.call_json("aidd_session", json!({ "action": "list", "limit": count }))?;
+            Ok(json_array(&data, "sessions"))
+        })?;
+        Ok(MemoryPage { items, next_cursor: None })
     }
 
-    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, AiddError> {
+        let data = self.call_json("aidd_evolution_status", json!({}))?;
+        Ok(pending_candidates(&data))
     }
 
-    fn list_evolution_log(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        // Only the engine's most recent actions are exposed over MCP
+        let data = self.call_json("aidd_evolution_status", json!({}))?;
+        Ok(json_array(&data, "recentActions").into_iter().take(limit.unwrap_or(50)).collect())
     }
 
-    fn list_permanent_memory(&self, _memory_type: &str) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn list_permanent_memory(
+        &self,
+        _memory_type: &str,
+        _limit: Option<usize>,
+        _offset: Option<usize>,
+    ) -> Result<PermanentMemoryPage, AiddError> {
+        Err(AiddError::Other("Permanent memory listing is not available via MCP adapter (the engine has no list tool)".to_string()))
+    }
+
+    fn search_permanent_memory(&self, _query: &str, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        Err(AiddError::Other("Permanent memory search is not available via MCP adapter".to_string()))
     }
 
-    fn delete_permanent_memory(&self, _memory_type: &str, _id: &str) -> Result<(), String> {
-        Ok(())
+    fn delete_permanent_memory(&self, _memory_type: &str, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Permanent memory deletion is not available via MCP adapter".to_string()))
     }
 
-    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, AiddError> {
+        let data = self.call_json("aidd_draft_list", json!({ "limit": DRAFT_LIST_LIMIT }))?;
+        Ok(json_array(&data, "drafts"))
     }
 
     fn list_artifacts(
         &self,
-        _artifact_type: Option<&str>,
-        _status: Option<&str>,
-        _limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+        artifact_type: Option<&str>,
+        status: Option<&str>,
+        limit: Option<usize>,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
+        fetch_in_range(limit.unwrap_or(100), "date", from_ms, to_ms, |count| {
+            let data = self.call_json(
+                "aidd_artifact",
+                json!({
+                    "action": "list",
+                    "type": artifact_type,
+                    "status": status,
+                    "limit": count
+                }),
+            )?;
+            Ok(json_array(&data, "artifacts"))
+        })
+    }
+
+    fn list_audit_scores(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        Err(AiddError::Other("Audit scores are not available via MCP adapter".to_string()))
+    }
+
+    fn get_session_stats(&self) -> Result<SessionStats, AiddError> {
+        Err(AiddError::Other("Session statistics are not available via MCP adapter".to_string()))
+    }
+
+    fn list_pattern_detections(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        Err(AiddError::Other("Pattern detections are not available via MCP adapter".to_string()))
+    }
+
+    fn get_activity_feed(&self, _limit: Option<usize>) -> Result<Vec<ActivityEntry>, AiddError> {
+        Err(AiddError::Other("The activity feed is not available via MCP adapter".to_string()))
     }
 
-    fn list_audit_scores(&self, _limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        Ok(vec![])
+    fn observation_daily_counts(&self, _since_ts: Option<i64>, _until_ts: Option<i64>) -> Result<Vec<DailyCount>, AiddError> {
+        Err(AiddError::Other("Daily observation counts are not available via MCP adapter".to_string()))
     }
 
-    fn get_governance_config(&self) -> Result<serde_json::Value, String> {
-        Err("Governance config reads are not supported via MCP adapter".to_string())
+    fn get_storage_info(&self) -> Result<StorageInfo, AiddError> {
+        Err(AiddError::Other("Storage info is not available via MCP adapter".to_string()))
     }
 
-    fn upsert_governance_config(&self, _config_json: &str) -> Result<(), String> {
-        Err("Governance config writes are not supported via MCP adapter".to_string())
+    fn export_bundle(&self, _out: &mut dyn std::io::Write) -> Result<(), AiddError> {
+        Err(AiddError::Other("Memory export is not supported via MCP adapter".to_string()))
+    }
+
+    fn import_bundle(&self, _bundle_json: &str, _mode: ImportMode) -> Result<ImportSummary, AiddError> {
+        Err(AiddError::Other("Memory import is not supported via MCP adapter".to_string()))
+    }
+
+    fn get_governance_config(&self) -> Result<serde_json::Value, AiddError> {
+        Err(AiddError::Other("Governance config reads are not supported via MCP adapter".to_string()))
+    }
+
+    fn upsert_governance_config(&self, _config_json: &str) -> Result<Vec<ConfigChange>, AiddError> {
+        Err(AiddError::Other("Governance config writes are not supported via MCP adapter".to_string()))
     }
 
     // Write operations — not supported via MCP adapter (use SQLite adapter)
-    fn create_permanent_memory(&self, _memory_type: &str, _title: &str, _content: &str) -> Result<String, String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn create_permanent_memory(&self, _memory_type: &str, _title: &str, _content: &str) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+    fn update_permanent_memory(&self, _id: &str, _title: &str, _content: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+    fn create_artifact(&self, _artifact_type: &str, _feature: &str, _title: &str, _description: &str, _content: &str) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_permanent_memory(&self, _id: &str, _title: &str, _content: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_artifact(&self, _id: &str, _artifact_type: &str, _feature: &str, _title: &str, _description: &str, _content: &str, _status: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn create_artifact(&self, _artifact_type: &str, _feature: &str, _title: &str, _description: &str, _content: &str) -> Result<String, String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn archive_artifact(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_artifact(&self, _id: &str, _artifact_type: &str, _feature: &str, _title: &str, _description: &str, _content: &str, _status: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn delete_artifact(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn archive_artifact(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn approve_evolution_candidate(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn delete_artifact(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn reject_evolution_candidate(&self, _id: &str, _reason: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn approve_evolution_candidate(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn approve_draft(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn reject_evolution_candidate(&self, _id: &str, _reason: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn reject_draft(&self, _id: &str, _reason: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn approve_draft(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn delete_session(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn reject_draft(&self, _id: &str, _reason: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_session(&self, _id: &str, _branch: Option<&str>, _input: Option<&str>, _output: Option<&str>) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn delete_session(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_session_full(&self, _id: &str, _updates_json: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_session(&self, _id: &str, _branch: Option<&str>, _input: Option<&str>, _output: Option<&str>) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn reopen_session(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_session_full(&self, _id: &str, _updates_json: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn create_observation(&self, _session_id: &str, _obs_type: &str, _title: &str, _narrative: Option<&str>, _facts: Option<&str>, _concepts: Option<&str>, _files_read: Option<&str>, _files_modified: Option<&str>, _discovery_tokens: Option<i64>, _created_at: Option<&str>) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn create_observation(&self, _session_id: &str, _obs_type: &str, _title: &str, _narrative: Option<&str>, _facts: Option<&str>, _concepts: Option<&str>, _files_read: Option<&str>, _files_modified: Option<&str>, _discovery_tokens: Option<i64>) -> Result<String, String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_observation(&self, _id: &str, _obs_type: &str, _title: &str, _narrative: Option<&str>, _facts: Option<&str>, _concepts: Option<&str>, _files_read: Option<&str>, _files_modified: Option<&str>, _discovery_tokens: Option<i64>) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_observation(&self, _id: &str, _obs_type: &str, _title: &str, _narrative: Option<&str>, _facts: Option<&str>, _concepts: Option<&str>, _files_read: Option<&str>, _files_modified: Option<&str>, _discovery_tokens: Option<i64>) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn delete_observation(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn delete_observation(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn create_evolution_candidate(&self, _evo_type: &str, _title: &str, _confidence: f64, _data: &str) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn create_evolution_candidate(&self, _evo_type: &str, _title: &str, _confidence: f64, _data: &str) -> Result<String, String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_evolution_candidate_entry(&self, _id: &str, _evo_type: &str, _title: &str, _confidence: f64, _data: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_evolution_candidate_entry(&self, _id: &str, _evo_type: &str, _title: &str, _confidence: f64, _data: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn delete_evolution_candidate(&self, _id: &str, _preserve_log: bool) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn delete_evolution_candidate(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn create_draft(&self, _category: &str, _title: &str, _filename: &str, _content: &str, _confidence: f64, _source: &str) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn create_draft(&self, _category: &str, _title: &str, _filename: &str, _content: &str, _confidence: f64, _source: &str) -> Result<String, String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn update_draft(&self, _id: &str, _title: &str, _content: &str, _category: &str, _confidence: Option<f64>, _filename: Option<&str>) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn update_draft(&self, _id: &str, _title: &str, _content: &str, _category: &str, _confidence: Option<f64>, _filename: Option<&str>) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+    fn delete_draft(&self, _id: &str) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
-    fn delete_draft(&self, _id: &str) -> Result<(), String> {
-        Err("Write operations not supported via MCP adapter".to_string())
+
+    fn delete_drafts_by_status(&self, _status: &str) -> Result<usize, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn create_banned_pattern(&self, _category: &str, _pattern: &str, _pattern_type: &str, _severity: &str, _hint: Option<&str>) -> Result<String, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn update_banned_pattern(&self, _id: &str, _category: &str, _pattern: &str, _pattern_type: &str, _severity: &str, _hint: Option<&str>, _active: bool) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn mark_detection_false_positive(&self, _id: i64) -> Result<(), AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn delete_drafts(&self, _ids: &[String]) -> Result<usize, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
     }
 
-    fn get_pattern_stats(&self) -> Result<PatternStats, String> {
+    fn reject_drafts(&self, _ids: &[String], _reason: &str) -> Result<usize, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn archive_artifacts(&self, _ids: &[String]) -> Result<usize, AiddError> {
+        Err(AiddError::Other("Write operations not supported via MCP adapter".to_string()))
+    }
+
+    fn get_pattern_stats(&self) -> Result<PatternStats, AiddError> {
         let result = self
             .client
             .call_tool("aidd_pattern_stats", json!({}))
             .map_err(|e| format!("aidd_pattern_stats failed: {}", e))?;
 
-        let text = Self::extract_text_content(&result).unwrap_or_default();
-        let data: serde_json::Value =
-            serde_json::from_str(&text).unwrap_or(serde_json::Value::Null);
+        let data = tool_result_json(&result).unwrap_or(serde_json::Value::Null);
 
         Ok(PatternStats {
             total_patterns: data
@@ -337,3 +528,57 @@ impl MemoryPort for McpMemoryAdapter {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranged_listing_asks_for_more_until_the_range_is_covered() {
+        // Newest first, as the engine lists them
+        let sessions: Vec<serde_json::Value> = (1..=9)
+            .rev()
+            .map(|day| json!({ "id": format!("s{}", day), "startedAt": format!("2026-01-0{}T09:00:00Z", day) }))
+            .chain([json!({ "id": "undated" })])
+            .collect();
+        let asked = std::cell::RefCell::new(vec![]);
+        let fetch = |limit: usize| -> Result<Vec<serde_json::Value>, AiddError> {
+            asked.borrow_mut().push(limit);
+            Ok(sessions.iter().take(limit).cloned().collect())
+        };
+        let ids = |entries: Vec<serde_json::Value>| -> Vec<String> {
+            entries.iter().map(|e| e["id"].as_str().unwrap().to_string()).collect()
+        };
+
+        assert_eq!(ids(fetch_in_range(2, "startedAt", None, None, fetch).unwrap()), ["s9", "s8"]);
+        assert_eq!(asked.take(), [2]);
+
+        // Only the oldest sessions are in range, beyond the first two the engine returns
+        let to = parse_artifact_date_text("2026-01-03T23:59:59Z").unwrap();
+        assert_eq!(ids(fetch_in_range(2, "startedAt", None, Some(to), fetch).unwrap()), ["s3", "s2"]);
+        assert_eq!(asked.take(), [2, 8]);
+
+        // Stops once the engine runs out, with whatever matched
+        let from = parse_artifact_date_text("2026-01-01").unwrap();
+        let matched = fetch_in_range(20, "startedAt", Some(from), Some(to), fetch).unwrap();
+        assert_eq!(ids(matched), ["s3", "s2", "s1"]);
+        assert_eq!(asked.take(), [20]);
+    }
+
+    #[test]
+    fn candidates_are_flattened_across_tiers_by_confidence() {
+        let data = json!({
+            "candidates": {
+                "total": 3,
+                "highConfidence": [{ "id": "a", "confidence": 95 }],
+                "mediumConfidence": [{ "id": "b", "confidence": 70 }],
+                "lowConfidence": [{ "id": "c", "confidence": 80 }]
+            }
+        });
+        let candidates = pending_candidates(&data);
+        let ids: Vec<&str> = candidates.iter().map(|c| c["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["a", "c", "b"]);
+        assert!(candidates.iter().all(|c| c["status"] == "pending"));
+        assert!(pending_candidates(&json!({})).is_empty());
+    }
+}
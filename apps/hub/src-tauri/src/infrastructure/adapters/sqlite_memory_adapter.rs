@@ -1,10 +1,16 @@
-use rusqlite::{Connection, OpenFlags, OptionalExtension};
-use std::path::PathBuf;
-use std::sync::Arc;
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Transaction};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use uuid::Uuid;
 
+use crate::domain::AiddError;
+use crate::domain::ports::outbound::{iso_from_unix_ms, Clock};
+use crate::infrastructure::clock::SystemClock;
 use crate::domain::ports::inbound::{
-    MemoryPort, ProjectPort, SessionSummary, SessionInfo, ObservationEntry, EvolutionStatus, PatternStats,
+    MemoryPort, ProjectPort, SessionSummary, SessionInfo, SessionStats, ObservationEntry, EvolutionStatus, PatternStats, StorageInfo, MemoryPage, PermanentMemoryPage, ActivityEntry,
+    ConfigChange, DailyCount, ImportMode, ImportSummary, MemorySource,
 };
 use crate::application::ProjectService;
 
@@ -20,23 +26,133 @@ const REQUIRED_TABLES: &[&str] = &[
     "permanent_memory",
 ];
 
+/// Oldest engine `schema_version` (stored in `meta`) this adapter can query.
+/// Version 3 moved session and artifact timestamps to integer columns.
+const MIN_SCHEMA_VERSION: u32 = 3;
+
+/// How long a connection waits on a lock held by another process (typically the
+/// engine writing to the same `data.db`) before failing with `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_millis(5000);
+
+/// A connection reused across calls for one database file.
+/// The schema is verified once, the first time the connection is used.
+struct CachedConnection {
+    path: PathBuf,
+    conn: Connection,
+    schema_verified: bool,
+}
+
+/// Holds at most one open connection, reopening it when the requested path changes
+/// (i.e. when the active project switches).
+struct ConnectionCache {
+    flags: OpenFlags,
+    slot: Mutex<Option<CachedConnection>>,
+}
+
+impl ConnectionCache {
+    fn new(flags: OpenFlags) -> Self {
+        Self {
+            flags,
+            slot: Mutex::new(None),
+        }
+    }
+
+    fn with_connection<T, F>(&self, path: &Path, f: F) -> Result<T, AiddError>
+    where
+        F: FnOnce(&Connection) -> Result<T, AiddError>,
+    {
+        let mut slot = self
+            .slot
+            .lock()
+            .map_err(|_| AiddError::Database("Database connection cache is poisoned".to_string()))?;
+
+        if slot.as_ref().is_none_or(|cached| cached.path != path) {
+            *slot = None;
+            let conn = Connection::open_with_flags(path, self.flags)
+                .map_err(|e| db_error("Failed to open database", e))?;
+            configure_connection(&conn, self.flags)?;
+            *slot = Some(CachedConnection {
+                path: path.to_path_buf(),
+                conn,
+                schema_verified: false,
+            });
+        }
+
+        let cached = slot.as_mut().expect("connection cache populated above");
+        if !cached.schema_verified {
+            verify_schema(&cached.conn)?;
+            cached.schema_verified = true;
+        }
+        f(&cached.conn)
+    }
+}
+
 /// SQLite Adapter for Memory Port.
 /// Queries and writes to the project's memory database.
-/// Dynamically resolves the active project's database path.
+/// Dynamically resolves the active project's database path, unless pinned to
+/// one project via `for_project`.
 pub struct SqliteMemoryAdapter {
     project_service: Arc<ProjectService>,
+    pinned_project: Option<String>,
+    /// Refuse writes; set when pinned to a registered project that isn't active.
+    read_only: bool,
+    read_conn: Arc<ConnectionCache>,
+    write_conn: Arc<ConnectionCache>,
+    clock: Arc<dyn Clock>,
+    /// Return read errors instead of empty results (see `MemoryPort::strict`).
+    strict_reads: bool,
 }
 
 impl SqliteMemoryAdapter {
     pub fn new(project_service: Arc<ProjectService>) -> Self {
-        Self { project_service }
+        Self::with_project(project_service, None, Arc::new(SystemClock))
+    }
+
+    fn with_project(
+        project_service: Arc<ProjectService>,
+        pinned_project: Option<String>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            project_service,
+            pinned_project,
+            read_only: false,
+            read_conn: Arc::new(ConnectionCache::new(OpenFlags::SQLITE_OPEN_READ_ONLY)),
+            // Never creates DB — no SQLITE_OPEN_CREATE
+            write_conn: Arc::new(ConnectionCache::new(OpenFlags::SQLITE_OPEN_READ_WRITE)),
+            clock,
+            strict_reads: false,
+        }
+    }
+
+    /// Pin to `project_path`, which must be a registered project. Projects that
+    /// aren't active are opened read-only, so browsing them never changes the
+    /// active state or writes behind the engine's back.
+    pub fn for_registered_project(&self, project_path: &str) -> Result<Self, AiddError> {
+        let registered = self.project_service.list()
+            .map_err(|e| AiddError::Other(format!("Failed to list projects: {}", e)))?;
+        if !registered.iter().any(|p| p.path == project_path) {
+            return Err(AiddError::Other(format!("Project is not registered: {}", project_path)));
+        }
+        let active = self.project_service.list_active_projects()
+            .map_err(|e| AiddError::Other(format!("Failed to get active projects: {}", e)))?;
+        let mut adapter = Self::with_project(
+            self.project_service.clone(),
+            Some(project_path.to_string()),
+            self.clock.clone(),
+        );
+        adapter.read_only = !active.iter().any(|p| p == project_path);
+        Ok(adapter)
     }
 
-    /// Get the database path for the active project
-    fn get_db_path(&self) -> Result<PathBuf, String> {
-        let active_path = self.project_service.get_active_path()
-            .map_err(|e| format!("Failed to get active project: {}", e))?
-            .ok_or_else(|| "No active project configured".to_string())?;
+    /// Get the database path for the pinned project, else the active one
+    fn get_db_path(&self) -> Result<PathBuf, AiddError> {
+        let active_path = match &self.pinned_project {
+            Some(path) => path.clone(),
+            None => self.project_service.get_active_path()
+                .map_err(|e| AiddError::Other(format!("Failed to get active project: {}", e)))?
+                .ok_or(AiddError::NoActiveProject)?,
+        };
 
         let db_path = PathBuf::from(active_path)
             .join(".aidd")
@@ -44,96 +160,235 @@ impl SqliteMemoryAdapter {
 
         // Verify file exists before opening (prevents creating empty DB)
         if !db_path.exists() {
-            return Err(format!("Database not found: {}", db_path.display()));
+            return Err(AiddError::DbNotFound { path: db_path.display().to_string() });
         }
 
         Ok(db_path)
     }
 
-    /// Open a read-only connection to the project database
-    fn open_connection(&self) -> Result<Connection, String> {
+    /// Run `f` on the cached read-write connection after schema verification.
+    fn with_rw_connection<T, F>(&self, f: F) -> Result<T, AiddError>
+    where
+        F: FnOnce(&Connection) -> Result<T, AiddError>,
+    {
+        if self.read_only {
+            return Err(AiddError::Other(format!(
+                "Project is open read-only: {}",
+                self.pinned_project.as_deref().unwrap_or_default()
+            )));
+        }
         let path = self.get_db_path()?;
-
-        Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
-            .map_err(|e| format!("Failed to open database: {}", e))
+        self.write_conn.with_connection(&path, f)
     }
 
-    /// Open a read-write connection (never creates DB — no SQLITE_OPEN_CREATE)
-    fn open_rw_connection(&self) -> Result<Connection, String> {
+    fn safe_query<T, F>(&self, f: F) -> Result<T, AiddError>
+    where
+        F: Fn(&Connection) -> Result<T, rusqlite::Error>,
+    {
         let path = self.get_db_path()?;
-
-        Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_WRITE)
-            .map_err(|e| format!("Failed to open database for writing: {}", e))
-    }
-
-    /// Verify that required tables exist in the database
-    fn verify_schema(&self, conn: &Connection) -> Result<(), String> {
-        verify_schema(conn)
+        self.read_conn.with_connection(&path, |conn| {
+            f(conn).map_err(|e| db_error("Database query failed", e))
+        })
     }
 
-    fn safe_query<T, F>(&self, f: F) -> Result<T, String>
+    fn safe_write<T, F>(&self, f: F) -> Result<T, AiddError>
     where
         F: Fn(&Connection) -> Result<T, rusqlite::Error>,
     {
-        let conn = self.open_connection()?;
-        self.verify_schema(&conn)?;
-        f(&conn).map_err(|e| format!("Database query failed: {}", e))
+        self.with_rw_connection(|conn| {
+            f(conn).map_err(|e| db_error("Database write failed", e))
+        })
     }
 
-    fn safe_write<T, F>(&self, f: F) -> Result<T, String>
+    /// Like `safe_write`, but runs `f` inside a transaction that commits only if it succeeds.
+    fn safe_write_tx<T, F>(&self, f: F) -> Result<T, AiddError>
     where
-        F: Fn(&Connection) -> Result<T, rusqlite::Error>,
+        F: Fn(&Transaction) -> Result<T, rusqlite::Error>,
     {
-        let conn = self.open_rw_connection()?;
-        self.verify_schema(&conn)?;
-        f(&conn).map_err(|e| format!("Database write failed: {}", e))
-    }
-
-    /// Generate an ISO 8601 timestamp for the current time
-    fn now_iso() -> String {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-        let secs = now.as_secs();
-        // Simple UTC ISO format — matches JS new Date().toISOString()
-        let days_since_epoch = secs / 86400;
-        let time_of_day = secs % 86400;
-        let hours = time_of_day / 3600;
-        let minutes = (time_of_day % 3600) / 60;
-        let seconds = time_of_day % 60;
-
-        // Compute year/month/day from days since epoch (1970-01-01)
-        let (year, month, day) = days_to_ymd(days_since_epoch);
-
-        format!(
-            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.000Z",
-            year, month, day, hours, minutes, seconds
-        )
+        self.with_rw_connection(|conn| {
+            run_in_transaction(conn, |tx| f(tx))
+                .map_err(|e| db_error("Database write failed", e))
+        })
+    }
+
+    /// Fall back to `fallback` when a read fails, so panels render empty rather
+    /// than erroring. The error is logged; strict views return it instead.
+    fn read_fallback<T>(&self, e: AiddError, fallback: T) -> Result<T, AiddError> {
+        if self.strict_reads {
+            return Err(e);
+        }
+        if !matches!(e, AiddError::NoActiveProject | AiddError::DbNotFound { .. }) {
+            eprintln!("[memory] Read failed, returning an empty result: {}", e);
+        }
+        fallback_unless_busy(e, fallback)
+    }
+
+    /// Generate an ISO 8601 timestamp (millisecond precision) for the current time
+    fn now_iso(&self) -> String {
+        self.clock.now_iso()
     }
 
     /// Generate current Unix timestamp in milliseconds.
-    fn now_unix_ms() -> i64 {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-        now.as_millis() as i64
-    }
-}
-
-/// Convert days since Unix epoch to (year, month, day)
-fn days_to_ymd(days: u64) -> (u64, u64, u64) {
-    // Algorithm from http://howardhinnant.github.io/date_algorithms.html
-    let z = days + 719468;
-    let era = z / 146097;
-    let doe = z - era * 146097;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-    (y, m, d)
+    fn now_unix_ms(&self) -> i64 {
+        self.clock.now_unix_ms()
+    }
+
+    /// Stream the export bundle to `out` one row at a time. Every table is read
+    /// inside one transaction, so the sections match each other.
+    fn write_export_bundle(&self, out: &mut dyn Write) -> Result<(), AiddError> {
+        let path = self.get_db_path()?;
+        let exported_at = serde_json::Value::from(self.now_iso());
+        self.read_conn.with_connection(&path, |conn| {
+            let query_error = |e: rusqlite::Error| db_error("Database query failed", e);
+            let tx = conn.unchecked_transaction().map_err(query_error)?;
+            write!(out, "{{\"schemaVersion\":{},\"exportedAt\":{}", EXPORT_SCHEMA_VERSION, exported_at)
+                .map_err(export_write_error)?;
+            for (key, table) in BUNDLE_SECTIONS {
+                write!(out, ",\"{}\":[", key).map_err(export_write_error)?;
+                // Tables the engine has not created yet export as empty sections
+                if table_exists(&tx, table).map_err(query_error)? {
+                    write_raw_rows(&tx, table, out)?;
+                }
+                out.write_all(b"]").map_err(export_write_error)?;
+            }
+            out.write_all(b"}").and_then(|_| out.flush()).map_err(export_write_error)
+        })
+    }
+}
+
+/// Delete a candidate and, unless `preserve_log`, its `evolution_log` rows.
+/// Pattern detections carry no candidate id, so they are left alone.
+fn delete_candidate_rows(tx: &Transaction, id: &str, preserve_log: bool) -> Result<(), rusqlite::Error> {
+    if !preserve_log {
+        tx.execute("DELETE FROM evolution_log WHERE candidate_id = ?1", rusqlite::params![id])?;
+    }
+    tx.execute("DELETE FROM evolution_candidates WHERE id = ?1", rusqlite::params![id])?;
+    Ok(())
+}
+
+fn query_pattern_stats(conn: &Connection) -> PatternStats {
+    let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, usize>(0)).unwrap_or(0);
+    PatternStats {
+        total_patterns: count("SELECT COUNT(*) FROM banned_patterns"),
+        active_patterns: count("SELECT COUNT(*) FROM banned_patterns WHERE active = 1"),
+        total_detections: count("SELECT COUNT(*) FROM pattern_detections"),
+        false_positives: count("SELECT COUNT(*) FROM pattern_detections WHERE source = 'false_positive'"),
+    }
+}
+
+/// Session and observation rollups, computed in SQL. Durations use the same
+/// normalized timestamps as the session list and only count sessions whose
+/// end is not before their start.
+fn query_session_stats(conn: &Connection) -> Result<SessionStats, rusqlite::Error> {
+    let (total, active, avg_duration_ms): (i64, i64, Option<f64>) = conn.query_row(
+        &format!(
+            "SELECT COUNT(*),
+                    COUNT(*) - COUNT(ended_at),
+                    (SELECT AVG(ended_at_ts - started_at_ts) FROM ({SESSIONS_WITH_TS_SQL})
+                     WHERE ended_at_ts >= started_at_ts)
+             FROM sessions"
+        ),
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    // Middle row (odd count) or the average of the middle two (even count)
+    let median_duration_ms: Option<f64> = conn.query_row(
+        &format!(
+            "WITH durations AS (
+                 SELECT ended_at_ts - started_at_ts AS ms FROM ({SESSIONS_WITH_TS_SQL})
+                 WHERE ended_at_ts >= started_at_ts
+             )
+             SELECT AVG(ms) FROM (
+                 SELECT ms FROM durations ORDER BY ms
+                 LIMIT 2 - (SELECT COUNT(*) FROM durations) % 2
+                 OFFSET ((SELECT COUNT(*) FROM durations) - 1) / 2
+             )"
+        ),
+        [],
+        |row| row.get(0),
+    )?;
+    let total_discovery_tokens: i64 =
+        conn.query_row("SELECT COALESCE(SUM(discovery_tokens), 0) FROM observations", [], |row| row.get(0))?;
+
+    let mut stmt = conn.prepare("SELECT type, COUNT(*) FROM observations GROUP BY type")?;
+    let observations_by_type = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)))?
+        .collect::<Result<_, _>>()?;
+
+    Ok(SessionStats {
+        total: total as usize,
+        active: active as usize,
+        completed: (total - active) as usize,
+        avg_duration_ms,
+        median_duration_ms,
+        total_discovery_tokens,
+        observations_by_type,
+    })
+}
+
+/// Newest detections first. The pattern is LEFT JOINed, so detections whose
+/// pattern was since deleted come back with `pattern: null`.
+fn query_pattern_detections(conn: &Connection, limit: usize) -> Result<Vec<serde_json::Value>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT d.id, d.session_id, d.model_id, d.pattern_id, d.matched_text, d.context, d.source, d.created_at,
+                p.id, p.category, p.pattern, p.type, p.severity, p.active
+         FROM pattern_detections d
+         LEFT JOIN banned_patterns p ON p.id = d.pattern_id
+         ORDER BY d.created_at DESC, d.id DESC LIMIT ?1",
+    )?;
+    let rows = stmt.query_map([limit as i64], |row| {
+        let pattern = match row.get::<_, Option<String>>(8)? {
+            Some(id) => serde_json::json!({
+                "id": id,
+                "category": row.get::<_, String>(9)?,
+                "pattern": row.get::<_, String>(10)?,
+                "type": row.get::<_, String>(11)?,
+                "severity": row.get::<_, String>(12)?,
+                "active": row.get::<_, i64>(13)? != 0,
+            }),
+            None => serde_json::Value::Null,
+        };
+        Ok(serde_json::json!({
+            "id": row.get::<_, i64>(0)?,
+            "sessionId": row.get::<_, Option<String>>(1)?,
+            "modelId": row.get::<_, String>(2)?,
+            "patternId": row.get::<_, Option<String>>(3)?,
+            "matchedText": row.get::<_, String>(4)?,
+            "context": row.get::<_, Option<String>>(5)?,
+            "source": row.get::<_, String>(6)?,
+            "createdAt": row.get::<_, String>(7)?,
+            "pattern": pattern,
+        }))
+    })?;
+    rows.collect()
+}
+
+fn mark_false_positive_row(tx: &Transaction, id: i64) -> Result<(), rusqlite::Error> {
+    tx.execute(
+        "UPDATE pattern_detections SET source = 'false_positive' WHERE id = ?1",
+        rusqlite::params![id],
+    )?;
+    Ok(())
+}
+
+/// Run `f` in a transaction on a shared connection; dropping the uncommitted
+/// transaction on error rolls every statement back.
+fn run_in_transaction<T, F>(conn: &Connection, f: F) -> Result<T, rusqlite::Error>
+where
+    F: FnOnce(&Transaction) -> Result<T, rusqlite::Error>,
+{
+    let tx = conn.unchecked_transaction()?;
+    let out = f(&tx)?;
+    tx.commit()?;
+    Ok(out)
+}
+
+/// Validate a caller-supplied creation timestamp and normalize it to ISO 8601,
+/// so imported rows sort alongside ones stamped with `now_iso()`.
+fn resolve_created_at(conn: &Connection, raw: &str) -> Result<String, AiddError> {
+    parse_timestamp_text_to_ms(conn, raw)
+        .map(iso_from_unix_ms)
+        .ok_or_else(|| AiddError::Other(format!("Invalid created_at timestamp: {}", raw)))
 }
 
 fn normalize_artifact_type(raw: &str) -> Option<String> {
@@ -267,6 +522,10 @@ fn default_governance_config() -> serde_json::Value {
             "overrideMode": "merge",
             "slimStartEnabled": true,
             "slimStartTargetTokens": 600
+        },
+        "guards": {
+            "requireDocsChecksum": true,
+            "requireAdr": true
         }
     })
 }
@@ -305,6 +564,7 @@ fn normalize_governance_config(input: &serde_json::Value) -> serde_json::Value {
     let model_tracking = input.get("modelTracking").cloned().unwrap_or_default();
     let ci = input.get("ci").cloned().unwrap_or_default();
     let content = input.get("content").cloned().unwrap_or_default();
+    let guards = input.get("guards").cloned().unwrap_or_default();
 
     let auto_apply = evolution
         .get("autoApplyThreshold")
@@ -369,10 +629,68 @@ fn normalize_governance_config(input: &serde_json::Value) -> serde_json::Value {
                 .unwrap_or_else(|| defaults["content"]["overrideMode"].as_str().unwrap_or("merge")),
             "slimStartEnabled": content.get("slimStartEnabled").and_then(|v| v.as_bool()).unwrap_or_else(|| defaults["content"]["slimStartEnabled"].as_bool().unwrap_or(true)),
             "slimStartTargetTokens": slim_target_tokens
+        },
+        "guards": {
+            "requireDocsChecksum": guards.get("requireDocsChecksum").and_then(|v| v.as_bool()).unwrap_or_else(|| defaults["guards"]["requireDocsChecksum"].as_bool().unwrap_or(true)),
+            "requireAdr": guards.get("requireAdr").and_then(|v| v.as_bool()).unwrap_or_else(|| defaults["guards"]["requireAdr"].as_bool().unwrap_or(true))
         }
     })
 }
 
+/// Stored governance config, normalized; defaults when absent or unparseable.
+fn read_governance_config(conn: &Connection) -> Result<serde_json::Value, rusqlite::Error> {
+    let raw: Option<String> = conn
+        .query_row("SELECT data FROM config WHERE id = 'governance' LIMIT 1", [], |row| row.get(0))
+        .optional()?;
+    Ok(raw
+        .and_then(|payload| serde_json::from_str::<serde_json::Value>(&payload).ok())
+        .map(|parsed| normalize_governance_config(&parsed))
+        .unwrap_or_else(default_governance_config))
+}
+
+/// Replace the governance config, returning the leaves that differ from the prior one.
+fn write_governance_config(
+    conn: &Connection,
+    normalized: &serde_json::Value,
+    updated_at: &str,
+) -> Result<Vec<ConfigChange>, rusqlite::Error> {
+    ensure_config_table(conn)?;
+    let prior = read_governance_config(conn)?;
+    conn.execute(
+        "INSERT INTO config (id, data, updated_at)
+         VALUES ('governance', ?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at",
+        rusqlite::params![normalized.to_string(), updated_at],
+    )?;
+
+    let mut changes = vec![];
+    diff_config("", &prior, normalized, &mut changes);
+    Ok(changes)
+}
+
+/// Recurse through nested objects; anything else (scalars, arrays) is compared whole.
+/// Keys are reported as dotted paths such as `evolution.killSwitch`.
+fn diff_config(prefix: &str, old: &serde_json::Value, new: &serde_json::Value, out: &mut Vec<ConfigChange>) {
+    use serde_json::Value;
+
+    if let (Value::Object(old_map), Value::Object(new_map)) = (old, new) {
+        let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            diff_config(
+                &path,
+                old_map.get(key).unwrap_or(&Value::Null),
+                new_map.get(key).unwrap_or(&Value::Null),
+                out,
+            );
+        }
+    } else if old != new {
+        out.push(ConfigChange { key: prefix.to_string(), old: old.clone(), new: new.clone() });
+    }
+}
+
 fn ensure_config_table(conn: &Connection) -> Result<(), rusqlite::Error> {
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS config (
@@ -423,6 +741,203 @@ fn parse_timestamp_text_to_ms(conn: &Connection, raw: &str) -> Option<i64> {
     .and_then(normalize_epoch_timestamp_ms)
 }
 
+/// Parse an artifact `date`/`created_at`/`updated_at` value into Unix ms.
+///
+/// Accepts epoch numbers and the date shapes artifact writers have produced:
+/// ISO 8601 (`YYYY-MM-DD`, optionally with `THH:MM[:SS[.fff]]` and `Z`/`±HH:MM`),
+/// dotted `YYYY.MM.DD` and slash `DD/MM/YYYY`. Anything else yields `None`.
+fn parse_artifact_date(value: &rusqlite::types::Value) -> Option<i64> {
+    use rusqlite::types::Value;
+    match value {
+        Value::Integer(n) => normalize_epoch_timestamp_ms(*n),
+        Value::Real(f) => normalize_epoch_timestamp_ms(*f as i64),
+        Value::Text(s) => parse_artifact_date_text(s),
+        Value::Null | Value::Blob(_) => None,
+    }
+}
+
+pub(super) fn parse_artifact_date_text(raw: &str) -> Option<i64> {
+    let trimmed = raw.trim();
+    if let Ok(num) = trimmed.parse::<i64>() {
+        return normalize_epoch_timestamp_ms(num);
+    }
+
+    let (date_part, time_part) = match trimmed.find(['T', ' ']) {
+        Some(idx) => (&trimmed[..idx], Some(trimmed[idx + 1..].trim())),
+        None => (trimmed, None),
+    };
+
+    let (year, month, day) = parse_calendar_date(date_part)?;
+    let days = days_from_ymd(year, month, day)?;
+    let (secs_of_day, offset_secs) = match time_part {
+        Some(t) if !t.is_empty() => parse_time_of_day(t)?,
+        _ => (0, 0),
+    };
+
+    let secs = days * 86400 + secs_of_day - offset_secs;
+    normalize_epoch_timestamp_ms(secs * 1000)
+}
+
+/// `YYYY-MM-DD`, `YYYY.MM.DD`, `DD.MM.YYYY` or `DD/MM/YYYY`; the four-digit
+/// field decides which end the year is on.
+fn parse_calendar_date(raw: &str) -> Option<(i64, u32, u32)> {
+    let sep = ['-', '.', '/'].into_iter().find(|c| raw.contains(*c))?;
+    let parts: Vec<&str> = raw.split(sep).collect();
+    if parts.len() != 3 || parts.iter().any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    let (y, m, d) = if parts[0].len() == 4 {
+        (parts[0], parts[1], parts[2])
+    } else if parts[2].len() == 4 && sep != '-' {
+        (parts[2], parts[1], parts[0])
+    } else {
+        return None;
+    };
+    let (year, month, day) = (y.parse().ok()?, m.parse().ok()?, d.parse().ok()?);
+    (day >= 1 && day <= days_in_month(year, month)?).then_some((year, month, day))
+}
+
+/// `HH:MM[:SS[.fff]]` with an optional `Z` or `±HH:MM` suffix. Returns
+/// `(seconds since midnight, UTC offset in seconds)`.
+fn parse_time_of_day(raw: &str) -> Option<(i64, i64)> {
+    let (clock, offset) = if let Some(rest) = raw.strip_suffix(['Z', 'z']) {
+        (rest, 0)
+    } else if let Some(idx) = raw.rfind(['+', '-']) {
+        let sign = if raw[idx..].starts_with('-') { -1 } else { 1 };
+        let (h, m) = raw[idx + 1..].split_once(':').unwrap_or((&raw[idx + 1..], "0"));
+        let minutes = h.parse::<i64>().ok()? * 60 + m.parse::<i64>().ok()?;
+        (&raw[..idx], sign * minutes * 60)
+    } else {
+        (raw, 0)
+    };
+
+    let clock = clock.split_once('.').map(|(whole, _)| whole).unwrap_or(clock);
+    let fields: Vec<i64> = clock
+        .split(':')
+        .map(|f| f.parse::<i64>().ok())
+        .collect::<Option<_>>()?;
+    let (h, m, s) = match fields.as_slice() {
+        [h, m] => (*h, *m, 0),
+        [h, m, s] => (*h, *m, *s),
+        _ => return None,
+    };
+    if h > 23 || m > 59 || s > 60 {
+        return None;
+    }
+    Some((h * 3600 + m * 60 + s, offset))
+}
+
+fn days_in_month(year: i64, month: u32) -> Option<u32> {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => Some(31),
+        4 | 6 | 9 | 11 => Some(30),
+        2 => Some(if leap { 29 } else { 28 }),
+        _ => None,
+    }
+}
+
+/// Inverse of `days_to_ymd`: days since 1970-01-01 for a civil date.
+fn days_from_ymd(year: i64, month: u32, day: u32) -> Option<i64> {
+    if year < 1970 {
+        return None;
+    }
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146097 + doe - 719468)
+}
+
+/// List artifacts newest first. Dates are normalized here rather than in SQL so
+/// an unrecognized format surfaces as `dateWarning` (falling back to the
+/// creation time) instead of silently sorting as "now".
+///
+/// Type and status are filtered in SQL. Because dates only parse in Rust, the
+/// inclusive `from_ms`/`to_ms` range is applied after parsing, against the same
+/// date (or createdAt fallback) used for sorting.
+fn query_artifacts(
+    conn: &Connection,
+    artifact_type: Option<&str>,
+    status: Option<&str>,
+    limit: usize,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+) -> Result<Vec<serde_json::Value>, rusqlite::Error> {
+    use rusqlite::types::Value;
+
+    let mut sql = String::from(
+        "SELECT id, session_id, type, feature, status, title, description, content,
+                date, created_at, updated_at
+         FROM artifacts",
+    );
+    let mut conditions: Vec<String> = vec![];
+    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
+
+    if let Some(t) = artifact_type {
+        conditions.push(format!("type = ?{}", params.len() + 1));
+        params.push(Box::new(t.to_string()));
+    }
+    if let Some(s) = status {
+        conditions.push(format!("status = ?{}", params.len() + 1));
+        params.push(Box::new(s.to_string()));
+    }
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+
+    let mut stmt = conn.prepare(&sql)?;
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+    let mut rows: Vec<(Option<i64>, Option<i64>, serde_json::Value)> = stmt
+        .query_map(param_refs.as_slice(), |row| {
+            let mut entry = serde_json::Map::new();
+            entry.insert("id".into(), serde_json::json!(row.get::<_, String>(0)?));
+            let session_id: Option<String> = row.get(1)?;
+            if let Some(sid) = session_id {
+                entry.insert("sessionId".into(), serde_json::json!(sid));
+            }
+            entry.insert("type".into(), serde_json::json!(row.get::<_, String>(2)?));
+            entry.insert("feature".into(), serde_json::json!(row.get::<_, String>(3)?));
+            entry.insert("status".into(), serde_json::json!(row.get::<_, String>(4)?));
+            entry.insert("title".into(), serde_json::json!(row.get::<_, String>(5)?));
+            entry.insert("description".into(), serde_json::json!(row.get::<_, String>(6)?));
+            entry.insert("content".into(), serde_json::json!(row.get::<_, String>(7)?));
+
+            let raw_date: Value = row.get(8)?;
+            let created_at = parse_artifact_date(&row.get::<_, Value>(9)?);
+            let updated_at = parse_artifact_date(&row.get::<_, Value>(10)?);
+            let date = parse_artifact_date(&raw_date);
+            if date.is_none() && raw_date != Value::Null {
+                let shown = match &raw_date {
+                    Value::Text(s) => s.clone(),
+                    other => format!("{:?}", other),
+                };
+                entry.insert("dateWarning".into(), serde_json::json!(format!("Unrecognized date format: {}", shown)));
+            }
+            let date = date.or(created_at);
+
+            entry.insert("date".into(), serde_json::json!(date));
+            entry.insert("createdAt".into(), serde_json::json!(created_at));
+            entry.insert("updatedAt".into(), serde_json::json!(updated_at.or(created_at)));
+            Ok((date, created_at, serde_json::Value::Object(entry)))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if from_ms.is_some() || to_ms.is_some() {
+        rows.retain(|(date, _, _)| {
+            date.is_some_and(|d| from_ms.is_none_or(|from| d >= from) && to_ms.is_none_or(|to| d <= to))
+        });
+    }
+    rows.sort_by_key(|(date, created_at, _)| std::cmp::Reverse((*date, *created_at)));
+    rows.truncate(limit);
+    Ok(rows.into_iter().map(|(_, _, entry)| entry).collect())
+}
+
 fn parse_timestamp_json_to_ms(conn: &Connection, value: &serde_json::Value) -> Option<i64> {
     if let Some(num) = value.as_i64() {
         return normalize_epoch_timestamp_ms(num);
@@ -443,9 +958,60 @@ fn parse_timestamp_from_data_field(
         .and_then(|v| parse_timestamp_json_to_ms(conn, v))
 }
 
-/// Verify that required tables exist in the database.
-/// Extracted as standalone function for testability.
-fn verify_schema(conn: &Connection) -> Result<(), String> {
+/// Set the busy timeout and WAL journaling on a freshly opened connection, so the
+/// Hub and the engine can read and write `data.db` at the same time. A read-only
+/// connection can't switch the journal mode itself; it keeps whatever the file uses.
+fn configure_connection(conn: &Connection, flags: OpenFlags) -> Result<(), AiddError> {
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| db_error("Failed to set busy timeout", e))?;
+    let wal = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get::<_, String>(0));
+    match wal {
+        Err(e) if !flags.contains(OpenFlags::SQLITE_OPEN_READ_ONLY) => {
+            Err(db_error("Failed to enable WAL journaling", e))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether SQLite gave up waiting on a lock held by another connection.
+fn is_busy(e: &rusqlite::Error) -> bool {
+    matches!(
+        e.sqlite_error_code(),
+        Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Wrap a SQLite error with context, keeping lock contention distinguishable
+/// so callers can retry it.
+fn db_error(context: &str, e: rusqlite::Error) -> AiddError {
+    if is_busy(&e) {
+        AiddError::DatabaseBusy(format!("{}: database is locked by another process ({})", context, e))
+    } else {
+        AiddError::Database(format!("{}: {}", context, e))
+    }
+}
+
+/// Fall back to `fallback` when a read fails, unless the database was busy:
+/// that is transient, and an empty result would hide data that exists.
+fn fallback_unless_busy<T>(e: AiddError, fallback: T) -> Result<T, AiddError> {
+    match e {
+        AiddError::DatabaseBusy(_) => Err(e),
+        _ => Ok(fallback),
+    }
+}
+
+/// Best-effort statement (e.g. FTS upkeep): other failures are ignored, but lock
+/// contention still aborts the write so it can be retried.
+fn ignore_unless_busy(result: Result<usize, rusqlite::Error>) -> Result<(), rusqlite::Error> {
+    match result {
+        Err(e) if is_busy(&e) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Verify that required tables exist and the engine's schema version is recent
+/// enough for our queries. Extracted as standalone function for testability.
+fn verify_schema(conn: &Connection) -> Result<(), AiddError> {
     for table in REQUIRED_TABLES {
         let exists: bool = conn.query_row(
             "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1)",
@@ -454,115 +1020,1089 @@ fn verify_schema(conn: &Connection) -> Result<(), String> {
         ).unwrap_or(false);
 
         if !exists {
-            return Err(format!("Missing required table: {}", table));
+            return Err(AiddError::SchemaMissing { table: table.to_string() });
         }
     }
+
+    let found = read_schema_version(conn);
+    if found < MIN_SCHEMA_VERSION {
+        return Err(AiddError::SchemaOutdated { found, expected: MIN_SCHEMA_VERSION });
+    }
     Ok(())
 }
 
-impl MemoryPort for SqliteMemoryAdapter {
-    fn get_session_summary(&self) -> Result<SessionSummary, String> {
-        self.safe_query(|conn| {
-            // Count total sessions
-            let total: usize = conn.query_row(
-                "SELECT COUNT(*) FROM sessions",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
+/// The engine's `meta.schema_version`; 0 for databases created before the
+/// engine started recording it (no `meta` table or no row).
+fn read_schema_version(conn: &Connection) -> u32 {
+    conn.query_row("SELECT value FROM meta WHERE key = 'schema_version'", [], |row| {
+        row.get::<_, String>(0)
+    })
+    .ok()
+    .and_then(|value| value.trim().parse().ok())
+    .unwrap_or(0)
+}
 
-            // Count active sessions (no ended_at)
-            let active: usize = conn.query_row(
-                "SELECT COUNT(*) FROM sessions WHERE ended_at IS NULL",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
+fn query_journal_mode(conn: &Connection) -> Result<String, rusqlite::Error> {
+    conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))
+}
 
-            // Compute completed
-            let completed = total.saturating_sub(active);
+/// Whether SQLite's `<db>-wal` / `<db>-shm` companion file is present.
+fn sidecar_exists(db_path: &Path, suffix: &str) -> bool {
+    let mut name = db_path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name).exists()
+}
 
-            // Get recent sessions
-            let mut stmt = conn.prepare(
-                "SELECT id, branch, started_at FROM sessions ORDER BY started_at DESC LIMIT 5"
-            )?;
+/// Clear the end marker of a session in both the `ended_at` column and the data blob,
+/// in a single UPDATE so the two cannot drift apart. Runs in `tx` so a concurrent
+/// writer can't change the blob between the read and the update.
+fn reopen_session_row(tx: &Transaction, id: &str) -> Result<(), AiddError> {
+    let current_data: Option<String> = tx
+        .query_row("SELECT data FROM sessions WHERE id = ?1", [id], |row| row.get(0))
+        .optional()
+        .map_err(|e| format!("Failed to read session: {}", e))?;
+    let current_data = current_data.ok_or_else(|| format!("Session not found: {}", id))?;
+
+    let mut data: serde_json::Value = serde_json::from_str(&current_data)
+        .unwrap_or(serde_json::json!({}));
+    if let Some(obj) = data.as_object_mut() {
+        obj.remove("endedAt");
+    }
+    let updated = serde_json::to_string(&data).unwrap_or_default();
 
-            let recent = stmt.query_map([], |row| {
-                let started_at_val = row.get_ref(2)?;
-                let started_at = match started_at_val {
-                    rusqlite::types::ValueRef::Integer(v) => v.to_string(),
-                    rusqlite::types::ValueRef::Text(v) => String::from_utf8_lossy(v).to_string(),
-                    _ => String::new(),
-                };
-                Ok(SessionInfo {
-                    id: row.get(0)?,
-                    branch: row.get(1)?,
-                    started_at,
-                    status: "completed".to_string(),
-                })
-            })?
-                .filter_map(|r| r.ok())
-                .collect();
+    tx.execute(
+        "UPDATE sessions SET ended_at = NULL, status = 'active', data = ?1 WHERE id = ?2",
+        rusqlite::params![updated, id],
+    )
+    .map_err(|e| format!("Failed to reopen session: {}", e))?;
+    Ok(())
+}
 
-            Ok(SessionSummary {
-                total,
-                active,
-                completed,
-                recent_sessions: recent,
-            })
-        }).or_else(|_| {
-            // Return empty structure if DB unavailable
-            Ok(SessionSummary {
-                total: 0,
-                active: 0,
-                completed: 0,
-                recent_sessions: vec![],
-            })
-        })
+/// Create the observations FTS index (same definition as the engine's migrations) if missing.
+fn ensure_observations_fts(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'observations_fts')",
+        [],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Ok(());
     }
 
-    fn list_all_observations(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
-        let limit = limit.unwrap_or(200);
-        self.safe_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, session_id, type, title, content, facts, concepts, \
-                 files_read, files_modified, discovery_tokens, created_at \
-                 FROM observations ORDER BY created_at DESC LIMIT ?1"
-            )?;
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS observations_fts USING fts5(
+            title, content, facts, concepts,
+            content='observations', content_rowid='rowid',
+            tokenize='porter unicode61'
+        );
+        CREATE TRIGGER IF NOT EXISTS observations_ai AFTER INSERT ON observations BEGIN
+            INSERT INTO observations_fts(rowid, title, content, facts, concepts)
+            VALUES (new.rowid, new.title, new.content, new.facts, new.concepts);
+        END;
+        CREATE TRIGGER IF NOT EXISTS observations_ad AFTER DELETE ON observations BEGIN
+            INSERT INTO observations_fts(observations_fts, rowid, title, content, facts, concepts)
+            VALUES ('delete', old.rowid, old.title, old.content, old.facts, old.concepts);
+        END;
+        CREATE TRIGGER IF NOT EXISTS observations_au AFTER UPDATE ON observations BEGIN
+            INSERT INTO observations_fts(observations_fts, rowid, title, content, facts, concepts)
+            VALUES ('delete', old.rowid, old.title, old.content, old.facts, old.concepts);
+            INSERT INTO observations_fts(rowid, title, content, facts, concepts)
+            VALUES (new.rowid, new.title, new.content, new.facts, new.concepts);
+        END;
+        INSERT INTO observations_fts(observations_fts) VALUES('rebuild');",
+    )
+}
 
-            let observations = stmt.query_map([limit], |row| {
-                let mut entry = serde_json::Map::new();
-                entry.insert("id".into(), serde_json::json!(row.get::<_, String>(0)?));
-                entry.insert("sessionId".into(), serde_json::json!(row.get::<_, String>(1)?));
-                entry.insert("type".into(), serde_json::json!(row.get::<_, String>(2)?));
-                entry.insert("title".into(), serde_json::json!(row.get::<_, String>(3)?));
-                entry.insert("narrative".into(), serde_json::json!(row.get::<_, Option<String>>(4)?.unwrap_or_default()));
+/// Turn free text into an FTS5 OR-query of quoted terms, mirroring the engine's sanitizer.
+fn build_fts_query(query: &str) -> Option<String> {
+    let cleaned: String = query
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c.is_whitespace() { c } else { ' ' })
+        .collect();
+    let terms: Vec<String> = cleaned
+        .split_whitespace()
+        .filter(|w| w.len() > 1)
+        .map(|w| format!("\"{}\"", w))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" OR "))
+    }
+}
 
-                // Parse JSON array fields
-                let facts_str: String = row.get::<_, Option<String>>(5)?.unwrap_or_default();
-                let concepts_str: String = row.get::<_, Option<String>>(6)?.unwrap_or_default();
-                let files_read_str: String = row.get::<_, Option<String>>(7)?.unwrap_or_default();
-                let files_modified_str: String = row.get::<_, Option<String>>(8)?.unwrap_or_default();
+/// Full-text search over title/content/facts/concepts ranked by bm25,
+/// falling back to a title/type LIKE scan when FTS is unavailable.
+fn search_observations_in(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<ObservationEntry>, rusqlite::Error> {
+    if let Some(fts_query) = build_fts_query(query) {
+        if ensure_observations_fts(conn).is_ok() {
+            if let Ok(results) = search_observations_fts(conn, &fts_query, limit) {
+                return Ok(results);
+            }
+        }
+    }
+    search_observations_like(conn, query, limit)
+}
 
-                entry.insert("facts".into(), serde_json::from_str(&facts_str).unwrap_or(serde_json::json!([])));
-                entry.insert("concepts".into(), serde_json::from_str(&concepts_str).unwrap_or(serde_json::json!([])));
-                entry.insert("filesRead".into(), serde_json::from_str(&files_read_str).unwrap_or(serde_json::json!([])));
-                entry.insert("filesModified".into(), serde_json::from_str(&files_modified_str).unwrap_or(serde_json::json!([])));
-                entry.insert("discoveryTokens".into(), serde_json::json!(row.get::<_, Option<i64>>(9)?.unwrap_or(0)));
-                entry.insert("createdAt".into(), serde_json::json!(row.get::<_, String>(10)?));
+/// Map a `permanent_memory` row (id, type, title, content, created_at, session_id)
+/// to the frontend shape: the JSON content merged with metadata, and the title
+/// under a type-specific key.
+fn permanent_memory_entry(row: &rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    let id: String = row.get(0)?;
+    let entry_type: String = row.get(1)?;
+    let title: String = row.get(2)?;
+    let content: String = row.get(3)?;
+    let created_at: String = row.get(4)?;
+    let session_id: Option<String> = row.get(5)?;
+
+    // Parse content JSON and merge with metadata
+    let content_value = serde_json::from_str::<serde_json::Value>(&content)
+        .unwrap_or(serde_json::json!({}));
+
+    let mut entry = serde_json::Map::new();
+    entry.insert("id".into(), serde_json::json!(id));
+    entry.insert("type".into(), serde_json::json!(entry_type));
+    entry.insert("createdAt".into(), serde_json::json!(created_at));
+    if let Some(sid) = session_id {
+        entry.insert("sessionId".into(), serde_json::json!(sid));
+    }
 
-                Ok(serde_json::Value::Object(entry))
+    // Type-specific field mapping
+    let title_key = match entry_type.as_str() {
+        "mistake" => "error",
+        "decision" => "decision",
+        "convention" => "convention",
+        _ => {
+            entry.insert("title".into(), serde_json::json!(title));
+            entry.insert("content".into(), content_value);
+            return Ok(serde_json::Value::Object(entry));
+        }
+    };
+    entry.insert(title_key.into(), serde_json::json!(title));
+    if let serde_json::Value::Object(map) = content_value {
+        for (k, v) in map {
+            entry.insert(k, v);
+        }
+    }
+    Ok(serde_json::Value::Object(entry))
+}
+
+/// Whether the Hub has to keep `permanent_memory_fts` in step itself: the index
+/// exists but the engine's sync triggers don't (a second insert would corrupt it).
+fn permanent_memory_fts_needs_sync(conn: &Connection) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'permanent_memory_fts') \
+            AND NOT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'trigger' AND name = 'pm_au')",
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Add (or, with `delete`, remove) the index entry for permanent memory `id`
+/// using the row's current title and content.
+fn sync_permanent_memory_fts(conn: &Connection, id: &str, delete: bool) -> Result<(), rusqlite::Error> {
+    let sql = if delete {
+        "INSERT INTO permanent_memory_fts(permanent_memory_fts, rowid, title, content) \
+         SELECT 'delete', rowid, title, content FROM permanent_memory WHERE id = ?1"
+    } else {
+        "INSERT INTO permanent_memory_fts(rowid, title, content) \
+         SELECT rowid, title, content FROM permanent_memory WHERE id = ?1"
+    };
+    ignore_unless_busy(conn.execute(sql, [id])) // FTS upkeep is best-effort
+}
+
+/// Full-text search over permanent memory titles and content ranked by bm25,
+/// falling back to a LIKE scan when the FTS index is unavailable.
+fn search_permanent_memory_in(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<serde_json::Value>, rusqlite::Error> {
+    if let Some(fts_query) = build_fts_query(query) {
+        let fts = conn.prepare(
+            "SELECT pm.id, pm.type, pm.title, pm.content, pm.created_at, pm.session_id \
+             FROM permanent_memory_fts fts \
+             JOIN permanent_memory pm ON pm.rowid = fts.rowid \
+             WHERE permanent_memory_fts MATCH ?1 \
+             ORDER BY bm25(permanent_memory_fts) LIMIT ?2"
+        );
+        if let Ok(mut stmt) = fts {
+            if let Ok(rows) = stmt.query_map(rusqlite::params![fts_query, limit as i64], permanent_memory_entry) {
+                return Ok(rows.filter_map(|r| r.ok()).collect());
+            }
+        }
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, type, title, content, created_at, session_id FROM permanent_memory \
+         WHERE title LIKE ?1 OR content LIKE ?1 \
+         ORDER BY created_at DESC LIMIT ?2"
+    )?;
+    let search_term = format!("%{}%", query);
+    let entries = stmt.query_map(rusqlite::params![search_term, limit as i64], permanent_memory_entry)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+/// Markers FTS5 `snippet()` puts around matched terms.
+const SNIPPET_OPEN: &str = "<mark>";
+const SNIPPET_CLOSE: &str = "</mark>";
+/// Tokens of context `snippet()` keeps around the match.
+const SNIPPET_TOKENS: i64 = 16;
+
+fn search_observations_fts(
+    conn: &Connection,
+    fts_query: &str,
+    limit: usize,
+) -> Result<Vec<ObservationEntry>, rusqlite::Error> {
+    // Column -1 lets FTS5 pick whichever column (title, content, facts, concepts) matched best
+    let mut stmt = conn.prepare(
+        "SELECT o.id, o.session_id, o.title, o.type, o.created_at, bm25(observations_fts) AS rank, \
+                snippet(observations_fts, -1, ?3, ?4, '…', ?5) \
+         FROM observations_fts fts \
+         JOIN observations o ON o.rowid = fts.rowid \
+         WHERE observations_fts MATCH ?1 \
+         ORDER BY rank LIMIT ?2"
+    )?;
+
+    let params = rusqlite::params![fts_query, limit as i64, SNIPPET_OPEN, SNIPPET_CLOSE, SNIPPET_TOKENS];
+    let observations = stmt.query_map(params, |row| {
+        // bm25 is negative with lower = better; flip it so callers sort descending
+        let rank: f64 = row.get(5)?;
+        Ok(ObservationEntry {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            title: row.get(2)?,
+            observation_type: row.get::<_, String>(3)?,
+            created_at: row.get(4)?,
+            score: Some(-rank),
+            snippet: row.get(6)?,
+        })
+    })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(observations)
+}
+
+fn search_observations_like(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<ObservationEntry>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT id, session_id, title, type, created_at FROM observations \
+         WHERE title LIKE ?1 OR type LIKE ?1 \
+         ORDER BY created_at DESC LIMIT ?2"
+    )?;
+
+    let search_term = format!("%{}%", query);
+    let observations = stmt.query_map([&search_term, &limit.to_string()], |row| {
+        Ok(ObservationEntry {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            title: row.get(2)?,
+            observation_type: row.get::<_, String>(3)?,
+            created_at: row.get(4)?,
+            score: None,
+            snippet: None,
+        })
+    })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(observations)
+}
+
+/// Version of the `export_bundle` JSON layout. Version 2 stores raw table rows.
+const EXPORT_SCHEMA_VERSION: u32 = 2;
+const DRAFT_STATUSES: &[&str] = &["pending", "approved", "rejected"];
+
+/// Ids bound per statement by the bulk operations, under SQLite's default
+/// limit of 999 host parameters with room for the non-id ones.
+const BULK_ID_CHUNK: usize = 900;
+
+/// Run `sql`, which must end in `IN`, once per chunk of `ids` with the
+/// placeholder list appended, binding `leading` ahead of each chunk. Duplicate
+/// ids are collapsed so each row counts once. Returns the rows affected.
+fn execute_for_ids(
+    tx: &Transaction,
+    sql: &str,
+    leading: &[rusqlite::types::Value],
+    ids: &[String],
+    chunk_size: usize,
+) -> Result<usize, rusqlite::Error> {
+    let mut ids: Vec<&String> = ids.iter().collect();
+    ids.sort();
+    ids.dedup();
+
+    let mut affected = 0;
+    for chunk in ids.chunks(chunk_size) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let params = leading
+            .iter()
+            .cloned()
+            .chain(chunk.iter().map(|id| rusqlite::types::Value::Text(id.to_string())));
+        affected += tx.execute(&format!("{} ({})", sql, placeholders), rusqlite::params_from_iter(params))?;
+    }
+    Ok(affected)
+}
+
+fn reject_draft_rows(
+    tx: &Transaction,
+    ids: &[String],
+    reason: &str,
+    now: &str,
+    chunk_size: usize,
+) -> Result<usize, rusqlite::Error> {
+    execute_for_ids(
+        tx,
+        "UPDATE drafts SET status = 'rejected', \
+         data = json_set(data, '$.rejectedReason', ?), updated_at = ? WHERE id IN",
+        &[reason.to_string().into(), now.to_string().into()],
+        ids,
+        chunk_size,
+    )
+}
+
+fn validate_draft_status(status: &str) -> Result<(), AiddError> {
+    if DRAFT_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(AiddError::Other(format!(
+            "Invalid draft status '{}'. Valid: {:?}",
+            status, DRAFT_STATUSES
+        )))
+    }
+}
+
+const PATTERN_TYPES: &[&str] = &["exact", "regex"];
+
+/// Reject a banned pattern the engine's detector could not use. The detector
+/// compiles regex patterns with JavaScript's `new RegExp(pattern, "gi")`, so
+/// they are checked as JavaScript: see `js_pattern_for_parsing` for what the
+/// Rust parser can and cannot vouch for. Syntax errors report the byte offset
+/// where parsing failed.
+fn validate_pattern(pattern: &str, pattern_type: &str) -> Result<(), AiddError> {
+    if !PATTERN_TYPES.contains(&pattern_type) {
+        return Err(AiddError::Other(format!(
+            "Invalid pattern type '{}'. Valid: {:?}",
+            pattern_type, PATTERN_TYPES
+        )));
+    }
+    if pattern.is_empty() {
+        return Err(AiddError::Other("Pattern must not be empty".to_string()));
+    }
+    if pattern_type != "regex" {
+        return Ok(());
+    }
+
+    let parseable = js_pattern_for_parsing(pattern)?;
+    if let Err(e) = regex_syntax::Parser::new().parse(&parseable) {
+        let (offset, message) = match &e {
+            regex_syntax::Error::Parse(err) => (err.span().start.offset, err.kind().to_string()),
+            regex_syntax::Error::Translate(err) => (err.span().start.offset, err.kind().to_string()),
+            _ => return Err(AiddError::Other(format!("Invalid regex pattern: {}", e))),
+        };
+        return Err(AiddError::Other(format!("Invalid regex at offset {}: {}", offset, message)));
+    }
+    Ok(())
+}
+
+/// Rewrite a JavaScript pattern so `regex_syntax` can check it, keeping byte
+/// offsets intact. Lookarounds (which JavaScript supports and Rust does not)
+/// become plain groups and backreferences become wildcards, so only their
+/// surroundings are checked. Constructs that are Rust-only, or that JavaScript
+/// would silently read as something else, are rejected: `(?P<name>)`, inline
+/// flags such as `(?i)`, `\A`, `\z`, `\p{..}` (needs the `u` flag) and POSIX
+/// classes like `[[:alpha:]]`.
+///
+/// Known gap: the rest is parsed with Rust's grammar, so a few patterns that
+/// JavaScript accepts outside unicode mode are still refused (a lone `{`, or
+/// identity escapes such as `\e`).
+fn js_pattern_for_parsing(pattern: &str) -> Result<String, AiddError> {
+    let unsupported = |offset: usize, what: &str| {
+        Err(AiddError::Other(format!("Invalid regex at offset {}: {}", offset, what)))
+    };
+    let mut out = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut i = 0;
+    while i < pattern.len() {
+        let rest = &pattern[i..];
+        let c = rest.chars().next().unwrap_or_default();
+        if c == '\\' {
+            let Some(escaped) = rest[1..].chars().next() else {
+                out.push(c); // the parser reports the dangling backslash
+                break;
+            };
+            match escaped {
+                'A' | 'z' => return unsupported(i, "\\A and \\z are not anchors in JavaScript; use ^ and $"),
+                'p' | 'P' => return unsupported(i, "Unicode property classes need the `u` flag, which the detector does not set"),
+                '1'..='9' if !in_class => {
+                    let digits = rest[1..].bytes().take_while(u8::is_ascii_digit).count();
+                    out.push_str(&".".repeat(1 + digits));
+                    i += 1 + digits;
+                    continue;
+                }
+                'k' if !in_class && rest[2..].starts_with('<') => {
+                    if let Some(end) = rest.find('>') {
+                        out.push_str(&".".repeat(end + 1));
+                        i += end + 1;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            out.push(c);
+            out.push(escaped);
+            i += 1 + escaped.len_utf8();
+            continue;
+        }
+
+        if in_class {
+            in_class = c != ']';
+        } else if rest.starts_with("[[:") {
+            return unsupported(i, "POSIX classes are not supported in JavaScript");
+        } else if c == '[' {
+            in_class = true;
+        } else if rest.starts_with("(?<=") || rest.starts_with("(?<!") {
+            out.push_str("(?:.");
+            i += 4;
+            continue;
+        } else if rest.starts_with("(?=") || rest.starts_with("(?!") {
+            out.push_str("(?:");
+            i += 3;
+            continue;
+        } else if rest.starts_with("(?P<") {
+            return unsupported(i, "(?P<name>...) is not JavaScript syntax; use (?<name>...)");
+        } else if rest.starts_with("(?") && rest[2..].starts_with(|f: char| f.is_ascii_alphabetic() || f == '-') {
+            return unsupported(i, "inline flags are not supported in JavaScript; patterns already match case-insensitively");
+        }
+        out.push(c);
+        i += c.len_utf8();
+    }
+    Ok(out)
+}
+
+/// Write every row of `table` to `out` as comma-separated objects keyed by column
+/// name. Blobs are wrapped as `{"$blob": "<hex>"}` so an import can tell them
+/// apart from text.
+fn write_raw_rows(conn: &Connection, table: &str, out: &mut dyn Write) -> Result<(), AiddError> {
+    use rusqlite::types::ValueRef;
+
+    let query_error = |e: rusqlite::Error| db_error("Database query failed", e);
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {} ORDER BY rowid", table)).map_err(query_error)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let mut rows = stmt.query([]).map_err(query_error)?;
+    let mut first = true;
+    while let Some(row) = rows.next().map_err(query_error)? {
+        let mut obj = serde_json::Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i).map_err(query_error)? {
+                ValueRef::Null => serde_json::Value::Null,
+                ValueRef::Integer(n) => n.into(),
+                ValueRef::Real(f) => f.into(),
+                ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+                ValueRef::Blob(b) => serde_json::json!({
+                    "$blob": b.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+                }),
+            };
+            obj.insert(column.clone(), value);
+        }
+        if !first {
+            out.write_all(b",").map_err(export_write_error)?;
+        }
+        first = false;
+        serde_json::to_writer(&mut *out, &obj)
+            .map_err(|e| AiddError::Io(format!("Failed to write memory export: {}", e)))?;
+    }
+    Ok(())
+}
+
+fn export_write_error(e: std::io::Error) -> AiddError {
+    AiddError::Io(format!("Failed to write memory export: {}", e))
+}
+
+/// A bundled row as `(column, value)` pairs, `id` first.
+type BundleRow = Vec<(String, rusqlite::types::Value)>;
+
+/// Bundle key and target table for each section, in import order.
+const BUNDLE_SECTIONS: &[(&str, &str)] = &[
+    ("sessions", "sessions"),
+    ("observations", "observations"),
+    ("evolutionCandidates", "evolution_candidates"),
+    ("evolutionLog", "evolution_log"),
+    ("permanentMemory", "permanent_memory"),
+    ("drafts", "drafts"),
+    ("artifacts", "artifacts"),
+];
+
+/// Validate a bundle and convert every row up front, so a malformed entry is
+/// rejected before the database is touched. Column names are checked against
+/// the target tables by `import_bundle_rows`.
+fn parse_bundle(bundle_json: &str) -> Result<Vec<(&'static str, Vec<BundleRow>)>, AiddError> {
+    let bundle: serde_json::Value = serde_json::from_str(bundle_json)
+        .map_err(|e| AiddError::Other(format!("Invalid memory bundle JSON: {}", e)))?;
+    match bundle.get("schemaVersion").and_then(|v| v.as_u64()) {
+        Some(version) if version == u64::from(EXPORT_SCHEMA_VERSION) => {}
+        Some(version) => {
+            return Err(AiddError::Other(format!("Unsupported memory bundle schemaVersion: {}", version)))
+        }
+        None => return Err(AiddError::Other("Memory bundle is missing schemaVersion".to_string())),
+    }
+
+    BUNDLE_SECTIONS
+        .iter()
+        .map(|(key, table)| {
+            let items = match bundle.get(*key) {
+                None | Some(serde_json::Value::Null) => vec![],
+                Some(serde_json::Value::Array(items)) => items.clone(),
+                Some(_) => return Err(AiddError::Other(format!("Invalid memory bundle: {} is not an array", key))),
+            };
+            let rows = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    item.as_object()
+                        .ok_or_else(|| "is not an object".to_string())
+                        .and_then(bundle_row)
+                        .map_err(|e| AiddError::Other(format!("Invalid memory bundle: {}[{}] {}", key, i, e)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((*table, rows))
+        })
+        .collect()
+}
+
+/// Convert one exported row back into column values, reversing `query_raw_rows`.
+fn bundle_row(obj: &serde_json::Map<String, serde_json::Value>) -> Result<BundleRow, String> {
+    use rusqlite::types::Value;
+
+    let id = obj.get("id").and_then(|v| v.as_str()).ok_or("is missing an id")?;
+    let mut row: BundleRow = vec![("id".to_string(), Value::Text(id.to_string()))];
+    for (column, value) in obj.iter().filter(|(column, _)| column.as_str() != "id") {
+        let value = match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Integer(i64::from(*b)),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Integer(i),
+                None => Value::Real(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => Value::Text(s.clone()),
+            serde_json::Value::Object(wrapper) => {
+                let hex = wrapper
+                    .get("$blob")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| format!("has an unsupported value in column {}", column))?;
+                Value::Blob(decode_hex(hex).ok_or_else(|| format!("has an invalid blob in column {}", column))?)
+            }
+            serde_json::Value::Array(_) => return Err(format!("has an unsupported value in column {}", column)),
+        };
+        row.push((column.clone(), value));
+    }
+    Ok(row)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+/// Column names of `table`, as declared in the database.
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?.collect();
+    columns
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1)",
+        [table],
+        |row| row.get(0),
+    )
+}
+
+/// Insert parsed bundle sections. Run inside a transaction: any failed insert
+/// (e.g. a section whose table is missing) must leave the database as it was.
+fn import_bundle_rows(
+    conn: &Connection,
+    sections: &[(&'static str, Vec<BundleRow>)],
+    mode: ImportMode,
+) -> Result<ImportSummary, rusqlite::Error> {
+    if mode == ImportMode::Replace {
+        for (table, _) in sections {
+            if table_exists(conn, table)? {
+                conn.execute(&format!("DELETE FROM {}", table), [])?;
+            }
+        }
+    }
+
+    // Replace rebuilds the whole index below; a merge indexes just what it adds
+    let index_merged = mode == ImportMode::Merge && permanent_memory_fts_needs_sync(conn)?;
+    let mut summary = ImportSummary::default();
+    for (table, rows) in sections {
+        if rows.is_empty() {
+            continue;
+        }
+        // Column names are spliced into the INSERT, so only the table's own are allowed
+        let known = table_columns(conn, table)?;
+        for row in rows {
+            if let Some((column, _)) = row.iter().find(|(column, _)| !known.contains(column)) {
+                return Err(rusqlite::Error::InvalidColumnName(format!("{}.{}", table, column)));
+            }
+            if mode == ImportMode::Merge {
+                let exists: bool = conn.query_row(
+                    &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1)", table),
+                    [&row[0].1],
+                    |r| r.get(0),
+                )?;
+                if exists {
+                    summary.skipped += 1;
+                    continue;
+                }
+            }
+            let columns: Vec<&str> = row.iter().map(|(column, _)| column.as_str()).collect();
+            let placeholders: Vec<String> = (1..=row.len()).map(|i| format!("?{}", i)).collect();
+            conn.prepare_cached(&format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table,
+                columns.join(", "),
+                placeholders.join(", "),
+            ))?
+            .execute(rusqlite::params_from_iter(row.iter().map(|(_, value)| value)))?;
+            if index_merged && *table == "permanent_memory" {
+                if let rusqlite::types::Value::Text(id) = &row[0].1 {
+                    sync_permanent_memory_fts(conn, id, false)?;
+                }
+            }
+            summary.imported += 1;
+        }
+    }
+
+    // The index is external-content: rebuilding re-reads permanent_memory, which
+    // also covers databases whose engine schema predates the sync triggers.
+    if mode == ImportMode::Replace && table_exists(conn, "permanent_memory_fts")? {
+        conn.execute("INSERT INTO permanent_memory_fts(permanent_memory_fts) VALUES('rebuild')", [])?;
+    }
+    Ok(summary)
+}
+
+/// Encode a keyset position as `<sort key>|<id>`; the id breaks ties between equal keys.
+fn encode_cursor(key: &str, id: &str) -> String {
+    format!("{}|{}", key, id)
+}
+
+fn decode_cursor(cursor: &str) -> Result<(String, String), AiddError> {
+    cursor
+        .rsplit_once('|')
+        .map(|(key, id)| (key.to_string(), id.to_string()))
+        .ok_or_else(|| AiddError::Other(format!("Invalid cursor: {}", cursor)))
+}
+
+/// Fetch one page of observations ordered by `(created_at, id)` descending,
+/// starting strictly after `after` when given.
+fn query_observations_page(
+    conn: &Connection,
+    limit: usize,
+    after: Option<&(String, String)>,
+    types: Option<&[String]>,
+) -> Result<MemoryPage, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT id, session_id, type, title, content, facts, concepts, \
+         files_read, files_modified, discovery_tokens, created_at \
+         FROM observations \
+         WHERE (?2 IS NULL OR created_at < ?2 OR (created_at = ?2 AND id < ?3)) \
+           AND (?4 IS NULL OR type IN (SELECT value FROM json_each(?4))) \
+         ORDER BY created_at DESC, id DESC LIMIT ?1"
+    )?;
+
+    // Types travel as one JSON array so any number of them binds to a single parameter
+    let types = types
+        .filter(|t| !t.is_empty())
+        .map(|t| serde_json::to_string(t).unwrap_or_default());
+    // One extra row tells us whether another page follows
+    let params = rusqlite::params![
+        (limit + 1) as i64,
+        after.map(|(created_at, _)| created_at.as_str()),
+        after.map(|(_, id)| id.as_str()),
+        types,
+    ];
+    let mut rows: Vec<(String, String, serde_json::Value)> = stmt.query_map(params, |row| {
+        let id: String = row.get(0)?;
+        let created_at: String = row.get(10)?;
+        let mut entry = serde_json::Map::new();
+        entry.insert("id".into(), serde_json::json!(id));
+        entry.insert("sessionId".into(), serde_json::json!(row.get::<_, String>(1)?));
+        entry.insert("type".into(), serde_json::json!(row.get::<_, String>(2)?));
+        entry.insert("title".into(), serde_json::json!(row.get::<_, String>(3)?));
+        entry.insert("narrative".into(), serde_json::json!(row.get::<_, Option<String>>(4)?.unwrap_or_default()));
+
+        // Parse JSON array fields
+        let facts_str: String = row.get::<_, Option<String>>(5)?.unwrap_or_default();
+        let concepts_str: String = row.get::<_, Option<String>>(6)?.unwrap_or_default();
+        let files_read_str: String = row.get::<_, Option<String>>(7)?.unwrap_or_default();
+        let files_modified_str: String = row.get::<_, Option<String>>(8)?.unwrap_or_default();
+
+        entry.insert("facts".into(), serde_json::from_str(&facts_str).unwrap_or(serde_json::json!([])));
+        entry.insert("concepts".into(), serde_json::from_str(&concepts_str).unwrap_or(serde_json::json!([])));
+        entry.insert("filesRead".into(), serde_json::from_str(&files_read_str).unwrap_or(serde_json::json!([])));
+        entry.insert("filesModified".into(), serde_json::from_str(&files_modified_str).unwrap_or(serde_json::json!([])));
+        entry.insert("discoveryTokens".into(), serde_json::json!(row.get::<_, Option<i64>>(9)?.unwrap_or(0)));
+        entry.insert("createdAt".into(), serde_json::json!(created_at));
+
+        Ok((created_at, id, serde_json::Value::Object(entry)))
+    })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let next_cursor = if rows.len() > limit {
+        rows.truncate(limit);
+        rows.last().map(|(created_at, id, _)| encode_cursor(created_at, id))
+    } else {
+        None
+    };
+
+    Ok(MemoryPage {
+        items: rows.into_iter().map(|(_, _, item)| item).collect(),
+        next_cursor,
+    })
+}
+
+/// Observation count per type, most frequent first; ties in type order.
+fn query_observation_type_counts(conn: &Connection) -> Result<Vec<(String, usize)>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT type, COUNT(*) FROM observations GROUP BY type ORDER BY COUNT(*) DESC, type")?;
+    let counts = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(counts)
+}
+
+/// Count observations per UTC day. `created_at` holds ISO text or epoch
+/// seconds/ms depending on the writer, so it is normalized to ms the same way
+/// session start times are; rows that do not parse are left out.
+fn query_observation_daily_counts(
+    conn: &Connection,
+    since_ts: Option<i64>,
+    until_ts: Option<i64>,
+) -> Result<Vec<DailyCount>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "SELECT strftime('%Y-%m-%d', created_at_ts / 1000, 'unixepoch') AS day, COUNT(*) FROM (
+            SELECT CASE
+                     WHEN created_at IS NULL OR created_at = '' THEN NULL
+                     WHEN typeof(created_at) = 'integer' OR created_at GLOB '[0-9]*' THEN
+                       CASE
+                         WHEN CAST(created_at AS INTEGER) >= 1000000000000 THEN CAST(created_at AS INTEGER)
+                         WHEN CAST(created_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(created_at AS INTEGER) * 1000
+                         ELSE CAST(strftime('%s', created_at) AS INTEGER) * 1000
+                       END
+                     ELSE CAST(strftime('%s', created_at) AS INTEGER) * 1000
+                   END AS created_at_ts
+             FROM observations
+         )
+         WHERE created_at_ts IS NOT NULL
+           AND (?1 IS NULL OR created_at_ts >= ?1)
+           AND (?2 IS NULL OR created_at_ts <= ?2)
+         GROUP BY day
+         ORDER BY day"
+    )?;
+
+    let counts = stmt.query_map(rusqlite::params![since_ts, until_ts], |row| {
+        Ok(DailyCount {
+            date: row.get(0)?,
+            count: row.get::<_, i64>(1)? as usize,
+        })
+    })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(counts)
+}
+
+/// `sessions` with `started_at`/`ended_at` normalized to epoch ms (`NULL` when
+/// absent or unparseable), whatever mix of seconds, ms and ISO text they hold.
+const SESSIONS_WITH_TS_SQL: &str = "
+    SELECT id, data,
+            CASE
+              WHEN typeof(started_at) = 'integer' THEN
+                CASE
+                  WHEN CAST(started_at AS INTEGER) >= 1000000000000 THEN CAST(started_at AS INTEGER)
+                  WHEN CAST(started_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(started_at AS INTEGER) * 1000
+                  ELSE NULL
+                END
+              WHEN started_at IS NULL OR started_at = '' THEN NULL
+              WHEN started_at GLOB '[0-9]*' THEN
+                CASE
+                  WHEN CAST(started_at AS INTEGER) >= 1000000000000 THEN CAST(started_at AS INTEGER)
+                  WHEN CAST(started_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(started_at AS INTEGER) * 1000
+                  ELSE NULL
+                END
+              ELSE CAST(strftime('%s', started_at) AS INTEGER) * 1000
+            END AS started_at_ts,
+            CASE
+              WHEN ended_at IS NULL OR ended_at = '' THEN NULL
+              WHEN typeof(ended_at) = 'integer' THEN
+                CASE
+                  WHEN CAST(ended_at AS INTEGER) >= 1000000000000 THEN CAST(ended_at AS INTEGER)
+                  WHEN CAST(ended_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(ended_at AS INTEGER) * 1000
+                  ELSE NULL
+                END
+              WHEN ended_at GLOB '[0-9]*' THEN
+                CASE
+                  WHEN CAST(ended_at AS INTEGER) >= 1000000000000 THEN CAST(ended_at AS INTEGER)
+                  WHEN CAST(ended_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(ended_at AS INTEGER) * 1000
+                  ELSE NULL
+                END
+              ELSE CAST(strftime('%s', ended_at) AS INTEGER) * 1000
+            END AS ended_at_ts
+     FROM sessions";
+
+/// Fetch one page of sessions ordered by normalized start time (ms) then id, descending.
+/// Sessions without a parseable start time sort last with key 0, and are excluded
+/// whenever `from_ms` or `to_ms` (both inclusive) is given.
+fn query_sessions_page(
+    conn: &Connection,
+    limit: usize,
+    after: Option<&(i64, String)>,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+) -> Result<MemoryPage, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        &format!(
+            "SELECT id, data, started_at_ts, ended_at_ts, COALESCE(started_at_ts, 0) AS sort_ts
+             FROM ({SESSIONS_WITH_TS_SQL})
+             WHERE (?2 IS NULL OR sort_ts < ?2 OR (sort_ts = ?2 AND id < ?3))
+               AND (?4 IS NULL OR started_at_ts >= ?4)
+               AND (?5 IS NULL OR started_at_ts <= ?5)
+             ORDER BY sort_ts DESC, id DESC
+             LIMIT ?1"
+        ),
+    )?;
+
+    // One extra row tells us whether another page follows
+    let params = rusqlite::params![
+        (limit + 1) as i64,
+        after.map(|(ts, _)| *ts),
+        after.map(|(_, id)| id.as_str()),
+        from_ms,
+        to_ms,
+    ];
+    let mut rows: Vec<(i64, String, serde_json::Value)> = stmt.query_map(params, |row| {
+        let id: String = row.get(0)?;
+        let data: String = row.get(1)?;
+        let started_at_ts: Option<i64> = row.get(2)?;
+        let ended_at_ts: Option<i64> = row.get(3)?;
+        let sort_ts: i64 = row.get(4)?;
+
+        let mut parsed = serde_json::from_str::<serde_json::Value>(&data)
+            .unwrap_or(serde_json::Value::Null);
+        let started = started_at_ts
+            .and_then(normalize_epoch_timestamp_ms)
+            .or_else(|| parse_timestamp_from_data_field(conn, &parsed, "startedAt"));
+        let ended = ended_at_ts
+            .and_then(normalize_epoch_timestamp_ms)
+            .or_else(|| parse_timestamp_from_data_field(conn, &parsed, "endedAt"));
+        if let Some(obj) = parsed.as_object_mut() {
+            if let Some(v) = started {
+                obj.insert("startedAtTs".into(), serde_json::json!(v));
+            }
+
+            if let Some(v) = ended {
+                obj.insert("endedAtTs".into(), serde_json::json!(v));
+            }
+        }
+
+        Ok((sort_ts, id, parsed))
+    })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let next_cursor = if rows.len() > limit {
+        rows.truncate(limit);
+        rows.last().map(|(ts, id, _)| encode_cursor(&ts.to_string(), id))
+    } else {
+        None
+    };
+
+    Ok(MemoryPage {
+        items: rows
+            .into_iter()
+            .map(|(_, _, item)| item)
+            .filter(|v| !v.is_null())
+            .collect(),
+        next_cursor,
+    })
+}
+
+/// Each feed source: kind tag and a query yielding `(id, title, raw timestamp)`.
+/// Timestamps are stored differently per table (ISO text vs epoch ms), so they are
+/// normalized in Rust before merging.
+const ACTIVITY_SOURCES: &[(&str, &str)] = &[
+    (
+        "observation",
+        "SELECT id, title, created_at FROM observations ORDER BY created_at DESC LIMIT ?1",
+    ),
+    (
+        "artifact",
+        "SELECT id, title, created_at FROM artifacts ORDER BY created_at DESC LIMIT ?1",
+    ),
+    (
+        "evolution",
+        "SELECT id, action || ': ' || title, timestamp FROM evolution_log ORDER BY timestamp DESC LIMIT ?1",
+    ),
+    (
+        "audit",
+        "SELECT CAST(id AS TEXT), 'Audit ' || verdict || ' (' || model_id || ')', created_at \
+         FROM audit_scores ORDER BY created_at DESC LIMIT ?1",
+    ),
+];
+
+/// Merge the newest `limit` rows of every activity source by timestamp.
+/// Sources whose table is missing (older engine schemas) are skipped.
+fn collect_activity_feed(conn: &Connection, limit: usize) -> Vec<ActivityEntry> {
+    let mut feed: Vec<ActivityEntry> = Vec::new();
+    for (kind, sql) in ACTIVITY_SOURCES {
+        let mut stmt = match conn.prepare(sql) {
+            Ok(stmt) => stmt,
+            Err(_) => continue,
+        };
+        let rows = stmt.query_map([limit as i64], |row| {
+            let id: String = row.get(0)?;
+            let title: String = row.get(1)?;
+            let timestamp = match row.get_ref(2)? {
+                rusqlite::types::ValueRef::Integer(v) => normalize_epoch_timestamp_ms(v),
+                rusqlite::types::ValueRef::Text(v) => {
+                    parse_timestamp_text_to_ms(conn, &String::from_utf8_lossy(v))
+                }
+                _ => None,
+            };
+            Ok((id, title, timestamp))
+        });
+        if let Ok(rows) = rows {
+            feed.extend(rows.filter_map(|r| r.ok()).map(|(id, title, timestamp)| ActivityEntry {
+                kind: kind.to_string(),
+                id,
+                title,
+                timestamp: timestamp.unwrap_or(0),
+            }));
+        }
+    }
+
+    feed.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+    feed.truncate(limit);
+    feed
+}
+
+impl MemoryPort for SqliteMemoryAdapter {
+    fn for_project(&self, project_path: &str) -> Result<Box<dyn MemoryPort>, AiddError> {
+        Ok(Box::new(self.for_registered_project(project_path)?))
+    }
+
+    fn source(&self) -> MemorySource {
+        MemorySource::Sqlite
+    }
+
+    fn strict(&self) -> Box<dyn MemoryPort> {
+        // Shares the connection caches, so strict reads don't reopen the database
+        Box::new(Self {
+            project_service: self.project_service.clone(),
+            pinned_project: self.pinned_project.clone(),
+            read_only: self.read_only,
+            read_conn: self.read_conn.clone(),
+            write_conn: self.write_conn.clone(),
+            clock: self.clock.clone(),
+            strict_reads: true,
+        })
+    }
+
+    fn get_session_summary(&self) -> Result<SessionSummary, AiddError> {
+        self.safe_query(|conn| {
+            // Count total sessions
+            let total: usize = conn.query_row(
+                "SELECT COUNT(*) FROM sessions",
+                [],
+                |row| row.get(0),
+            ).unwrap_or(0);
+
+            // Count active sessions (no ended_at)
+            let active: usize = conn.query_row(
+                "SELECT COUNT(*) FROM sessions WHERE ended_at IS NULL",
+                [],
+                |row| row.get(0),
+            ).unwrap_or(0);
+
+            // Compute completed
+            let completed = total.saturating_sub(active);
+
+            // Get recent sessions
+            let mut stmt = conn.prepare(
+                "SELECT id, branch, started_at FROM sessions ORDER BY started_at DESC LIMIT 5"
+            )?;
+
+            let recent = stmt.query_map([], |row| {
+                let started_at_val = row.get_ref(2)?;
+                let started_at = match started_at_val {
+                    rusqlite::types::ValueRef::Integer(v) => v.to_string(),
+                    rusqlite::types::ValueRef::Text(v) => String::from_utf8_lossy(v).to_string(),
+                    _ => String::new(),
+                };
+                Ok(SessionInfo {
+                    id: row.get(0)?,
+                    branch: row.get(1)?,
+                    started_at,
+                    status: "completed".to_string(),
+                })
             })?
                 .filter_map(|r| r.ok())
                 .collect();
 
-            Ok(observations)
-        }).or_else(|_| Ok(vec![]))
+            Ok(SessionSummary {
+                total,
+                active,
+                completed,
+                recent_sessions: recent,
+            })
+        }).or_else(|e| {
+            // Return empty structure if DB unavailable
+            self.read_fallback(e, SessionSummary {
+                total: 0,
+                active: 0,
+                completed: 0,
+                recent_sessions: vec![],
+            })
+        })
+    }
+
+    fn list_all_observations(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        types: Option<&[String]>,
+    ) -> Result<MemoryPage, AiddError> {
+        let limit = limit.unwrap_or(200);
+        let after = cursor.map(decode_cursor).transpose()?;
+        self.safe_query(move |conn| query_observations_page(conn, limit, after.as_ref(), types))
+            .or_else(|e| self.read_fallback(e, MemoryPage::default()))
+    }
+
+    fn get_observation_type_counts(&self) -> Result<Vec<(String, usize)>, AiddError> {
+        self.safe_query(query_observation_type_counts).or_else(|e| self.read_fallback(e, Vec::new()))
     }
 
     fn list_observations_by_session(
         &self,
         session_id: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
         let session_id = session_id.to_string();
         let limit = limit.unwrap_or(500);
         self.safe_query(move |conn| {
@@ -601,41 +2141,25 @@ impl MemoryPort for SqliteMemoryAdapter {
                 .collect();
 
             Ok(observations)
-        }).or_else(|_| Ok(vec![]))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
     fn search_observations(
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<ObservationEntry>, String> {
+    ) -> Result<Vec<ObservationEntry>, AiddError> {
         let limit = limit.unwrap_or(10);
+        let query = query.to_string();
 
-        self.safe_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT id, session_id, title, type, created_at FROM observations \
-                 WHERE title LIKE ?1 OR type LIKE ?1 \
-                 ORDER BY created_at DESC LIMIT ?2"
-            )?;
-
-            let search_term = format!("%{}%", query);
-            let observations = stmt.query_map([&search_term, &limit.to_string()], |row| {
-                Ok(ObservationEntry {
-                    id: row.get(0)?,
-                    session_id: row.get(1)?,
-                    title: row.get(2)?,
-                    observation_type: row.get::<_, String>(3)?,
-                    created_at: row.get(4)?,
-                })
-            })?
-                .filter_map(|r| r.ok())
-                .collect();
-
-            Ok(observations)
-        }).or_else(|_| Ok(vec![]))
+        // Read-write because the FTS index may have to be created on first use
+        self.with_rw_connection(move |conn| {
+            search_observations_in(conn, &query, limit)
+                .map_err(|e| AiddError::Database(format!("Database query failed: {}", e)))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
-    fn get_evolution_status(&self) -> Result<EvolutionStatus, String> {
+    fn get_evolution_status(&self) -> Result<EvolutionStatus, AiddError> {
         self.safe_query(|conn| {
             let pending: usize = conn.query_row(
                 "SELECT COUNT(*) FROM evolution_candidates WHERE status = 'pending'",
@@ -667,8 +2191,8 @@ impl MemoryPort for SqliteMemoryAdapter {
                 rejected_count: rejected,
                 auto_applied_count: auto_applied,
             })
-        }).or_else(|_| {
-            Ok(EvolutionStatus {
+        }).or_else(|e| {
+            self.read_fallback(e, EvolutionStatus {
                 pending_count: 0,
                 approved_count: 0,
                 rejected_count: 0,
@@ -677,40 +2201,13 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn get_pattern_stats(&self) -> Result<PatternStats, String> {
-        self.safe_query(|conn| {
-            let total: usize = conn.query_row(
-                "SELECT COUNT(*) FROM banned_patterns",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
-
-            let active: usize = conn.query_row(
-                "SELECT COUNT(*) FROM banned_patterns WHERE active = 1",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
-
-            let detections: usize = conn.query_row(
-                "SELECT COUNT(*) FROM pattern_detections",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
-
-            let false_positives: usize = conn.query_row(
-                "SELECT COUNT(*) FROM pattern_detections WHERE source = 'false_positive'",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
+    fn get_session_stats(&self) -> Result<SessionStats, AiddError> {
+        self.safe_query(query_session_stats).or_else(|e| self.read_fallback(e, SessionStats::default()))
+    }
 
-            Ok(PatternStats {
-                total_patterns: total,
-                active_patterns: active,
-                total_detections: detections,
-                false_positives,
-            })
-        }).or_else(|_| {
-            Ok(PatternStats {
+    fn get_pattern_stats(&self) -> Result<PatternStats, AiddError> {
+        self.safe_query(|conn| Ok(query_pattern_stats(conn))).or_else(|e| {
+            self.read_fallback(e, PatternStats {
                 total_patterns: 0,
                 active_patterns: 0,
                 total_detections: 0,
@@ -719,82 +2216,26 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn list_all_sessions(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+    fn list_all_sessions(
+        &self,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<MemoryPage, AiddError> {
         let limit = limit.unwrap_or(100);
-        self.safe_query(move |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT data,
-                        CASE
-                          WHEN typeof(started_at) = 'integer' THEN
-                            CASE
-                              WHEN CAST(started_at AS INTEGER) >= 1000000000000 THEN CAST(started_at AS INTEGER)
-                              WHEN CAST(started_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(started_at AS INTEGER) * 1000
-                              ELSE NULL
-                            END
-                          WHEN started_at IS NULL OR started_at = '' THEN NULL
-                          WHEN started_at GLOB '[0-9]*' THEN
-                            CASE
-                              WHEN CAST(started_at AS INTEGER) >= 1000000000000 THEN CAST(started_at AS INTEGER)
-                              WHEN CAST(started_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(started_at AS INTEGER) * 1000
-                              ELSE NULL
-                            END
-                          ELSE CAST(strftime('%s', started_at) AS INTEGER) * 1000
-                        END AS started_at_ts,
-                        CASE
-                          WHEN ended_at IS NULL OR ended_at = '' THEN NULL
-                          WHEN typeof(ended_at) = 'integer' THEN
-                            CASE
-                              WHEN CAST(ended_at AS INTEGER) >= 1000000000000 THEN CAST(ended_at AS INTEGER)
-                              WHEN CAST(ended_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(ended_at AS INTEGER) * 1000
-                              ELSE NULL
-                            END
-                          WHEN ended_at GLOB '[0-9]*' THEN
-                            CASE
-                              WHEN CAST(ended_at AS INTEGER) >= 1000000000000 THEN CAST(ended_at AS INTEGER)
-                              WHEN CAST(ended_at AS INTEGER) BETWEEN 1000000000 AND 9999999999 THEN CAST(ended_at AS INTEGER) * 1000
-                              ELSE NULL
-                            END
-                          ELSE CAST(strftime('%s', ended_at) AS INTEGER) * 1000
-                        END AS ended_at_ts
-                 FROM sessions
-                 ORDER BY started_at_ts DESC
-                 LIMIT ?1"
-            )?;
-
-            let sessions = stmt.query_map([limit], |row| {
-                let data: String = row.get(0)?;
-                let started_at_ts: Option<i64> = row.get(1)?;
-                let ended_at_ts: Option<i64> = row.get(2)?;
-
-                let mut parsed = serde_json::from_str::<serde_json::Value>(&data)
-                    .unwrap_or(serde_json::Value::Null);
-                let started = started_at_ts
-                    .and_then(normalize_epoch_timestamp_ms)
-                    .or_else(|| parse_timestamp_from_data_field(conn, &parsed, "startedAt"));
-                let ended = ended_at_ts
-                    .and_then(normalize_epoch_timestamp_ms)
-                    .or_else(|| parse_timestamp_from_data_field(conn, &parsed, "endedAt"));
-                if let Some(obj) = parsed.as_object_mut() {
-                    if let Some(v) = started {
-                        obj.insert("startedAtTs".into(), serde_json::json!(v));
-                    }
-
-                    if let Some(v) = ended {
-                        obj.insert("endedAtTs".into(), serde_json::json!(v));
-                    }
-                }
-
-                Ok(parsed)
-            })?
-                .filter_map(|r| r.ok())
-                .filter(|v| !v.is_null())
-                .collect();
-
-            Ok(sessions)
-        }).or_else(|_| Ok(vec![]))
+        let after = cursor
+            .map(|c| {
+                let (key, id) = decode_cursor(c)?;
+                let ts = key.parse::<i64>().map_err(|_| AiddError::Other(format!("Invalid cursor: {}", c)))?;
+                Ok::<_, AiddError>((ts, id))
+            })
+            .transpose()?;
+        self.safe_query(move |conn| query_sessions_page(conn, limit, after.as_ref(), from_ms, to_ms))
+            .or_else(|e| self.read_fallback(e, MemoryPage::default()))
     }
 
-    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, String> {
+    fn list_evolution_candidates(&self) -> Result<Vec<serde_json::Value>, AiddError> {
         self.safe_query(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT json_set(data, '$.status', status) FROM evolution_candidates \
@@ -810,10 +2251,10 @@ impl MemoryPort for SqliteMemoryAdapter {
                 .collect();
 
             Ok(candidates)
-        }).or_else(|_| Ok(vec![]))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
-    fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+    fn list_evolution_log(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
         let limit = limit.unwrap_or(50);
         self.safe_query(move |conn| {
             let mut stmt = conn.prepare(
@@ -835,79 +2276,45 @@ impl MemoryPort for SqliteMemoryAdapter {
                 .collect();
 
             Ok(entries)
-        }).or_else(|_| Ok(vec![]))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
-    fn list_permanent_memory(&self, memory_type: &str) -> Result<Vec<serde_json::Value>, String> {
+    fn list_permanent_memory(
+        &self,
+        memory_type: &str,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<PermanentMemoryPage, AiddError> {
         let memory_type = memory_type.to_string();
+        let limit = limit.unwrap_or(100);
+        let offset = offset.unwrap_or(0);
         self.safe_query(move |conn| {
+            let total: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM permanent_memory WHERE type = ?1",
+                [&memory_type],
+                |row| row.get(0),
+            )?;
             let mut stmt = conn.prepare(
                 "SELECT id, type, title, content, created_at, session_id \
-                 FROM permanent_memory WHERE type = ?1 ORDER BY created_at DESC"
+                 FROM permanent_memory WHERE type = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2 OFFSET ?3"
             )?;
 
-            let entries = stmt.query_map([&memory_type], |row| {
-                let id: String = row.get(0)?;
-                let entry_type: String = row.get(1)?;
-                let title: String = row.get(2)?;
-                let content: String = row.get(3)?;
-                let created_at: String = row.get(4)?;
-                let session_id: Option<String> = row.get(5)?;
-
-                // Parse content JSON and merge with metadata
-                let content_value = serde_json::from_str::<serde_json::Value>(&content)
-                    .unwrap_or(serde_json::json!({}));
-
-                let mut entry = serde_json::Map::new();
-                entry.insert("id".into(), serde_json::json!(id));
-                entry.insert("type".into(), serde_json::json!(entry_type));
-                entry.insert("createdAt".into(), serde_json::json!(created_at));
-                if let Some(sid) = session_id {
-                    entry.insert("sessionId".into(), serde_json::json!(sid));
-                }
-
-                // Type-specific field mapping
-                match memory_type.as_str() {
-                    "mistake" => {
-                        entry.insert("error".into(), serde_json::json!(title));
-                        if let serde_json::Value::Object(map) = content_value {
-                            for (k, v) in map {
-                                entry.insert(k, v);
-                            }
-                        }
-                    }
-                    "decision" => {
-                        entry.insert("decision".into(), serde_json::json!(title));
-                        if let serde_json::Value::Object(map) = content_value {
-                            for (k, v) in map {
-                                entry.insert(k, v);
-                            }
-                        }
-                    }
-                    "convention" => {
-                        entry.insert("convention".into(), serde_json::json!(title));
-                        if let serde_json::Value::Object(map) = content_value {
-                            for (k, v) in map {
-                                entry.insert(k, v);
-                            }
-                        }
-                    }
-                    _ => {
-                        entry.insert("title".into(), serde_json::json!(title));
-                        entry.insert("content".into(), content_value);
-                    }
-                }
-
-                Ok(serde_json::Value::Object(entry))
-            })?
+            let items = stmt.query_map(rusqlite::params![memory_type, limit as i64, offset as i64], permanent_memory_entry)?
                 .filter_map(|r| r.ok())
                 .collect();
 
-            Ok(entries)
-        }).or_else(|_| Ok(vec![]))
+            Ok(PermanentMemoryPage { items, total: total as usize })
+        }).or_else(|e| self.read_fallback(e, PermanentMemoryPage::default()))
     }
 
-    fn delete_permanent_memory(&self, _memory_type: &str, id: &str) -> Result<(), String> {
+    fn search_permanent_memory(&self, query: &str, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        let limit = limit.unwrap_or(20);
+        let query = query.to_string();
+        self.safe_query(move |conn| search_permanent_memory_in(conn, &query, limit))
+            .or_else(|e| self.read_fallback(e, vec![]))
+    }
+
+    fn delete_permanent_memory(&self, _memory_type: &str, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         self.safe_write(move |conn| {
             conn.execute(
@@ -915,15 +2322,15 @@ impl MemoryPort for SqliteMemoryAdapter {
                 [&id],
             )?;
             // Also clean FTS index
-            conn.execute(
+            ignore_unless_busy(conn.execute(
                 "DELETE FROM permanent_memory_fts WHERE rowid NOT IN (SELECT rowid FROM permanent_memory)",
                 [],
-            ).ok(); // Ignore FTS errors
+            ))?; // FTS upkeep is best-effort
             Ok(())
         })
     }
 
-    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, String> {
+    fn list_drafts(&self) -> Result<Vec<serde_json::Value>, AiddError> {
         self.safe_query(move |conn| {
             let mut stmt = conn.prepare(
                 "SELECT id, category, title, content, status, data, created_at, updated_at \
@@ -957,7 +2364,7 @@ impl MemoryPort for SqliteMemoryAdapter {
                 .collect();
 
             Ok(drafts)
-        }).or_else(|_| Ok(vec![]))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
     fn list_artifacts(
@@ -965,84 +2372,23 @@ impl MemoryPort for SqliteMemoryAdapter {
         artifact_type: Option<&str>,
         status: Option<&str>,
         limit: Option<usize>,
-    ) -> Result<Vec<serde_json::Value>, String> {
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>, AiddError> {
         let artifact_type = artifact_type.map(|s| s.to_string());
         let status = status.map(|s| s.to_string());
         let limit = limit.unwrap_or(100);
 
-        self.safe_query(move |conn| {
-            let mut sql = String::from(
-                "SELECT id, session_id, type, feature, status, title, description, content,
-                        CASE
-                          WHEN typeof(date) = 'integer' THEN CAST(date AS INTEGER)
-                          WHEN date IS NULL OR date = '' THEN CAST(strftime('%s', 'now') AS INTEGER) * 1000
-                          WHEN date GLOB '[0-9]*' THEN CAST(date AS INTEGER)
-                          WHEN instr(date, '.') > 0 THEN CAST(strftime('%s', replace(date, '.', '-') || ' 00:00:00') AS INTEGER) * 1000
-                          ELSE CAST(strftime('%s', date) AS INTEGER) * 1000
-                        END AS date_ts,
-                        CASE
-                          WHEN typeof(created_at) = 'integer' THEN CAST(created_at AS INTEGER)
-                          WHEN created_at IS NULL OR created_at = '' THEN CAST(strftime('%s', 'now') AS INTEGER) * 1000
-                          WHEN created_at GLOB '[0-9]*' THEN CAST(created_at AS INTEGER)
-                          ELSE CAST(strftime('%s', created_at) AS INTEGER) * 1000
-                        END AS created_at_ts,
-                        CASE
-                          WHEN typeof(updated_at) = 'integer' THEN CAST(updated_at AS INTEGER)
-                          WHEN updated_at IS NULL OR updated_at = '' THEN CAST(strftime('%s', 'now') AS INTEGER) * 1000
-                          WHEN updated_at GLOB '[0-9]*' THEN CAST(updated_at AS INTEGER)
-                          ELSE CAST(strftime('%s', updated_at) AS INTEGER) * 1000
-                        END AS updated_at_ts
-                 FROM artifacts"
-            );
-            let mut conditions: Vec<String> = vec![];
-            let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
-
-            if let Some(ref t) = artifact_type {
-                conditions.push(format!("type = ?{}", params.len() + 1));
-                params.push(Box::new(t.clone()));
-            }
-            if let Some(ref s) = status {
-                conditions.push(format!("status = ?{}", params.len() + 1));
-                params.push(Box::new(s.clone()));
-            }
-
-            if !conditions.is_empty() {
-                sql.push_str(" WHERE ");
-                sql.push_str(&conditions.join(" AND "));
-            }
-
-            sql.push_str(&format!(" ORDER BY date_ts DESC, created_at_ts DESC LIMIT ?{}", params.len() + 1));
-            params.push(Box::new(limit));
-
-            let mut stmt = conn.prepare(&sql)?;
-            let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-
-            let artifacts = stmt.query_map(param_refs.as_slice(), |row| {
-                let mut entry = serde_json::Map::new();
-                entry.insert("id".into(), serde_json::json!(row.get::<_, String>(0)?));
-                let session_id: Option<String> = row.get(1)?;
-                if let Some(sid) = session_id {
-                    entry.insert("sessionId".into(), serde_json::json!(sid));
-                }
-                entry.insert("type".into(), serde_json::json!(row.get::<_, String>(2)?));
-                entry.insert("feature".into(), serde_json::json!(row.get::<_, String>(3)?));
-                entry.insert("status".into(), serde_json::json!(row.get::<_, String>(4)?));
-                entry.insert("title".into(), serde_json::json!(row.get::<_, String>(5)?));
-                entry.insert("description".into(), serde_json::json!(row.get::<_, String>(6)?));
-                entry.insert("content".into(), serde_json::json!(row.get::<_, String>(7)?));
-                entry.insert("date".into(), serde_json::json!(row.get::<_, i64>(8)?));
-                entry.insert("createdAt".into(), serde_json::json!(row.get::<_, i64>(9)?));
-                entry.insert("updatedAt".into(), serde_json::json!(row.get::<_, i64>(10)?));
-                Ok(serde_json::Value::Object(entry))
-            })?
-                .filter_map(|r| r.ok())
-                .collect();
+        self.safe_query(move |conn| query_artifacts(conn, artifact_type.as_deref(), status.as_deref(), limit, from_ms, to_ms))
+            .or_else(|e| self.read_fallback(e, vec![]))
+    }
 
-            Ok(artifacts)
-        }).or_else(|_| Ok(vec![]))
+    fn list_pattern_detections(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
+        let limit = limit.unwrap_or(200);
+        self.safe_query(move |conn| query_pattern_detections(conn, limit))
     }
 
-    fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, String> {
+    fn list_audit_scores(&self, limit: Option<usize>) -> Result<Vec<serde_json::Value>, AiddError> {
         let limit = limit.unwrap_or(200);
 
         self.safe_query(move |conn| {
@@ -1082,89 +2428,109 @@ impl MemoryPort for SqliteMemoryAdapter {
                 .collect();
 
             Ok(scores)
-        }).or_else(|_| Ok(vec![]))
+        }).or_else(|e| self.read_fallback(e, vec![]))
     }
 
-    fn get_governance_config(&self) -> Result<serde_json::Value, String> {
-        let conn = self.open_rw_connection()?;
-        self.verify_schema(&conn)?;
-        ensure_config_table(&conn).map_err(|e| format!("Failed to ensure config table: {}", e))?;
+    fn get_activity_feed(&self, limit: Option<usize>) -> Result<Vec<ActivityEntry>, AiddError> {
+        let limit = limit.unwrap_or(50);
+        self.safe_query(move |conn| Ok(collect_activity_feed(conn, limit)))
+            .or_else(|e| self.read_fallback(e, vec![]))
+    }
 
-        let raw: Option<String> = conn
-            .query_row(
-                "SELECT data FROM config WHERE id = 'governance' LIMIT 1",
-                [],
-                |row| row.get(0),
-            )
-            .optional()
-            .map_err(|e| format!("Failed to read governance config: {}", e))?;
+    fn observation_daily_counts(&self, since_ts: Option<i64>, until_ts: Option<i64>) -> Result<Vec<DailyCount>, AiddError> {
+        self.safe_query(move |conn| query_observation_daily_counts(conn, since_ts, until_ts))
+    }
 
-        if let Some(payload) = raw {
-            if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&payload) {
-                return Ok(normalize_governance_config(&parsed));
-            }
-        }
-        Ok(default_governance_config())
+    fn get_storage_info(&self) -> Result<StorageInfo, AiddError> {
+        let path = self.get_db_path()?;
+        let journal_mode = self.safe_query(query_journal_mode)?;
+        let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(StorageInfo {
+            db_path: path.to_string_lossy().to_string(),
+            size_bytes,
+            journal_mode,
+            wal_file_exists: sidecar_exists(&path, "-wal"),
+            shm_file_exists: sidecar_exists(&path, "-shm"),
+        })
     }
 
-    fn upsert_governance_config(&self, config_json: &str) -> Result<(), String> {
+    fn export_bundle(&self, out: &mut dyn Write) -> Result<(), AiddError> {
+        self.write_export_bundle(out)
+    }
+
+    fn import_bundle(&self, bundle_json: &str, mode: ImportMode) -> Result<ImportSummary, AiddError> {
+        let sections = parse_bundle(bundle_json)?;
+        self.safe_write_tx(move |tx| import_bundle_rows(tx, &sections, mode))
+    }
+
+    fn get_governance_config(&self) -> Result<serde_json::Value, AiddError> {
+        self.with_rw_connection(|conn| {
+            ensure_config_table(conn).map_err(|e| format!("Failed to ensure config table: {}", e))?;
+            read_governance_config(conn)
+                .map_err(|e| AiddError::Database(format!("Failed to read governance config: {}", e)))
+        })
+    }
+
+    fn upsert_governance_config(&self, config_json: &str) -> Result<Vec<ConfigChange>, AiddError> {
         let parsed: serde_json::Value = serde_json::from_str(config_json)
             .map_err(|e| format!("Invalid governance config JSON: {}", e))?;
         let normalized = normalize_governance_config(&parsed);
-        let payload = normalized.to_string();
-        let updated_at = Self::now_iso();
+        let updated_at = self.now_iso();
 
-        self.safe_write(move |conn| {
-            ensure_config_table(conn)?;
-            conn.execute(
-                "INSERT INTO config (id, data, updated_at)
-                 VALUES ('governance', ?1, ?2)
-                 ON CONFLICT(id) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at",
-                rusqlite::params![payload, updated_at],
-            )?;
-            Ok(())
-        })
+        self.safe_write_tx(move |tx| write_governance_config(tx, &normalized, &updated_at))
     }
 
     // --- Write operations ---
 
-    fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, String> {
+    fn create_permanent_memory(&self, memory_type: &str, title: &str, content: &str) -> Result<String, AiddError> {
         let id = Uuid::new_v4().to_string();
         let memory_type = memory_type.to_string();
         let title = title.to_string();
         let content = content.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         let id_clone = id.clone();
-        self.safe_write(move |conn| {
-            conn.execute(
+        self.safe_write_tx(move |tx| {
+            tx.execute(
                 "INSERT INTO permanent_memory (id, type, title, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
                 rusqlite::params![id_clone, memory_type, title, content, now],
             )?;
+            if permanent_memory_fts_needs_sync(tx).unwrap_or(false) {
+                sync_permanent_memory_fts(tx, &id_clone, false)?;
+            }
             Ok(id_clone.clone())
         })
     }
 
-    fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), String> {
+    fn update_permanent_memory(&self, id: &str, title: &str, content: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         let title = title.to_string();
         let content = content.to_string();
-        self.safe_write(move |conn| {
-            conn.execute(
+        self.safe_write_tx(move |tx| {
+            // Without the engine's triggers, swap the old index entry for the new one by hand
+            let sync_fts = permanent_memory_fts_needs_sync(tx).unwrap_or(false);
+            if sync_fts {
+                sync_permanent_memory_fts(tx, &id, true)?;
+            }
+            tx.execute(
                 "UPDATE permanent_memory SET title = ?1, content = ?2 WHERE id = ?3",
                 rusqlite::params![title, content, id],
             )?;
+            if sync_fts {
+                sync_permanent_memory_fts(tx, &id, false)?;
+            }
             Ok(())
         })
     }
 
-    fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, String> {
+    fn create_artifact(&self, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str) -> Result<String, AiddError> {
         let id = Uuid::new_v4().to_string();
         let artifact_type = artifact_type.to_string();
         let feature = feature.to_string();
         let title = title.to_string();
         let description = description.to_string();
         let content = content.to_string();
-        let now = Self::now_unix_ms();
+        let now = self.now_unix_ms();
         let id_clone = id.clone();
         self.safe_write(move |conn| {
             conn.execute(
@@ -1176,7 +2542,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), String> {
+    fn update_artifact(&self, id: &str, artifact_type: &str, feature: &str, title: &str, description: &str, content: &str, status: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         let artifact_type = artifact_type.to_string();
         let feature = feature.to_string();
@@ -1184,7 +2550,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         let description = description.to_string();
         let content = content.to_string();
         let status = status.to_string();
-        let now = Self::now_unix_ms();
+        let now = self.now_unix_ms();
         self.safe_write(move |conn| {
             conn.execute(
                 "UPDATE artifacts SET type = ?1, feature = ?2, title = ?3, description = ?4, content = ?5, status = ?6, updated_at = ?7 WHERE id = ?8",
@@ -1194,9 +2560,9 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn archive_artifact(&self, id: &str) -> Result<(), String> {
+    fn archive_artifact(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
-        let now = Self::now_unix_ms();
+        let now = self.now_unix_ms();
         self.safe_write(move |conn| {
             conn.execute(
                 "UPDATE artifacts SET status = 'done', updated_at = ?1 WHERE id = ?2",
@@ -1206,7 +2572,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn delete_artifact(&self, id: &str) -> Result<(), String> {
+    fn delete_artifact(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         self.safe_write(move |conn| {
             conn.execute("DELETE FROM artifacts WHERE id = ?1", rusqlite::params![id])?;
@@ -1214,11 +2580,11 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn approve_evolution_candidate(&self, id: &str) -> Result<(), String> {
+    fn approve_evolution_candidate(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         let log_id = Uuid::new_v4().to_string();
-        self.safe_write(move |conn| {
+        self.safe_write_tx(move |conn| {
             // Get candidate title and confidence for the log entry
             let (title, confidence): (String, f64) = conn.query_row(
                 "SELECT title, confidence FROM evolution_candidates WHERE id = ?1",
@@ -1238,12 +2604,12 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), String> {
+    fn reject_evolution_candidate(&self, id: &str, reason: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         let reason = reason.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         let log_id = Uuid::new_v4().to_string();
-        self.safe_write(move |conn| {
+        self.safe_write_tx(move |conn| {
             let (title, confidence): (String, f64) = conn.query_row(
                 "SELECT title, confidence FROM evolution_candidates WHERE id = ?1",
                 [&id],
@@ -1264,11 +2630,11 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn approve_draft(&self, id: &str) -> Result<(), String> {
+    fn approve_draft(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
-        let now = Self::now_iso();
-        let now_ms = Self::now_unix_ms();
-        self.safe_write(move |conn| {
+        let now = self.now_iso();
+        let now_ms = self.now_unix_ms();
+        self.safe_write_tx(move |conn| {
             let (category, title, content, status, data_str): (String, String, String, String, String) = conn.query_row(
                 "SELECT category, title, content, status, data FROM drafts WHERE id = ?1",
                 rusqlite::params![id],
@@ -1335,10 +2701,10 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn reject_draft(&self, id: &str, reason: &str) -> Result<(), String> {
+    fn reject_draft(&self, id: &str, reason: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         let reason = reason.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         self.safe_write(move |conn| {
             conn.execute(
                 "UPDATE drafts SET status = 'rejected', \
@@ -1349,9 +2715,9 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn delete_session(&self, id: &str) -> Result<(), String> {
+    fn delete_session(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
-        self.safe_write(move |conn| {
+        self.safe_write_tx(move |conn| {
             // Cascade: delete observations first, then the session
             conn.execute("DELETE FROM observations WHERE session_id = ?1", rusqlite::params![id])?;
             conn.execute("DELETE FROM sessions WHERE id = ?1", rusqlite::params![id])?;
@@ -1359,7 +2725,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), String> {
+    fn update_session(&self, id: &str, branch: Option<&str>, input: Option<&str>, output: Option<&str>) -> Result<(), AiddError> {
         let id = id.to_string();
         let branch = branch.map(|s| s.to_string());
         let input = input.map(|s| s.to_string());
@@ -1401,7 +2767,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), String> {
+    fn update_session_full(&self, id: &str, updates_json: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         let updates_json = updates_json.to_string();
         self.safe_write(move |conn| {
@@ -1525,6 +2891,15 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
+    fn reopen_session(&self, id: &str) -> Result<(), AiddError> {
+        let id = id.to_string();
+        self.with_rw_connection(move |conn| {
+            let tx = conn.unchecked_transaction().map_err(|e| db_error("Database write failed", e))?;
+            reopen_session_row(&tx, &id)?;
+            tx.commit().map_err(|e| db_error("Database write failed", e))
+        })
+    }
+
     // --- Observation CRUD ---
 
     fn create_observation(
@@ -1538,7 +2913,8 @@ impl MemoryPort for SqliteMemoryAdapter {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<String, String> {
+        created_at: Option<&str>,
+    ) -> Result<String, AiddError> {
         let id = Uuid::new_v4().to_string();
         let session_id = session_id.to_string();
         let obs_type = obs_type.to_string();
@@ -1548,9 +2924,14 @@ impl MemoryPort for SqliteMemoryAdapter {
         let concepts = concepts.map(|s| s.to_string());
         let files_read = files_read.map(|s| s.to_string());
         let files_modified = files_modified.map(|s| s.to_string());
-        let now = Self::now_iso();
+        let created_at = created_at.map(|s| s.to_string());
+        let now = self.now_iso();
         let id_clone = id.clone();
-        self.safe_write(move |conn| {
+        self.with_rw_connection(move |conn| {
+            let created_at = match created_at.as_deref() {
+                Some(raw) => resolve_created_at(conn, raw)?,
+                None => now,
+            };
             conn.execute(
                 "INSERT INTO observations (id, session_id, type, title, content, facts, concepts, files_read, files_modified, discovery_tokens, created_at) \
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
@@ -1565,9 +2946,9 @@ impl MemoryPort for SqliteMemoryAdapter {
                     files_read.as_deref().unwrap_or("[]"),
                     files_modified.as_deref().unwrap_or("[]"),
                     discovery_tokens.unwrap_or(0),
-                    now,
+                    created_at,
                 ],
-            )?;
+            ).map_err(|e| format!("Database write failed: {}", e))?;
             Ok(id_clone.clone())
         })
     }
@@ -1583,7 +2964,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         files_read: Option<&str>,
         files_modified: Option<&str>,
         discovery_tokens: Option<i64>,
-    ) -> Result<(), String> {
+    ) -> Result<(), AiddError> {
         let id = id.to_string();
         let obs_type = obs_type.to_string();
         let title = title.to_string();
@@ -1601,7 +2982,7 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn delete_observation(&self, id: &str) -> Result<(), String> {
+    fn delete_observation(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         self.safe_write(move |conn| {
             conn.execute("DELETE FROM observations WHERE id = ?1", rusqlite::params![id])?;
@@ -1617,12 +2998,12 @@ impl MemoryPort for SqliteMemoryAdapter {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, AiddError> {
         let id = Uuid::new_v4().to_string();
         let evo_type = evo_type.to_string();
         let title = title.to_string();
         let data = data.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         let id_clone = id.clone();
         self.safe_write(move |conn| {
             conn.execute(
@@ -1641,12 +3022,12 @@ impl MemoryPort for SqliteMemoryAdapter {
         title: &str,
         confidence: f64,
         data: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), AiddError> {
         let id = id.to_string();
         let evo_type = evo_type.to_string();
         let title = title.to_string();
         let data = data.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         self.safe_write(move |conn| {
             conn.execute(
                 "UPDATE evolution_candidates SET type = ?1, title = ?2, confidence = ?3, data = ?4, updated_at = ?5 WHERE id = ?6",
@@ -1656,12 +3037,9 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn delete_evolution_candidate(&self, id: &str) -> Result<(), String> {
+    fn delete_evolution_candidate(&self, id: &str, preserve_log: bool) -> Result<(), AiddError> {
         let id = id.to_string();
-        self.safe_write(move |conn| {
-            conn.execute("DELETE FROM evolution_candidates WHERE id = ?1", rusqlite::params![id])?;
-            Ok(())
-        })
+        self.safe_write_tx(move |tx| delete_candidate_rows(tx, &id, preserve_log))
     }
 
     // --- Draft CRUD ---
@@ -1674,12 +3052,12 @@ impl MemoryPort for SqliteMemoryAdapter {
         content: &str,
         confidence: f64,
         source: &str,
-    ) -> Result<String, String> {
+    ) -> Result<String, AiddError> {
         let id = Uuid::new_v4().to_string();
         let category = category.to_string();
         let title = title.to_string();
         let content = content.to_string();
-        let now = Self::now_iso();
+        let now = self.now_iso();
         let mut data_json = serde_json::json!({
             "filename": filename,
             "confidence": confidence,
@@ -1704,14 +3082,14 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), String> {
+    fn update_draft(&self, id: &str, title: &str, content: &str, category: &str, confidence: Option<f64>, filename: Option<&str>) -> Result<(), AiddError> {
         let id = id.to_string();
         let title = title.to_string();
         let content = content.to_string();
         let category = category.to_string();
         let confidence = confidence;
         let filename = filename.map(|s| s.to_string());
-        let now = Self::now_iso();
+        let now = self.now_iso();
         self.safe_write(move |conn| {
             conn.execute(
                 "UPDATE drafts SET title = ?1, content = ?2, category = ?3, updated_at = ?4 WHERE id = ?5",
@@ -1734,13 +3112,70 @@ impl MemoryPort for SqliteMemoryAdapter {
         })
     }
 
-    fn delete_draft(&self, id: &str) -> Result<(), String> {
+    fn delete_draft(&self, id: &str) -> Result<(), AiddError> {
         let id = id.to_string();
         self.safe_write(move |conn| {
             conn.execute("DELETE FROM drafts WHERE id = ?1", rusqlite::params![id])?;
             Ok(())
         })
     }
+
+    fn delete_drafts_by_status(&self, status: &str) -> Result<usize, AiddError> {
+        validate_draft_status(status)?;
+        let status = status.to_string();
+        self.safe_write_tx(move |tx| tx.execute("DELETE FROM drafts WHERE status = ?1", [&status]))
+    }
+
+    fn create_banned_pattern(&self, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>) -> Result<String, AiddError> {
+        validate_pattern(pattern, pattern_type)?;
+        let id = Uuid::new_v4().to_string();
+        let now = self.now_iso();
+        self.safe_write(|conn| {
+            conn.execute(
+                "INSERT INTO banned_patterns (id, category, pattern, type, severity, origin, active, use_count, hint, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'learned', 1, 0, ?6, ?7)",
+                rusqlite::params![id, category, pattern, pattern_type, severity, hint, now],
+            )?;
+            Ok(id.clone())
+        })
+    }
+
+    fn update_banned_pattern(&self, id: &str, category: &str, pattern: &str, pattern_type: &str, severity: &str, hint: Option<&str>, active: bool) -> Result<(), AiddError> {
+        validate_pattern(pattern, pattern_type)?;
+        self.safe_write(|conn| {
+            conn.execute(
+                "UPDATE banned_patterns SET category = ?1, pattern = ?2, type = ?3, severity = ?4, hint = ?5, active = ?6 WHERE id = ?7",
+                rusqlite::params![category, pattern, pattern_type, severity, hint, active, id],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn mark_detection_false_positive(&self, id: i64) -> Result<(), AiddError> {
+        self.safe_write_tx(move |tx| mark_false_positive_row(tx, id))
+    }
+
+    fn delete_drafts(&self, ids: &[String]) -> Result<usize, AiddError> {
+        self.safe_write_tx(|tx| execute_for_ids(tx, "DELETE FROM drafts WHERE id IN", &[], ids, BULK_ID_CHUNK))
+    }
+
+    fn reject_drafts(&self, ids: &[String], reason: &str) -> Result<usize, AiddError> {
+        let now = self.now_iso();
+        self.safe_write_tx(|tx| reject_draft_rows(tx, ids, reason, &now, BULK_ID_CHUNK))
+    }
+
+    fn archive_artifacts(&self, ids: &[String]) -> Result<usize, AiddError> {
+        let now = self.now_unix_ms();
+        self.safe_write_tx(|tx| {
+            execute_for_ids(
+                tx,
+                "UPDATE artifacts SET status = 'done', updated_at = ? WHERE id IN",
+                &[now.into()],
+                ids,
+                BULK_ID_CHUNK,
+            )
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1751,6 +3186,11 @@ mod tests {
     /// Create an in-memory database matching the real AIDD schema from migrations.ts
     fn create_test_db() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
+        apply_test_schema(&conn);
+        conn
+    }
+
+    fn apply_test_schema(conn: &Connection) {
         conn.execute_batch(
             "CREATE TABLE sessions (
                 id TEXT PRIMARY KEY,
@@ -1825,9 +3265,55 @@ mod tests {
                 content TEXT NOT NULL DEFAULT '',
                 created_at TEXT NOT NULL DEFAULT '',
                 session_id TEXT
-            );"
+            );
+            CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT);
+            INSERT INTO meta (key, value) VALUES ('schema_version', '4');"
         ).unwrap();
-        conn
+    }
+
+    #[test]
+    fn deleting_a_candidate_cascades_to_its_log_unless_preserved() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "INSERT INTO evolution_candidates (id) VALUES ('c1'), ('c2');
+             INSERT INTO evolution_log (id, candidate_id, action) VALUES
+                ('l1', 'c1', 'created'), ('l2', 'c1', 'approved'), ('l3', 'c2', 'created');",
+        )
+        .unwrap();
+        let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap();
+
+        run_in_transaction(&conn, |tx| delete_candidate_rows(tx, "c1", false)).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM evolution_candidates WHERE id = 'c1'"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM evolution_log WHERE candidate_id = 'c1'"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM evolution_log"), 1);
+
+        run_in_transaction(&conn, |tx| delete_candidate_rows(tx, "c2", true)).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM evolution_candidates"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM evolution_log WHERE candidate_id = 'c2'"), 1);
+    }
+
+    /// File-backed copy of the test schema, for tests that need real paths.
+    fn create_test_db_file(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aidd-sqlite-{}-{}", label, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.db");
+        let _ = std::fs::remove_file(&path);
+        apply_test_schema(&Connection::open(&path).unwrap());
+        path
+    }
+
+    /// An adapter whose only registered project, also the active one, is `root`.
+    fn adapter_for(root: &Path) -> SqliteMemoryAdapter {
+        use crate::domain::model::ProjectEntry;
+        use crate::domain::ports::outbound::HubData;
+        use crate::infrastructure::filesystem::FileAdapter;
+        use crate::infrastructure::persistence::MemoryStore;
+
+        let path = root.to_string_lossy().to_string();
+        let entry = ProjectEntry { name: "test".to_string(), path: path.clone(), detected: true, last_accessed: 0 };
+        let data = HubData { projects: vec![entry], active_project: Some(path), ..HubData::default() };
+        let projects = Arc::new(ProjectService::new(Arc::new(MemoryStore::new(data)), Arc::new(FileAdapter), Arc::new(SystemClock)));
+        SqliteMemoryAdapter::new(projects)
     }
 
     #[test]
@@ -1848,7 +3334,18 @@ mod tests {
 
         let result = verify_schema(&conn);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Missing required table"));
+        assert!(matches!(result, Err(AiddError::SchemaMissing { .. })));
+    }
+
+    #[test]
+    fn verify_schema_reports_an_outdated_engine_schema() {
+        let conn = create_test_db();
+        conn.execute("UPDATE meta SET value = '2' WHERE key = 'schema_version'", []).unwrap();
+        assert_eq!(verify_schema(&conn), Err(AiddError::SchemaOutdated { found: 2, expected: MIN_SCHEMA_VERSION }));
+
+        // Predates versioning altogether
+        conn.execute_batch("DROP TABLE meta;").unwrap();
+        assert_eq!(verify_schema(&conn), Err(AiddError::SchemaOutdated { found: 0, expected: MIN_SCHEMA_VERSION }));
     }
 
     #[test]
@@ -1856,7 +3353,7 @@ mod tests {
         let conn = Connection::open_in_memory().unwrap();
         let result = verify_schema(&conn);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Missing required table: sessions"));
+        assert_eq!(result, Err(AiddError::SchemaMissing { table: "sessions".to_string() }));
     }
 
     #[test]
@@ -1951,6 +3448,62 @@ mod tests {
         assert_eq!(active, 1);
     }
 
+    #[test]
+    fn marking_a_false_positive_updates_pattern_stats() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "INSERT INTO banned_patterns (id, category, pattern) VALUES ('p1', 'filler', 'delve');
+             INSERT INTO pattern_detections (model_id, pattern_id, matched_text, created_at) VALUES
+                ('m', 'p1', 'delve into', '2026-01-01T00:00:00Z'),
+                ('m', 'gone', 'stale', '2026-01-02T00:00:00Z');",
+        )
+        .unwrap();
+
+        let detections = query_pattern_detections(&conn, 10).unwrap();
+        assert_eq!(detections.len(), 2);
+        assert_eq!(detections[0]["matchedText"], "stale");
+        assert!(detections[0]["pattern"].is_null());
+        assert_eq!(detections[1]["pattern"]["pattern"], "delve");
+        assert_eq!(query_pattern_stats(&conn).false_positives, 0);
+
+        let id = detections[1]["id"].as_i64().unwrap();
+        run_in_transaction(&conn, |tx| mark_false_positive_row(tx, id)).unwrap();
+
+        let stats = query_pattern_stats(&conn);
+        assert_eq!(stats.total_detections, 2);
+        assert_eq!(stats.false_positives, 1);
+    }
+
+    #[test]
+    fn validate_pattern_reports_offset_of_unbalanced_group() {
+        let err = validate_pattern(r"\bdelve (into", "regex").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid regex at offset 8: unclosed group");
+        // Literal patterns are matched verbatim, so the same text is fine
+        assert!(validate_pattern(r"\bdelve (into", "exact").is_ok());
+    }
+
+    #[test]
+    fn validate_pattern_accepts_valid_regex() {
+        assert!(validate_pattern(r"\b(?:delve|tapestry)\b", "regex").is_ok());
+        assert!(validate_pattern("delve", "fuzzy").is_err());
+    }
+
+    #[test]
+    fn validate_pattern_follows_javascript_syntax() {
+        // Valid for the engine's `new RegExp`, though Rust's regex crate refuses them
+        for pattern in [r"(?<=\bthe )tapestry", r"delve(?! into)", r"(\w+) \1", r"(?<word>delve) \k<word>", r"\(?=x"] {
+            assert!(validate_pattern(pattern, "regex").is_ok(), "{}", pattern);
+        }
+        // Rust-only syntax the engine would reject or read differently
+        for (pattern, offset) in [(r"(?P<word>delve)", 0), (r"(?i)delve", 0), (r"delve\z", 5), (r"\p{L}+", 0), (r"x[[:alpha:]]", 1)] {
+            let err = validate_pattern(pattern, "regex").unwrap_err().to_string();
+            assert!(err.starts_with(&format!("Invalid regex at offset {}:", offset)), "{}: {}", pattern, err);
+        }
+        // Errors after a rewritten construct still point at the original offset
+        let err = validate_pattern(r"(?<=a)(b", "regex").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid regex at offset 6: unclosed group");
+    }
+
     #[test]
     fn observation_search_matches_title() {
         let conn = create_test_db();
@@ -2058,4 +3611,924 @@ mod tests {
             .unwrap_or(false);
         assert!(exists);
     }
+
+    #[test]
+    fn cached_connection_verifies_schema_only_once() {
+        let path = create_test_db_file("cache");
+        let cache = ConnectionCache::new(OpenFlags::SQLITE_OPEN_READ_ONLY);
+
+        cache.with_connection(&path, |_| Ok(())).unwrap();
+
+        // Break the schema behind the cache's back: a re-check would now fail.
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("DROP TABLE permanent_memory;")
+            .unwrap();
+
+        let second = cache.with_connection(&path, |conn| {
+            conn.query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get::<_, i64>(0))
+                .map_err(|e| AiddError::Database(e.to_string()))
+        });
+
+        assert_eq!(second, Ok(0));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn connection_cache_reopens_when_path_changes() {
+        let first = create_test_db_file("cache-a");
+        let second = std::env::temp_dir().join(format!("aidd-sqlite-cache-b-{}", std::process::id()));
+        std::fs::create_dir_all(&second).unwrap();
+        let second = second.join("data.db");
+        let _ = std::fs::remove_file(&second);
+        Connection::open(&second).unwrap();
+
+        let cache = ConnectionCache::new(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        cache.with_connection(&first, |_| Ok(())).unwrap();
+        let result = cache.with_connection(&second, |_| Ok(()));
+        assert_eq!(result, Err(AiddError::SchemaMissing { table: "sessions".to_string() }));
+
+        let _ = std::fs::remove_dir_all(first.parent().unwrap());
+        let _ = std::fs::remove_dir_all(second.parent().unwrap());
+    }
+
+    #[test]
+    fn write_waits_out_a_concurrent_writer() {
+        let path = create_test_db_file("busy");
+        let cache = ConnectionCache::new(OpenFlags::SQLITE_OPEN_READ_WRITE);
+        let journal_mode = cache.with_connection(&path, |conn| {
+            query_journal_mode(conn).map_err(|e| db_error("query", e))
+        });
+        assert_eq!(journal_mode, Ok("wal".to_string()));
+
+        // Another process (the engine) holds the write lock for a moment
+        let engine = Connection::open(&path).unwrap();
+        engine.execute_batch("BEGIN IMMEDIATE; INSERT INTO sessions (id) VALUES ('engine');").unwrap();
+        let release = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            engine.execute_batch("COMMIT").unwrap();
+        });
+        let inserted = cache.with_connection(&path, |conn| {
+            conn.execute("INSERT INTO sessions (id) VALUES ('hub')", []).map_err(|e| db_error("write", e))
+        });
+        release.join().unwrap();
+        assert_eq!(inserted, Ok(1));
+
+        // Without a busy timeout the same contention surfaces as a retryable error
+        let engine = Connection::open(&path).unwrap();
+        engine.execute_batch("BEGIN IMMEDIATE").unwrap();
+        let impatient = Connection::open(&path).unwrap();
+        impatient.busy_timeout(Duration::ZERO).unwrap();
+        let err = impatient
+            .execute("INSERT INTO sessions (id) VALUES ('late')", [])
+            .map_err(|e| db_error("write", e))
+            .unwrap_err();
+        assert!(matches!(err, AiddError::DatabaseBusy(_)), "{:?}", err);
+        assert!(err.is_transient());
+        assert!(fallback_unless_busy(err, 0).is_err());
+
+        drop(impatient);
+        drop(engine);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn journal_mode_reports_memory_for_in_memory_db() {
+        let conn = create_test_db();
+        assert_eq!(query_journal_mode(&conn).unwrap(), "memory");
+    }
+
+    #[test]
+    fn journal_mode_reports_wal_with_sidecars() {
+        let path = create_test_db_file("wal");
+        let conn = Connection::open(&path).unwrap();
+        conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get::<_, String>(0))
+            .unwrap();
+        conn.execute("INSERT INTO sessions (id) VALUES ('s1')", []).unwrap();
+
+        let reader = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+        assert_eq!(query_journal_mode(&reader).unwrap(), "wal");
+        assert!(sidecar_exists(&path, "-wal"));
+        assert!(sidecar_exists(&path, "-shm"));
+
+        drop(reader);
+        drop(conn);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn reopened_session_counts_as_active() {
+        let conn = create_test_db();
+        conn.execute(
+            "INSERT INTO sessions (id, branch, started_at, ended_at, status, data)
+             VALUES ('s1', 'main', 1700000000000, 1700000100000, 'completed', ?1)",
+            [r#"{"branch":"main","endedAt":"2023-11-14T22:15:00.000Z"}"#],
+        )
+        .unwrap();
+
+        let tx = conn.unchecked_transaction().unwrap();
+        reopen_session_row(&tx, "s1").unwrap();
+        tx.commit().unwrap();
+
+        let active: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sessions WHERE ended_at IS NULL", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(active, 1);
+        let data: String = conn
+            .query_row("SELECT data FROM sessions WHERE id = 's1'", [], |row| row.get(0))
+            .unwrap();
+        let data: serde_json::Value = serde_json::from_str(&data).unwrap();
+        assert!(data.get("endedAt").is_none());
+        assert_eq!(data["branch"], "main");
+
+        assert_eq!(
+            reopen_session_row(&conn.unchecked_transaction().unwrap(), "missing"),
+            Err(AiddError::Other("Session not found: missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn fts_search_matches_content_only_terms() {
+        let conn = create_test_db();
+        conn.execute(
+            "INSERT INTO observations (id, session_id, type, title, content, created_at)
+             VALUES ('o1', 's1', 'discovery', 'Reconnect logic', 'websocket backoff jitter', '2026-01-01')",
+            [],
+        ).unwrap();
+
+        let results = search_observations_in(&conn, "jitter", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "o1");
+        assert!(results[0].score.is_some());
+
+        // Rows written after the index exists are picked up by the sync triggers
+        conn.execute(
+            "INSERT INTO observations (id, session_id, type, title, facts, created_at)
+             VALUES ('o2', 's1', 'decision', 'Retry policy', 'jitter capped at 5s', '2026-01-02')",
+            [],
+        ).unwrap();
+        let results = search_observations_in(&conn, "jitter", 10).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn search_falls_back_to_like_without_fts() {
+        let path = create_test_db_file("fts-fallback");
+        Connection::open(&path).unwrap().execute(
+            "INSERT INTO observations (id, session_id, type, title, content, created_at)
+             VALUES ('o1', 's1', 'discovery', 'Reconnect logic', 'websocket backoff', '2026-01-01')",
+            [],
+        ).unwrap();
+
+        // A read-only connection cannot create the FTS index
+        let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+        let by_title = search_observations_in(&conn, "Reconnect", 10).unwrap();
+        assert_eq!(by_title.len(), 1);
+        assert!(by_title[0].score.is_none());
+        let by_content = search_observations_in(&conn, "websocket", 10).unwrap();
+        assert!(by_content.is_empty());
+
+        drop(conn);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn observation_pages_break_timestamp_ties_by_id() {
+        let conn = create_test_db();
+        for id in ["a", "b", "c", "d", "e"] {
+            let created_at = if id == "e" { "2026-01-02" } else { "2026-01-01" };
+            conn.execute(
+                "INSERT INTO observations (id, session_id, title, created_at) VALUES (?1, 's1', ?1, ?2)",
+                [id, created_at],
+            ).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<(String, String)> = None;
+        loop {
+            let page = query_observations_page(&conn, 2, cursor.as_ref(), None).unwrap();
+            seen.extend(page.items.iter().map(|o| o["id"].as_str().unwrap().to_string()));
+            match page.next_cursor {
+                Some(c) => cursor = Some(decode_cursor(&c).unwrap()),
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec!["e", "d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn session_pages_cover_every_row_once() {
+        let conn = create_test_db();
+        for (id, started) in [("s1", 1_700_000_000_000i64), ("s2", 1_700_000_000_000), ("s3", 1_700_000_100_000)] {
+            conn.execute(
+                "INSERT INTO sessions (id, started_at, data) VALUES (?1, ?2, ?3)",
+                rusqlite::params![id, started, serde_json::json!({ "id": id }).to_string()],
+            ).unwrap();
+        }
+
+        let first = query_sessions_page(&conn, 2, None, None, None).unwrap();
+        let ids: Vec<&str> = first.items.iter().map(|s| s["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["s3", "s2"]);
+        let cursor = first.next_cursor.expect("more sessions remain");
+        assert_eq!(cursor, "1700000000000|s2");
+
+        let second = query_sessions_page(&conn, 2, Some(&(1_700_000_000_000, "s2".to_string())), None, None).unwrap();
+        let ids: Vec<&str> = second.items.iter().map(|s| s["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["s1"]);
+        assert!(second.next_cursor.is_none());
+
+        let ids = |page: MemoryPage| -> Vec<String> {
+            page.items.iter().map(|s| s["id"].as_str().unwrap().to_string()).collect()
+        };
+        let later = query_sessions_page(&conn, 10, None, Some(1_700_000_000_001), None).unwrap();
+        assert_eq!(ids(later), ["s3"]);
+        let earlier = query_sessions_page(&conn, 10, None, None, Some(1_700_000_000_000)).unwrap();
+        assert_eq!(ids(earlier), ["s2", "s1"]);
+        // The range combines with the keyset cursor
+        let after_s2 = (1_700_000_000_000, "s2".to_string());
+        let bounded = query_sessions_page(&conn, 10, Some(&after_s2), Some(1_700_000_000_000), Some(1_700_000_100_000)).unwrap();
+        assert_eq!(ids(bounded), ["s1"]);
+    }
+
+    #[test]
+    fn backdated_observation_sorts_before_newer_ones() {
+        let conn = create_test_db();
+        let backdated = resolve_created_at(&conn, "2021-03-04T05:06:07Z").unwrap();
+        assert_eq!(backdated, "2021-03-04T05:06:07.000Z");
+        assert_eq!(resolve_created_at(&conn, "1614834367").unwrap(), backdated);
+        assert!(resolve_created_at(&conn, "last tuesday").is_err());
+
+        for (id, created_at) in [("old", backdated), ("new", "2030-01-01T00:00:00.000Z".to_string())] {
+            conn.execute(
+                "INSERT INTO observations (id, session_id, title, created_at) VALUES (?1, 's1', ?1, ?2)",
+                [id, created_at.as_str()],
+            ).unwrap();
+        }
+
+        let page = query_observations_page(&conn, 10, None, None).unwrap();
+        let ids: Vec<&str> = page.items.iter().map(|o| o["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["new", "old"]);
+    }
+
+    #[test]
+    fn activity_feed_interleaves_sources_by_time() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "CREATE TABLE artifacts (
+                id TEXT PRIMARY KEY, session_id TEXT, type TEXT NOT NULL, feature TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'active', title TEXT NOT NULL,
+                description TEXT NOT NULL DEFAULT '', content TEXT NOT NULL DEFAULT '',
+                date INTEGER NOT NULL, created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE audit_scores (
+                id INTEGER PRIMARY KEY AUTOINCREMENT, session_id TEXT, model_id TEXT NOT NULL,
+                input_hash TEXT NOT NULL, scores TEXT NOT NULL, verdict TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            INSERT INTO observations (id, session_id, title, created_at)
+                VALUES ('o1', 's1', 'Found flaky test', '2026-01-01T10:00:00.000Z');
+            INSERT INTO artifacts (id, type, feature, title, date, created_at, updated_at)
+                VALUES ('a1', 'plan', 'sync', 'Sync plan', 1767261600000, 1767265200000, 1767265200000);
+            INSERT INTO evolution_log (id, candidate_id, action, title, timestamp)
+                VALUES ('e1', 'c1', 'approved', 'Prefer rename', '2026-01-01T12:00:00.000Z');
+            INSERT INTO audit_scores (model_id, input_hash, scores, verdict, created_at)
+                VALUES ('model-x', 'h1', '{}', 'pass', '2026-01-01T09:00:00.000Z');",
+        ).unwrap();
+
+        let feed = collect_activity_feed(&conn, 10);
+        let kinds: Vec<&str> = feed.iter().map(|e| e.kind.as_str()).collect();
+        // 12:00 evolution, 11:00 artifact (epoch ms), 10:00 observation, 09:00 audit
+        assert_eq!(kinds, vec!["evolution", "artifact", "observation", "audit"]);
+        assert_eq!(feed[0].title, "approved: Prefer rename");
+        assert_eq!(feed[3].id, "1");
+
+        assert_eq!(collect_activity_feed(&conn, 2).len(), 2);
+    }
+
+    #[test]
+    fn failed_statement_rolls_back_whole_transaction() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "INSERT INTO sessions (id) VALUES ('s1');
+             INSERT INTO observations (id, session_id) VALUES ('o1', 's1');",
+        ).unwrap();
+
+        let result = run_in_transaction(&conn, |tx| {
+            tx.execute("DELETE FROM observations WHERE session_id = 's1'", [])?;
+            tx.execute("DELETE FROM no_such_table WHERE id = 's1'", [])?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM observations", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1);
+
+        run_in_transaction(&conn, |tx| {
+            tx.execute("DELETE FROM observations WHERE session_id = 's1'", [])?;
+            tx.execute("DELETE FROM sessions WHERE id = 's1'", [])?;
+            Ok(())
+        }).unwrap();
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM observations", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn artifact_dates_parse_known_formats_and_flag_the_rest() {
+        use rusqlite::types::Value;
+        let text = |s: &str| parse_artifact_date(&Value::Text(s.to_string()));
+
+        // 2026-01-15T00:00:00Z
+        let midnight = 1_768_435_200_000;
+        assert_eq!(text("2026-01-15"), Some(midnight));
+        assert_eq!(text("2026-01-15T10:30:00.250Z"), Some(midnight + 37_800_000));
+        assert_eq!(text("2026-01-15T12:30:00+02:00"), Some(midnight + 37_800_000));
+        assert_eq!(text("2026.01.15"), Some(midnight));
+        assert_eq!(text("15/01/2026"), Some(midnight));
+        assert_eq!(parse_artifact_date(&Value::Integer(midnight)), Some(midnight));
+        assert_eq!(text("Jan 15th"), None);
+        assert_eq!(text("31/02/2026"), None);
+
+        let conn = create_test_db();
+        conn.execute_batch(
+            "CREATE TABLE artifacts (
+                id TEXT PRIMARY KEY, session_id TEXT, type TEXT NOT NULL, feature TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'active', title TEXT NOT NULL,
+                description TEXT NOT NULL DEFAULT '', content TEXT NOT NULL DEFAULT '',
+                date, created_at, updated_at
+            );
+            INSERT INTO artifacts (id, type, feature, title, date, created_at, updated_at) VALUES
+                ('iso', 'plan', 'f', 'ISO', '2026-01-17', 1767225600000, 1767225600000),
+                ('dotted', 'plan', 'f', 'Dotted', '2026.01.16', 1767225600000, 1767225600000),
+                ('slash', 'plan', 'f', 'Slash', '15/01/2026', 1767225600000, 1767225600000),
+                ('bad', 'plan', 'f', 'Bad', 'someday', '2025-12-01T00:00:00Z', NULL);",
+        ).unwrap();
+
+        let artifacts = query_artifacts(&conn, None, None, 10, None, None).unwrap();
+        let ids: Vec<&str> = artifacts.iter().map(|a| a["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["iso", "dotted", "slash", "bad"]);
+        assert_eq!(artifacts[2]["date"], serde_json::json!(midnight));
+
+        let bad = &artifacts[3];
+        assert_eq!(bad["dateWarning"], "Unrecognized date format: someday");
+        assert_eq!(bad["date"], serde_json::json!(1_764_547_200_000i64));
+        assert_eq!(bad["updatedAt"], bad["createdAt"]);
+        assert!(artifacts[0].get("dateWarning").is_none());
+
+        assert_eq!(query_artifacts(&conn, None, None, 2, None, None).unwrap().len(), 2);
+
+        // Inclusive ranges, open on either end, stacked on the type filter
+        let in_range = |from, to| -> Vec<String> {
+            query_artifacts(&conn, Some("plan"), None, 10, from, to).unwrap()
+                .iter().map(|a| a["id"].as_str().unwrap().to_string()).collect()
+        };
+        assert_eq!(in_range(Some(midnight), Some(midnight + 86_400_000)), ["dotted", "slash"]);
+        assert_eq!(in_range(Some(midnight + 86_400_000), None), ["iso", "dotted"]);
+        assert_eq!(in_range(None, Some(midnight)), ["slash", "bad"]);
+    }
+
+    #[test]
+    fn workspace_mode_queries_one_of_several_active_projects() {
+        use crate::domain::model::ProjectEntry;
+        use crate::domain::ports::outbound::HubData;
+        use crate::infrastructure::filesystem::FileAdapter;
+        use crate::infrastructure::persistence::MemoryStore;
+
+        let root = std::env::temp_dir().join(format!("aidd-workspace-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let mut projects = vec![];
+        for (name, session_count) in [("a", 1), ("b", 2), ("c", 0)] {
+            let dir = root.join(name);
+            std::fs::create_dir_all(dir.join(".aidd")).unwrap();
+            let conn = Connection::open(dir.join(".aidd").join("data.db")).unwrap();
+            apply_test_schema(&conn);
+            for i in 0..session_count {
+                conn.execute("INSERT INTO sessions (id) VALUES (?1)", rusqlite::params![format!("{}-{}", name, i)]).unwrap();
+            }
+            let path = dir.to_string_lossy().to_string();
+            projects.push(ProjectEntry { name: name.to_string(), path, detected: true, last_accessed: 0 });
+        }
+        let paths: Vec<String> = projects.iter().map(|p| p.path.clone()).collect();
+        let data = HubData { projects, active_project: Some(paths[0].clone()), ..HubData::default() };
+        let projects = Arc::new(ProjectService::new(Arc::new(MemoryStore::new(data)), Arc::new(FileAdapter), Arc::new(SystemClock)));
+
+        projects.add_active_project(&paths[1]).unwrap();
+        projects.add_active_project(&paths[1]).unwrap();
+        assert_eq!(projects.list_active_projects().unwrap(), vec![paths[0].clone(), paths[1].clone()]);
+        assert!(projects.add_active_project("/not/registered").is_err());
+
+        let adapter = SqliteMemoryAdapter::new(projects.clone());
+        assert_eq!(adapter.get_session_summary().unwrap().total, 1);
+        assert_eq!(adapter.for_project(&paths[1]).unwrap().get_session_summary().unwrap().total, 2);
+        assert!(matches!(adapter.for_project("/not/registered"), Err(AiddError::Other(_))));
+
+        // Registered but inactive: readable without switching, but never writable
+        let browsed = adapter.for_project(&paths[2]).unwrap();
+        assert_eq!(browsed.get_session_summary().unwrap().total, 0);
+        assert!(browsed.delete_session("c-0").is_err());
+        assert!(adapter.for_project(&paths[1]).unwrap().delete_session("b-1").is_ok());
+        assert_eq!(projects.list_active_projects().unwrap(), vec![paths[0].clone(), paths[1].clone()]);
+
+        projects.remove_active_project(&paths[0]).unwrap();
+        assert_eq!(projects.get_active_path().unwrap(), Some(paths[1].clone()));
+        assert_eq!(projects.list_active_projects().unwrap(), vec![paths[1].clone()]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn strict_reads_surface_a_missing_table() {
+        use crate::domain::ports::inbound::Loaded;
+
+        let root = std::env::temp_dir().join(format!("aidd-strict-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        Connection::open(root.join(".aidd").join("data.db"))
+            .unwrap()
+            .execute_batch("CREATE TABLE sessions (id TEXT PRIMARY KEY);")
+            .unwrap();
+
+        let adapter = adapter_for(&root);
+
+        // The lenient path still renders empty
+        assert_eq!(adapter.get_observation_type_counts(), Ok(vec![]));
+        assert_eq!(adapter.list_drafts(), Ok(vec![]));
+
+        let missing = AiddError::SchemaMissing { table: "observations".to_string() };
+        let strict = adapter.strict();
+        assert_eq!(strict.get_observation_type_counts(), Err(missing.clone()));
+        assert_eq!(strict.list_drafts(), Err(missing.clone()));
+        assert_eq!(
+            Loaded::from(strict.get_session_stats()),
+            Loaded { data: SessionStats::default(), error: Some(missing) }
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn export_bundle_keeps_every_column_of_every_table() {
+        let root = std::env::temp_dir().join(format!("aidd-export-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        let conn = Connection::open(root.join(".aidd").join("data.db")).unwrap();
+        apply_test_schema(&conn);
+        for i in 0..5 {
+            conn.execute(
+                "INSERT INTO observations (id, session_id, title, created_at) VALUES (?1, 's0', 'note', ?2)",
+                rusqlite::params![format!("o{}", i), format!("2026-01-0{}T00:00:00Z", i + 1)],
+            ).unwrap();
+        }
+        conn.execute_batch(
+            "INSERT INTO sessions (id, memory_session_id, parent_session_id, started_at, ended_at, status, model_id)
+                VALUES ('s0', 'mem-1', 'parent-1', 1700000000000, 1700000100000, 'abandoned', 'model-a');
+             INSERT INTO evolution_candidates (id, status, data) VALUES
+                ('e1', 'pending', '{\"id\":\"e1\"}'), ('e2', 'approved', '{\"id\":\"e2\"}');
+             INSERT INTO permanent_memory (id, type, title, content) VALUES
+                ('m1', 'decision', 'Use WAL', '{}'), ('m2', 'mistake', 'Forgot index', '{}');"
+        ).unwrap();
+
+        let adapter = adapter_for(&root);
+
+        let mut out = vec![];
+        adapter.export_bundle(&mut out).unwrap();
+        let bundle: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(bundle["schemaVersion"], 2);
+        assert!(bundle["exportedAt"].is_string());
+        let session = &bundle["sessions"][0];
+        assert_eq!(session["status"], "abandoned");
+        assert_eq!(session["memory_session_id"], "mem-1");
+        assert_eq!(session["parent_session_id"], "parent-1");
+        assert_eq!(session["model_id"], "model-a");
+        assert_eq!(bundle["observations"].as_array().unwrap().len(), 5);
+        assert_eq!(bundle["evolutionCandidates"][1]["status"], "approved");
+        assert_eq!(bundle["permanentMemory"].as_array().unwrap().len(), 2);
+        // Tables the engine has not created yet export as empty sections
+        assert_eq!(bundle["drafts"], serde_json::json!([]));
+        assert_eq!(bundle["artifacts"], serde_json::json!([]));
+
+        let copy = create_test_db();
+        let summary = import_into(&copy, bundle, ImportMode::Replace).unwrap();
+        assert_eq!(summary.imported, 10);
+        let (status, model): (String, String) = copy
+            .query_row("SELECT status, model_id FROM sessions WHERE id = 's0'", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((status.as_str(), model.as_str()), ("abandoned", "model-a"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn governance_upsert_reports_only_changed_keys() {
+        let conn = create_test_db();
+        let defaults = default_governance_config();
+        let first = write_governance_config(&conn, &defaults, "2026-01-01T00:00:00Z").unwrap();
+        assert!(first.is_empty(), "defaults match the implicit prior config: {:?}", first);
+
+        let mut edited = defaults.clone();
+        edited["evolution"]["killSwitch"] = serde_json::json!(true);
+        let changes = write_governance_config(&conn, &edited, "2026-01-02T00:00:00Z").unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "evolution.killSwitch");
+        assert_eq!(changes[0].old, serde_json::json!(false));
+        assert_eq!(changes[0].new, serde_json::json!(true));
+        assert_eq!(read_governance_config(&conn).unwrap(), edited);
+    }
+
+    fn import_into(conn: &Connection, bundle: serde_json::Value, mode: ImportMode) -> Result<ImportSummary, AiddError> {
+        let sections = parse_bundle(&bundle.to_string())?;
+        run_in_transaction(conn, |tx| import_bundle_rows(tx, &sections, mode))
+            .map_err(|e| AiddError::Database(e.to_string()))
+    }
+
+    fn seeded_import_db() -> Connection {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "INSERT INTO sessions (id, branch, data) VALUES ('s1', 'main', '{\"id\":\"s1\",\"branch\":\"main\"}');
+             INSERT INTO observations (id, session_id, title, created_at) VALUES ('o1', 's1', 'kept', '2026-01-01');
+             INSERT INTO observations (id, session_id, title, created_at) VALUES ('o3', 's1', 'local only', '2026-01-03');"
+        ).unwrap();
+        conn
+    }
+
+    fn sample_bundle() -> serde_json::Value {
+        serde_json::json!({
+            "schemaVersion": 2,
+            "exportedAt": "2026-02-01T00:00:00.000Z",
+            "sessions": [
+                { "id": "s1", "branch": "imported", "started_at": 1_700_000_000_000i64, "status": "active", "data": "{}" },
+                {
+                    "id": "s2", "branch": "feature", "started_at": 1_700_000_100_000i64, "ended_at": 1_700_000_200_000i64,
+                    "status": "abandoned", "model_id": "model-a", "data": "{}"
+                }
+            ],
+            "observations": [
+                { "id": "o1", "session_id": "s1", "type": "note", "title": "imported", "facts": "[\"a\"]", "created_at": "2026-01-01" },
+                { "id": "o2", "session_id": "s2", "type": "note", "title": "new", "facts": "[]", "created_at": "2026-01-02" }
+            ],
+            "permanentMemory": [
+                { "id": "m1", "type": "decision", "title": "Use WAL", "content": "{\"rationale\":\"readers\"}", "created_at": "2026-01-01" }
+            ]
+        })
+    }
+
+    #[test]
+    fn merge_import_skips_rows_that_already_exist() {
+        let conn = seeded_import_db();
+        let summary = import_into(&conn, sample_bundle(), ImportMode::Merge).unwrap();
+        assert_eq!((summary.imported, summary.skipped), (3, 2));
+
+        let branch: String = conn.query_row("SELECT branch FROM sessions WHERE id = 's1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(branch, "main");
+        let (status, model): (String, String) = conn
+            .query_row("SELECT status, model_id FROM sessions WHERE id = 's2'", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((status.as_str(), model.as_str()), ("abandoned", "model-a"));
+        let observations: i64 = conn.query_row("SELECT COUNT(*) FROM observations", [], |r| r.get(0)).unwrap();
+        assert_eq!(observations, 3);
+        let content: String = conn.query_row("SELECT content FROM permanent_memory WHERE id = 'm1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&content).unwrap(), serde_json::json!({ "rationale": "readers" }));
+    }
+
+    #[test]
+    fn merge_import_indexes_new_permanent_memory_for_search() {
+        let conn = seeded_import_db();
+        // Index without the engine's sync triggers, so the import has to maintain it
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE permanent_memory_fts USING fts5(
+                title, content, content='permanent_memory', content_rowid='rowid'
+            );"
+        ).unwrap();
+
+        import_into(&conn, sample_bundle(), ImportMode::Merge).unwrap();
+
+        let found: Vec<String> = search_permanent_memory_in(&conn, "WAL", 10)
+            .unwrap()
+            .iter()
+            .map(|e| e["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(found, ["m1"]);
+        let integrity: rusqlite::Result<usize> = conn.execute(
+            "INSERT INTO permanent_memory_fts(permanent_memory_fts) VALUES('integrity-check')",
+            [],
+        );
+        assert!(integrity.is_ok(), "FTS index out of sync: {:?}", integrity);
+    }
+
+    #[test]
+    fn replace_import_wipes_first_and_rolls_back_bad_bundles() {
+        let conn = seeded_import_db();
+        let summary = import_into(&conn, sample_bundle(), ImportMode::Replace).unwrap();
+        assert_eq!((summary.imported, summary.skipped), (5, 0));
+
+        let branch: String = conn.query_row("SELECT branch FROM sessions WHERE id = 's1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(branch, "imported");
+        let ids: Vec<String> = conn
+            .prepare("SELECT id FROM observations ORDER BY id").unwrap()
+            .query_map([], |r| r.get(0)).unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(ids, vec!["o1", "o2"]);
+
+        let mut future = sample_bundle();
+        future["schemaVersion"] = serde_json::json!(3);
+        assert!(import_into(&conn, future, ImportMode::Replace).is_err());
+
+        // Column names end up in the INSERT, so unknown ones are refused
+        let mut injected = sample_bundle();
+        injected["sessions"][0]["branch) VALUES ('x'); --"] = serde_json::json!("x");
+        assert!(matches!(import_into(&conn, injected, ImportMode::Replace), Err(AiddError::Database(_))));
+
+        // The test schema has no drafts table: the insert fails after the wipe
+        // ran, and the rollback must restore it.
+        let mut bad = sample_bundle();
+        bad["drafts"] = serde_json::json!([{ "id": "d1", "title": "orphan" }]);
+        assert!(matches!(import_into(&conn, bad, ImportMode::Replace), Err(AiddError::Database(_))));
+        let sessions: i64 = conn.query_row("SELECT COUNT(*) FROM sessions", [], |r| r.get(0)).unwrap();
+        assert_eq!(sessions, 2);
+    }
+
+    #[test]
+    fn fts_results_carry_highlighted_snippets() {
+        let conn = create_test_db();
+        conn.execute(
+            "INSERT INTO observations (id, session_id, type, title, content, created_at)
+             VALUES ('o1', 's1', 'discovery', 'Reconnect logic', 'websocket backoff uses jitter between retries', '2026-01-01')",
+            [],
+        ).unwrap();
+
+        let results = search_observations_in(&conn, "jitter", 10).unwrap();
+        let snippet = results[0].snippet.as_deref().unwrap();
+        assert!(snippet.contains("<mark>jitter</mark>"), "{}", snippet);
+
+        let like = search_observations_like(&conn, "Reconnect", 10).unwrap();
+        assert!(like[0].snippet.is_none());
+    }
+
+    #[test]
+    fn now_iso_keeps_milliseconds_across_boundaries() {
+        use crate::domain::ports::outbound::HubData;
+        use crate::infrastructure::clock::FixedClock;
+        use crate::infrastructure::filesystem::FileAdapter;
+        use crate::infrastructure::persistence::MemoryStore;
+
+        let projects = Arc::new(ProjectService::new(Arc::new(MemoryStore::new(HubData::default())), Arc::new(FileAdapter), Arc::new(SystemClock)));
+        let at = |ms: u64| {
+            let clock = FixedClock::at_unix_ms(ms);
+            SqliteMemoryAdapter::with_project(projects.clone(), None, Arc::new(clock)).now_iso()
+        };
+
+        assert_eq!(at(1_769_903_999_999), "2026-01-31T23:59:59.999Z");
+        assert_eq!(at(1_769_904_000_000), "2026-02-01T00:00:00.000Z");
+        assert_eq!(at(1_709_210_096_789), "2024-02-29T12:34:56.789Z");
+    }
+
+    #[test]
+    fn daily_counts_group_mixed_timestamp_formats_by_day() {
+        let conn = create_test_db();
+        let rows: [(&str, rusqlite::types::Value); 6] = [
+            ("a", "2026-01-01T08:00:00.000Z".to_string().into()),
+            ("b", "2026-01-01T23:59:59Z".to_string().into()),
+            ("c", 1_767_312_000_000_i64.into()), // 2026-01-02T00:00:00Z in ms
+            ("d", "1767398400".to_string().into()), // 2026-01-03T00:00:00Z in seconds
+            ("e", "2026-01-03T12:00:00Z".to_string().into()),
+            ("f", "not a date".to_string().into()),
+        ];
+        for (id, created_at) in rows {
+            conn.execute(
+                "INSERT INTO observations (id, session_id, title, created_at) VALUES (?1, 's1', ?1, ?2)",
+                rusqlite::params![id, created_at],
+            ).unwrap();
+        }
+
+        let day = |date: &str, count| DailyCount { date: date.to_string(), count };
+        assert_eq!(
+            query_observation_daily_counts(&conn, None, None).unwrap(),
+            vec![day("2026-01-01", 2), day("2026-01-02", 1), day("2026-01-03", 2)]
+        );
+        // Bounds are inclusive and apply to the normalized timestamp
+        assert_eq!(
+            query_observation_daily_counts(&conn, Some(1_767_312_000_000), Some(1_767_398_400_000)).unwrap(),
+            vec![day("2026-01-02", 1), day("2026-01-03", 1)]
+        );
+    }
+
+    #[test]
+    fn clearing_rejected_drafts_keeps_the_rest() {
+        let root = std::env::temp_dir().join(format!("aidd-clear-drafts-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        let conn = Connection::open(root.join(".aidd").join("data.db")).unwrap();
+        apply_test_schema(&conn);
+        conn.execute_batch(
+            "CREATE TABLE drafts (id TEXT PRIMARY KEY, category TEXT, title TEXT, content TEXT,
+                status TEXT, data TEXT, created_at TEXT, updated_at TEXT);
+             INSERT INTO drafts (id, status) VALUES
+                ('d1', 'rejected'), ('d2', 'pending'), ('d3', 'rejected'), ('d4', 'approved');"
+        ).unwrap();
+
+        let adapter = adapter_for(&root);
+
+        assert_eq!(adapter.delete_drafts_by_status("rejected").unwrap(), 2);
+        assert_eq!(adapter.delete_drafts_by_status("rejected").unwrap(), 0);
+        assert!(adapter.delete_drafts_by_status("all").is_err());
+
+        let mut stmt = conn.prepare("SELECT id FROM drafts ORDER BY id").unwrap();
+        let left: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect();
+        assert_eq!(left, ["d2", "d4"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn bulk_reject_changes_only_the_given_drafts() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "CREATE TABLE drafts (id TEXT PRIMARY KEY, category TEXT, title TEXT, content TEXT,
+                status TEXT, data TEXT, created_at TEXT, updated_at TEXT);
+             INSERT INTO drafts (id, status, data, updated_at) VALUES
+                ('d1', 'pending', '{}', 'old'), ('d2', 'pending', '{}', 'old'), ('d3', 'pending', '{}', 'old'),
+                ('d4', 'pending', '{}', 'old'), ('d5', 'pending', '{}', 'old');"
+        ).unwrap();
+
+        // A chunk size of 2 spreads the ids (one duplicated) over two statements
+        let ids = ["d1", "d3", "d5", "d3"].map(String::from);
+        let now = "2026-02-01T00:00:00.000Z";
+        let changed = run_in_transaction(&conn, |tx| reject_draft_rows(tx, &ids, "stale", now, 2)).unwrap();
+        assert_eq!(changed, 3);
+
+        let mut stmt = conn
+            .prepare("SELECT id, status, json_extract(data, '$.rejectedReason'), updated_at FROM drafts ORDER BY id")
+            .unwrap();
+        let rows: Vec<(String, String, Option<String>, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        for (id, status, reason, updated_at) in rows {
+            if ["d1", "d3", "d5"].contains(&id.as_str()) {
+                assert_eq!((status.as_str(), reason.as_deref(), updated_at.as_str()), ("rejected", Some("stale"), now));
+            } else {
+                assert_eq!((status.as_str(), reason, updated_at.as_str()), ("pending", None, "old"), "{}", id);
+            }
+        }
+
+        let none: [String; 0] = [];
+        assert_eq!(run_in_transaction(&conn, |tx| reject_draft_rows(tx, &none, "stale", now, 2)).unwrap(), 0);
+    }
+
+    #[test]
+    fn session_stats_aggregate_durations_and_tokens() {
+        let conn = create_test_db();
+        conn.execute_batch(
+            "INSERT INTO sessions (id, started_at, ended_at) VALUES
+                ('s1', 1700000000000, 1700000060000), ('s2', 1700000000, 1700000300),
+                ('s3', '2026-01-01T00:00:00Z', '2026-01-01T00:02:00Z'), ('s4', 1700000000000, 1700000180000),
+                ('open', 1700000000000, NULL);
+             INSERT INTO observations (id, session_id, type, discovery_tokens) VALUES
+                ('o1', 's1', 'decision', 120), ('o2', 's1', 'mistake', 30), ('o3', 's2', 'decision', NULL),
+                ('o4', 's3', 'decision', 50);"
+        ).unwrap();
+
+        let stats = query_session_stats(&conn).unwrap();
+        assert_eq!((stats.total, stats.active, stats.completed), (5, 1, 4));
+        // Durations: 60s (ms), 300s (seconds), 120s (ISO), 180s (ms)
+        assert_eq!(stats.avg_duration_ms, Some(165_000.0));
+        assert_eq!(stats.median_duration_ms, Some(150_000.0));
+        assert_eq!(stats.total_discovery_tokens, 200);
+        assert_eq!(stats.observations_by_type.get("decision"), Some(&3));
+        assert_eq!(stats.observations_by_type.get("mistake"), Some(&1));
+
+        conn.execute("DELETE FROM sessions WHERE id = 's4'", []).unwrap();
+        assert_eq!(query_session_stats(&conn).unwrap().median_duration_ms, Some(120_000.0));
+    }
+
+    #[test]
+    fn session_stats_of_an_empty_db_have_no_durations() {
+        let stats = query_session_stats(&create_test_db()).unwrap();
+        assert_eq!(stats, SessionStats::default());
+    }
+
+    #[test]
+    fn observation_types_are_counted_and_filterable() {
+        let conn = create_test_db();
+        for (id, obs_type, created_at) in [
+            ("o1", "decision", "2026-01-01"),
+            ("o2", "mistake", "2026-01-02"),
+            ("o3", "decision", "2026-01-03"),
+            ("o4", "convention", "2026-01-04"),
+            ("o5", "decision", "2026-01-05"),
+            ("o6", "mistake", "2026-01-06"),
+        ] {
+            conn.execute(
+                "INSERT INTO observations (id, session_id, type, title, created_at) VALUES (?1, 's1', ?2, ?1, ?3)",
+                [id, obs_type, created_at],
+            ).unwrap();
+        }
+
+        let counts = query_observation_type_counts(&conn).unwrap();
+        let counts: Vec<(&str, usize)> = counts.iter().map(|(t, n)| (t.as_str(), *n)).collect();
+        assert_eq!(counts, [("decision", 3), ("mistake", 2), ("convention", 1)]);
+
+        let ids = |page: MemoryPage| -> Vec<String> {
+            page.items.iter().map(|o| o["id"].as_str().unwrap().to_string()).collect()
+        };
+        let types = ["mistake".to_string(), "convention".to_string()];
+        assert_eq!(ids(query_observations_page(&conn, 10, None, Some(&types)).unwrap()), ["o6", "o4", "o2"]);
+        // The filter combines with keyset paging
+        let first = query_observations_page(&conn, 2, None, Some(&types)).unwrap();
+        let cursor = decode_cursor(first.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(ids(query_observations_page(&conn, 2, Some(&cursor), Some(&types)).unwrap()), ["o2"]);
+        // An empty selection means no filter
+        assert_eq!(query_observations_page(&conn, 10, None, Some(&[])).unwrap().items.len(), 6);
+    }
+
+    #[test]
+    fn permanent_memory_pages_report_the_full_total() {
+        let root = std::env::temp_dir().join(format!("aidd-permanent-page-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        let conn = Connection::open(root.join(".aidd").join("data.db")).unwrap();
+        apply_test_schema(&conn);
+        for i in 0..5 {
+            conn.execute(
+                "INSERT INTO permanent_memory (id, type, title, content, created_at) VALUES (?1, 'mistake', ?2, '{}', ?3)",
+                rusqlite::params![format!("m{}", i), format!("Mistake {}", i), format!("2026-01-0{}T00:00:00Z", i + 1)],
+            ).unwrap();
+        }
+        conn.execute(
+            "INSERT INTO permanent_memory (id, type, title, content) VALUES ('d1', 'decision', 'Use WAL', '{}')",
+            [],
+        ).unwrap();
+        for id in ["c1", "c2", "c3"] {
+            conn.execute(
+                "INSERT INTO permanent_memory (id, type, title, content, created_at)
+                 VALUES (?1, 'convention', ?1, '{}', '2026-01-01T00:00:00Z')",
+                [id],
+            ).unwrap();
+        }
+
+        let adapter = adapter_for(&root);
+
+        let page = adapter.list_permanent_memory("mistake", Some(2), Some(1)).unwrap();
+        assert_eq!(page.total, 5);
+        let errors: Vec<_> = page.items.iter().map(|e| e["error"].as_str().unwrap()).collect();
+        assert_eq!(errors, ["Mistake 3", "Mistake 2"]);
+
+        let past_end = adapter.list_permanent_memory("mistake", Some(2), Some(10)).unwrap();
+        assert!(past_end.items.is_empty());
+        assert_eq!(past_end.total, 5);
+
+        // Rows sharing a timestamp page in id order, each exactly once
+        let ids: Vec<_> = (0..3)
+            .map(|offset| adapter.list_permanent_memory("convention", Some(1), Some(offset)).unwrap())
+            .map(|page| page.items[0]["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, ["c3", "c2", "c1"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn edited_permanent_memory_is_searchable_by_its_new_content() {
+        let root = std::env::temp_dir().join(format!("aidd-permanent-fts-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        let conn = Connection::open(root.join(".aidd").join("data.db")).unwrap();
+        apply_test_schema(&conn);
+        // Index without the engine's sync triggers, so the Hub has to maintain it
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE permanent_memory_fts USING fts5(
+                title, content, content='permanent_memory', content_rowid='rowid'
+            );"
+        ).unwrap();
+
+        let adapter = adapter_for(&root);
+
+        let id = adapter.create_permanent_memory("decision", "Use postgres", "{\"reasoning\":\"relational data\"}").unwrap();
+        let found = |query: &str| -> Vec<String> {
+            adapter.search_permanent_memory(query, None).unwrap()
+                .iter()
+                .map(|e| e["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(found("postgres"), [id.clone()]);
+
+        adapter.update_permanent_memory(&id, "Use sqlite", "{\"reasoning\":\"embedded storage\"}").unwrap();
+        assert_eq!(found("sqlite"), [id.clone()]);
+        assert_eq!(found("embedded"), [id.clone()]);
+        assert!(found("postgres").is_empty());
+
+        let integrity: rusqlite::Result<usize> = conn.execute(
+            "INSERT INTO permanent_memory_fts(permanent_memory_fts) VALUES('integrity-check')",
+            [],
+        );
+        assert!(integrity.is_ok(), "FTS index out of sync: {:?}", integrity);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
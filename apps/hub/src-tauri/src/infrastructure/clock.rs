@@ -0,0 +1,45 @@
+use std::time::SystemTime;
+#[cfg(test)]
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::domain::ports::outbound::Clock;
+
+/// Wall-clock time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Always reports the same instant, for deterministic timestamps in tests.
+#[cfg(test)]
+pub(crate) struct FixedClock(pub SystemTime);
+
+#[cfg(test)]
+impl FixedClock {
+    pub(crate) fn at_unix_ms(ms: u64) -> Self {
+        Self(UNIX_EPOCH + Duration::from_millis(ms))
+    }
+}
+
+#[cfg(test)]
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_clock_yields_the_same_iso_and_ms_every_time() {
+        let clock = FixedClock::at_unix_ms(1_769_904_000_123);
+        assert_eq!(clock.now_unix_ms(), 1_769_904_000_123);
+        assert_eq!(clock.now_iso(), "2026-02-01T00:00:00.123Z");
+        assert_eq!(clock.now_iso(), clock.now_iso());
+    }
+}
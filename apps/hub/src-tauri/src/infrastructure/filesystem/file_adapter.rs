@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::domain::ports::outbound::FileSystemPort;
+use crate::domain::ports::outbound::{FileSystemPort, FsEntry, FsMetadata};
 
 /// Standard filesystem adapter using `std::fs`.
 pub struct FileAdapter;
@@ -33,4 +33,57 @@ impl FileSystemPort for FileAdapter {
         std::fs::create_dir_all(path)
             .map_err(|e| format!("Failed to create directories: {}", e))
     }
+
+    fn remove_file(&self, path: &str) -> Result<(), String> {
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to delete {}: {}", path, e))
+    }
+
+    fn remove_dir_all(&self, path: &str) -> Result<(), String> {
+        std::fs::remove_dir_all(path)
+            .map_err(|e| format!("Failed to remove {}: {}", path, e))
+    }
+
+    fn copy_file(&self, from: &str, to: &str) -> Result<(), String> {
+        std::fs::copy(from, to)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", from, e))
+    }
+
+    fn list_dirs(&self, path: &str) -> Result<Vec<String>, String> {
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read dir {}: {}", path, e))?;
+        let mut dirs: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        dirs.sort();
+        Ok(dirs)
+    }
+
+    fn read_dir(&self, path: &str) -> Result<Vec<FsEntry>, String> {
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read dir {}: {}", path, e))?;
+        let mut result: Vec<FsEntry> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| {
+                let p = e.path();
+                FsEntry {
+                    name: e.file_name().to_string_lossy().to_string(),
+                    is_dir: p.is_dir(),
+                    path: p.to_string_lossy().to_string(),
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(result)
+    }
+
+    fn metadata(&self, path: &str) -> Result<FsMetadata, String> {
+        let meta = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to stat {}: {}", path, e))?;
+        Ok(FsMetadata { modified: meta.modified().ok() })
+    }
 }
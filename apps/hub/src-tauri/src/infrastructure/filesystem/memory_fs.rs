@@ -0,0 +1,140 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::domain::ports::outbound::{FileSystemPort, FsEntry, FsMetadata};
+
+/// In-memory `FileSystemPort` for tests that must not touch the real disk.
+/// Directories exist implicitly above every file, or explicitly via `create_dir_all`.
+#[derive(Default)]
+pub(crate) struct MemoryFs {
+    files: Mutex<BTreeMap<PathBuf, (String, SystemTime)>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl MemoryFs {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, for building fixtures.
+    pub(crate) fn with_file(self, path: impl AsRef<Path>, content: &str) -> Self {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), (content.to_string(), SystemTime::now()));
+        self
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.lock().unwrap().contains_key(path)
+    }
+}
+
+impl FileSystemPort for MemoryFs {
+    fn read_to_string(&self, path: &str) -> Result<String, String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(Path::new(path))
+            .map(|(content, _)| content.clone())
+            .ok_or_else(|| format!("Failed to read {}: not found", path))
+    }
+
+    fn write(&self, path: &str, content: &str) -> Result<(), String> {
+        if self.is_dir(path) {
+            return Err(format!("Failed to write {}: is a directory", path));
+        }
+        self.files
+            .lock()
+            .unwrap()
+            .insert(PathBuf::from(path), (content.to_string(), SystemTime::now()));
+        Ok(())
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.is_file(Path::new(path)) || self.is_dir(path)
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        let path = Path::new(path);
+        self.dirs.lock().unwrap().iter().any(|d| d.starts_with(path))
+            || self.files.lock().unwrap().keys().any(|f| f != path && f.starts_with(path))
+    }
+
+    fn create_dir_all(&self, path: &str) -> Result<(), String> {
+        self.dirs.lock().unwrap().insert(PathBuf::from(path));
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &str) -> Result<(), String> {
+        self.files
+            .lock()
+            .unwrap()
+            .remove(Path::new(path))
+            .map(|_| ())
+            .ok_or_else(|| format!("Failed to delete {}: not found", path))
+    }
+
+    fn remove_dir_all(&self, path: &str) -> Result<(), String> {
+        if !self.is_dir(path) {
+            return Err(format!("Failed to remove {}: not found", path));
+        }
+        let root = Path::new(path);
+        self.files.lock().unwrap().retain(|f, _| !f.starts_with(root));
+        self.dirs.lock().unwrap().retain(|d| !d.starts_with(root));
+        Ok(())
+    }
+
+    fn copy_file(&self, from: &str, to: &str) -> Result<(), String> {
+        let content = self.read_to_string(from).map_err(|_| format!("Failed to copy {}: not found", from))?;
+        self.write(to, &content)
+    }
+
+    fn list_dirs(&self, path: &str) -> Result<Vec<String>, String> {
+        Ok(self
+            .read_dir(path)?
+            .into_iter()
+            .filter(|e| e.is_dir)
+            .map(|e| e.path)
+            .collect())
+    }
+
+    fn read_dir(&self, path: &str) -> Result<Vec<FsEntry>, String> {
+        if !self.is_dir(path) {
+            return Err(format!("Failed to read dir {}: not found", path));
+        }
+        let root = Path::new(path);
+        // Name -> is_dir; a name seen below its own level is a directory
+        let mut children: BTreeMap<String, bool> = BTreeMap::new();
+        let files: Vec<PathBuf> = self.files.lock().unwrap().keys().cloned().collect();
+        let dirs: Vec<PathBuf> = self.dirs.lock().unwrap().iter().cloned().collect();
+        for (candidate, is_file) in files.iter().map(|f| (f, true)).chain(dirs.iter().map(|d| (d, false))) {
+            let Ok(rest) = candidate.strip_prefix(root) else { continue };
+            let mut parts = rest.components();
+            let Some(first) = parts.next() else { continue };
+            let is_dir = !is_file || parts.next().is_some();
+            let entry = children.entry(first.as_os_str().to_string_lossy().to_string()).or_insert(is_dir);
+            *entry |= is_dir;
+        }
+        Ok(children
+            .into_iter()
+            .map(|(name, is_dir)| FsEntry {
+                path: root.join(&name).to_string_lossy().to_string(),
+                name,
+                is_dir,
+            })
+            .collect())
+    }
+
+    fn metadata(&self, path: &str) -> Result<FsMetadata, String> {
+        if let Some((_, modified)) = self.files.lock().unwrap().get(Path::new(path)) {
+            return Ok(FsMetadata { modified: Some(*modified) });
+        }
+        if self.is_dir(path) {
+            return Ok(FsMetadata { modified: None });
+        }
+        Err(format!("Failed to stat {}: not found", path))
+    }
+}
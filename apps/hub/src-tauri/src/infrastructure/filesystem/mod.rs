@@ -1,3 +1,7 @@
 mod file_adapter;
+#[cfg(test)]
+mod memory_fs;
 
 pub use file_adapter::FileAdapter;
+#[cfg(test)]
+pub(crate) use memory_fs::MemoryFs;
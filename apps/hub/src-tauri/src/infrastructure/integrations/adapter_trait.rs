@@ -1,18 +1,156 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use crate::domain::model::{IntegrationConfig, IntegrationResult, IntegrationType};
 
+/// How an adapter treats an aidd-engine MCP entry that is already present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum McpWrite {
+    /// Bring an existing entry up to date (integrate).
+    Upsert,
+    /// Leave an existing entry exactly as it is (repair).
+    InsertMissing,
+}
+
 /// Infrastructure trait — each AI tool adapter implements this.
 pub(crate) trait ToolAdapter: Send + Sync {
     fn tool_type(&self) -> IntegrationType;
-    fn integrate(&self, project_path: &Path, framework_path: &Path, dev_mode: bool) -> Result<IntegrationResult, String>;
+    /// Write the integration's files. Instruction files are only ever created;
+    /// `mcp_write` decides whether an existing MCP entry is updated.
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String>;
+    /// `env` is merged into the `env` block of any MCP entry the adapter writes.
+    fn integrate(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+    ) -> Result<IntegrationResult, String> {
+        self.apply(project_path, framework_path, dev_mode, env, McpWrite::Upsert)
+    }
+    /// Create only what has gone missing since the integration was set up,
+    /// keeping the dev mode `check` reports. Nothing that exists is overwritten.
+    fn repair(&self, project_path: &Path, framework_path: &Path) -> Result<IntegrationResult, String> {
+        let dev_mode = self.check(project_path)?.dev_mode;
+        self.apply(project_path, framework_path, dev_mode, &BTreeMap::new(), McpWrite::InsertMissing)
+    }
     fn remove(&self, project_path: &Path) -> Result<IntegrationResult, String>;
     fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String>;
+
+    /// Config files holding this adapter's aidd-engine MCP entry, primary first;
+    /// empty for tools without MCP config.
+    fn mcp_config_files(&self, _project_path: &Path) -> Vec<McpConfigFile> {
+        Vec::new()
+    }
+}
+
+/// A config file an adapter writes its aidd-engine MCP entry to.
+#[derive(Debug, Clone)]
+pub(crate) struct McpConfigFile {
+    pub path: PathBuf,
+    pub layout: McpLayout,
+    /// Committed with the project, so the entry's `env` is never written there.
+    pub shared: bool,
+}
+
+impl McpConfigFile {
+    /// `entry` as it belongs in this file: without `env` when the file is shared.
+    pub fn entry_for(&self, entry: &serde_json::Value) -> serde_json::Value {
+        let mut entry = entry.clone();
+        if self.shared {
+            if let Some(map) = entry.as_object_mut() {
+                map.remove("env");
+            }
+        }
+        entry
+    }
+}
+
+/// How a config file nests its MCP servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum McpLayout {
+    /// `{ "<key>": { "aidd-engine": {...} } }` — `mcpServers`, or `servers` for VS Code.
+    Object(&'static str),
+    /// `{ "mcpServers": [{ "name": "aidd-engine", ... }] }` (Continue).
+    Array,
+}
+
+/// The aidd-engine entry as it currently is on disk (without Continue's `name`),
+/// or `None` when the file or entry is missing.
+pub(crate) fn read_installed_mcp_entry(file: &McpConfigFile) -> Result<Option<serde_json::Value>, String> {
+    if !file.path.exists() {
+        return Ok(None);
+    }
+    let config = read_jsonc_or_default(&file.path)?;
+    let entry = match file.layout {
+        McpLayout::Object(key) => config.get(key).and_then(|s| s.get("aidd-engine")).cloned(),
+        McpLayout::Array => config
+            .get("mcpServers")
+            .and_then(|s| s.as_array())
+            .and_then(|servers| servers.iter().find(|s| is_aidd_array_entry(s)))
+            .map(|entry| {
+                let mut entry = entry.clone();
+                if let Some(map) = entry.as_object_mut() {
+                    map.remove("name");
+                }
+                entry
+            }),
+    };
+    Ok(entry)
+}
+
+/// Replace an existing aidd-engine entry with `entry` verbatim (minus `env` for
+/// shared files). Returns whether the file was written; files without the entry
+/// are left alone.
+pub(crate) fn write_installed_mcp_entry(file: &McpConfigFile, entry: &serde_json::Value) -> Result<bool, String> {
+    if read_installed_mcp_entry(file)?.is_none() {
+        return Ok(false);
+    }
+    let entry = &file.entry_for(entry);
+    let mut config = read_jsonc_or_default(&file.path)?;
+    match file.layout {
+        McpLayout::Object(key) => {
+            // Keep comments and formatting where the text allows it
+            let content = std::fs::read_to_string(&file.path)
+                .map_err(|e| format!("Failed to read {}: {}", file.path.display(), e))?;
+            if let Some(spliced) = splice_jsonc_value(&content, &[key, "aidd-engine"], entry) {
+                std::fs::write(&file.path, spliced)
+                    .map_err(|e| format!("Failed to write {}: {}", file.path.display(), e))?;
+                return Ok(true);
+            }
+            config[key]["aidd-engine"] = entry.clone();
+        }
+        McpLayout::Array => {
+            let mut named = entry.clone();
+            named["name"] = serde_json::json!("aidd-engine");
+            if let Some(slot) = config
+                .get_mut("mcpServers")
+                .and_then(|s| s.as_array_mut())
+                .and_then(|servers| servers.iter_mut().find(|s| is_aidd_array_entry(s)))
+            {
+                *slot = named;
+            }
+        }
+    }
+    write_json(&file.path, &config)?;
+    Ok(true)
 }
 
 /// Generate the MCP server JSON entry.
 /// In dev mode, points to the local dist/index.js; otherwise uses npx.
-pub(crate) fn mcp_server_entry(project_path: &Path, dev_mode: bool) -> serde_json::Value {
-    if dev_mode {
+/// A non-empty `env` is written as the entry's `env` block.
+pub(crate) fn mcp_server_entry(
+    project_path: &Path,
+    dev_mode: bool,
+    env: &BTreeMap<String, String>,
+) -> serde_json::Value {
+    let mut entry = if dev_mode {
         let engine_path = project_path.join("mcps").join("mcp-aidd-engine").join("dist").join("index.js");
         serde_json::json!({
             "command": "node",
@@ -23,9 +161,62 @@ pub(crate) fn mcp_server_entry(project_path: &Path, dev_mode: bool) -> serde_jso
             "command": "npx",
             "args": ["-y", "@aidd.md/mcp-engine"]
         })
+    };
+    if !env.is_empty() {
+        entry["env"] = serde_json::json!(env);
+    }
+    entry
+}
+
+/// Keep the `env` block of an entry already on disk: its variables are carried
+/// into `desired`, with values from `desired` winning on conflicts.
+pub(crate) fn carry_over_env(desired: &mut serde_json::Value, existing: Option<&serde_json::Value>) {
+    let Some(existing_env) = existing.and_then(|e| e.get("env")).and_then(|e| e.as_object()) else {
+        return;
+    };
+    let mut merged = existing_env.clone();
+    if let Some(env) = desired.get("env").and_then(|e| e.as_object()) {
+        merged.extend(env.clone());
+    }
+    desired["env"] = serde_json::Value::Object(merged);
+}
+
+/// Server names the Hub writes its MCP entry under.
+pub(crate) const AIDD_SERVER_NAMES: [&str; 2] = ["aidd", "aidd-engine"];
+
+/// Whether an MCP entry is one the Hub wrote: a canonical server name plus the
+/// exact launch command from `mcp_server_entry` (npx package or dev-mode script).
+/// A user's own entry pointing at `@aidd.md/...` under another name or with
+/// other arguments is not ours to touch.
+pub(crate) fn is_aidd_authored(name: &str, entry: &serde_json::Value) -> bool {
+    if !AIDD_SERVER_NAMES.contains(&name) {
+        return false;
+    }
+    let command = entry.get("command").and_then(|c| c.as_str());
+    let args: Vec<&str> = entry
+        .get("args")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    match (command, args.as_slice()) {
+        (Some("npx"), ["-y", "@aidd.md/mcp-engine"]) => true,
+        (Some("node"), [script]) => {
+            Path::new(script).ends_with(Path::new("mcps").join("mcp-aidd-engine").join("dist").join("index.js"))
+        }
+        _ => false,
     }
 }
 
+/// Note an aidd-engine entry left alone by a repair.
+pub(crate) fn record_entry_kept(mcp_path: &Path, result: &mut IntegrationResult) {
+    result.messages.push(format!("aidd entry already present in {} — not overwritten", mcp_path.display()));
+}
+
+/// Note an aidd-engine entry left in place because the user wrote it.
+pub(crate) fn record_user_entry_kept(mcp_path: &Path, result: &mut IntegrationResult) {
+    result.messages.push(format!("Kept user-defined aidd-engine entry in {}", mcp_path.display()));
+}
+
 /// Check if an existing MCP server entry is in dev mode (command is "node").
 pub(crate) fn is_dev_mode_entry(entry: &serde_json::Value) -> bool {
     entry.get("command").and_then(|c| c.as_str()) == Some("node")
@@ -57,6 +248,308 @@ pub(crate) fn read_json_or_default(path: &Path) -> Result<serde_json::Value, Str
         .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
 }
 
+/// Helper: like `read_json_or_default`, but tolerates JSONC (comments and
+/// trailing commas). Use for editor-owned files such as VS Code settings.
+pub(crate) fn read_jsonc_or_default(path: &Path) -> Result<serde_json::Value, String> {
+    if !path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&strip_jsonc(&content))
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Strip `//` and `/* */` comments and trailing commas, leaving strings untouched.
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+                out.push(' ');
+            }
+            '}' | ']' => {
+                let end = out.trim_end().len();
+                if out[..end].ends_with(',') {
+                    out.remove(end - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Set the member at `path` (a chain of object keys) by splicing the raw JSONC
+/// text, so comments, key order and indentation elsewhere survive. Missing
+/// objects along the path are created. Returns `None` when the text can't be
+/// edited in place (e.g. a non-object on the path), leaving a full rewrite to
+/// the caller.
+pub(crate) fn splice_jsonc_value(content: &str, path: &[&str], value: &serde_json::Value) -> Option<String> {
+    let unit = indent_unit(content);
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut scanner = JsoncScanner { src: content.as_bytes(), pos: 0 };
+    scanner.skip_trivia();
+    let mut object = scanner.object()?;
+
+    for (depth, segment) in path.iter().enumerate() {
+        // The last duplicate wins, matching serde_json
+        let member = object.members.iter().rev().find(|m| m.key == *segment);
+        let (start, end, text) = match member {
+            Some(m) if depth + 1 == path.len() => {
+                let indent = line_indent(content, m.key_start);
+                (m.value_start, m.value_end, render_json(value, indent, &unit, newline)?)
+            }
+            Some(m) => {
+                scanner.pos = m.value_start;
+                object = scanner.object()?;
+                continue;
+            }
+            None => {
+                let nested = path[depth + 1..]
+                    .iter()
+                    .rev()
+                    .fold(value.clone(), |inner, key| serde_json::json!({ *key: inner }));
+                let key = serde_json::to_string(segment).ok()?;
+                match object.members.last() {
+                    Some(last) => {
+                        let indent = line_indent(content, last.key_start);
+                        let rendered = render_json(&nested, indent, &unit, newline)?;
+                        (last.value_end, last.value_end, format!(",{newline}{indent}{key}: {rendered}"))
+                    }
+                    None => {
+                        let outer = line_indent(content, object.open);
+                        let indent = format!("{outer}{unit}");
+                        let rendered = render_json(&nested, &indent, &unit, newline)?;
+                        (object.open + 1, object.open + 1, format!("{newline}{indent}{key}: {rendered}{newline}{outer}"))
+                    }
+                }
+            }
+        };
+        let spliced = format!("{}{}{}", &content[..start], text, &content[end..]);
+        // Never hand back text that no longer parses
+        serde_json::from_str::<serde_json::Value>(&strip_jsonc(&spliced)).ok()?;
+        return Some(spliced);
+    }
+    None
+}
+
+/// Delete the member at `path` from the raw JSONC text, undoing what
+/// `splice_jsonc_value` added without touching the rest of the file. Returns
+/// `None` when the member is missing or the text can't be edited in place.
+pub(crate) fn remove_jsonc_member(content: &str, path: &[&str]) -> Option<String> {
+    let (last, parents) = path.split_last()?;
+    let mut scanner = JsoncScanner { src: content.as_bytes(), pos: 0 };
+    scanner.skip_trivia();
+    let mut object = scanner.object()?;
+    for segment in parents {
+        scanner.pos = object.members.iter().rev().find(|m| m.key == *segment)?.value_start;
+        object = scanner.object()?;
+    }
+    let index = object.members.iter().rposition(|m| m.key == *last)?;
+    let member = &object.members[index];
+    let (start, end) = match (index.checked_sub(1), object.members.get(index + 1)) {
+        // Take the separator before it, as an appended member brought it along
+        (Some(prev), _) => (object.members[prev].value_end, member.value_end),
+        (None, Some(next)) => (member.key_start, next.key_start),
+        (None, None) => (object.open + 1, object.close),
+    };
+    let spliced = format!("{}{}", &content[..start], &content[end..]);
+    serde_json::from_str::<serde_json::Value>(&strip_jsonc(&spliced)).ok()?;
+    Some(spliced)
+}
+
+/// Pretty-print `value` with the file's indent unit, continuation lines
+/// prefixed by `indent` so the value lines up under its key.
+fn render_json(value: &serde_json::Value, indent: &str, unit: &str, newline: &str) -> Option<String> {
+    use serde::Serialize;
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    value.serialize(&mut serializer).ok()?;
+    let pretty = String::from_utf8(buf).ok()?;
+    Some(pretty.lines().collect::<Vec<_>>().join(&format!("{newline}{indent}")))
+}
+
+/// Leading whitespace of the line containing byte offset `pos`.
+fn line_indent(content: &str, pos: usize) -> &str {
+    let start = content[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// The indentation of the first indented line, defaulting to two spaces.
+fn indent_unit(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+/// Byte offsets of one `"key": value` member of a JSONC object.
+struct JsoncMember {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+struct JsoncObject {
+    open: usize,
+    close: usize,
+    members: Vec<JsoncMember>,
+}
+
+/// Minimal JSONC reader that records member offsets instead of building values.
+struct JsoncScanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl JsoncScanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match (self.peek(), self.src.get(self.pos + 1).copied()) {
+                (Some(b' ' | b'\t' | b'\r' | b'\n'), _) => self.pos += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    while self.peek().is_some_and(|b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.pos += 2;
+                    while self.pos < self.src.len() && !self.src[self.pos..].starts_with(b"*/") {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.src.len());
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> Option<()> {
+        self.skip_trivia();
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+
+    /// Parse a string literal at the cursor and return its decoded value.
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        if self.peek()? != b'"' {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        serde_json::from_slice(&self.src[start..self.pos]).ok()
+    }
+
+    /// Skip any value at the cursor, leaving the cursor just past it.
+    fn value(&mut self) -> Option<()> {
+        self.skip_trivia();
+        match self.peek()? {
+            b'{' => self.object().map(|_| ()),
+            b'[' => {
+                self.pos += 1;
+                loop {
+                    self.skip_trivia();
+                    if self.peek()? == b']' {
+                        self.pos += 1;
+                        return Some(());
+                    }
+                    self.value()?;
+                    self.skip_trivia();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+            }
+            b'"' => self.string().map(|_| ()),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']' | b'/' | b' ' | b'\t' | b'\r' | b'\n'))
+                {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(())
+            }
+        }
+    }
+
+    /// Parse the object at the cursor, recording where each member sits.
+    fn object(&mut self) -> Option<JsoncObject> {
+        self.skip_trivia();
+        let open = self.pos;
+        self.eat(b'{')?;
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.peek()? == b'}' {
+                let close = self.pos;
+                self.pos += 1;
+                return Some(JsoncObject { open, close, members });
+            }
+            let key_start = self.pos;
+            let key = self.string()?;
+            self.eat(b':')?;
+            self.skip_trivia();
+            let value_start = self.pos;
+            self.value()?;
+            members.push(JsoncMember { key, key_start, value_start, value_end: self.pos });
+            self.skip_trivia();
+            if self.peek()? == b',' {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
 /// Helper: write a JSON value to a file (pretty-printed), creating parent dirs.
 pub(crate) fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), String> {
     if let Some(parent) = path.parent() {
@@ -247,12 +740,19 @@ pub(crate) fn project_name(project_path: &Path) -> String {
 // MCP JSON helpers for integrations with MCP configs
 // ---------------------------------------------------------------------------
 
-/// Upsert the aidd-engine entry in an MCP JSON config file.
-/// Returns (was_created, was_modified).
+/// Note an MCP config whose aidd-engine entry already matches, so nothing is written.
+pub(crate) fn record_entry_current(mcp_path: &Path, result: &mut IntegrationResult) {
+    result.messages.push(format!("aidd entry already current in {}", mcp_path.display()));
+}
+
+/// Upsert the aidd-engine entry in an MCP JSON config file. Leaves the file
+/// untouched (and out of `files_modified`) when the entry already matches.
 pub(crate) fn upsert_mcp_entry(
     mcp_path: &Path,
     project_path: &Path,
     dev_mode: bool,
+    env: &BTreeMap<String, String>,
+    mcp_write: McpWrite,
     result: &mut IntegrationResult,
 ) -> Result<(), String> {
     let mut config = read_json_or_default(mcp_path)?;
@@ -262,12 +762,22 @@ pub(crate) fn upsert_mcp_entry(
         .ok_or("MCP config is not a JSON object")?
         .entry("mcpServers")
         .or_insert_with(|| serde_json::json!({}));
+    if mcp_write == McpWrite::InsertMissing && servers.get("aidd-engine").is_some() {
+        record_entry_kept(mcp_path, result);
+        return Ok(());
+    }
 
+    let mut desired = mcp_server_entry(project_path, dev_mode, env);
+    carry_over_env(&mut desired, servers.get("aidd-engine"));
     let had_entry = servers.get("aidd-engine").is_some();
+    if servers.get("aidd-engine") == Some(&desired) {
+        record_entry_current(mcp_path, result);
+        return Ok(());
+    }
     servers
         .as_object_mut()
         .ok_or("mcpServers is not a JSON object")?
-        .insert("aidd-engine".to_string(), mcp_server_entry(project_path, dev_mode));
+        .insert("aidd-engine".to_string(), desired);
 
     write_json(mcp_path, &config)?;
     if had_entry {
@@ -278,7 +788,7 @@ pub(crate) fn upsert_mcp_entry(
     Ok(())
 }
 
-/// Remove the aidd-engine entry from an MCP JSON config file.
+/// Remove the aidd-engine entry from an MCP JSON config file, if the Hub wrote it.
 pub(crate) fn remove_mcp_entry(
     mcp_path: &Path,
     result: &mut IntegrationResult,
@@ -288,9 +798,14 @@ pub(crate) fn remove_mcp_entry(
     }
     let mut config = read_json_or_default(mcp_path)?;
     if let Some(servers) = config.get_mut("mcpServers").and_then(|s| s.as_object_mut()) {
-        if servers.remove("aidd-engine").is_some() {
-            write_json(mcp_path, &config)?;
-            result.files_modified.push(mcp_path.to_string_lossy().to_string());
+        match servers.get("aidd-engine").map(|entry| is_aidd_authored("aidd-engine", entry)) {
+            Some(true) => {
+                servers.remove("aidd-engine");
+                write_json(mcp_path, &config)?;
+                result.files_modified.push(mcp_path.to_string_lossy().to_string());
+            }
+            Some(false) => record_user_entry_kept(mcp_path, result),
+            None => {}
         }
     }
     Ok(())
@@ -309,6 +824,97 @@ pub(crate) fn check_mcp_entry(mcp_path: &Path) -> Result<(bool, bool), String> {
     }
 }
 
+/// Upsert the aidd-engine entry in a config whose `mcpServers` is an array of
+/// `{ "name": ..., "command": ..., "args": [...] }` objects (Continue).
+pub(crate) fn upsert_mcp_array_entry(
+    config_path: &Path,
+    project_path: &Path,
+    dev_mode: bool,
+    env: &BTreeMap<String, String>,
+    mcp_write: McpWrite,
+    result: &mut IntegrationResult,
+) -> Result<(), String> {
+    let mut config = read_json_or_default(config_path)?;
+
+    let servers = config
+        .as_object_mut()
+        .ok_or("MCP config is not a JSON object")?
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!([]))
+        .as_array_mut()
+        .ok_or("mcpServers is not a JSON array")?;
+
+    let mut entry = mcp_server_entry(project_path, dev_mode, env);
+    entry["name"] = serde_json::json!("aidd-engine");
+
+    let existing = servers.iter().position(is_aidd_array_entry);
+    if mcp_write == McpWrite::InsertMissing && existing.is_some() {
+        record_entry_kept(config_path, result);
+        return Ok(());
+    }
+    carry_over_env(&mut entry, existing.map(|idx| &servers[idx]));
+    if existing.is_some_and(|idx| servers[idx] == entry) {
+        record_entry_current(config_path, result);
+        return Ok(());
+    }
+    match existing {
+        Some(idx) => servers[idx] = entry,
+        None => servers.push(entry),
+    }
+
+    write_json(config_path, &config)?;
+    if existing.is_some() {
+        result.files_modified.push(config_path.to_string_lossy().to_string());
+    } else {
+        result.files_created.push(config_path.to_string_lossy().to_string());
+    }
+    Ok(())
+}
+
+/// Remove the Hub-written aidd-engine entry from an array-shaped `mcpServers`,
+/// keeping the rest.
+pub(crate) fn remove_mcp_array_entry(
+    config_path: &Path,
+    result: &mut IntegrationResult,
+) -> Result<(), String> {
+    if !config_path.exists() {
+        return Ok(());
+    }
+    let mut config = read_json_or_default(config_path)?;
+    if let Some(servers) = config.get_mut("mcpServers").and_then(|s| s.as_array_mut()) {
+        let before = servers.len();
+        servers.retain(|s| !(is_aidd_array_entry(s) && is_aidd_authored("aidd-engine", s)));
+        if servers.len() != before {
+            write_json(config_path, &config)?;
+            result.files_modified.push(config_path.to_string_lossy().to_string());
+        } else if servers.iter().any(is_aidd_array_entry) {
+            record_user_entry_kept(config_path, result);
+        }
+    }
+    Ok(())
+}
+
+/// Array-shaped counterpart of `check_mcp_entry`. Returns (has_entry, is_dev_mode).
+/// Comments and trailing commas are tolerated; other JSON5 syntax is an error.
+pub(crate) fn check_mcp_array_entry(config_path: &Path) -> Result<(bool, bool), String> {
+    if !config_path.exists() {
+        return Ok((false, false));
+    }
+    let config = read_jsonc_or_default(config_path)?;
+    let entry = config
+        .get("mcpServers")
+        .and_then(|s| s.as_array())
+        .and_then(|servers| servers.iter().find(|s| is_aidd_array_entry(s)));
+    match entry {
+        Some(entry) => Ok((true, is_dev_mode_entry(entry))),
+        None => Ok((false, false)),
+    }
+}
+
+fn is_aidd_array_entry(entry: &serde_json::Value) -> bool {
+    entry.get("name").and_then(|n| n.as_str()) == Some("aidd-engine")
+}
+
 // ---------------------------------------------------------------------------
 // Content path resolution
 // ---------------------------------------------------------------------------
@@ -0,0 +1,296 @@
+use crate::domain::model::{AgentDefinition, AgentsDocument, RoutingRule};
+
+/// Field a labelled line or sub-heading feeds into.
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Purpose,
+    Skills,
+    Triggers,
+    Capabilities,
+}
+
+fn field_for(label: &str) -> Option<Field> {
+    match label.trim().to_lowercase().as_str() {
+        "purpose" | "description" | "role" | "capability" => Some(Field::Purpose),
+        "skills" | "skill" => Some(Field::Skills),
+        "activation" | "triggers" | "trigger" => Some(Field::Triggers),
+        "capabilities" | "responsibilities" => Some(Field::Capabilities),
+        _ => None,
+    }
+}
+
+/// Agent being collected, plus whether it carried any recognised field.
+/// Headings without one (e.g. numbered design principles) are not agents.
+struct Candidate {
+    agent: AgentDefinition,
+    structured: bool,
+}
+
+/// Parse AGENTS.md-style markdown into an [`AgentsDocument`].
+///
+/// Agents are `###` headings carrying `**Purpose:**` / `**Skills:**` /
+/// `**Activation:**` style fields, or a `## Orchestrator` section. Routing comes
+/// from a `## Routing` section (`trigger → agent` bullets or a two-column table).
+pub(crate) fn parse_agents_md(content: &str) -> AgentsDocument {
+    let mut doc = AgentsDocument::default();
+    let mut section = String::new();
+    let mut current: Option<Candidate> = None;
+    let mut list_field: Option<Field> = None;
+    let mut collected: Vec<Candidate> = Vec::new();
+    let mut table_header_seen = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if hashes > 0 && trimmed[hashes..].starts_with(' ') {
+            let heading = clean(&trimmed[hashes..]);
+            table_header_seen = false;
+            match hashes {
+                1 => {
+                    if doc.title.is_none() {
+                        doc.title = Some(heading);
+                    }
+                    continue;
+                }
+                2 => {
+                    collected.extend(current.take());
+                    list_field = None;
+                    section = heading.to_lowercase();
+                    if section == "orchestrator" {
+                        current = Some(Candidate { agent: named(&heading), structured: true });
+                    }
+                    continue;
+                }
+                _ => {
+                    if let Some(field) = field_for(&heading) {
+                        if current.is_some() {
+                            list_field = Some(field);
+                            continue;
+                        }
+                    }
+                    if hashes == 3 && !is_non_agent_section(&section) {
+                        collected.extend(current.take());
+                        list_field = None;
+                        current = Some(Candidate { agent: named(&heading), structured: false });
+                    }
+                    continue;
+                }
+            }
+        }
+
+        if section.contains("routing") {
+            table_header_seen = parse_routing_line(trimmed, table_header_seen, &mut doc.routing);
+            continue;
+        }
+
+        let Some(candidate) = current.as_mut() else { continue };
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .map(str::trim);
+
+        if let Some((field, value)) = labelled(item.unwrap_or(trimmed)) {
+            candidate.structured = true;
+            list_field = None;
+            apply(&mut candidate.agent, field, &value);
+        } else if let (Some(item), Some(field)) = (item, list_field) {
+            apply_item(&mut candidate.agent, field, &clean(item));
+        } else if !trimmed.is_empty() && item.is_none() && candidate.agent.purpose.is_none() {
+            candidate.agent.purpose = Some(clean(trimmed));
+        }
+    }
+    collected.extend(current.take());
+
+    for candidate in collected.into_iter().filter(|c| c.structured) {
+        let is_orchestrator = candidate.agent.name.to_lowercase().contains("orchestrator");
+        if is_orchestrator && doc.orchestrator.is_none() {
+            doc.orchestrator = Some(candidate.agent);
+        } else {
+            doc.agents.push(candidate.agent);
+        }
+    }
+
+    if doc.routing.is_empty() {
+        doc.routing = doc
+            .orchestrator
+            .iter()
+            .chain(doc.agents.iter())
+            .flat_map(|agent| {
+                agent.triggers.iter().map(|trigger| RoutingRule {
+                    trigger: trigger.clone(),
+                    agent: agent.name.clone(),
+                })
+            })
+            .collect();
+    }
+    doc
+}
+
+/// `##` sections whose `###` headings describe workflows or routing, not agents.
+fn is_non_agent_section(section: &str) -> bool {
+    section.contains("workflow") || section.contains("routing")
+}
+
+/// Split `Name — Role` (em/en dash or spaced hyphen) into name and role.
+fn named(heading: &str) -> AgentDefinition {
+    let (name, role) = ["—", "–", " - "]
+        .iter()
+        .find_map(|sep| heading.split_once(sep))
+        .map(|(name, role)| (name.trim().to_string(), Some(role.trim().to_string())))
+        .unwrap_or_else(|| (heading.to_string(), None));
+    AgentDefinition { name, role, ..AgentDefinition::default() }
+}
+
+/// `**Label:** value` or `**Label**: value`, for labels this parser knows.
+fn labelled(line: &str) -> Option<(Field, String)> {
+    let rest = line.strip_prefix("**")?;
+    let (label, value) = rest.split_once("**")?;
+    let (label, value) = match label.strip_suffix(':') {
+        Some(label) => (label, value),
+        None => (label, value.strip_prefix(':')?),
+    };
+    let field = field_for(label)?;
+    // Some documents run a heading onto the end of a field line; drop it.
+    let value = value.split("###").next().unwrap_or_default();
+    Some((field, clean(value)))
+}
+
+fn apply(agent: &mut AgentDefinition, field: Field, value: &str) {
+    match field {
+        Field::Purpose => agent.purpose = Some(value.to_string()),
+        Field::Skills => agent.skills = Some(value.trim_end_matches('/').to_string()),
+        Field::Triggers | Field::Capabilities => {
+            for part in value.split(',') {
+                apply_item(agent, field, part.trim());
+            }
+        }
+    }
+}
+
+fn apply_item(agent: &mut AgentDefinition, field: Field, item: &str) {
+    if item.is_empty() {
+        return;
+    }
+    match field {
+        Field::Triggers => agent.triggers.push(item.to_string()),
+        Field::Capabilities => agent.capabilities.push(item.to_string()),
+        Field::Purpose | Field::Skills => apply(agent, field, item),
+    }
+}
+
+/// Parse one line of a routing section. Returns whether a table header has been
+/// consumed, so the header row itself isn't read as a rule.
+fn parse_routing_line(line: &str, header_seen: bool, routing: &mut Vec<RoutingRule>) -> bool {
+    if let Some(row) = line.strip_prefix('|') {
+        let cells: Vec<&str> = row.trim_end_matches('|').split('|').map(str::trim).collect();
+        if cells.iter().all(|c| c.chars().all(|ch| matches!(ch, '-' | ':' | ' '))) {
+            return header_seen;
+        }
+        if !header_seen {
+            return true;
+        }
+        if let [trigger, agent, ..] = cells.as_slice() {
+            push_rule(routing, trigger, agent);
+        }
+        return true;
+    }
+
+    let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")).unwrap_or(line);
+    if let Some((trigger, agent)) = ["→", "->", "=>"].iter().find_map(|arrow| item.split_once(arrow)) {
+        push_rule(routing, trigger, agent);
+    }
+    false
+}
+
+fn push_rule(routing: &mut Vec<RoutingRule>, trigger: &str, agent: &str) {
+    let (trigger, agent) = (clean(trigger), clean(agent));
+    if !trigger.is_empty() && !agent.is_empty() {
+        routing.push(RoutingRule { trigger, agent });
+    }
+}
+
+/// Strip markdown emphasis and code ticks.
+fn clean(text: &str) -> String {
+    text.replace("**", "").replace('`', "").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGENTS_MD: &str = r#"# AIDD — AI-Driven Development
+
+## Agent System: Hierarchy and Roles
+
+### Master Orchestrator (The Polymath)
+
+**Purpose:** Entry point for all requests.
+**Activation:** All requests
+
+### **Knowledge Architect** — TKB Curator & Schema Enforcer
+
+- **Capability:** Maintains the Technology Knowledge Base
+- **Triggers:** Adding TKB entries, quarterly reviews
+
+### Quality Engineer
+
+**Purpose:** Test generation, coverage analysis
+**Skills:** `content/skills/quality-engineer/`
+**Activation:** `/test`, `/analyze`
+
+#### Capabilities
+- Edge-case discovery
+- Coverage reports
+
+## Workflow Orchestrators
+
+### Full-Stack Feature
+
+**Purpose:** Not an agent.
+
+## System Design Principles
+
+### 1. Single Source of Truth
+
+Plain prose, no fields.
+"#;
+
+    #[test]
+    fn parses_roster_and_derives_routing_from_triggers() {
+        let doc = parse_agents_md(AGENTS_MD);
+        assert_eq!(doc.title.as_deref(), Some("AIDD — AI-Driven Development"));
+
+        let orchestrator = doc.orchestrator.as_ref().unwrap();
+        assert_eq!(orchestrator.name, "Master Orchestrator (The Polymath)");
+        assert_eq!(orchestrator.purpose.as_deref(), Some("Entry point for all requests."));
+
+        let names: Vec<&str> = doc.agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Knowledge Architect", "Quality Engineer"]);
+        assert_eq!(doc.agents[0].role.as_deref(), Some("TKB Curator & Schema Enforcer"));
+        assert_eq!(doc.agents[1].skills.as_deref(), Some("content/skills/quality-engineer"));
+        assert_eq!(doc.agents[1].triggers, vec!["/test", "/analyze"]);
+        assert_eq!(doc.agents[1].capabilities, vec!["Edge-case discovery", "Coverage reports"]);
+
+        assert!(doc.routing.contains(&RoutingRule {
+            trigger: "/test".to_string(),
+            agent: "Quality Engineer".to_string(),
+        }));
+    }
+
+    #[test]
+    fn explicit_routing_and_template_orchestrator_are_read() {
+        let doc = parse_agents_md(
+            "## Orchestrator\n\nThe primary agent.\n\n### Capabilities\n- Task routing\n\n\
+             ## Routing\n\n| Trigger | Agent |\n|---|---|\n| `/design` | Interface Artisan |\n\n- /audit → System Architect\n",
+        );
+        let orchestrator = doc.orchestrator.unwrap();
+        assert_eq!(orchestrator.purpose.as_deref(), Some("The primary agent."));
+        assert_eq!(orchestrator.capabilities, vec!["Task routing"]);
+        assert!(doc.agents.is_empty());
+        let routes: Vec<(&str, &str)> =
+            doc.routing.iter().map(|r| (r.trigger.as_str(), r.agent.as_str())).collect();
+        assert_eq!(routes, vec![("/design", "Interface Artisan"), ("/audit", "System Architect")]);
+
+        assert_eq!(parse_agents_md("# AGENTS.md\n\nSee routing.md.\n").agents.len(), 0);
+    }
+}
@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use crate::domain::model::{
     IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
 };
 use super::adapter_trait::{
-    ToolAdapter, ensure_file, remove_file_if_exists,
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
     ensure_agents_files, has_agents_dir, agents_dir_path,
     upsert_mcp_entry, remove_mcp_entry, check_mcp_entry,
     project_instructions, project_name,
@@ -28,6 +29,11 @@ impl ClaudeAdapter {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_home_dir(home_dir: std::path::PathBuf) -> Self {
+        Self { home_dir }
+    }
+
     /// User/local scope MCP config: ~/.claude.json
     fn mcp_json_path(&self) -> std::path::PathBuf {
         self.home_dir.join(".claude.json")
@@ -39,7 +45,14 @@ impl ToolAdapter for ClaudeAdapter {
         IntegrationType::ClaudeCode
     }
 
-    fn integrate(&self, project_path: &Path, framework_path: &Path, dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
         let mut result = IntegrationResult {
             tool: IntegrationType::ClaudeCode,
             files_created: Vec::new(),
@@ -48,12 +61,13 @@ impl ToolAdapter for ClaudeAdapter {
         };
 
         // 1. Global MCP config
-        upsert_mcp_entry(&self.mcp_json_path(), project_path, dev_mode, &mut result)?;
+        upsert_mcp_entry(&self.mcp_json_path(), project_path, dev_mode, env, mcp_write, &mut result)?;
 
-        // 2. Project-scoped .mcp.json (team-shareable via git)
+        // 2. Project-scoped .mcp.json (team-shareable via git). It gets committed,
+        //    so `env` (API keys and the like) only goes to the user-scope config.
         let project_mcp = project_path.join(".mcp.json");
         if !project_mcp.exists() {
-            upsert_mcp_entry(&project_mcp, project_path, dev_mode, &mut result)?;
+            upsert_mcp_entry(&project_mcp, project_path, dev_mode, &BTreeMap::new(), mcp_write, &mut result)?;
         }
 
         // 3. Project CLAUDE.md
@@ -99,6 +113,13 @@ impl ToolAdapter for ClaudeAdapter {
         Ok(result)
     }
 
+    fn mcp_config_files(&self, project_path: &Path) -> Vec<McpConfigFile> {
+        vec![
+            McpConfigFile { path: self.mcp_json_path(), layout: McpLayout::Object("mcpServers"), shared: false },
+            McpConfigFile { path: project_path.join(".mcp.json"), layout: McpLayout::Object("mcpServers"), shared: true },
+        ]
+    }
+
     fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
         let mut config_files = Vec::new();
 
@@ -142,3 +163,45 @@ impl ToolAdapter for ClaudeAdapter {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::integrations::adapter_trait::write_installed_mcp_entry;
+
+    #[test]
+    fn env_is_kept_out_of_the_committed_project_config() {
+        let root = std::env::temp_dir().join(format!("aidd-claude-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let home = root.join("home");
+        let project = root.join("project");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+
+        let adapter = ClaudeAdapter::with_home_dir(home.clone());
+        let env = BTreeMap::from([("AIDD_API_KEY".to_string(), "secret".to_string())]);
+        adapter.integrate(&project, &root.join("no-framework"), false, &env).unwrap();
+
+        let read = |path: &Path| -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+        };
+        let global = read(&home.join(".claude.json"));
+        assert_eq!(global["mcpServers"]["aidd-engine"]["env"]["AIDD_API_KEY"], "secret");
+        let shared = read(&project.join(".mcp.json"));
+        assert!(shared["mcpServers"]["aidd-engine"].is_object());
+        assert!(shared["mcpServers"]["aidd-engine"].get("env").is_none());
+
+        // Restoring an exported entry keeps the shared file free of env as well
+        let files = adapter.mcp_config_files(&project);
+        let mut entry = global["mcpServers"]["aidd-engine"].clone();
+        entry["args"] = serde_json::json!(["@aidd.md/mcp-engine@next"]);
+        for file in &files {
+            write_installed_mcp_entry(file, &entry).unwrap();
+        }
+        assert_eq!(read(&home.join(".claude.json"))["mcpServers"]["aidd-engine"], entry);
+        let shared = read(&project.join(".mcp.json"));
+        assert_eq!(shared["mcpServers"]["aidd-engine"]["args"][0], "@aidd.md/mcp-engine@next");
+        assert!(shared["mcpServers"]["aidd-engine"].get("env").is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
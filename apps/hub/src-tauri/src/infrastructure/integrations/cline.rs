@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::domain::model::{
+    IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
+};
+use super::adapter_trait::{
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
+    ensure_agents_files, has_agents_dir, agents_dir_path,
+    upsert_mcp_entry, remove_mcp_entry, check_mcp_entry,
+    rules_pointer,
+};
+
+/// Cline (VS Code extension) integration adapter.
+///
+/// Files managed:
+/// - Global: `<config dir>/Code/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json` — MCP server config
+/// - Project: `.clinerules` — rules pointer to AIDD content
+/// - Project: agents routing.md (config-resolved path)
+/// - Project: `AGENTS.md` — thin redirect (cross-tool compat)
+pub struct ClineAdapter {
+    config_dir: PathBuf,
+}
+
+impl ClineAdapter {
+    pub fn new() -> Self {
+        Self {
+            config_dir: dirs::config_dir().expect("Cannot resolve config directory"),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_config_dir(config_dir: PathBuf) -> Self {
+        Self { config_dir }
+    }
+
+    fn mcp_config_path(&self) -> PathBuf {
+        cline_mcp_settings_path(&self.config_dir)
+    }
+}
+
+/// Cline keeps its MCP settings in the extension's VS Code global storage.
+pub(crate) fn cline_mcp_settings_path(config_dir: &Path) -> PathBuf {
+    config_dir
+        .join("Code")
+        .join("User")
+        .join("globalStorage")
+        .join("saoudrizwan.claude-dev")
+        .join("settings")
+        .join("cline_mcp_settings.json")
+}
+
+impl ToolAdapter for ClineAdapter {
+    fn tool_type(&self) -> IntegrationType {
+        IntegrationType::Cline
+    }
+
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
+        let mut result = IntegrationResult {
+            tool: IntegrationType::Cline,
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            messages: Vec::new(),
+        };
+
+        // 1. Global MCP config (same `mcpServers` object shape as Cursor)
+        upsert_mcp_entry(&self.mcp_config_path(), project_path, dev_mode, env, mcp_write, &mut result)?;
+
+        // 2. Project .clinerules (thin pointer to AIDD content)
+        let clinerules = project_path.join(".clinerules");
+        if let Some(path) = ensure_file(&clinerules, &rules_pointer())? {
+            result.files_created.push(path);
+        } else {
+            result.messages.push(".clinerules already exists — not overwritten".to_string());
+        }
+
+        // 3. Agents files (config-aware)
+        ensure_agents_files(project_path, framework_path, &mut result)?;
+
+        Ok(result)
+    }
+
+    fn remove(&self, project_path: &Path) -> Result<IntegrationResult, String> {
+        let mut result = IntegrationResult {
+            tool: IntegrationType::Cline,
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            messages: Vec::new(),
+        };
+
+        remove_mcp_entry(&self.mcp_config_path(), &mut result)?;
+
+        let clinerules = project_path.join(".clinerules");
+        if clinerules.is_file() && remove_file_if_exists(&clinerules)? {
+            result.messages.push(format!("Removed {}", clinerules.display()));
+        }
+
+        result.messages.push("AGENTS.md preserved (shared across integrations)".to_string());
+        Ok(result)
+    }
+
+    fn mcp_config_files(&self, _project_path: &Path) -> Vec<McpConfigFile> {
+        vec![McpConfigFile { path: self.mcp_config_path(), layout: McpLayout::Object("mcpServers"), shared: false }]
+    }
+
+    fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
+        let mut config_files = Vec::new();
+
+        let (has_mcp, dev_mode) = check_mcp_entry(&self.mcp_config_path())?;
+        if has_mcp {
+            config_files.push(self.mcp_config_path().to_string_lossy().to_string());
+        }
+
+        let has_agents = has_agents_dir(project_path);
+        if has_agents {
+            config_files.push(agents_dir_path(project_path).to_string_lossy().to_string());
+        }
+
+        let clinerules = project_path.join(".clinerules");
+        if clinerules.exists() {
+            config_files.push(clinerules.to_string_lossy().to_string());
+        }
+
+        let status = if has_mcp && has_agents {
+            IntegrationStatus::Configured
+        } else if has_mcp || has_agents {
+            IntegrationStatus::NeedsUpdate
+        } else {
+            IntegrationStatus::NotConfigured
+        };
+
+        Ok(IntegrationConfig {
+            integration_type: IntegrationType::Cline,
+            status,
+            config_files,
+            dev_mode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcp_settings_and_clinerules_are_written_and_removed() {
+        let root = std::env::temp_dir().join(format!("aidd-cline-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let config_dir = root.join("config");
+        let project = root.join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let settings = cline_mcp_settings_path(&config_dir);
+        std::fs::create_dir_all(settings.parent().unwrap()).unwrap();
+        std::fs::write(&settings, r#"{"mcpServers": {"github": {"command": "gh-mcp"}}}"#).unwrap();
+
+        let adapter = ClineAdapter::with_config_dir(config_dir.clone());
+        adapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+
+        let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&settings).unwrap()).unwrap();
+        assert!(config["mcpServers"]["aidd-engine"].is_object());
+        assert_eq!(config["mcpServers"]["github"]["command"], "gh-mcp");
+        assert!(project.join(".clinerules").is_file());
+        let status = adapter.check(&project).unwrap();
+        assert!(matches!(status.status, IntegrationStatus::Configured));
+        assert!(status.config_files.contains(&settings.to_string_lossy().to_string()));
+
+        adapter.remove(&project).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&settings).unwrap()).unwrap();
+        assert_eq!(config["mcpServers"], serde_json::json!({"github": {"command": "gh-mcp"}}));
+        assert!(!project.join(".clinerules").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn a_clinerules_directory_is_left_alone() {
+        let root = std::env::temp_dir().join(format!("aidd-cline-dir-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".clinerules")).unwrap();
+        std::fs::write(project.join(".clinerules").join("style.md"), "# Style").unwrap();
+
+        let adapter = ClineAdapter::with_config_dir(root.join("config"));
+        let result = adapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+        assert!(result.messages.iter().any(|m| m.starts_with(".clinerules already exists")));
+
+        adapter.remove(&project).unwrap();
+        assert!(project.join(".clinerules").join("style.md").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::domain::model::{
+    IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
+};
+use super::adapter_trait::{
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
+    ensure_agents_files, has_agents_dir, agents_dir_path,
+    upsert_mcp_array_entry, remove_mcp_array_entry, check_mcp_array_entry,
+    rules_pointer,
+};
+
+/// Continue.dev integration adapter.
+///
+/// Files managed:
+/// - Global: `~/.continue/config.json` — MCP server in the `mcpServers` array
+/// - Project: `.continue/rules/aidd.md` — rules pointer to AIDD content
+/// - Project: agents routing.md (config-resolved path)
+/// - Project: `AGENTS.md` — thin redirect (cross-tool compat)
+///
+/// A `config.json` written as JSON5 (comments, trailing commas) fails to parse
+/// and is reported as an error rather than rewritten.
+pub struct ContinueAdapter {
+    home_dir: PathBuf,
+}
+
+impl ContinueAdapter {
+    pub fn new() -> Self {
+        Self {
+            home_dir: dirs::home_dir().expect("Cannot resolve home directory"),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_home_dir(home_dir: PathBuf) -> Self {
+        Self { home_dir }
+    }
+
+    fn mcp_config_path(&self) -> PathBuf {
+        self.home_dir.join(".continue").join("config.json")
+    }
+}
+
+fn rules_path(project_path: &Path) -> PathBuf {
+    project_path.join(".continue").join("rules").join("aidd.md")
+}
+
+impl ToolAdapter for ContinueAdapter {
+    fn tool_type(&self) -> IntegrationType {
+        IntegrationType::Continue
+    }
+
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
+        let mut result = IntegrationResult {
+            tool: IntegrationType::Continue,
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            messages: Vec::new(),
+        };
+
+        // 1. Global MCP config (array of named servers)
+        upsert_mcp_array_entry(&self.mcp_config_path(), project_path, dev_mode, env, mcp_write, &mut result)?;
+
+        // 2. Project rule (thin pointer to AIDD content)
+        let rules = rules_path(project_path);
+        if let Some(path) = ensure_file(&rules, &rules_pointer())? {
+            result.files_created.push(path);
+        } else {
+            result.messages.push(".continue/rules/aidd.md already exists — not overwritten".to_string());
+        }
+
+        // 3. Agents files (config-aware)
+        ensure_agents_files(project_path, framework_path, &mut result)?;
+
+        Ok(result)
+    }
+
+    fn remove(&self, project_path: &Path) -> Result<IntegrationResult, String> {
+        let mut result = IntegrationResult {
+            tool: IntegrationType::Continue,
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+            messages: Vec::new(),
+        };
+
+        remove_mcp_array_entry(&self.mcp_config_path(), &mut result)?;
+
+        let rules = rules_path(project_path);
+        if remove_file_if_exists(&rules)? {
+            result.messages.push(format!("Removed {}", rules.display()));
+        }
+
+        result.messages.push("AGENTS.md preserved (shared across integrations)".to_string());
+        Ok(result)
+    }
+
+    fn mcp_config_files(&self, _project_path: &Path) -> Vec<McpConfigFile> {
+        vec![McpConfigFile { path: self.mcp_config_path(), layout: McpLayout::Array, shared: false }]
+    }
+
+    fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
+        let mut config_files = Vec::new();
+
+        // Continue also accepts JSON5 configs the Hub cannot parse. That must not
+        // fail the status of every tool, so such a file counts as needing an update.
+        let (has_mcp, dev_mode, unreadable) = match check_mcp_array_entry(&self.mcp_config_path()) {
+            Ok((has_mcp, dev_mode)) => (has_mcp, dev_mode, false),
+            Err(e) => {
+                eprintln!("[integrations] Continue MCP config is unreadable: {}", e);
+                (false, false, true)
+            }
+        };
+        if has_mcp || unreadable {
+            config_files.push(self.mcp_config_path().to_string_lossy().to_string());
+        }
+
+        let has_agents = has_agents_dir(project_path);
+        if has_agents {
+            config_files.push(agents_dir_path(project_path).to_string_lossy().to_string());
+        }
+
+        let rules = rules_path(project_path);
+        if rules.exists() {
+            config_files.push(rules.to_string_lossy().to_string());
+        }
+
+        let status = if has_mcp && has_agents {
+            IntegrationStatus::Configured
+        } else if has_mcp || has_agents || unreadable {
+            IntegrationStatus::NeedsUpdate
+        } else {
+            IntegrationStatus::NotConfigured
+        };
+
+        Ok(IntegrationConfig {
+            integration_type: IntegrationType::Continue,
+            status,
+            config_files,
+            dev_mode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcp_array_entry_is_upserted_and_removed_without_touching_others() {
+        let root = std::env::temp_dir().join(format!("aidd-continue-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let home = root.join("home");
+        let project = root.join("project");
+        std::fs::create_dir_all(home.join(".continue")).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+        let config_path = home.join(".continue").join("config.json");
+        std::fs::write(
+            &config_path,
+            r#"{"models": [{"title": "local"}], "mcpServers": [{"name": "sqlite", "command": "uvx", "args": ["mcp-server-sqlite"]}]}"#,
+        )
+        .unwrap();
+
+        let adapter = ContinueAdapter::with_home_dir(home.clone());
+        adapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+        // Second run replaces the entry in place instead of appending a duplicate.
+        let result = adapter.integrate(&project, &root.join("no-framework"), true, &BTreeMap::new()).unwrap();
+        assert_eq!(result.files_modified, vec![config_path.to_string_lossy().to_string()]);
+
+        let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let names: Vec<&str> = config["mcpServers"].as_array().unwrap().iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["sqlite", "aidd-engine"]);
+        let status = adapter.check(&project).unwrap();
+        assert!(matches!(status.status, IntegrationStatus::Configured));
+        assert!(status.dev_mode);
+
+        adapter.remove(&project).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config["mcpServers"], serde_json::json!([{"name": "sqlite", "command": "uvx", "args": ["mcp-server-sqlite"]}]));
+        assert_eq!(config["models"][0]["title"], "local");
+        assert!(!rules_path(&project).exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unparseable_json5_config_reports_needs_update_instead_of_failing() {
+        let root = std::env::temp_dir().join(format!("aidd-continue-json5-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let home = root.join("home");
+        let project = root.join("project");
+        std::fs::create_dir_all(home.join(".continue")).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+        let config_path = home.join(".continue").join("config.json");
+        let adapter = ContinueAdapter::with_home_dir(home.clone());
+
+        // Comments and trailing commas are fine
+        std::fs::write(
+            &config_path,
+            "// user config\n{\"mcpServers\": [{\"name\": \"aidd-engine\", \"command\": \"npx\",},],}",
+        )
+        .unwrap();
+        assert!(adapter.check(&project).unwrap().config_files.contains(&config_path.to_string_lossy().to_string()));
+
+        // Unquoted keys are JSON5 only
+        std::fs::write(&config_path, "{ mcpServers: [] }").unwrap();
+        let status = adapter.check(&project).unwrap();
+        assert!(matches!(status.status, IntegrationStatus::NeedsUpdate));
+        assert_eq!(status.config_files, vec![config_path.to_string_lossy().to_string()]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
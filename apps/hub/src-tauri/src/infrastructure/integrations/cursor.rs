@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use crate::domain::model::{
     IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
 };
 use super::adapter_trait::{
-    ToolAdapter, ensure_file, remove_file_if_exists,
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
     ensure_agents_files, has_agents_dir, agents_dir_path,
     upsert_mcp_entry, remove_mcp_entry, check_mcp_entry,
     rules_pointer,
@@ -23,7 +24,14 @@ impl ToolAdapter for CursorAdapter {
         IntegrationType::Cursor
     }
 
-    fn integrate(&self, project_path: &Path, framework_path: &Path, dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
         let mut result = IntegrationResult {
             tool: IntegrationType::Cursor,
             files_created: Vec::new(),
@@ -33,7 +41,7 @@ impl ToolAdapter for CursorAdapter {
 
         // 1. Project MCP config
         let mcp_path = project_path.join(".cursor").join("mcp.json");
-        upsert_mcp_entry(&mcp_path, project_path, dev_mode, &mut result)?;
+        upsert_mcp_entry(&mcp_path, project_path, dev_mode, env, mcp_write, &mut result)?;
 
         // 2. Cursor rules (.mdc format with YAML frontmatter)
         let rules_path = project_path.join(".cursor").join("rules").join("aidd.mdc");
@@ -73,6 +81,11 @@ impl ToolAdapter for CursorAdapter {
         Ok(result)
     }
 
+    fn mcp_config_files(&self, project_path: &Path) -> Vec<McpConfigFile> {
+        let path = project_path.join(".cursor").join("mcp.json");
+        vec![McpConfigFile { path, layout: McpLayout::Object("mcpServers"), shared: false }]
+    }
+
     fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
         let mut config_files = Vec::new();
 
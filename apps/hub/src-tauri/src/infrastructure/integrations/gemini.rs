@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use crate::domain::model::{
     IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
 };
 use super::adapter_trait::{
-    ToolAdapter, ensure_file,
+    ToolAdapter, McpWrite, ensure_file,
     ensure_agents_files, has_agents_dir, agents_dir_path,
 };
 
@@ -22,7 +23,14 @@ impl ToolAdapter for GeminiAdapter {
         IntegrationType::Gemini
     }
 
-    fn integrate(&self, project_path: &Path, framework_path: &Path, _dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        _dev_mode: bool,
+        _env: &BTreeMap<String, String>,
+        _mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
         let mut result = IntegrationResult {
             tool: IntegrationType::Gemini,
             files_created: Vec::new(),
@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
-use crate::domain::model::{DiscoveredMcp, McpConfigScope, McpToolSource};
-use super::adapter_trait::read_json_or_default;
+use crate::domain::model::{DiscoveredMcp, McpConfigScope, McpToolSource, McpValidation};
+use crate::infrastructure::process::command_path::resolve_command;
+use super::adapter_trait::{is_aidd_authored, read_json_or_default, read_jsonc_or_default};
+use super::cline::cline_mcp_settings_path;
 
 /// Scans MCP config files from all supported AI tools across global and project scopes.
 pub struct McpConfigScanner {
@@ -132,6 +134,36 @@ impl McpConfigScanner {
             &mut discovered,
         );
 
+        // ── Cline ────────────────────────────────────────────────────
+
+        // Global: <config dir>/Code/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json
+        if let Some(ref config_dir) = self.config_dir {
+            self.scan_standard_config(
+                &cline_mcp_settings_path(config_dir),
+                McpToolSource::Cline,
+                McpConfigScope::Global,
+                &mut discovered,
+            );
+        }
+
+        // ── Continue ─────────────────────────────────────────────────
+
+        // Global: ~/.continue/config.json (`mcpServers` is an array)
+        self.scan_array_config(
+            &self.home_dir.join(".continue").join("config.json"),
+            McpToolSource::Continue,
+            McpConfigScope::Global,
+            &mut discovered,
+        );
+
+        annotate_duplicates(&mut discovered);
+        for entry in &mut discovered {
+            let cwd = match entry.scope {
+                McpConfigScope::Project => project_path.map(Path::new),
+                McpConfigScope::Global => None,
+            };
+            entry.validation = validate_entry(entry, cwd);
+        }
         Ok(discovered)
     }
 
@@ -236,7 +268,40 @@ impl McpConfigScanner {
         }
     }
 
+    /// Scan a config whose `mcpServers` is an array of named entries:
+    /// `{ "mcpServers": [{ "name": "...", "command": "..." }] }`. Used by Continue,
+    /// whose JSON5 configs (comments) fail to parse and are skipped.
+    fn scan_array_config(
+        &self,
+        path: &Path,
+        tool: McpToolSource,
+        scope: McpConfigScope,
+        out: &mut Vec<DiscoveredMcp>,
+    ) {
+        if !path.exists() {
+            return;
+        }
+        let config = match read_json_or_default(path) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        let servers = match config.get("mcpServers").and_then(|v| v.as_array()) {
+            Some(s) => s,
+            None => return,
+        };
+
+        let config_path = path.to_string_lossy().to_string();
+
+        for entry in servers {
+            if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                push_discovered(name, entry, &tool, &scope, &config_path, out);
+            }
+        }
+    }
+
     /// Scan VS Code settings which use `{ "mcp": { "servers": { ... } } }` format.
+    /// VS Code reads these as JSONC, so comments and trailing commas are allowed.
     fn scan_vscode_config(
         &self,
         path: &Path,
@@ -246,7 +311,7 @@ impl McpConfigScanner {
         if !path.exists() {
             return;
         }
-        let config = match read_json_or_default(path) {
+        let config = match read_jsonc_or_default(path) {
             Ok(v) => v,
             Err(_) => return,
         };
@@ -312,6 +377,7 @@ fn push_discovered(
         });
 
     let is_aidd = detect_aidd(&command, &args);
+    let installed_by_aidd = is_aidd_authored(name, entry);
 
     out.push(DiscoveredMcp {
         name: name.to_string(),
@@ -323,9 +389,63 @@ fn push_discovered(
         url,
         transport_type,
         is_aidd,
+        installed_by_aidd,
+        also_in: Vec::new(),
+        validation: McpValidation::Ok,
     });
 }
 
+/// What makes two entries the same logical server: the url for remote servers,
+/// otherwise the command line. Entries with neither cannot be matched.
+fn launch_key(entry: &DiscoveredMcp) -> Option<String> {
+    if let Some(url) = &entry.url {
+        return Some(format!("url:{}", url.trim_end_matches('/')));
+    }
+    let command = entry.command.as_deref()?;
+    let args = entry.args.as_deref().unwrap_or_default().join(" ");
+    Some(format!("cmd:{} {}", command.trim(), args))
+}
+
+/// Fill `also_in` for entries that share a launch key with entries from other
+/// config files. Entries stay one per file; only the annotation is added.
+/// Repeats within one file are not counted.
+fn annotate_duplicates(discovered: &mut [DiscoveredMcp]) {
+    let keys: Vec<Option<String>> = discovered.iter().map(launch_key).collect();
+    for i in 0..discovered.len() {
+        let Some(key) = &keys[i] else { continue };
+        let config_path = &discovered[i].config_path;
+        let mut also_in: Vec<McpToolSource> = Vec::new();
+        for (j, other) in discovered.iter().enumerate() {
+            let elsewhere = other.config_path != *config_path;
+            if elsewhere && keys[j].as_ref() == Some(key) && !also_in.contains(&other.tool) {
+                also_in.push(other.tool.clone());
+            }
+        }
+        discovered[i].also_in = also_in;
+    }
+}
+
+/// Check that an entry could be launched: stdio entries need a command that
+/// resolves (on `PATH`, including `.cmd` shims like `npx` on Windows, or
+/// relative to `cwd`), http/sse entries an http(s) URL. Never fails the scan.
+fn validate_entry(entry: &DiscoveredMcp, cwd: Option<&Path>) -> McpValidation {
+    let remote = matches!(entry.transport_type.as_deref(), Some("http" | "sse" | "streamable-http"));
+    if remote || (entry.command.is_none() && entry.url.is_some()) {
+        let valid = entry
+            .url
+            .as_deref()
+            .and_then(|url| reqwest::Url::parse(url).ok())
+            .is_some_and(|url| matches!(url.scheme(), "http" | "https"));
+        return if valid { McpValidation::Ok } else { McpValidation::InvalidUrl };
+    }
+
+    match entry.command.as_deref().map(str::trim) {
+        None | Some("") => McpValidation::MissingCommand,
+        Some(command) if resolve_command(command, cwd).is_none() => McpValidation::CommandNotFound,
+        Some(_) => McpValidation::Ok,
+    }
+}
+
 /// Check if a discovered MCP entry belongs to the aidd.md ecosystem.
 fn detect_aidd(command: &Option<String>, args: &Option<Vec<String>>) -> bool {
     if let Some(ref args) = args {
@@ -383,4 +503,160 @@ mod tests {
         let val = serde_json::json!({ "name": "foo", "version": "1.0" });
         assert!(!is_server_definition(&val));
     }
+
+    #[test]
+    fn scan_finds_continue_array_and_skips_json5() {
+        let home = std::env::temp_dir().join(format!("aidd-scan-continue-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(home.join(".continue")).unwrap();
+        let config = home.join(".continue").join("config.json");
+        std::fs::write(
+            &config,
+            r#"{"mcpServers": [{"name": "aidd-engine", "command": "npx", "args": ["-y", "@aidd.md/mcp-engine"]}, {"command": "unnamed"}]}"#,
+        )
+        .unwrap();
+        let scanner = McpConfigScanner { home_dir: home.clone(), config_dir: None };
+
+        let found = scanner.scan(None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "aidd-engine");
+        assert!(matches!(found[0].tool, McpToolSource::Continue));
+        assert!(found[0].is_aidd);
+
+        std::fs::write(&config, "{\n  // JSON5 comment\n  \"mcpServers\": [],\n}").unwrap();
+        assert!(scanner.scan(None).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn installed_by_aidd_only_matches_hub_written_entries() {
+        let home = std::env::temp_dir().join(format!("aidd-scan-authored-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(home.join(".cursor")).unwrap();
+        std::fs::write(
+            home.join(".cursor").join("mcp.json"),
+            r#"{"mcpServers": {
+                "aidd-engine": {"command": "npx", "args": ["-y", "@aidd.md/mcp-engine"]},
+                "aidd": {"command": "node", "args": ["/work/aidd/mcps/mcp-aidd-engine/dist/index.js"]},
+                "my-aidd": {"command": "npx", "args": ["-y", "@aidd.md/mcp-engine"]},
+                "aidd-pinned": {"command": "npx", "args": ["-y", "@aidd.md/mcp-engine@next"]}
+            }}"#,
+        )
+        .unwrap();
+        let scanner = McpConfigScanner { home_dir: home.clone(), config_dir: None };
+
+        let found = scanner.scan(None).unwrap();
+        let entry = |name: &str| found.iter().find(|d| d.name == name).unwrap();
+        assert!(found.iter().all(|d| d.is_aidd));
+        assert!(entry("aidd-engine").installed_by_aidd);
+        assert!(entry("aidd").installed_by_aidd);
+        assert!(!entry("my-aidd").installed_by_aidd);
+        assert!(!entry("aidd-pinned").installed_by_aidd);
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn same_server_in_two_tools_is_grouped() {
+        let home = std::env::temp_dir().join(format!("aidd-scan-dupes-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(home.join(".cursor")).unwrap();
+        std::fs::create_dir_all(home.join(".gemini")).unwrap();
+        let engine = r#"{"command": "npx", "args": ["-y", "@aidd.md/mcp-engine"]}"#;
+        let fs = r#"{"command": "npx", "args": ["-y", "server-filesystem"]}"#;
+        std::fs::write(
+            home.join(".cursor").join("mcp.json"),
+            format!(r#"{{"mcpServers": {{"aidd": {}, "fs": {}, "fs-copy": {}}}}}"#, engine, fs, fs),
+        )
+        .unwrap();
+        std::fs::write(
+            home.join(".gemini").join("settings.json"),
+            format!(r#"{{"mcpServers": {{"aidd-engine": {}}}}}"#, engine),
+        )
+        .unwrap();
+        let scanner = McpConfigScanner { home_dir: home.clone(), config_dir: None };
+
+        let found = scanner.scan(None).unwrap();
+        // Raw per-file entries are kept
+        assert_eq!(found.len(), 4);
+        let by_name = |name: &str| found.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(by_name("aidd").also_in, vec![McpToolSource::Gemini]);
+        assert_eq!(by_name("aidd-engine").also_in, vec![McpToolSource::Cursor]);
+        // A repeat within the same file is not "also in" another tool
+        assert!(by_name("fs").also_in.is_empty());
+        assert!(by_name("fs-copy").also_in.is_empty());
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn broken_definitions_are_flagged_without_failing_the_scan() {
+        let home = std::env::temp_dir().join(format!("aidd-scan-validate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(home.join(".cursor")).unwrap();
+        std::fs::write(
+            home.join(".cursor").join("mcp.json"),
+            r#"{"mcpServers": {
+                "ok": {"command": "sh", "args": ["-c", "true"]},
+                "gone": {"command": "aidd-no-such-binary-xyz"},
+                "bare": {"type": "stdio", "args": ["server.js"]},
+                "remote": {"type": "http", "url": "https://example.com/mcp"},
+                "typo": {"type": "sse", "url": "example.com/mcp"}
+            }}"#,
+        )
+        .unwrap();
+        let scanner = McpConfigScanner { home_dir: home.clone(), config_dir: None };
+
+        let found = scanner.scan(None).unwrap();
+        let status = |name: &str| found.iter().find(|d| d.name == name).unwrap().validation.clone();
+        assert_eq!(found.len(), 5);
+        assert_eq!(status("ok"), McpValidation::Ok);
+        assert_eq!(status("gone"), McpValidation::CommandNotFound);
+        assert_eq!(status("bare"), McpValidation::MissingCommand);
+        assert_eq!(status("remote"), McpValidation::Ok);
+        assert_eq!(status("typo"), McpValidation::InvalidUrl);
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn scan_reads_commented_vscode_settings() {
+        let home = std::env::temp_dir().join(format!("aidd-scan-jsonc-{}", std::process::id()));
+        let project = home.join("project");
+        let _ = std::fs::remove_dir_all(&home);
+        std::fs::create_dir_all(project.join(".vscode")).unwrap();
+        std::fs::write(
+            project.join(".vscode").join("settings.json"),
+            r#"{
+                // Editor preferences
+                "editor.tabSize": 2,
+                /* MCP servers */
+                "mcp": {
+                    "servers": {
+                        "aidd-engine": {
+                            "type": "stdio",
+                            "command": "npx",
+                            "args": ["-y", "@aidd.md/mcp-engine", "--url=http://localhost/*x*/"], // trailing
+                        },
+                    },
+                },
+            }"#,
+        )
+        .unwrap();
+        let scanner = McpConfigScanner { home_dir: home.clone(), config_dir: None };
+
+        let found = scanner.scan(Some(&project.to_string_lossy())).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "aidd-engine");
+        assert!(matches!(found[0].tool, McpToolSource::Vscode));
+        assert!(found[0].is_aidd);
+        assert_eq!(
+            found[0].args.as_deref().unwrap()[2],
+            "--url=http://localhost/*x*/",
+        );
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
 }
@@ -4,11 +4,16 @@ pub(crate) mod cursor;
 pub(crate) mod vscode;
 pub(crate) mod gemini;
 pub(crate) mod windsurf;
+pub(crate) mod cline;
+pub(crate) mod continue_dev;
 pub(crate) mod mcp_config_scanner;
+pub(crate) mod agents_md;
 
 pub use claude::ClaudeAdapter;
 pub use cursor::CursorAdapter;
 pub use vscode::VscodeAdapter;
 pub use gemini::GeminiAdapter;
 pub use windsurf::WindsurfAdapter;
+pub use cline::ClineAdapter;
+pub use continue_dev::ContinueAdapter;
 pub use mcp_config_scanner::McpConfigScanner;
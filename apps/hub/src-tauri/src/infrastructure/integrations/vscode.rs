@@ -1,11 +1,13 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use crate::domain::model::{
     IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
 };
 use super::adapter_trait::{
-    ToolAdapter, ensure_file, remove_file_if_exists,
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
     ensure_agents_files, has_agents_dir, agents_dir_path,
-    read_json_or_default, write_json, mcp_server_entry,
+    read_jsonc_or_default, write_json, splice_jsonc_value, remove_jsonc_member, mcp_server_entry, carry_over_env,
+    record_entry_current, record_entry_kept, is_aidd_authored, record_user_entry_kept,
     project_instructions, project_name,
 };
 
@@ -23,7 +25,14 @@ impl ToolAdapter for VscodeAdapter {
         IntegrationType::Vscode
     }
 
-    fn integrate(&self, project_path: &Path, framework_path: &Path, dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
         let mut result = IntegrationResult {
             tool: IntegrationType::Vscode,
             files_created: Vec::new(),
@@ -33,7 +42,7 @@ impl ToolAdapter for VscodeAdapter {
 
         // 1. Auto-generate .vscode/mcp.json (VS Code native MCP config)
         let vscode_mcp = project_path.join(".vscode").join("mcp.json");
-        upsert_vscode_mcp(&vscode_mcp, project_path, dev_mode, &mut result)?;
+        upsert_vscode_mcp(&vscode_mcp, project_path, dev_mode, env, mcp_write, &mut result)?;
 
         // 2. Copilot instructions
         let copilot_md = project_path.join(".github").join("copilot-instructions.md");
@@ -76,6 +85,11 @@ impl ToolAdapter for VscodeAdapter {
         Ok(result)
     }
 
+    fn mcp_config_files(&self, project_path: &Path) -> Vec<McpConfigFile> {
+        let path = project_path.join(".vscode").join("mcp.json");
+        vec![McpConfigFile { path, layout: McpLayout::Object("servers"), shared: false }]
+    }
+
     fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
         let mut config_files = Vec::new();
 
@@ -120,9 +134,11 @@ fn upsert_vscode_mcp(
     mcp_path: &std::path::Path,
     project_path: &Path,
     dev_mode: bool,
+    env: &BTreeMap<String, String>,
+    mcp_write: McpWrite,
     result: &mut IntegrationResult,
 ) -> Result<(), String> {
-    let mut config = read_json_or_default(mcp_path)?;
+    let mut config = read_jsonc_or_default(mcp_path)?;
     let existed = mcp_path.exists();
 
     let servers = config
@@ -130,17 +146,39 @@ fn upsert_vscode_mcp(
         .ok_or("mcp.json is not a JSON object")?
         .entry("servers")
         .or_insert_with(|| serde_json::json!({}));
+    if mcp_write == McpWrite::InsertMissing && servers.get("aidd-engine").is_some() {
+        record_entry_kept(mcp_path, result);
+        return Ok(());
+    }
 
-    let mut entry = mcp_server_entry(project_path, dev_mode);
+    let mut entry = mcp_server_entry(project_path, dev_mode, env);
     // VS Code requires an explicit "type" field
     entry.as_object_mut().unwrap().insert("type".to_string(), serde_json::json!("stdio"));
+    carry_over_env(&mut entry, servers.get("aidd-engine"));
 
-    servers
-        .as_object_mut()
-        .ok_or("servers is not a JSON object")?
-        .insert("aidd-engine".to_string(), entry);
-
-    write_json(mcp_path, &config)?;
+    if servers.get("aidd-engine") == Some(&entry) {
+        record_entry_current(mcp_path, result);
+        return Ok(());
+    }
+    // Edit only the aidd-engine subtree so the user's comments and formatting survive
+    let spliced = if existed {
+        std::fs::read_to_string(mcp_path)
+            .ok()
+            .and_then(|raw| splice_jsonc_value(&raw, &["servers", "aidd-engine"], &entry))
+    } else {
+        None
+    };
+    match spliced {
+        Some(content) => std::fs::write(mcp_path, content)
+            .map_err(|e| format!("Failed to write {}: {}", mcp_path.display(), e))?,
+        None => {
+            servers
+                .as_object_mut()
+                .ok_or("servers is not a JSON object")?
+                .insert("aidd-engine".to_string(), entry);
+            write_json(mcp_path, &config)?;
+        }
+    }
     if existed {
         result.files_modified.push(mcp_path.to_string_lossy().to_string());
     } else {
@@ -156,11 +194,26 @@ fn remove_vscode_mcp(
     if !mcp_path.exists() {
         return Ok(());
     }
-    let mut config = read_json_or_default(mcp_path)?;
+    let mut config = read_jsonc_or_default(mcp_path)?;
     if let Some(servers) = config.get_mut("servers").and_then(|s| s.as_object_mut()) {
-        if servers.remove("aidd-engine").is_some() {
-            write_json(mcp_path, &config)?;
-            result.files_modified.push(mcp_path.to_string_lossy().to_string());
+        match servers.get("aidd-engine").map(|entry| is_aidd_authored("aidd-engine", entry)) {
+            Some(true) => {
+                // Cut just the aidd-engine member so the rest of the file stays as the user wrote it
+                let spliced = std::fs::read_to_string(mcp_path)
+                    .ok()
+                    .and_then(|raw| remove_jsonc_member(&raw, &["servers", "aidd-engine"]));
+                match spliced {
+                    Some(content) => std::fs::write(mcp_path, content)
+                        .map_err(|e| format!("Failed to write {}: {}", mcp_path.display(), e))?,
+                    None => {
+                        servers.remove("aidd-engine");
+                        write_json(mcp_path, &config)?;
+                    }
+                }
+                result.files_modified.push(mcp_path.to_string_lossy().to_string());
+            }
+            Some(false) => record_user_entry_kept(mcp_path, result),
+            None => {}
         }
     }
     Ok(())
@@ -170,8 +223,95 @@ fn check_vscode_mcp(mcp_path: &std::path::Path) -> bool {
     if !mcp_path.exists() {
         return false;
     }
-    read_json_or_default(mcp_path)
+    read_jsonc_or_default(mcp_path)
         .ok()
         .and_then(|c| c.get("servers")?.get("aidd-engine").cloned())
         .is_some()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrate_keeps_comments_in_existing_mcp_json() {
+        let root = std::env::temp_dir().join(format!("aidd-vscode-jsonc-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".vscode")).unwrap();
+        let mcp_path = project.join(".vscode").join("mcp.json");
+        std::fs::write(
+            &mcp_path,
+            "{\n    // Shared team servers\n    \"servers\": {\n        \"fs\": { \"type\": \"stdio\", \"command\": \"npx\" }, /* keep */\n    },\n}\n",
+        )
+        .unwrap();
+
+        let adapter = VscodeAdapter;
+        adapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+        let raw = std::fs::read_to_string(&mcp_path).unwrap();
+        assert!(raw.contains("// Shared team servers"));
+        assert!(raw.contains("/* keep */"));
+        assert!(raw.contains("\"fs\": { \"type\": \"stdio\", \"command\": \"npx\" }"));
+
+        // Second run updates the entry in place rather than adding another
+        adapter.integrate(&project, &root.join("no-framework"), true, &BTreeMap::new()).unwrap();
+        let raw = std::fs::read_to_string(&mcp_path).unwrap();
+        assert!(raw.contains("// Shared team servers"));
+        assert_eq!(raw.matches("aidd-engine").count(), 1);
+        let config = read_jsonc_or_default(&mcp_path).unwrap();
+        assert_eq!(config["servers"]["aidd-engine"]["command"], "node");
+        assert_eq!(config["servers"]["fs"]["command"], "npx");
+        assert!(check_vscode_mcp(&mcp_path));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn integrate_then_remove_restores_mcp_json_byte_for_byte() {
+        let root = std::env::temp_dir().join(format!("aidd-vscode-roundtrip-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".vscode")).unwrap();
+        let mcp_path = project.join(".vscode").join("mcp.json");
+        let originals = [
+            "{\n    // Shared team servers\n    \"servers\": {\n        \"fs\": { \"type\": \"stdio\", \"command\": \"npx\" }, /* keep */\n    },\n}\n",
+            "{\n  \"servers\": {}\n}\n",
+        ];
+
+        for original in originals {
+            std::fs::write(&mcp_path, original).unwrap();
+            VscodeAdapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+            assert!(check_vscode_mcp(&mcp_path));
+            VscodeAdapter.remove(&project).unwrap();
+            assert_eq!(std::fs::read_to_string(&mcp_path).unwrap(), original);
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn remove_keeps_a_user_written_aidd_engine_entry() {
+        let root = std::env::temp_dir().join(format!("aidd-vscode-user-entry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join(".vscode")).unwrap();
+        let mcp_path = project.join(".vscode").join("mcp.json");
+        // Same package, but pinned to a tag — the user's own entry, not ours
+        std::fs::write(
+            &mcp_path,
+            r#"{"servers": {"aidd-engine": {"type": "stdio", "command": "npx", "args": ["-y", "@aidd.md/mcp-engine@next"]}}}"#,
+        )
+        .unwrap();
+
+        let result = VscodeAdapter.remove(&project).unwrap();
+        assert!(result.files_modified.is_empty());
+        assert!(result.messages.iter().any(|m| m.starts_with("Kept user-defined aidd-engine entry")));
+        assert!(check_vscode_mcp(&mcp_path));
+
+        VscodeAdapter.integrate(&project, &root.join("no-framework"), false, &BTreeMap::new()).unwrap();
+        VscodeAdapter.remove(&project).unwrap();
+        assert!(!check_vscode_mcp(&mcp_path));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
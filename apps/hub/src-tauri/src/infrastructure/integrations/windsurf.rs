@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use crate::domain::model::{
     IntegrationConfig, IntegrationResult, IntegrationStatus, IntegrationType,
 };
 use super::adapter_trait::{
-    ToolAdapter, ensure_file, remove_file_if_exists,
+    ToolAdapter, McpWrite, McpConfigFile, McpLayout, ensure_file, remove_file_if_exists,
     ensure_agents_files, has_agents_dir, agents_dir_path,
     upsert_mcp_entry, remove_mcp_entry, check_mcp_entry,
     rules_pointer,
@@ -37,7 +38,14 @@ impl ToolAdapter for WindsurfAdapter {
         IntegrationType::Windsurf
     }
 
-    fn integrate(&self, project_path: &Path, framework_path: &Path, dev_mode: bool) -> Result<IntegrationResult, String> {
+    fn apply(
+        &self,
+        project_path: &Path,
+        framework_path: &Path,
+        dev_mode: bool,
+        env: &BTreeMap<String, String>,
+        mcp_write: McpWrite,
+    ) -> Result<IntegrationResult, String> {
         let mut result = IntegrationResult {
             tool: IntegrationType::Windsurf,
             files_created: Vec::new(),
@@ -46,7 +54,7 @@ impl ToolAdapter for WindsurfAdapter {
         };
 
         // 1. Global MCP config
-        upsert_mcp_entry(&self.mcp_config_path(), project_path, dev_mode, &mut result)?;
+        upsert_mcp_entry(&self.mcp_config_path(), project_path, dev_mode, env, mcp_write, &mut result)?;
 
         // 2. Project .windsurfrules (thin pointer to AIDD content)
         let windsurfrules = project_path.join(".windsurfrules");
@@ -82,6 +90,10 @@ impl ToolAdapter for WindsurfAdapter {
         Ok(result)
     }
 
+    fn mcp_config_files(&self, _project_path: &Path) -> Vec<McpConfigFile> {
+        vec![McpConfigFile { path: self.mcp_config_path(), layout: McpLayout::Object("mcpServers"), shared: false }]
+    }
+
     fn check(&self, project_path: &Path) -> Result<IntegrationConfig, String> {
         let mut config_files = Vec::new();
 
@@ -1,8 +1,13 @@
+use crate::domain::AiddError;
+use crate::infrastructure::process::stderr_log::{spawn_stderr_reader, StderrLog, STDERR_LOG_CAPACITY};
 use serde_json::{json, Value};
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Mutex;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 /// MCP protocol version supported by this client.
 const MCP_PROTOCOL_VERSION: &str = "2025-11-05";
@@ -11,23 +16,44 @@ const MCP_PROTOCOL_VERSION: &str = "2025-11-05";
 const CLIENT_NAME: &str = "aidd-hub";
 const CLIENT_VERSION: &str = "1.0.0";
 
+/// How long a single request waits for its response unless overridden.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+type Outcome = Result<Value, String>;
+
+/// Requests awaiting a response, keyed by JSON-RPC id.
+#[derive(Default)]
+struct PendingRequests {
+    slots: HashMap<u64, Sender<Outcome>>,
+    /// Set once the reader thread stops; later requests fail with this reason.
+    closed: Option<String>,
+}
+
 /// JSON-RPC 2.0 client for MCP servers over stdio.
 ///
-/// Spawns a dedicated engine process and communicates via stdin/stdout.
-/// Thread-safe: all I/O is Mutex-protected.
+/// Spawns a dedicated engine process (or attaches to a running one's pipes)
+/// and communicates via stdin/stdout.
+/// Thread-safe: writes are Mutex-protected and a reader thread routes each
+/// response (including batch arrays) to the caller waiting on its id, so
+/// requests may be issued concurrently. A request gives up after
+/// `request_timeout`; on expiry an owned child is killed.
 pub struct McpClient {
-    _child: Mutex<Child>,
+    /// `None` when the process is owned elsewhere (see `from_pipes`).
+    _child: Mutex<Option<Child>>,
     stdin: Mutex<BufWriter<ChildStdin>>,
-    stdout: Mutex<BufReader<ChildStdout>>,
+    pending: Arc<Mutex<PendingRequests>>,
     next_id: AtomicU64,
     initialized: std::sync::atomic::AtomicBool,
+    request_timeout: Duration,
+    /// Wall time of the last completed `initialize` handshake.
+    init_duration: Mutex<Option<Duration>>,
 }
 
 impl McpClient {
   /// Spawn an MCP server process and create a client connected to it.
   ///
   /// The process is spawned with piped stdin/stdout for JSON-RPC communication.
-  /// Stderr is inherited (logs to parent process stderr).
+  /// Stderr is captured; its tail is appended to the error when the server exits.
   pub fn spawn(command: &str, args: &[&str]) -> Result<Self, String> {
         Self::spawn_with_context(command, args, None, None)
     }
@@ -46,7 +72,7 @@ impl McpClient {
         cmd.args(args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::inherit());
+            .stderr(Stdio::piped());
 
         if let Some(dir) = cwd {
             cmd.current_dir(dir);
@@ -57,31 +83,99 @@ impl McpClient {
             }
         }
 
-        let mut child = cmd
+        let child = cmd
             .spawn()
             .map_err(|e| format!("Failed to spawn MCP server: {}", e))?;
+        Self::from_child(child)
+    }
 
+    /// Wrap an already-spawned child's piped stdin/stdout.
+    ///
+    /// The client takes ownership of the child: it is killed on timeout or drop.
+    /// Stderr is captured only if it is still piped.
+    pub fn from_child(mut child: Child) -> Result<Self, String> {
         let stdin = child.stdin.take().ok_or("Failed to capture stdin")?;
         let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
 
-        Ok(Self {
+        let stderr_log = Arc::new(Mutex::new(StderrLog::new(STDERR_LOG_CAPACITY)));
+        if let Some(stderr) = child.stderr.take() {
+            spawn_stderr_reader(stderr, stderr_log.clone(), |_| {});
+        }
+
+        Ok(Self::connect(Some(child), stdin, stdout, stderr_log))
+    }
+
+    /// Talk to a process someone else owns (e.g. one started by the process
+    /// manager) through its pipes. The process is never killed by the client;
+    /// `stderr_log` is the owner's capture, quoted when the server goes away.
+    pub fn from_pipes(stdin: ChildStdin, stdout: ChildStdout, stderr_log: Arc<Mutex<StderrLog>>) -> Self {
+        Self::connect(None, stdin, stdout, stderr_log)
+    }
+
+    fn connect(
+        child: Option<Child>,
+        stdin: ChildStdin,
+        stdout: ChildStdout,
+        stderr_log: Arc<Mutex<StderrLog>>,
+    ) -> Self {
+        let pending = Arc::new(Mutex::new(PendingRequests::default()));
+        Self::spawn_reader(BufReader::new(stdout), pending.clone(), stderr_log);
+
+        Self {
             _child: Mutex::new(child),
             stdin: Mutex::new(BufWriter::new(stdin)),
-            stdout: Mutex::new(BufReader::new(stdout)),
+            pending,
             next_id: AtomicU64::new(1),
             initialized: std::sync::atomic::AtomicBool::new(false),
-        })
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            init_duration: Mutex::new(None),
+        }
+    }
+
+    /// Override the per-request response timeout (default 30s).
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Read every message from the server and hand each response to the slot
+    /// registered for its id. On EOF/read error, fail all pending requests (with
+    /// the server's last stderr lines) and mark the client closed.
+    fn spawn_reader(
+        mut reader: BufReader<ChildStdout>,
+        pending: Arc<Mutex<PendingRequests>>,
+        stderr_log: Arc<Mutex<StderrLog>>,
+    ) {
+        std::thread::spawn(move || loop {
+            match Self::read_message(&mut reader) {
+                Ok(Value::Array(batch)) => batch.into_iter().for_each(|msg| dispatch(&pending, msg)),
+                Ok(msg) => dispatch(&pending, msg),
+                Err(e) => {
+                    let tail = lock_unpoisoned(&stderr_log).tail(5);
+                    let e = if tail.is_empty() { e } else { format!("{}. Server stderr:\n{}", e, tail) };
+                    let mut state = lock_unpoisoned(&pending);
+                    for (_, slot) in state.slots.drain() {
+                        let _ = slot.send(Err(e.clone()));
+                    }
+                    state.closed = Some(e);
+                    break;
+                }
+            }
+        });
     }
 
     /// Perform the MCP initialization handshake.
     ///
     /// Must be called before any tool invocations.
     /// Sends `initialize` request + `notifications/initialized` notification.
-    pub fn initialize(&self) -> Result<Value, String> {
+    /// A server that never answers fails with `AiddError::Timeout`.
+    pub fn initialize(&self) -> Result<Value, AiddError> {
         if self.initialized.load(Ordering::SeqCst) {
             return Ok(json!({"already_initialized": true}));
         }
 
+        let started = Instant::now();
+
         // Step 1: Send initialize request
         let result = self.send_request(
             "initialize",
@@ -98,6 +192,7 @@ impl McpClient {
         // Step 2: Send initialized notification (no response expected)
         self.send_notification("notifications/initialized", json!({}))?;
 
+        *lock_unpoisoned(&self.init_duration) = Some(started.elapsed());
         self.initialized.store(true, Ordering::SeqCst);
         Ok(result)
     }
@@ -117,6 +212,7 @@ impl McpClient {
                 "arguments": arguments
             }),
         )
+        .map_err(String::from)
     }
 
     /// List available MCP tools.
@@ -125,11 +221,23 @@ impl McpClient {
             return Err("Client not initialized. Call initialize() first.".to_string());
         }
 
-        self.send_request("tools/list", json!({}))
+        self.send_request("tools/list", json!({})).map_err(String::from)
+    }
+
+    /// Send an MCP `ping` and return the round-trip time. Allowed before
+    /// initialization; `timeout` replaces the client's request timeout.
+    pub fn ping(&self, timeout: Duration) -> Result<Duration, String> {
+        let started = Instant::now();
+        self.send_request_with_timeout("ping", json!({}), timeout)?;
+        Ok(started.elapsed())
     }
 
     /// Send a JSON-RPC 2.0 request and wait for the response.
-    fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+    fn send_request(&self, method: &str, params: Value) -> Result<Value, AiddError> {
+        self.send_request_with_timeout(method, params, self.request_timeout)
+    }
+
+    fn send_request_with_timeout(&self, method: &str, params: Value, timeout: Duration) -> Result<Value, AiddError> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
 
         let request = json!({
@@ -139,36 +247,37 @@ impl McpClient {
             "params": params
         });
 
-        // Write request using Content-Length framing (MCP stdio transport).
+        // Register before writing so the reader can't see the response first.
+        let (tx, rx) = mpsc::channel();
         {
-            let mut stdin = self.stdin.lock().map_err(|e| format!("stdin lock: {}", e))?;
-            Self::write_message(&mut stdin, &request)?;
+            let mut state = lock_unpoisoned(&self.pending);
+            if let Some(reason) = &state.closed {
+                return Err(reason.clone().into());
+            }
+            state.slots.insert(id, tx);
         }
 
-        // Read response — skip notifications until we get a response with matching id
-        let mut stdout = self.stdout.lock().map_err(|e| format!("stdout lock: {}", e))?;
-
-        loop {
-            let msg = Self::read_message(&mut stdout)?;
-
-            // Check if this is our response (has matching id)
-            if let Some(resp_id) = msg.get("id") {
-                if resp_id.as_u64() == Some(id) {
-                    // Check for error
-                    if let Some(error) = msg.get("error") {
-                        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
-                        let message = error
-                            .get("message")
-                            .and_then(|m| m.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(format!("JSON-RPC error {}: {}", code, message));
-                    }
+        // Write request using Content-Length framing (MCP stdio transport).
+        let written = {
+            let mut stdin = lock_unpoisoned(&self.stdin);
+            Self::write_message(&mut stdin, &request)
+        };
+        if let Err(e) = written {
+            lock_unpoisoned(&self.pending).slots.remove(&id);
+            return Err(e.into());
+        }
 
-                    // Return result
-                    return Ok(msg.get("result").cloned().unwrap_or(Value::Null));
-                }
+        match rx.recv_timeout(timeout) {
+            Ok(outcome) => outcome.map_err(AiddError::from),
+            Err(RecvTimeoutError::Timeout) => {
+                lock_unpoisoned(&self.pending).slots.remove(&id);
+                self.kill_child();
+                Err(AiddError::Timeout {
+                    operation: format!("MCP request '{}'", method),
+                    after_ms: timeout.as_millis() as u64,
+                })
             }
-            // Not our response (notification or different id) — skip and keep reading
+            Err(RecvTimeoutError::Disconnected) => Err("Server closed connection (EOF)".to_string().into()),
         }
     }
 
@@ -180,11 +289,35 @@ impl McpClient {
             "params": params
         });
 
-        let mut stdin = self.stdin.lock().map_err(|e| format!("stdin lock: {}", e))?;
+        let mut stdin = lock_unpoisoned(&self.stdin);
         Self::write_message(&mut stdin, &notification)?;
         Ok(())
     }
 
+    /// Fail every request still waiting on a response with `reason`, refuse new
+    /// ones and kill an owned child. Returns how many requests were cancelled.
+    pub fn shutdown(&self, reason: &str) -> usize {
+        let cancelled = {
+            let mut state = lock_unpoisoned(&self.pending);
+            let cancelled = state.slots.len();
+            for (_, slot) in state.slots.drain() {
+                let _ = slot.send(Err(reason.to_string()));
+            }
+            state.closed.get_or_insert_with(|| reason.to_string());
+            cancelled
+        };
+        self.kill_child();
+        cancelled
+    }
+
+    /// Kill the server so the reader thread sees EOF and later calls fail fast.
+    fn kill_child(&self) {
+        if let Some(child) = lock_unpoisoned(&self._child).as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
     fn write_message(writer: &mut BufWriter<ChildStdin>, message: &Value) -> Result<(), String> {
         let payload = serde_json::to_string(message).map_err(|e| format!("serialize: {}", e))?;
         let header = format!("Content-Length: {}\r\n\r\n", payload.as_bytes().len());
@@ -214,8 +347,8 @@ impl McpClient {
                 continue;
             }
 
-            // Backward-compat: accept newline-delimited JSON if encountered.
-            if trimmed.starts_with('{') {
+            // Backward-compat: accept newline-delimited JSON (objects or batches) if encountered.
+            if trimmed.starts_with('{') || trimmed.starts_with('[') {
                 return serde_json::from_str(trimmed).map_err(|e| format!("parse response: {}", e));
             }
 
@@ -258,6 +391,17 @@ impl McpClient {
         serde_json::from_str(&json_str).map_err(|e| format!("parse response: {}", e))
     }
 
+    /// How long the `initialize` handshake took, once it has completed.
+    pub fn last_init_duration(&self) -> Option<Duration> {
+        *lock_unpoisoned(&self.init_duration)
+    }
+
+    /// Whether the server is still connected: false once its output closed
+    /// (exit, kill after a timeout) or the client was shut down.
+    pub fn is_alive(&self) -> bool {
+        lock_unpoisoned(&self.pending).closed.is_none()
+    }
+
     /// Check if the client has been initialized.
     pub fn is_initialized(&self) -> bool {
         self.initialized.load(Ordering::SeqCst)
@@ -266,13 +410,42 @@ impl McpClient {
 
 impl Drop for McpClient {
     fn drop(&mut self) {
-        if let Ok(mut child) = self._child.lock() {
-            let _ = child.kill();
-            let _ = child.wait();
-        }
+        self.kill_child();
+    }
+}
+
+/// Route a response to its waiting request. Notifications, server requests and
+/// responses for abandoned (timed-out) ids are dropped.
+fn dispatch(pending: &Mutex<PendingRequests>, msg: Value) {
+    let Some(id) = msg.get("id").and_then(|v| v.as_u64()) else {
+        return;
+    };
+    if msg.get("result").is_none() && msg.get("error").is_none() {
+        return;
+    }
+    if let Some(slot) = lock_unpoisoned(pending).slots.remove(&id) {
+        let _ = slot.send(response_outcome(&msg));
     }
 }
 
+fn response_outcome(msg: &Value) -> Outcome {
+    if let Some(error) = msg.get("error") {
+        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error");
+        return Err(format!("JSON-RPC error {}: {}", code, message));
+    }
+    Ok(msg.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// The guarded I/O handles stay usable after a panicking holder, so recover
+/// the guard rather than failing every subsequent request.
+fn lock_unpoisoned<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,4 +500,91 @@ mod tests {
         );
         assert_eq!(params["clientInfo"]["name"].as_str().unwrap(), "aidd-hub");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn hung_server_times_out_and_is_killed() {
+        // `sleep` reads nothing and never replies, like an engine stuck mid-call.
+        let client = McpClient::spawn("sleep", &["30"])
+            .unwrap()
+            .with_request_timeout(Duration::from_millis(200));
+
+        let started = Instant::now();
+        let err = client.send_request("tools/list", json!({})).unwrap_err();
+        assert!(matches!(err, AiddError::Timeout { after_ms: 200, .. }), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // The child is gone, so the next caller gets a prompt error, not a hang or a poisoned lock.
+        let started = Instant::now();
+        assert!(client.send_request("tools/list", json!({})).is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn concurrent_requests_each_receive_their_own_response() {
+        // Replies to ids 1 and 2 out of order, as one newline-delimited batch.
+        let script = r#"sleep 0.5; printf '%s\n' '[{"jsonrpc":"2.0","id":2,"result":"second"},{"jsonrpc":"2.0","method":"notifications/progress"},{"jsonrpc":"2.0","id":1,"result":"first"}]'; sleep 30"#;
+        let client = Arc::new(
+            McpClient::spawn("sh", &["-c", script])
+                .unwrap()
+                .with_request_timeout(Duration::from_secs(5)),
+        );
+
+        let callers: Vec<_> = (0..2)
+            .map(|_| {
+                let client = client.clone();
+                std::thread::spawn(move || client.send_request("tools/list", json!({})))
+            })
+            .collect();
+        let mut results: Vec<Value> = callers
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .collect();
+        results.sort_by_key(|v| v.to_string());
+
+        assert_eq!(results, vec![json!("first"), json!("second")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn initialize_records_handshake_duration() {
+        // Answers the initialize request (id 1) after a delay, then swallows the notification.
+        let script = r#"sleep 0.2; printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-11-05","serverInfo":{"name":"fake"}}}'; cat > /dev/null"#;
+        let client = McpClient::spawn("sh", &["-c", script]).unwrap();
+        assert_eq!(client.last_init_duration(), None);
+
+        let result = client.initialize().unwrap();
+        assert_eq!(result["serverInfo"]["name"], "fake");
+
+        let elapsed = client.last_init_duration().expect("duration recorded");
+        assert!(elapsed >= Duration::from_millis(150), "{:?}", elapsed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn client_drives_an_existing_process_through_its_pipes() {
+        // Answers initialize (id 1) after its header line, then tools/list (id 2)
+        // once that request's header has arrived (framed bodies carry no newline).
+        let script = r#"read -r _; printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"managed"}}}'; for _ in 1 2 3 4 5; do read -r _; done; printf '%s\n' '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"aidd_session"}]}}'; sleep 30"#;
+        let mut child = Command::new("sh")
+            .args(["-c", script])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let log = Arc::new(Mutex::new(StderrLog::new(STDERR_LOG_CAPACITY)));
+
+        let client = McpClient::from_pipes(stdin, stdout, log).with_request_timeout(Duration::from_secs(5));
+        assert_eq!(client.initialize().unwrap()["serverInfo"]["name"], "managed");
+        assert_eq!(client.list_tools().unwrap()["tools"][0]["name"], "aidd_session");
+
+        // The client does not own the process, so dropping it leaves the child running.
+        drop(client);
+        assert!(child.try_wait().unwrap().is_none());
+        let _ = child.kill();
+        let _ = child.wait();
+    }
 }
@@ -1,3 +1,5 @@
 mod mcp_client;
+mod tool_result;
 
-pub use mcp_client::McpClient;
+pub use mcp_client::{McpClient, DEFAULT_REQUEST_TIMEOUT};
+pub use tool_result::tool_result_json;
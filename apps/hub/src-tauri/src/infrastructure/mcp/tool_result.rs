@@ -0,0 +1,67 @@
+use serde_json::Value;
+
+/// JSON payload of an MCP `tools/call` result.
+///
+/// Prefers `structuredContent`, then the first text block parsed as JSON, then
+/// all text blocks concatenated (servers may split a large payload across
+/// blocks). `None` when no form parses.
+pub fn tool_result_json(result: &Value) -> Option<Value> {
+    if let Some(structured) = result.get("structuredContent") {
+        return Some(structured.clone());
+    }
+
+    let texts: Vec<&str> = result
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|block| block.get("type").and_then(|v| v.as_str()) == Some("text"))
+        .filter_map(|block| block.get("text").and_then(|v| v.as_str()))
+        .collect();
+
+    let first = texts.first()?;
+    if let Ok(parsed) = serde_json::from_str(first) {
+        return Some(parsed);
+    }
+    if texts.len() > 1 {
+        return serde_json::from_str(&texts.concat()).ok();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn structured_content_wins_over_text() {
+        let result = json!({
+            "structuredContent": { "pending": 3 },
+            "content": [{ "type": "text", "text": "{\"pending\": 0}" }]
+        });
+        assert_eq!(tool_result_json(&result), Some(json!({ "pending": 3 })));
+    }
+
+    #[test]
+    fn single_text_block_is_parsed() {
+        let result = json!({
+            "content": [
+                { "type": "image", "data": "..." },
+                { "type": "text", "text": "{\"sessions\": []}" }
+            ]
+        });
+        assert_eq!(tool_result_json(&result), Some(json!({ "sessions": [] })));
+        assert_eq!(tool_result_json(&json!({ "content": [{ "type": "text", "text": "not json" }] })), None);
+    }
+
+    #[test]
+    fn split_text_blocks_are_concatenated() {
+        let result = json!({
+            "content": [
+                { "type": "text", "text": "{\"entries\": [{\"id\": " },
+                { "type": "text", "text": "\"obs-1\"}]}" }
+            ]
+        });
+        assert_eq!(tool_result_json(&result), Some(json!({ "entries": [{ "id": "obs-1" }] })));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod adapters;
+pub mod clock;
 pub mod filesystem;
 pub mod integrations;
 pub mod mcp;
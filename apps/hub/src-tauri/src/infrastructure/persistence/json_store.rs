@@ -25,6 +25,11 @@ impl JsonStore {
         })
     }
 
+    #[cfg(test)]
+    pub(crate) fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
     /// Returns the `~/.aidd/` directory path.
     pub fn aidd_dir(&self) -> &Path {
         self.path.parent().unwrap()
@@ -44,8 +49,14 @@ impl ProjectRepository for JsonStore {
             return Ok(HubData::default());
         }
 
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse hub.json: {}", e))
+        let mut data: HubData = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse hub.json: {}", e))?;
+        data.migrate();
+        if data.repair_active_project() {
+            // Best effort: the repaired copy is returned either way.
+            let _ = self.save(&data);
+        }
+        Ok(data)
     }
 
     fn save(&self, data: &HubData) -> Result<(), String> {
@@ -56,3 +67,40 @@ impl ProjectRepository for JsonStore {
             .map_err(|e| format!("Failed to write hub.json: {}", e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dangling_active_project_is_repaired_and_persisted() {
+        let dir = std::env::temp_dir().join(format!("aidd-hub-store-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hub.json");
+        fs::write(
+            &path,
+            r#"{
+                "projects": [
+                    {"name": "a", "path": "/repo/a", "detected": true},
+                    {"name": "b", "path": "/repo/b", "detected": true}
+                ],
+                "active_project": "/repo/removed"
+            }"#,
+        )
+        .unwrap();
+
+        let store = JsonStore::at(path.clone());
+        assert_eq!(store.load().unwrap().active_project.as_deref(), Some("/repo/a"));
+
+        let persisted: HubData = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(persisted.active_project.as_deref(), Some("/repo/a"));
+
+        let mut empty = HubData { active_project: Some("/repo/gone".to_string()), ..HubData::default() };
+        assert!(empty.repair_active_project());
+        assert_eq!(empty.active_project, None);
+        assert!(!empty.repair_active_project());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
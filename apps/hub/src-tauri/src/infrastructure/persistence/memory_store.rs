@@ -0,0 +1,23 @@
+use std::sync::Mutex;
+
+use crate::domain::ports::outbound::{HubData, ProjectRepository};
+
+/// In-memory `ProjectRepository` for tests that must not touch `~/.aidd/hub.json`.
+pub(crate) struct MemoryStore(Mutex<HubData>);
+
+impl MemoryStore {
+    pub(crate) fn new(data: HubData) -> Self {
+        Self(Mutex::new(data))
+    }
+}
+
+impl ProjectRepository for MemoryStore {
+    fn load(&self) -> Result<HubData, String> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+
+    fn save(&self, data: &HubData) -> Result<(), String> {
+        *self.0.lock().unwrap() = data.clone();
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
 mod json_store;
+#[cfg(test)]
+mod memory_store;
 
 pub use json_store::JsonStore;
+#[cfg(test)]
+pub(crate) use memory_store::MemoryStore;
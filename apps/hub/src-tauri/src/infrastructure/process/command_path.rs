@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+/// Locate `command` the way spawning it would. A command containing a path
+/// separator is checked directly (relative to `cwd` when given); a bare name is
+/// looked up in each `PATH` entry, also trying `PATHEXT` extensions on Windows
+/// (so `npx` finds `npx.cmd`).
+pub fn resolve_command(command: &str, cwd: Option<&Path>) -> Option<PathBuf> {
+    if command.is_empty() {
+        return None;
+    }
+
+    if command.contains('/') || command.contains('\\') {
+        let path = Path::new(command);
+        let path = match cwd {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        return with_extensions(&path).into_iter().find(|p| is_executable(p));
+    }
+
+    let search = std::env::var_os("PATH")?;
+    std::env::split_paths(&search)
+        .flat_map(|dir| with_extensions(&dir.join(command)))
+        .find(|p| is_executable(p))
+}
+
+#[cfg(windows)]
+fn with_extensions(path: &Path) -> Vec<PathBuf> {
+    let exts = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    let mut candidates = vec![path.to_path_buf()];
+    candidates.extend(exts.split(';').filter(|e| !e.is_empty()).map(|ext| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(ext);
+        PathBuf::from(name)
+    }));
+    candidates
+}
+
+#[cfg(not(windows))]
+fn with_extensions(path: &Path) -> Vec<PathBuf> {
+    vec![path.to_path_buf()]
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
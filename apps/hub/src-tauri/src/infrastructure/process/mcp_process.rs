@@ -1,8 +1,29 @@
 use std::collections::HashMap;
-use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use crate::domain::model::{McpServer, McpServerMode, McpServerStatus};
+use crate::domain::model::{McpPackage, McpResetSummary, McpServer, McpServerMode, McpServerStatus, RestartPolicy};
+use crate::domain::ports::outbound::Clock;
+use crate::infrastructure::clock::SystemClock;
+use crate::infrastructure::mcp::McpClient;
+use super::stderr_log::{secret_env_values, spawn_stderr_reader, StderrLog, STDERR_LOG_CAPACITY};
+
+/// Error given to requests cancelled by a state reset.
+pub const RESET_REASON: &str = "MCP state was reset";
+
+/// How long a liveness ping waits before the server counts as unresponsive.
+pub const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long `stop_all` (and `stop` by default) lets a server exit on its own
+/// after SIGTERM before killing it.
+pub const STOP_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How often a stopping server is polled for exit during its grace period.
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Callback invoked with `(server_id, line)` for stderr lines that look like errors.
+pub type ErrorLineListener = Arc<dyn Fn(&str, &str) + Send + Sync>;
 
 /// Tracks a running MCP server process.
 struct RunningProcess {
@@ -10,94 +31,341 @@ struct RunningProcess {
     name: String,
     mode: McpServerMode,
     started_at: String,
+    /// Command line used to (re)spawn the process, including extra args.
+    command: Vec<String>,
+    /// Extra environment applied on top of the Hub's, kept for restarts.
+    env: HashMap<String, String>,
+    restart_policy: RestartPolicy,
+    restart_count: u32,
+    last_exit_code: Option<i32>,
+    /// When a scheduled restart may run; `None` if none is pending.
+    restart_due: Option<Instant>,
+    /// Client over this child's stdio, created on first use.
+    client: Option<Arc<McpClient>>,
+    /// Set when the last ping went unanswered; cleared by a successful one or a restart.
+    unresponsive: bool,
 }
 
 /// Infrastructure adapter for spawning/killing MCP server processes.
 pub struct McpProcessManager {
     processes: Mutex<HashMap<String, RunningProcess>>,
+    /// Stderr per server id. Kept after the process exits so a crash can be inspected.
+    logs: Mutex<HashMap<String, Arc<Mutex<StderrLog>>>>,
+    error_listener: Mutex<Option<ErrorLineListener>>,
+    /// Stamps `started_at` (ISO 8601).
+    clock: Arc<dyn Clock>,
 }
 
 impl McpProcessManager {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             processes: Mutex::new(HashMap::new()),
+            logs: Mutex::new(HashMap::new()),
+            error_listener: Mutex::new(None),
+            clock,
         }
     }
 
+    /// Register the callback notified of error-looking stderr lines.
+    pub fn set_error_listener(&self, listener: ErrorLineListener) {
+        *self.error_listener.lock().unwrap_or_else(|e| e.into_inner()) = Some(listener);
+    }
+
+    /// Captured stderr of a server (running or exited since it was last started).
+    pub fn get_logs(&self, server_id: &str) -> Result<String, String> {
+        let logs = self.logs.lock().map_err(|e| e.to_string())?;
+        let log = logs
+            .get(server_id)
+            .ok_or_else(|| format!("No logs for server '{}'", server_id))?;
+        let contents = log.lock().map_err(|e| e.to_string())?.contents().to_string();
+        Ok(contents)
+    }
+
     /// Start an MCP server process.
     ///
-    /// `package` is one of: "engine", "core", "memory", "tools"
-    pub fn start(&self, package: &str, mode: McpServerMode) -> Result<McpServer, String> {
+    /// `package` is any `McpPackage` alias; the server runs under its canonical
+    /// id ("engine", "core", "memory", "tools"). `env` is added
+    /// to the inherited environment and `extra_args` appended to the command;
+    /// both are reused when the supervisor restarts the server. Values of
+    /// secret-looking env vars are masked in the captured stderr.
+    pub fn start(
+        &self,
+        package: &str,
+        mode: McpServerMode,
+        env: HashMap<String, String>,
+        extra_args: Vec<String>,
+    ) -> Result<McpServer, String> {
+        let package = McpPackage::from_str(package)?;
+        let name = package.npm_name().to_string();
+        let mut cmd_args = vec!["npx".to_string(), "-y".to_string(), name.clone()];
+        cmd_args.extend(extra_args);
+        self.start_command(package.id(), name, cmd_args, env, mode)
+    }
+
+    fn start_command(
+        &self,
+        server_id: &str,
+        name: String,
+        command: Vec<String>,
+        env: HashMap<String, String>,
+        mode: McpServerMode,
+    ) -> Result<McpServer, String> {
         let mut procs = self.processes.lock().map_err(|e| e.to_string())?;
 
         // Check if already running
-        if procs.contains_key(package) {
-            return Err(format!("Server '{}' is already running", package));
+        if procs.contains_key(server_id) {
+            return Err(format!("Server '{}' is already running", server_id));
         }
 
-        let (name, cmd_args) = resolve_command(package)?;
-
-        let child = Command::new(&cmd_args[0])
-            .args(&cmd_args[1..])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to start {}: {}", name, e))?;
+        let log = Arc::new(Mutex::new(
+            StderrLog::new(STDERR_LOG_CAPACITY).with_redactions(secret_env_values(&env)),
+        ));
+        let child = self.spawn_child(server_id, &name, &command, &env, &log)?;
+        self.logs
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(server_id.to_string(), log);
 
         let pid = child.id();
-        let now = chrono_now();
+        let now = self.clock.now_iso();
 
         procs.insert(
-            package.to_string(),
+            server_id.to_string(),
             RunningProcess {
                 child,
                 name: name.clone(),
                 mode: mode.clone(),
                 started_at: now.clone(),
+                command,
+                env,
+                restart_policy: RestartPolicy::Off,
+                restart_count: 0,
+                last_exit_code: None,
+                restart_due: None,
+                client: None,
+                unresponsive: false,
             },
         );
 
         Ok(McpServer {
-            id: package.to_string(),
+            id: server_id.to_string(),
             name,
             mode,
             status: McpServerStatus::Running,
             pid: Some(pid),
             started_at: Some(now),
             error: None,
+            restart_count: 0,
+            last_exit_code: None,
         })
     }
 
-    /// Stop a running MCP server.
-    pub fn stop(&self, server_id: &str) -> Result<(), String> {
+    /// Spawn `command`, draining its stderr into `log`.
+    fn spawn_child(
+        &self,
+        server_id: &str,
+        name: &str,
+        command: &[String],
+        env: &HashMap<String, String>,
+        log: &Arc<Mutex<StderrLog>>,
+    ) -> Result<Child, String> {
+        let mut child = Command::new(&command[0])
+            .args(&command[1..])
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", name, e))?;
+
+        if let Some(stderr) = child.stderr.take() {
+            let listener = self.error_listener.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            let server_id = server_id.to_string();
+            spawn_stderr_reader(stderr, log.clone(), move |line| {
+                if let Some(notify) = &listener {
+                    notify(&server_id, line);
+                }
+            });
+        }
+        Ok(child)
+    }
+
+    /// Set how a tracked server is handled when it crashes.
+    pub fn set_restart_policy(&self, server_id: &str, policy: RestartPolicy) -> Result<(), String> {
         let mut procs = self.processes.lock().map_err(|e| e.to_string())?;
+        let proc = procs
+            .get_mut(server_id)
+            .ok_or_else(|| format!("No running server with id '{}'", server_id))?;
+        proc.restart_policy = policy;
+        Ok(())
+    }
 
-        if let Some(mut proc) = procs.remove(server_id) {
-            proc.child
-                .kill()
-                .map_err(|e| format!("Failed to kill {}: {}", server_id, e))?;
-            let _ = proc.child.wait(); // reap zombie
+    /// Respawn servers that exited with a failure and whose restart policy still
+    /// allows it. Backoff is non-blocking: the first pass schedules the restart,
+    /// a later pass past the due time performs it.
+    pub fn supervise(&self) {
+        let mut procs = self.processes.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+
+        for (id, proc) in procs.iter_mut() {
+            let exit_code = match proc.child.try_wait() {
+                Ok(Some(status)) if !status.success() => status.code(),
+                _ => continue,
+            };
+            let RestartPolicy::OnFailure { max_retries, backoff_ms } = proc.restart_policy else {
+                continue;
+            };
+            if proc.restart_count >= max_retries {
+                continue;
+            }
+
+            let due = *proc.restart_due.get_or_insert_with(|| {
+                let factor = 1u64 << proc.restart_count.min(16);
+                now + Duration::from_millis(backoff_ms.saturating_mul(factor))
+            });
+            if now < due {
+                continue;
+            }
+
+            let log = self
+                .logs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(id.clone())
+                .or_insert_with(|| {
+                    let log = StderrLog::new(STDERR_LOG_CAPACITY).with_redactions(secret_env_values(&proc.env));
+                    Arc::new(Mutex::new(log))
+                })
+                .clone();
+            proc.restart_count += 1;
+            proc.last_exit_code = exit_code;
+            proc.restart_due = None;
+            match self.spawn_child(id, &proc.name, &proc.command, &proc.env, &log) {
+                Ok(child) => {
+                    proc.child = child;
+                    proc.client = None;
+                    proc.unresponsive = false;
+                    proc.started_at = self.clock.now_iso();
+                }
+                Err(e) => eprintln!("[mcpd] Failed to restart {}: {}", id, e),
+            }
+        }
+    }
+
+    /// Run `supervise` every `interval` until the manager is dropped.
+    pub fn spawn_supervisor(self: &Arc<Self>, interval: Duration) {
+        let manager = Arc::downgrade(self);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            match manager.upgrade() {
+                Some(manager) => manager.supervise(),
+                None => break,
+            }
+        });
+    }
+
+    /// A JSON-RPC client over the stdio of the server tracked as `server_id`, so
+    /// requests reach the process already running instead of a second spawn.
+    /// `None` when the server is not running. The client is shared by callers
+    /// and replaced when the supervisor restarts the process.
+    pub fn client(&self, server_id: &str) -> Result<Option<Arc<McpClient>>, String> {
+        let mut procs = self.processes.lock().map_err(|e| e.to_string())?;
+        let Some(proc) = procs.get_mut(server_id) else {
+            return Ok(None);
+        };
+        if !matches!(proc.child.try_wait(), Ok(None)) {
+            return Ok(None);
+        }
+        if let Some(client) = &proc.client {
+            return Ok(Some(client.clone()));
+        }
+
+        let (Some(stdin), Some(stdout)) = (proc.child.stdin.take(), proc.child.stdout.take()) else {
+            return Ok(None);
+        };
+        let log = self
+            .logs
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(server_id)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(Mutex::new(StderrLog::new(STDERR_LOG_CAPACITY))));
+        let client = Arc::new(McpClient::from_pipes(stdin, stdout, log));
+        proc.client = Some(client.clone());
+        Ok(Some(client))
+    }
+
+    /// Send an MCP `ping` to a running server and return the round-trip time.
+    /// A server that doesn't answer within `timeout` is reported as
+    /// `Unresponsive` by `get_servers` until a later ping succeeds.
+    pub fn ping(&self, server_id: &str, timeout: Duration) -> Result<Duration, String> {
+        let client = self
+            .client(server_id)?
+            .ok_or_else(|| format!("No running server with id '{}'", server_id))?;
+        let result = client.ping(timeout);
+
+        let mut procs = self.processes.lock().map_err(|e| e.to_string())?;
+        if let Some(proc) = procs.get_mut(server_id) {
+            // The supervisor may have swapped in a restarted process meanwhile
+            if proc.client.as_ref().is_some_and(|c| Arc::ptr_eq(c, &client)) {
+                proc.unresponsive = result.is_err();
+            }
+        }
+        result.map_err(|e| format!("Server '{}' did not answer ping: {}", server_id, e))
+    }
+
+    /// Stop a running MCP server, giving it up to `grace` to exit after SIGTERM
+    /// before it is killed.
+    pub fn stop(&self, server_id: &str, grace: Duration) -> Result<(), String> {
+        // Don't hold the lock through the grace period
+        let proc = self.processes.lock().map_err(|e| e.to_string())?.remove(server_id);
+
+        if let Some(mut proc) = proc {
+            terminate(&mut proc.child, grace)
+                .map_err(|e| format!("Failed to stop {}: {}", server_id, e))?;
             Ok(())
         } else {
             Err(format!("No running server with id '{}'", server_id))
         }
     }
 
-    /// Stop all running servers (called on app shutdown).
+    /// Stop all running servers (called on app shutdown). They share one
+    /// `STOP_GRACE_PERIOD` rather than waiting out one each.
     pub fn stop_all(&self) -> Result<(), String> {
-        let mut procs = self.processes.lock().map_err(|e| e.to_string())?;
-        for (id, mut proc) in procs.drain() {
-            if let Err(e) = proc.child.kill() {
-                eprintln!("[mcpd] Failed to kill {}: {}", id, e);
+        let drained: Vec<(String, RunningProcess)> =
+            self.processes.lock().map_err(|e| e.to_string())?.drain().collect();
+        terminate_all(drained);
+        Ok(())
+    }
+
+    /// Stop every tracked server the same way `stop_all` does and shut down the
+    /// clients attached to them first, failing their in-flight requests.
+    /// Captured logs are kept. Safe to call when nothing is running.
+    pub fn reset(&self) -> McpResetSummary {
+        let mut procs = self.processes.lock().unwrap_or_else(|e| e.into_inner());
+        let mut drained: Vec<(String, RunningProcess)> = procs.drain().collect();
+        drop(procs);
+        drained.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut summary = McpResetSummary::default();
+        for (id, proc) in drained.iter_mut() {
+            if let Some(client) = proc.client.take() {
+                summary.cancelled_requests += client.shutdown(RESET_REASON);
+                summary.clients_closed += 1;
             }
-            let _ = proc.child.wait();
+            summary.stopped_servers.push(id.clone());
         }
-        Ok(())
+        terminate_all(drained);
+        summary
     }
 
     /// Get status of all tracked servers (running + check if still alive).
     pub fn get_servers(&self) -> Vec<McpServer> {
+        self.supervise();
         let mut procs = self.processes.lock().unwrap_or_else(|e| e.into_inner());
         let mut result = Vec::new();
         let mut dead = Vec::new();
@@ -105,8 +373,9 @@ impl McpProcessManager {
         for (id, proc) in procs.iter_mut() {
             // Check if process is still alive
             match proc.child.try_wait() {
-                Ok(Some(_exit)) => {
-                    // Process has exited
+                Ok(Some(exit)) => {
+                    // Process has exited; keep tracking it while a restart is scheduled
+                    let restarting = proc.restart_due.is_some();
                     result.push(McpServer {
                         id: id.clone(),
                         name: proc.name.clone(),
@@ -114,20 +383,34 @@ impl McpProcessManager {
                         status: McpServerStatus::Stopped,
                         pid: None,
                         started_at: Some(proc.started_at.clone()),
-                        error: Some("Process exited unexpectedly".to_string()),
+                        error: Some(if restarting {
+                            "Process exited unexpectedly; restart scheduled".to_string()
+                        } else {
+                            "Process exited unexpectedly".to_string()
+                        }),
+                        restart_count: proc.restart_count,
+                        last_exit_code: exit.code().or(proc.last_exit_code),
                     });
-                    dead.push(id.clone());
+                    if !restarting {
+                        dead.push(id.clone());
+                    }
                 }
                 Ok(None) => {
-                    // Still running
+                    // Still running, though not necessarily answering
                     result.push(McpServer {
                         id: id.clone(),
                         name: proc.name.clone(),
                         mode: proc.mode.clone(),
-                        status: McpServerStatus::Running,
+                        status: if proc.unresponsive {
+                            McpServerStatus::Unresponsive
+                        } else {
+                            McpServerStatus::Running
+                        },
                         pid: Some(proc.child.id()),
                         started_at: Some(proc.started_at.clone()),
-                        error: None,
+                        error: proc.unresponsive.then(|| "Did not answer the last ping".to_string()),
+                        restart_count: proc.restart_count,
+                        last_exit_code: proc.last_exit_code,
                     });
                 }
                 Err(e) => {
@@ -139,6 +422,8 @@ impl McpProcessManager {
                         pid: None,
                         started_at: Some(proc.started_at.clone()),
                         error: Some(format!("Status check failed: {}", e)),
+                        restart_count: proc.restart_count,
+                        last_exit_code: proc.last_exit_code,
                     });
                 }
             }
@@ -153,33 +438,242 @@ impl McpProcessManager {
     }
 }
 
-/// Resolve package name to display name and command.
-fn resolve_command(package: &str) -> Result<(String, Vec<String>), String> {
-    match package {
-        "engine" => Ok((
-            "@aidd.md/mcp-engine".to_string(),
-            vec!["npx".to_string(), "-y".to_string(), "@aidd.md/mcp-engine".to_string()],
-        )),
-        "core" => Ok((
-            "@aidd.md/mcp-core".to_string(),
-            vec!["npx".to_string(), "-y".to_string(), "@aidd.md/mcp-core".to_string()],
-        )),
-        "memory" => Ok((
-            "@aidd.md/mcp-memory".to_string(),
-            vec!["npx".to_string(), "-y".to_string(), "@aidd.md/mcp-memory".to_string()],
-        )),
-        "tools" => Ok((
-            "@aidd.md/mcp-tools".to_string(),
-            vec!["npx".to_string(), "-y".to_string(), "@aidd.md/mcp-tools".to_string()],
-        )),
-        _ => Err(format!(
-            "Unknown package '{}'. Valid: engine, core, memory, tools",
-            package
-        )),
+/// Ask `child` to exit and wait up to `grace` before killing it, so the engine
+/// can flush its SQLite writes and close the database cleanly. Windows has no
+/// SIGTERM, so there the process is killed straight away.
+fn terminate(child: &mut Child, grace: Duration) -> std::io::Result<ExitStatus> {
+    if let Some(status) = child.try_wait()? {
+        return Ok(status);
     }
+    let grace = if request_exit(child) { grace } else { Duration::ZERO };
+    wait_or_kill(child, Instant::now() + grace)
+}
+
+/// `terminate` for many servers at once: every one is signalled up front, then
+/// all of them share a single `STOP_GRACE_PERIOD`.
+fn terminate_all(procs: Vec<(String, RunningProcess)>) {
+    let signalled: Vec<bool> = procs.iter().map(|(_, proc)| request_exit(&proc.child)).collect();
+    let deadline = Instant::now() + STOP_GRACE_PERIOD;
+    for ((id, mut proc), signalled) in procs.into_iter().zip(signalled) {
+        let deadline = if signalled { deadline } else { Instant::now() };
+        if let Err(e) = wait_or_kill(&mut proc.child, deadline) {
+            eprintln!("[mcpd] Failed to stop {}: {}", id, e);
+        }
+    }
+}
+
+/// Poll `child` until it exits or `deadline` passes, then kill it.
+fn wait_or_kill(child: &mut Child, deadline: Instant) -> std::io::Result<ExitStatus> {
+    while Instant::now() < deadline {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        std::thread::sleep(STOP_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+    if let Some(status) = child.try_wait()? {
+        return Ok(status);
+    }
+    child.kill()?;
+    child.wait() // reap zombie
+}
+
+/// Send SIGTERM; false when the signal couldn't be delivered (or on Windows).
+#[cfg(unix)]
+fn request_exit(child: &Child) -> bool {
+    // SAFETY: the child hasn't been reaped yet, so its pid can't have been reused
+    unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) == 0 }
+}
+
+#[cfg(not(unix))]
+fn request_exit(_child: &Child) -> bool {
+    false
 }
 
-fn chrono_now() -> String {
-    let now = std::time::SystemTime::now();
-    format!("{:?}", now)
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn crashing_command() -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()]
+    }
+
+    fn wait_for_exit(manager: &McpProcessManager, id: &str) {
+        for _ in 0..100 {
+            let exited = manager
+                .processes
+                .lock()
+                .unwrap()
+                .get_mut(id)
+                .is_none_or(|p| matches!(p.child.try_wait(), Ok(Some(_))));
+            if exited {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        panic!("process {} did not exit", id);
+    }
+
+    #[test]
+    fn crashed_server_is_restarted_up_to_the_retry_limit() {
+        let manager = McpProcessManager::new();
+        manager
+            .start_command("crashy", "crashy".to_string(), crashing_command(), HashMap::new(), McpServerMode::HubHosted)
+            .unwrap();
+        manager
+            .set_restart_policy("crashy", RestartPolicy::OnFailure { max_retries: 2, backoff_ms: 0 })
+            .unwrap();
+
+        for _ in 0..2 {
+            wait_for_exit(&manager, "crashy");
+            manager.supervise();
+        }
+        wait_for_exit(&manager, "crashy");
+
+        let servers = manager.get_servers();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].restart_count, 2);
+        assert_eq!(servers[0].last_exit_code, Some(3));
+        // Retries exhausted: the dead process is no longer tracked.
+        assert!(manager.get_servers().is_empty());
+    }
+
+    #[test]
+    fn restarts_reuse_env_and_mask_secret_values_in_logs() {
+        let manager = McpProcessManager::new();
+        let script = r#"echo "level=$AIDD_LOG_LEVEL key=$AIDD_API_KEY" >&2; exit 3"#;
+        let command = vec!["sh".to_string(), "-c".to_string(), script.to_string()];
+        let env = HashMap::from([
+            ("AIDD_LOG_LEVEL".to_string(), "debug".to_string()),
+            ("AIDD_API_KEY".to_string(), "sk-secret".to_string()),
+        ]);
+        manager.start_command("envy", "envy".to_string(), command, env, McpServerMode::HubHosted).unwrap();
+        manager
+            .set_restart_policy("envy", RestartPolicy::OnFailure { max_retries: 1, backoff_ms: 0 })
+            .unwrap();
+        wait_for_exit(&manager, "envy");
+        manager.supervise();
+        wait_for_exit(&manager, "envy");
+
+        let expected = "level=debug key=***\n".repeat(2);
+        for _ in 0..100 {
+            if manager.get_logs("envy").unwrap() == expected {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        panic!("unexpected logs: {:?}", manager.get_logs("envy").unwrap());
+    }
+
+    #[test]
+    fn started_at_is_iso_from_the_injected_clock() {
+        use crate::infrastructure::clock::FixedClock;
+
+        let manager = McpProcessManager::with_clock(Arc::new(FixedClock::at_unix_ms(1_769_904_000_000)));
+        let command = vec!["sleep".to_string(), "30".to_string()];
+        let server = manager
+            .start_command("timed", "timed".to_string(), command, HashMap::new(), McpServerMode::HubHosted)
+            .unwrap();
+        assert_eq!(server.started_at.as_deref(), Some("2026-02-01T00:00:00.000Z"));
+        assert_eq!(manager.get_servers()[0].started_at, server.started_at);
+        manager.stop_all().unwrap();
+    }
+
+    #[test]
+    fn servers_without_policy_are_not_restarted() {
+        let manager = McpProcessManager::new();
+        manager
+            .start_command("once", "once".to_string(), crashing_command(), HashMap::new(), McpServerMode::HubHosted)
+            .unwrap();
+        wait_for_exit(&manager, "once");
+
+        let servers = manager.get_servers();
+        assert_eq!(servers[0].restart_count, 0);
+        assert!(manager.get_servers().is_empty());
+    }
+
+    #[test]
+    fn reset_stops_servers_and_closes_pooled_clients() {
+        let manager = McpProcessManager::new();
+        for id in ["b", "a"] {
+            let command = vec!["sleep".to_string(), "30".to_string()];
+            manager.start_command(id, id.to_string(), command, HashMap::new(), McpServerMode::HubHosted).unwrap();
+        }
+        // `sleep` never answers, so this initialize stays in flight until the reset
+        let client = manager.client("a").unwrap().expect("running server has a client");
+        let caller = std::thread::spawn(move || client.initialize());
+        std::thread::sleep(Duration::from_millis(200));
+
+        let summary = manager.reset();
+        assert_eq!(summary.stopped_servers, ["a", "b"]);
+        assert_eq!(summary.clients_closed, 1);
+        assert_eq!(summary.cancelled_requests, 1);
+        assert_eq!(caller.join().unwrap().unwrap_err().to_string(), RESET_REASON);
+        assert!(manager.get_servers().is_empty());
+        assert!(manager.client("a").unwrap().is_none());
+
+        assert_eq!(manager.reset(), McpResetSummary::default());
+    }
+
+    #[test]
+    fn unanswered_ping_marks_server_unresponsive_until_one_succeeds() {
+        let manager = McpProcessManager::new();
+        // Answers only the second request it sees: the first ping goes unanswered
+        let script = r#"read -r _; read -r _; read -r _; read -r _; printf '%s\n' '{"jsonrpc":"2.0","id":2,"result":{}}'; sleep 30"#;
+        let command = vec!["sh".to_string(), "-c".to_string(), script.to_string()];
+        manager.start_command("slow", "slow".to_string(), command, HashMap::new(), McpServerMode::HubHosted).unwrap();
+
+        assert!(manager.ping("slow", Duration::from_millis(200)).is_err());
+        let servers = manager.get_servers();
+        assert!(matches!(servers[0].status, McpServerStatus::Unresponsive));
+        assert!(servers[0].pid.is_some());
+
+        let latency = manager.ping("slow", Duration::from_secs(5)).unwrap();
+        assert!(latency < Duration::from_secs(5));
+        assert!(matches!(manager.get_servers()[0].status, McpServerStatus::Running));
+
+        assert!(manager.ping("missing", PING_TIMEOUT).is_err());
+        manager.stop_all().unwrap();
+    }
+
+    #[test]
+    fn stop_lets_a_server_exit_on_sigterm_and_kills_one_that_ignores_it() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let spawn = |script: &str| Command::new("sh").args(["-c", script]).spawn().unwrap();
+
+        let mut polite = spawn("trap 'exit 7' TERM; while true; do sleep 0.05; done");
+        std::thread::sleep(Duration::from_millis(100)); // let the trap install
+        let status = terminate(&mut polite, Duration::from_secs(5)).unwrap();
+        assert_eq!(status.code(), Some(7));
+
+        let mut stubborn = spawn("trap '' TERM; while true; do sleep 0.05; done");
+        std::thread::sleep(Duration::from_millis(100));
+        let started = Instant::now();
+        let status = terminate(&mut stubborn, Duration::from_millis(200)).unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        let manager = McpProcessManager::new();
+        let command = vec!["sh".to_string(), "-c".to_string(), "trap 'exit 0' TERM; while true; do sleep 0.05; done".to_string()];
+        manager.start_command("graceful", "graceful".to_string(), command, HashMap::new(), McpServerMode::HubHosted).unwrap();
+        manager.stop("graceful", STOP_GRACE_PERIOD).unwrap();
+        assert!(manager.get_servers().is_empty());
+        assert!(manager.stop("graceful", STOP_GRACE_PERIOD).is_err());
+    }
+
+    #[test]
+    fn reset_gives_servers_a_chance_to_exit_on_sigterm() {
+        let marker = std::env::temp_dir().join(format!("aidd-reset-term-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let manager = McpProcessManager::new();
+        let script = format!("trap 'touch {}; exit 0' TERM; while true; do sleep 0.05; done", marker.display());
+        let command = vec!["sh".to_string(), "-c".to_string(), script];
+        manager.start_command("polite", "polite".to_string(), command, HashMap::new(), McpServerMode::HubHosted).unwrap();
+        std::thread::sleep(Duration::from_millis(100)); // let the trap install
+
+        assert_eq!(manager.reset().stopped_servers, ["polite"]);
+        assert!(marker.exists(), "the server was killed without SIGTERM");
+
+        let _ = std::fs::remove_file(&marker);
+    }
 }
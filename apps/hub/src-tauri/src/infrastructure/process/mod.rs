@@ -1,3 +1,5 @@
+pub mod command_path;
 pub mod mcp_process;
+pub mod stderr_log;
 
-pub use mcp_process::McpProcessManager;
+pub use mcp_process::{McpProcessManager, PING_TIMEOUT, RESET_REASON, STOP_GRACE_PERIOD};
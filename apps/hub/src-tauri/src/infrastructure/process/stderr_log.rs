@@ -0,0 +1,179 @@
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+
+/// Bytes of stderr retained per process.
+pub const STDERR_LOG_CAPACITY: usize = 64 * 1024;
+
+/// Env var names whose values are masked in captured stderr.
+const SECRET_KEY_MARKERS: [&str; 6] = ["KEY", "TOKEN", "SECRET", "PASSWORD", "AUTH", "CREDENTIAL"];
+
+/// Values of `env` whose names look like they hold secrets.
+pub fn secret_env_values<'a>(env: impl IntoIterator<Item = (&'a String, &'a String)>) -> Vec<String> {
+    env.into_iter()
+        .filter(|(key, value)| {
+            let key = key.to_ascii_uppercase();
+            !value.is_empty() && SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+        })
+        .map(|(_, value)| value.clone())
+        .collect()
+}
+
+/// Ring buffer holding the most recent stderr output of a child process.
+#[derive(Debug)]
+pub struct StderrLog {
+    text: String,
+    capacity: usize,
+    /// Strings replaced by `***` before a line is stored or reported.
+    redactions: Vec<String>,
+}
+
+impl StderrLog {
+    pub fn new(capacity: usize) -> Self {
+        Self { text: String::new(), capacity, redactions: Vec::new() }
+    }
+
+    /// Mask each of `values` (e.g. API keys passed in the env) wherever it appears.
+    pub fn with_redactions(mut self, values: Vec<String>) -> Self {
+        self.redactions = values;
+        self
+    }
+
+    fn redact(&self, line: &str) -> String {
+        self.redactions
+            .iter()
+            .fold(line.to_string(), |line, secret| line.replace(secret.as_str(), "***"))
+    }
+
+    /// Append a line, dropping the oldest output once over capacity.
+    pub fn push_line(&mut self, line: &str) {
+        self.text.push_str(line);
+        self.text.push('\n');
+        if self.text.len() > self.capacity {
+            let mut cut = self.text.len() - self.capacity;
+            while !self.text.is_char_boundary(cut) {
+                cut += 1;
+            }
+            // Prefer restarting at a line boundary so the first line isn't partial.
+            if let Some(nl) = self.text[cut..].find('\n') {
+                if cut + nl + 1 < self.text.len() {
+                    cut += nl + 1;
+                }
+            }
+            self.text.drain(..cut);
+        }
+    }
+
+    pub fn contents(&self) -> &str {
+        &self.text
+    }
+
+    /// The last `n` lines, oldest first.
+    pub fn tail(&self, n: usize) -> String {
+        let lines: Vec<&str> = self.text.lines().collect();
+        lines[lines.len().saturating_sub(n)..].join("\n")
+    }
+}
+
+/// Heuristic for stderr lines worth surfacing immediately.
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    ["error", "panic", "fatal", "uncaught", "exception"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+}
+
+/// Drain `stderr` on a background thread into `log`, calling `on_error` for
+/// lines matching `is_error_line`. The thread ends when the pipe closes.
+pub fn spawn_stderr_reader<R, F>(stderr: R, log: Arc<Mutex<StderrLog>>, mut on_error: F)
+where
+    R: Read + Send + 'static,
+    F: FnMut(&str) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stderr);
+        let mut raw = Vec::new();
+        loop {
+            raw.clear();
+            match reader.read_until(b'\n', &mut raw) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&raw);
+                    let line = line.trim_end_matches(['\r', '\n']);
+                    let line = {
+                        let mut log = log.lock().unwrap_or_else(|e| e.into_inner());
+                        let line = log.redact(line);
+                        log.push_line(&line);
+                        line
+                    };
+                    if is_error_line(&line) {
+                        on_error(&line);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn ring_buffer_keeps_only_recent_whole_lines() {
+        let mut log = StderrLog::new(32);
+        for i in 0..10 {
+            log.push_line(&format!("line number {}", i));
+        }
+        assert!(log.contents().len() <= 32);
+        assert!(log.contents().starts_with("line number"));
+        assert!(log.contents().ends_with("line number 9\n"));
+        assert_eq!(log.tail(1), "line number 9");
+    }
+
+    #[test]
+    fn reader_captures_output_and_reports_error_lines() {
+        let input = b"starting engine\nError: Cannot find module 'better-sqlite3'\r\nready\n".to_vec();
+        let log = Arc::new(Mutex::new(StderrLog::new(STDERR_LOG_CAPACITY)));
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        spawn_stderr_reader(std::io::Cursor::new(input), log.clone(), move |line| {
+            let _ = tx.send(line.to_string());
+        });
+
+        // The sender is dropped when the reader thread finishes.
+        let errors: Vec<String> = rx.iter().collect();
+        assert_eq!(errors, vec!["Error: Cannot find module 'better-sqlite3'".to_string()]);
+        assert_eq!(
+            log.lock().unwrap().contents(),
+            "starting engine\nError: Cannot find module 'better-sqlite3'\nready\n"
+        );
+    }
+
+    #[test]
+    fn secret_env_values_are_masked_in_stored_and_reported_lines() {
+        let env: HashMap<String, String> = [
+            ("OPENAI_API_KEY", "sk-live-123"),
+            ("GITHUB_TOKEN", "ghp_abc"),
+            ("AIDD_LOG_LEVEL", "debug"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let mut secrets = secret_env_values(&env);
+        secrets.sort();
+        assert_eq!(secrets, ["ghp_abc", "sk-live-123"]);
+
+        let input = b"debug: using key sk-live-123\nError: 401 for token ghp_abc\n".to_vec();
+        let log = Arc::new(Mutex::new(StderrLog::new(STDERR_LOG_CAPACITY).with_redactions(secrets)));
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        spawn_stderr_reader(std::io::Cursor::new(input), log.clone(), move |line| {
+            let _ = tx.send(line.to_string());
+        });
+
+        let errors: Vec<String> = rx.iter().collect();
+        assert_eq!(errors, vec!["Error: 401 for token ***".to_string()]);
+        assert_eq!(log.lock().unwrap().contents(), "debug: using key ***\nError: 401 for token ***\n");
+    }
+}
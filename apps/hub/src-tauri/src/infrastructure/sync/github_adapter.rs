@@ -1,62 +1,89 @@
 use std::io::Read;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::model::{ReleaseInfo, SyncPhase, SyncProgress};
+use crate::domain::ports::outbound::iso_from_unix_ms;
+use crate::domain::AiddError;
 
 const GITHUB_OWNER: &str = "DerianAndre";
 const GITHUB_REPO: &str = "aidd.md";
 const GITHUB_API_BASE: &str = "https://api.github.com";
 
+/// Framework directories a release populates. Only these are extracted, and
+/// only files under them are considered for pruning.
+pub(crate) const RELEASE_CATEGORIES: [&str; 6] = [
+    "rules/", "skills/", "knowledge/", "workflows/", "templates/", "specs/",
+];
+
+/// Releases requested per page from `/releases` (GitHub's maximum).
+const RELEASES_PER_PAGE: usize = 100;
+/// Upper bound on pages fetched by `list_releases`.
+const MAX_RELEASE_PAGES: usize = 10;
+
+/// Minimum gap between two progress reports of the same phase.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time allowed for a release metadata request (not downloads, which can be large).
+const API_TIMEOUT: Duration = Duration::from_secs(15);
+
 /// A single GitHub release from the API.
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
     body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
 }
 
 /// Infrastructure adapter for fetching framework releases from GitHub.
 pub struct GitHubAdapter {
     client: reqwest::Client,
+    api_base: String,
+    api_timeout: Duration,
 }
 
 impl GitHubAdapter {
     pub fn new() -> Self {
+        Self::with_api_base(GITHUB_API_BASE, API_TIMEOUT)
+    }
+
+    /// Adapter talking to another API host, e.g. a local mock server in tests.
+    pub(crate) fn with_api_base(api_base: &str, api_timeout: Duration) -> Self {
         let client = reqwest::Client::builder()
             .user_agent("aidd-hub/1.0")
             .build()
             .unwrap_or_default();
-        Self { client }
+        Self { client, api_base: api_base.to_string(), api_timeout }
     }
 
     /// Fetch the latest release tag and changelog from GitHub.
-    pub async fn fetch_latest_release(&self) -> Result<(String, Option<String>), String> {
+    ///
+    /// Failures are classified so callers can tell being offline from a timeout,
+    /// a rate limit, or GitHub itself failing.
+    pub async fn fetch_latest_release(&self) -> Result<(String, Option<String>), AiddError> {
         let url = format!(
             "{}/repos/{}/{}/releases/latest",
-            GITHUB_API_BASE, GITHUB_OWNER, GITHUB_REPO
+            self.api_base, GITHUB_OWNER, GITHUB_REPO
         );
 
-        let resp = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("GitHub API request failed: {}", e))?;
+        let resp = self.api_get(&url, "Checking for updates").await?;
 
         if resp.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err("No releases found for this repository".to_string());
-        }
-
-        if !resp.status().is_success() {
-            return Err(format!(
-                "GitHub API returned status {}",
-                resp.status()
-            ));
+            return Err(AiddError::Network("No releases found for this repository".to_string()));
         }
 
         let release: GitHubRelease = resp
             .json()
             .await
-            .map_err(|e| format!("Failed to parse GitHub release: {}", e))?;
+            .map_err(|e| AiddError::Network(format!("Failed to parse GitHub release: {}", e)))?;
 
         // Strip leading 'v' if present (e.g. "v1.0.0" → "1.0.0")
         let version = release.tag_name.strip_prefix('v').unwrap_or(&release.tag_name);
@@ -64,13 +91,52 @@ impl GitHubAdapter {
         Ok((version.to_string(), release.body))
     }
 
+    /// List published releases, newest first, following pagination. Drafts are
+    /// never returned; prereleases only when `include_prereleases` is set.
+    pub async fn list_releases(&self, include_prereleases: bool) -> Result<Vec<ReleaseInfo>, AiddError> {
+        let mut releases = Vec::new();
+        for page in 1..=MAX_RELEASE_PAGES {
+            let url = format!(
+                "{}/repos/{}/{}/releases?per_page={}&page={}",
+                self.api_base, GITHUB_OWNER, GITHUB_REPO, RELEASES_PER_PAGE, page
+            );
+
+            let resp = self.api_get(&url, "Listing releases").await?;
+            if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(AiddError::Network(format!("GitHub API returned status {}", resp.status())));
+            }
+
+            let batch: Vec<GitHubRelease> = resp
+                .json()
+                .await
+                .map_err(|e| AiddError::Network(format!("Failed to parse GitHub releases: {}", e)))?;
+            let last_page = batch.len() < RELEASES_PER_PAGE;
+            releases.extend(batch);
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(to_release_infos(releases, include_prereleases))
+    }
+
     /// Download and extract a release zipball into the target directory.
     /// Overwrites existing files but does NOT delete files not in the release.
+    /// Returns the `/`-separated relative paths of the files written.
+    ///
+    /// The download is rejected before extraction if its size differs from the
+    /// `Content-Length` header or, when `expected_sha256` is given, if its SHA256
+    /// digest does not match. A rejected download is `AiddError::Other`: fetching
+    /// the same archive again is not expected to fix it.
+    ///
+    /// `on_progress` receives throttled download and extraction progress.
     pub async fn download_and_extract(
         &self,
         version: &str,
         target_dir: &Path,
-    ) -> Result<(), String> {
+        expected_sha256: Option<String>,
+        on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+    ) -> Result<Vec<String>, AiddError> {
         // Try tagged release zipball first, fallback to archive URL
         let tag = if version.starts_with('v') {
             version.to_string()
@@ -80,49 +146,204 @@ impl GitHubAdapter {
 
         let url = format!(
             "{}/repos/{}/{}/zipball/{}",
-            GITHUB_API_BASE, GITHUB_OWNER, GITHUB_REPO, tag
+            self.api_base, GITHUB_OWNER, GITHUB_REPO, tag
         );
 
-        let resp = self
+        let mut resp = self
             .client
             .get(&url)
             .send()
             .await
-            .map_err(|e| format!("Download failed: {}", e))?;
+            .map_err(|e| self.request_error(e, "Framework download"))?;
 
         if !resp.status().is_success() {
-            return Err(format!(
-                "Download returned status {}",
-                resp.status()
-            ));
+            return Err(status_error(resp.status(), resp.headers()));
+        }
+
+        let content_length = resp.content_length();
+        let mut progress = ProgressThrottle::new(on_progress);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| AiddError::Network(format!("Failed to read download body: {}", e)))?
+        {
+            bytes.extend_from_slice(&chunk);
+            progress.report(SyncProgress {
+                phase: SyncPhase::Download,
+                current: bytes.len() as u64,
+                total: content_length,
+            });
         }
 
-        let bytes = resp
-            .bytes()
+        // Verify integrity, then extract zip into target directory
+        verify_and_extract(&bytes, content_length, expected_sha256.as_deref(), target_dir, on_progress)
+    }
+
+    /// GET an API URL, turning transport failures and error statuses (other
+    /// than 404, which callers interpret) into classified errors.
+    async fn api_get(&self, url: &str, operation: &str) -> Result<reqwest::Response, AiddError> {
+        let resp = self
+            .client
+            .get(url)
+            .timeout(self.api_timeout)
+            .send()
             .await
-            .map_err(|e| format!("Failed to read download body: {}", e))?;
+            .map_err(|e| self.request_error(e, operation))?;
+
+        let status = resp.status();
+        if status.is_success() || status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(resp);
+        }
+        Err(status_error(status, resp.headers()))
+    }
+
+    fn request_error(&self, err: reqwest::Error, operation: &str) -> AiddError {
+        if err.is_timeout() {
+            AiddError::Timeout {
+                operation: operation.to_string(),
+                after_ms: self.api_timeout.as_millis() as u64,
+            }
+        } else if err.is_connect() {
+            let host = err.url().and_then(|u| u.host_str()).unwrap_or("api.github.com");
+            AiddError::Offline { host: host.to_string() }
+        } else {
+            AiddError::Network(format!("GitHub API request failed: {}", err))
+        }
+    }
+}
+
+/// Classify an unsuccessful API status. GitHub signals primary rate limits with
+/// 403 + `x-ratelimit-remaining: 0` and secondary ones with 429 (or 403 +
+/// `retry-after`).
+fn status_error(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap) -> AiddError {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let exhausted = header("x-ratelimit-remaining") == Some("0");
+    let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || (status == reqwest::StatusCode::FORBIDDEN && (exhausted || header("retry-after").is_some()));
+
+    if rate_limited {
+        let reset_at = header("x-ratelimit-reset")
+            .and_then(|secs| secs.parse::<i64>().ok())
+            .map(|secs| iso_from_unix_ms(secs * 1000));
+        return AiddError::RateLimited { service: "GitHub".to_string(), reset_at };
+    }
+    if status.is_server_error() {
+        return AiddError::ServerError { service: "GitHub".to_string(), status: status.as_u16() };
+    }
+    AiddError::Network(format!("GitHub API returned status {}", status))
+}
+
+/// Map API releases to [`ReleaseInfo`], dropping drafts and (unless asked for)
+/// prereleases.
+fn to_release_infos(releases: Vec<GitHubRelease>, include_prereleases: bool) -> Vec<ReleaseInfo> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft && (include_prereleases || !r.prerelease))
+        .map(|r| ReleaseInfo {
+            version: r.tag_name.strip_prefix('v').unwrap_or(&r.tag_name).to_string(),
+            tag: r.tag_name,
+            published_at: r.published_at,
+            prerelease: r.prerelease,
+            changelog: r.body,
+        })
+        .collect()
+}
+
+/// Forwards progress at most once per [`PROGRESS_INTERVAL`] so a fast download
+/// doesn't flood the IPC channel. A phase's final report always goes through.
+struct ProgressThrottle<'a> {
+    listener: &'a (dyn Fn(SyncProgress) + Send + Sync),
+    last_sent: Option<Instant>,
+}
+
+impl<'a> ProgressThrottle<'a> {
+    fn new(listener: &'a (dyn Fn(SyncProgress) + Send + Sync)) -> Self {
+        Self { listener, last_sent: None }
+    }
+
+    fn report(&mut self, progress: SyncProgress) {
+        let finished = progress.total.is_some_and(|total| progress.current >= total);
+        let due = self.last_sent.is_none_or(|at| at.elapsed() >= PROGRESS_INTERVAL);
+        if finished || due {
+            self.last_sent = Some(Instant::now());
+            (self.listener)(progress);
+        }
+    }
+}
+
+/// Verify a downloaded archive and extract it only if every check passes.
+fn verify_and_extract(
+    data: &[u8],
+    content_length: Option<u64>,
+    expected_sha256: Option<&str>,
+    target_dir: &Path,
+    on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+) -> Result<Vec<String>, AiddError> {
+    verify_download(data, content_length, expected_sha256).map_err(AiddError::Other)?;
+    extract_zip(data, target_dir, on_progress).map_err(AiddError::Io)
+}
 
-        // Extract zip into target directory
-        extract_zip(&bytes, target_dir)
+/// Check the received byte count against `Content-Length` and the SHA256 digest
+/// against the expected checksum (if any).
+fn verify_download(
+    data: &[u8],
+    content_length: Option<u64>,
+    expected_sha256: Option<&str>,
+) -> Result<(), String> {
+    if let Some(expected_len) = content_length {
+        if data.len() as u64 != expected_len {
+            return Err(format!(
+                "Download incomplete: received {} bytes, expected {}",
+                data.len(),
+                expected_len
+            ));
+        }
+    }
+
+    if let Some(expected) = expected_sha256 {
+        let actual = sha256_hex(data);
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(format!(
+                "Checksum mismatch: expected sha256 {}, got {}",
+                expected.trim(),
+                actual
+            ));
+        }
     }
+
+    Ok(())
+}
+
+/// Compute the lowercase hex SHA256 digest of a byte slice.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
 /// Extract a zip archive, stripping the top-level directory GitHub adds.
 /// Only extracts framework-relevant directories: rules/, skills/, knowledge/,
 /// workflows/, templates/, specs/, and top-level files like AGENTS.md.
-fn extract_zip(data: &[u8], target_dir: &Path) -> Result<(), String> {
+/// Returns the relative paths of the extracted files.
+fn extract_zip(
+    data: &[u8],
+    target_dir: &Path,
+    on_progress: &(dyn Fn(SyncProgress) + Send + Sync),
+) -> Result<Vec<String>, String> {
     let cursor = std::io::Cursor::new(data);
     let mut archive = zip::ZipArchive::new(cursor)
         .map_err(|e| format!("Failed to open zip: {}", e))?;
 
-    // Framework categories to extract
-    let categories = [
-        "rules/", "skills/", "knowledge/", "workflows/", "templates/", "specs/",
-    ];
     // Top-level files to extract
     let top_files = ["AGENTS.md", "CONTRIBUTING.md", "README.md"];
 
+    let mut extracted = Vec::new();
+    let total = Some(archive.len() as u64);
+    let mut progress = ProgressThrottle::new(on_progress);
     for i in 0..archive.len() {
+        progress.report(SyncProgress { phase: SyncPhase::Extract, current: i as u64, total });
         let mut file = archive
             .by_index(i)
             .map_err(|e| format!("Zip entry error: {}", e))?;
@@ -141,7 +362,7 @@ fn extract_zip(data: &[u8], target_dir: &Path) -> Result<(), String> {
         let relative_str = relative.to_string_lossy();
 
         // Filter: only extract framework-relevant content
-        let should_extract = categories.iter().any(|cat| relative_str.starts_with(cat))
+        let should_extract = RELEASE_CATEGORIES.iter().any(|cat| relative_str.starts_with(cat))
             || top_files.iter().any(|f| relative_str == *f);
 
         if !should_extract {
@@ -166,8 +387,194 @@ fn extract_zip(data: &[u8], target_dir: &Path) -> Result<(), String> {
 
             std::fs::write(&target_path, &content)
                 .map_err(|e| format!("Failed to write {}: {}", target_path.display(), e))?;
+            extracted.push(relative_str.replace('\\', "/"));
         }
     }
+    progress.report(SyncProgress { phase: SyncPhase::Extract, current: archive.len() as u64, total });
 
-    Ok(())
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_target(label: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "aidd-hub-{}-{}",
+            label,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn sha256_hex_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn checksum_mismatch_skips_extraction() {
+        let target = temp_target("sha-mismatch");
+        let data = b"not the archive we expected";
+        let wrong = sha256_hex(b"something else");
+
+        let result = verify_and_extract(data, Some(data.len() as u64), Some(&wrong), &target, &|_| {});
+
+        let err = result.unwrap_err();
+        assert!(matches!(&err, AiddError::Other(message) if message.contains("Checksum mismatch")), "{}", err);
+        assert!(!target.exists(), "extraction must not run on checksum mismatch");
+    }
+
+    #[test]
+    fn content_length_mismatch_skips_extraction() {
+        let target = temp_target("length-mismatch");
+        let data = b"truncated";
+
+        let result = verify_and_extract(data, Some(data.len() as u64 + 10), None, &target, &|_| {});
+
+        assert!(result.unwrap_err().to_string().contains("Download incomplete"));
+        assert!(!target.exists(), "extraction must not run on truncated download");
+    }
+
+    #[test]
+    fn checksum_comparison_is_case_insensitive() {
+        let data = b"abc";
+        let upper = sha256_hex(data).to_uppercase();
+        assert!(verify_download(data, Some(3), Some(&upper)).is_ok());
+    }
+
+    #[test]
+    fn extraction_reports_progress_and_throttles() {
+        use std::io::Write;
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for name in ["repo/rules/a.md", "repo/rules/b.md", "repo/README.md", "repo/src/main.rs"] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(b"# x\n").unwrap();
+        }
+        let data = zip.finish().unwrap().into_inner();
+        let target = temp_target("extract-progress");
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let extracted = extract_zip(&data, &target, &|p| events.lock().unwrap().push(p)).unwrap();
+        assert_eq!(extracted, ["rules/a.md", "rules/b.md", "README.md"]);
+        let events = events.into_inner().unwrap();
+
+        // The first report passes, the rest land within the interval, the final one is forced
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], SyncProgress { phase: SyncPhase::Extract, current: 0, total: Some(4) });
+        assert_eq!(events[1], SyncProgress { phase: SyncPhase::Extract, current: 4, total: Some(4) });
+        assert!(target.join("rules").join("a.md").exists());
+        assert!(!target.join("src").exists());
+
+        let _ = std::fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn release_listing_skips_drafts_and_optionally_prereleases() {
+        let releases: Vec<GitHubRelease> = serde_json::from_str(
+            r#"[
+                {"tag_name": "v1.3.0-rc.1", "body": "RC", "published_at": "2026-03-01T00:00:00Z", "prerelease": true},
+                {"tag_name": "v1.2.0", "body": "Stable", "published_at": "2026-02-01T00:00:00Z", "prerelease": false},
+                {"tag_name": "v1.4.0", "body": null, "published_at": null, "prerelease": false, "draft": true},
+                {"tag_name": "1.1.0", "body": null, "published_at": "2026-01-01T00:00:00Z"}
+            ]"#,
+        )
+        .unwrap();
+
+        let stable = to_release_infos(releases, false);
+        let versions: Vec<&str> = stable.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.0", "1.1.0"]);
+        assert_eq!(stable[0].tag, "v1.2.0");
+        assert_eq!(stable[0].changelog.as_deref(), Some("Stable"));
+        assert_eq!(stable[0].published_at.as_deref(), Some("2026-02-01T00:00:00Z"));
+
+        let releases: Vec<GitHubRelease> = serde_json::from_str(
+            r#"[{"tag_name": "v1.3.0-rc.1", "body": "RC", "prerelease": true}]"#,
+        )
+        .unwrap();
+        let all = to_release_infos(releases, true);
+        assert_eq!(all.len(), 1);
+        assert!(all[0].prerelease);
+    }
+
+    /// Serve one connection on a local port: answer it with `response`, or hold
+    /// it open without replying when `response` is `None`.
+    fn mock_api(response: Option<&'static str>) -> GitHubAdapter {
+        use std::io::Write;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else { return };
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            match response {
+                Some(response) => {
+                    let _ = stream.write_all(response.as_bytes());
+                }
+                None => std::thread::sleep(Duration::from_secs(2)),
+            }
+        });
+        GitHubAdapter::with_api_base(&base, Duration::from_millis(200))
+    }
+
+    #[tokio::test]
+    async fn exhausted_rate_limit_is_reported_as_rate_limited() {
+        let github = mock_api(Some(
+            "HTTP/1.1 403 Forbidden\r\nx-ratelimit-remaining: 0\r\nx-ratelimit-reset: 1769904000\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ));
+
+        let err = github.fetch_latest_release().await.unwrap_err();
+
+        assert_eq!(
+            err,
+            AiddError::RateLimited {
+                service: "GitHub".to_string(),
+                reset_at: Some("2026-02-01T00:00:00.000Z".to_string()),
+            }
+        );
+        assert_eq!(err.code(), "rate_limited");
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn unanswered_request_is_reported_as_timeout() {
+        let github = mock_api(None);
+
+        let err = github.fetch_latest_release().await.unwrap_err();
+
+        assert_eq!(
+            err,
+            AiddError::Timeout { operation: "Checking for updates".to_string(), after_ms: 200 }
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_connection_is_reported_as_offline() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let github = GitHubAdapter::with_api_base(&format!("http://127.0.0.1:{}", port), Duration::from_millis(200));
+
+        let err = github.fetch_latest_release().await.unwrap_err();
+
+        assert_eq!(err, AiddError::Offline { host: "127.0.0.1".to_string() });
+    }
+
+    #[test]
+    fn status_errors_separate_server_failures_from_plain_refusals() {
+        let headers = reqwest::header::HeaderMap::new();
+        assert_eq!(
+            status_error(reqwest::StatusCode::BAD_GATEWAY, &headers),
+            AiddError::ServerError { service: "GitHub".to_string(), status: 502 }
+        );
+        // A 403 without rate-limit headers is a permission problem, not worth retrying
+        let forbidden = status_error(reqwest::StatusCode::FORBIDDEN, &headers);
+        assert_eq!(forbidden.code(), "network");
+        assert!(!forbidden.is_transient());
+    }
 }
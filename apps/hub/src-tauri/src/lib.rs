@@ -5,7 +5,9 @@ mod presentation;
 
 use std::sync::Arc;
 
+use domain::ports::inbound::McpPort;
 use application::{FrameworkService, IntegrationService, McpService, McpHealthService, OverrideService, ProjectService, MemoryService};
+use infrastructure::clock::SystemClock;
 use infrastructure::filesystem::FileAdapter;
 use infrastructure::persistence::JsonStore;
 use infrastructure::adapters::SqliteMemoryAdapter;
@@ -29,14 +31,16 @@ pub fn run() {
         JsonStore::new().expect("Failed to initialize ~/.aidd/ storage"),
     );
     let file_adapter = Arc::new(FileAdapter);
+    let clock = Arc::new(SystemClock);
 
     // Application services
     let project_service = Arc::new(ProjectService::new(
         json_store.clone(),
         file_adapter.clone(),
+        clock.clone(),
     ));
     let framework_service = Arc::new(
-        FrameworkService::new(json_store.aidd_dir(), json_store.clone(), file_adapter.clone())
+        FrameworkService::new(json_store.aidd_dir(), json_store.clone(), file_adapter.clone(), clock.clone())
             .expect("Failed to initialize framework service"),
     );
     let integration_service = Arc::new(
@@ -47,9 +51,12 @@ pub fn run() {
         file_adapter.clone(),
     ));
     let process_manager = Arc::new(infrastructure::process::McpProcessManager::new());
+    process_manager.spawn_supervisor(std::time::Duration::from_secs(2));
     let mcp_service = Arc::new(McpService::new(process_manager.clone(), project_service.clone()));
     let config_scanner = infrastructure::integrations::McpConfigScanner::new();
-    let mcp_health_service = Arc::new(McpHealthService::new(config_scanner, process_manager));
+    let mcp_health_service = Arc::new(McpHealthService::new(config_scanner, process_manager.clone()));
+    let exit_mcp_service = mcp_service.clone();
+    let sync_framework_service = framework_service.clone();
 
     // Memory service with SQLite adapter (wired to active project)
     let sqlite_memory_adapter = Box::new(SqliteMemoryAdapter::new(project_service.clone()));
@@ -70,47 +77,94 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .manage(ctx)
+        .manage(presentation::commands::watcher_commands::WatcherRegistry::default())
+        .setup(move |app| {
+            presentation::commands::mcp_commands::forward_server_errors(app.handle(), &process_manager);
+            // The scheduler itself skips checks while auto_sync is off
+            if let Err(e) = presentation::commands::framework_commands::start_auto_sync(
+                app.handle(),
+                &sync_framework_service,
+                application::DEFAULT_AUTO_SYNC_INTERVAL_SECS,
+            ) {
+                eprintln!("[framework] Failed to start auto-sync: {}", e);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Project management (DDD)
             presentation::commands::project_commands::detect_project,
             presentation::commands::project_commands::add_project,
             presentation::commands::project_commands::remove_project,
             presentation::commands::project_commands::list_projects,
+            presentation::commands::project_commands::list_projects_by_recent,
             presentation::commands::project_commands::get_active_project,
             presentation::commands::project_commands::set_active_project,
+            presentation::commands::project_commands::list_active_projects,
+            presentation::commands::project_commands::add_active_project,
+            presentation::commands::project_commands::remove_active_project,
+            presentation::commands::project_commands::get_detection_policy,
+            presentation::commands::project_commands::set_detection_policy,
+            presentation::commands::project_commands::diagnose_project,
+            presentation::commands::project_commands::verify_all_projects,
             // Framework management (DDD)
             presentation::commands::framework_commands::get_framework_path,
             presentation::commands::framework_commands::get_framework_version,
             presentation::commands::framework_commands::list_framework_entities,
+            presentation::commands::framework_commands::search_framework_entities,
             presentation::commands::framework_commands::read_framework_entity,
             presentation::commands::framework_commands::write_framework_entity,
+            presentation::commands::framework_commands::write_framework_entity_if_unchanged,
+            presentation::commands::framework_commands::write_framework_entity_parts,
             presentation::commands::framework_commands::delete_framework_entity,
+            presentation::commands::framework_commands::duplicate_framework_entity,
             // Framework sync
             presentation::commands::framework_commands::get_sync_status,
             presentation::commands::framework_commands::check_for_updates,
+            presentation::commands::framework_commands::list_framework_versions,
             presentation::commands::framework_commands::sync_framework,
+            presentation::commands::framework_commands::preview_framework_sync,
+            presentation::commands::framework_commands::rollback_framework,
             presentation::commands::framework_commands::set_auto_sync,
+            presentation::commands::framework_commands::set_auto_install,
+            presentation::commands::framework_commands::start_auto_sync_scheduler,
+            presentation::commands::framework_commands::stop_auto_sync_scheduler,
             // Integration management (DDD)
             presentation::commands::integration_commands::integrate_tool,
             presentation::commands::integration_commands::remove_integration,
+            presentation::commands::integration_commands::remove_all_integrations,
+            presentation::commands::integration_commands::repair_integration,
             presentation::commands::integration_commands::check_integrations,
             presentation::commands::integration_commands::list_integration_types,
+            presentation::commands::integration_commands::export_integrations,
+            presentation::commands::integration_commands::import_integrations,
+            presentation::commands::integration_commands::parse_agents_md,
             // Project overrides
             presentation::commands::override_commands::get_project_overrides,
             presentation::commands::override_commands::set_agent_override,
+            presentation::commands::override_commands::set_skill_override,
             presentation::commands::override_commands::add_project_rule,
             presentation::commands::override_commands::remove_project_rule,
             presentation::commands::override_commands::list_project_rules,
             presentation::commands::override_commands::get_effective_entities,
+            presentation::commands::override_commands::diff_override,
+            presentation::commands::override_commands::framework_content_budget,
             // MCP server management (DDD)
             presentation::commands::mcp_commands::start_mcp_server,
             presentation::commands::mcp_commands::stop_mcp_server,
             presentation::commands::mcp_commands::stop_all_mcp_servers,
+            presentation::commands::mcp_commands::reset_mcp_state,
             presentation::commands::mcp_commands::get_mcp_servers,
+            presentation::commands::mcp_commands::get_mcp_server_logs,
+            presentation::commands::mcp_commands::set_restart_policy,
+            presentation::commands::mcp_commands::ping_server,
             presentation::commands::mcp_commands::list_mcp_tools,
             presentation::commands::mcp_commands::call_mcp_tool,
+            presentation::commands::mcp_commands::get_mcp_connect_info,
+            presentation::commands::mcp_commands::validate_mcp_spec,
             // MCP health scanning
             presentation::commands::mcp_health_commands::scan_mcp_health,
+            presentation::commands::mcp_health_commands::start_mcp_health_monitor,
+            presentation::commands::mcp_health_commands::stop_mcp_health_monitor,
             // Filesystem
             presentation::commands::filesystem_commands::read_file,
             presentation::commands::filesystem_commands::write_file,
@@ -120,25 +174,38 @@ pub fn run() {
             presentation::commands::filesystem_commands::file_exists,
             presentation::commands::filesystem_commands::read_json_file,
             presentation::commands::filesystem_commands::write_json_file,
+            presentation::commands::filesystem_commands::read_entity_at_revision,
             // File watcher
             presentation::commands::watcher_commands::start_watching,
+            presentation::commands::watcher_commands::start_watching_many,
             presentation::commands::watcher_commands::stop_watching,
+            presentation::commands::watcher_commands::list_watchers,
             // Memory management (DDD + Hexagonal)
             presentation::commands::memory_commands::get_memory_snapshot,
             presentation::commands::memory_commands::get_sessions,
+            presentation::commands::memory_commands::get_session_stats,
             presentation::commands::memory_commands::get_evolution_status,
             presentation::commands::memory_commands::get_pattern_stats,
+            presentation::commands::memory_commands::get_memory_storage_info,
+            presentation::commands::memory_commands::get_memory_source,
+            presentation::commands::memory_commands::export_memory_bundle,
+            presentation::commands::memory_commands::import_memory_bundle,
+            presentation::commands::memory_commands::get_activity_feed,
+            presentation::commands::memory_commands::observation_daily_counts,
             presentation::commands::memory_commands::list_all_observations,
+            presentation::commands::memory_commands::get_observation_type_counts,
             presentation::commands::memory_commands::list_observations_by_session,
             presentation::commands::memory_commands::search_observations,
             presentation::commands::memory_commands::list_all_sessions,
             presentation::commands::memory_commands::list_evolution_candidates,
             presentation::commands::memory_commands::list_evolution_log,
             presentation::commands::memory_commands::list_permanent_memory,
+            presentation::commands::memory_commands::search_permanent_memory,
             presentation::commands::memory_commands::delete_permanent_memory,
             presentation::commands::memory_commands::list_drafts,
             presentation::commands::memory_commands::list_artifacts,
             presentation::commands::memory_commands::list_audit_scores,
+            presentation::commands::memory_commands::list_pattern_detections,
             presentation::commands::memory_commands::get_governance_config,
             presentation::commands::memory_commands::upsert_governance_config,
             // Memory write commands
@@ -155,6 +222,7 @@ pub fn run() {
             presentation::commands::memory_commands::delete_session,
             presentation::commands::memory_commands::update_session,
             presentation::commands::memory_commands::update_session_full,
+            presentation::commands::memory_commands::reopen_session,
             // Observation CRUD
             presentation::commands::memory_commands::create_observation,
             presentation::commands::memory_commands::update_observation,
@@ -167,7 +235,24 @@ pub fn run() {
             presentation::commands::memory_commands::create_draft,
             presentation::commands::memory_commands::update_draft,
             presentation::commands::memory_commands::delete_draft,
+            presentation::commands::memory_commands::delete_drafts_by_status,
+            presentation::commands::memory_commands::create_banned_pattern,
+            presentation::commands::memory_commands::update_banned_pattern,
+            presentation::commands::memory_commands::mark_detection_false_positive,
+            // Bulk draft/artifact operations
+            presentation::commands::memory_commands::delete_drafts,
+            presentation::commands::memory_commands::reject_drafts,
+            presentation::commands::memory_commands::archive_artifacts,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running aidd.md Hub");
+        .build(tauri::generate_context!())
+        .expect("error while building aidd.md Hub")
+        .run(move |_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Don't leave MCP children behind when the Hub quits
+                if let Err(e) = exit_mcp_service.stop_all() {
+                    eprintln!("[mcp] Failed to stop servers on exit: {}", e);
+                }
+                exit_mcp_service.shutdown_clients();
+            }
+        });
 }
@@ -1,6 +1,9 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -11,6 +14,14 @@ pub struct FileEntry {
     pub size: u64,
 }
 
+/// Entries found by a directory scan, possibly cut short by its caps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryListing {
+    pub entries: Vec<FileEntry>,
+    /// Whether `max_depth` or `max_entries` stopped the scan early.
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarkdownEntity {
     pub path: String,
@@ -49,42 +60,188 @@ pub async fn file_exists(path: String) -> Result<bool, String> {
 }
 
 /// List files in a directory with optional extension filter and recursive flag.
+///
+/// `respect_gitignore` (default: same as `recursive`) skips paths excluded by the
+/// `.gitignore` files in effect, plus `node_modules`, `.git` and `target`.
+/// `max_depth` and `max_entries` bound the scan; hitting either sets `truncated`.
 #[tauri::command]
 pub async fn list_directory(
     path: String,
     extensions: Option<Vec<String>>,
     recursive: bool,
-) -> Result<Vec<FileEntry>, String> {
+    respect_gitignore: Option<bool>,
+    max_depth: Option<usize>,
+    max_entries: Option<usize>,
+) -> Result<DirectoryListing, String> {
     let p = Path::new(&path);
     if !p.is_dir() {
         return Err(format!("{} is not a directory", path));
     }
 
-    let mut entries = Vec::new();
-    collect_entries(p, &extensions, recursive, &mut entries)?;
-    Ok(entries)
+    let mut scan = Scan::new(p, &extensions, recursive, respect_gitignore.unwrap_or(recursive));
+    scan.max_depth = max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+    scan.max_entries = max_entries.unwrap_or(DEFAULT_MAX_ENTRIES);
+    collect_entries(p, 0, &mut scan)?;
+    Ok(DirectoryListing { entries: scan.entries, truncated: scan.truncated })
 }
 
-fn collect_entries(
-    dir: &Path,
-    extensions: &Option<Vec<String>>,
+/// Directory levels below the base a recursive scan descends into by default.
+const DEFAULT_MAX_DEPTH: usize = 32;
+/// Entries a single scan returns by default before reporting truncation.
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// State of one directory scan.
+struct Scan<'a> {
+    extensions: &'a Option<Vec<String>>,
     recursive: bool,
-    entries: &mut Vec<FileEntry>,
-) -> Result<(), String> {
+    max_depth: usize,
+    max_entries: usize,
+    ignores: IgnoreStack,
+    /// Canonical base directory; symlinks resolving outside it are not followed.
+    root: PathBuf,
+    /// Canonical directories already descended into, so symlink cycles end.
+    visited: HashSet<PathBuf>,
+    entries: Vec<FileEntry>,
+    truncated: bool,
+}
+
+impl<'a> Scan<'a> {
+    fn new(base: &Path, extensions: &'a Option<Vec<String>>, recursive: bool, respect_gitignore: bool) -> Self {
+        let root = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+        Self {
+            extensions,
+            recursive,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            ignores: IgnoreStack::new(base, respect_gitignore),
+            visited: HashSet::from([root.clone()]),
+            root,
+            entries: Vec::new(),
+            truncated: false,
+        }
+    }
+}
+
+/// Directories never worth descending into, whatever `.gitignore` says.
+const ALWAYS_IGNORED_DIRS: &[&str] = &["node_modules", ".git", "target"];
+
+/// `.gitignore` matchers in effect while scanning, outermost first.
+///
+/// Matchers are rooted at canonical directories; scanned paths are mapped onto
+/// the canonical base so relative or symlinked base paths match the same way.
+struct IgnoreStack {
+    enabled: bool,
+    base: PathBuf,
+    canonical_base: PathBuf,
+    matchers: Vec<Gitignore>,
+}
+
+impl IgnoreStack {
+    /// Load `.gitignore` files from `base` up to the enclosing git root (or the
+    /// filesystem root when `base` isn't in a repository).
+    fn new(base: &Path, enabled: bool) -> Self {
+        let canonical_base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+        let mut matchers = Vec::new();
+        if enabled {
+            for dir in canonical_base.ancestors() {
+                matchers.extend(load_gitignore(dir));
+                if dir.join(".git").exists() {
+                    break;
+                }
+            }
+            matchers.reverse();
+        }
+        Self { enabled, base: base.to_path_buf(), canonical_base, matchers }
+    }
+
+    /// Push the `.gitignore` of a directory being descended into. Returns whether
+    /// one was pushed, to hand back to [`IgnoreStack::leave`].
+    fn enter(&mut self, dir: &Path) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match load_gitignore(&self.canonical(dir)) {
+            Some(matcher) => {
+                self.matchers.push(matcher);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn leave(&mut self, pushed: bool) {
+        if pushed {
+            self.matchers.pop();
+        }
+    }
+
+    /// The deepest matcher with an opinion wins, so a nested `!keep` re-includes
+    /// what an outer file ignored.
+    fn is_ignored(&self, path: &Path, name: &str, is_dir: bool) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if is_dir && ALWAYS_IGNORED_DIRS.contains(&name) {
+            return true;
+        }
+        let path = self.canonical(path);
+        for matcher in self.matchers.iter().rev() {
+            match matcher.matched(&path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn canonical(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(&self.base) {
+            Ok(relative) => self.canonical_base.join(relative),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+fn load_gitignore(dir: &Path) -> Option<Gitignore> {
+    let file = dir.join(".gitignore");
+    if !file.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    builder.add(&file);
+    builder.build().ok().filter(|matcher| !matcher.is_empty())
+}
+
+fn collect_entries(dir: &Path, depth: usize, scan: &mut Scan) -> Result<(), String> {
     let read_dir = fs::read_dir(dir).map_err(|e| format!("Failed to read dir: {}", e))?;
 
     for entry in read_dir {
+        if scan.entries.len() >= scan.max_entries {
+            scan.truncated = true;
+            return Ok(());
+        }
+
         let entry = entry.map_err(|e| format!("Dir entry error: {}", e))?;
         let path = entry.path();
-        let metadata = entry
+        let mut metadata = entry
             .metadata()
             .map_err(|e| format!("Metadata error: {}", e))?;
 
+        // Symlinks are described by their target; dangling ones stay as links.
+        let mut link_target = None;
+        if metadata.file_type().is_symlink() {
+            if let Ok(resolved) = fs::metadata(&path) {
+                metadata = resolved;
+            }
+            link_target = path.canonicalize().ok();
+        }
+
         let is_dir = metadata.is_dir();
         let name = entry.file_name().to_string_lossy().to_string();
 
         // Skip hidden files/dirs
-        if name.starts_with('.') {
+        if name.starts_with('.') || scan.ignores.is_ignored(&path, &name, is_dir) {
             continue;
         }
 
@@ -94,7 +251,7 @@ fn collect_entries(
 
         // Filter by extension if specified
         if !is_dir {
-            if let Some(ref exts) = extensions {
+            if let Some(ref exts) = scan.extensions {
                 if let Some(ref ext) = extension {
                     if !exts.iter().any(|e| e == ext) {
                         continue;
@@ -105,7 +262,7 @@ fn collect_entries(
             }
         }
 
-        entries.push(FileEntry {
+        scan.entries.push(FileEntry {
             name: name.clone(),
             path: path.to_string_lossy().to_string(),
             is_dir,
@@ -113,30 +270,52 @@ fn collect_entries(
             size: metadata.len(),
         });
 
-        if is_dir && recursive {
-            collect_entries(&path, extensions, recursive, entries)?;
+        if !is_dir || !scan.recursive {
+            continue;
+        }
+        if depth >= scan.max_depth {
+            scan.truncated = true;
+            continue;
         }
+        let canonical = match link_target {
+            Some(target) if !target.starts_with(&scan.root) => continue,
+            Some(target) => target,
+            None => path.canonicalize().unwrap_or_else(|_| path.clone()),
+        };
+        if !scan.visited.insert(canonical) {
+            continue;
+        }
+
+        let pushed = scan.ignores.enter(&path);
+        let result = collect_entries(&path, depth + 1, scan);
+        scan.ignores.leave(pushed);
+        result?;
     }
 
     Ok(())
 }
 
 /// List markdown files in a directory, parsing YAML frontmatter from each.
+///
+/// `respect_gitignore` behaves as in [`list_directory`]; the scan uses the
+/// default depth and entry caps.
 #[tauri::command]
 pub async fn list_markdown_entities(
     base_path: String,
     recursive: bool,
+    respect_gitignore: Option<bool>,
 ) -> Result<Vec<MarkdownEntity>, String> {
     let p = Path::new(&base_path);
     if !p.is_dir() {
         return Err(format!("{} is not a directory", base_path));
     }
 
-    let mut file_entries = Vec::new();
-    collect_entries(p, &Some(vec!["md".to_string()]), recursive, &mut file_entries)?;
+    let extensions = Some(vec!["md".to_string()]);
+    let mut scan = Scan::new(p, &extensions, recursive, respect_gitignore.unwrap_or(recursive));
+    collect_entries(p, 0, &mut scan)?;
 
     let mut entities = Vec::new();
-    for entry in file_entries {
+    for entry in scan.entries {
         if entry.is_dir {
             continue;
         }
@@ -223,3 +402,169 @@ pub async fn write_json_file(path: String, data: serde_json::Value) -> Result<()
         .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
     fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))
 }
+
+/// Read a file's content as of a git revision (commit, tag, branch, `HEAD~1`, ...).
+#[tauri::command]
+pub async fn read_entity_at_revision(path: String, revision: String) -> Result<String, String> {
+    read_file_at_revision(Path::new(&path), &revision)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<std::process::Output, String> {
+    std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))
+}
+
+fn read_file_at_revision(path: &Path, revision: &str) -> Result<String, String> {
+    if revision.trim().is_empty() || revision.starts_with('-') {
+        return Err(format!("Invalid revision '{}'", revision));
+    }
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Not a file path: {}", path.display()))?;
+    // The file may not exist in the working tree anymore, so resolve via its directory.
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", path.display(), e))?;
+
+    let toplevel = git(&dir, &["rev-parse", "--show-toplevel"])?;
+    if !toplevel.status.success() {
+        return Err(format!("Not a git repository: {}", path.display()));
+    }
+    let root = Path::new(String::from_utf8_lossy(&toplevel.stdout).trim())
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve git root: {}", e))?;
+    let relative = dir
+        .join(file_name)
+        .strip_prefix(&root)
+        .map_err(|_| format!("{} is outside the git repository", path.display()))?
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let commit = format!("{}^{{commit}}", revision);
+    let verified = git(&root, &["rev-parse", "--verify", "--quiet", &commit])?;
+    if !verified.status.success() {
+        return Err(format!("Revision '{}' not found", revision));
+    }
+
+    let shown = git(&root, &["show", &format!("{}:{}", revision, relative)])?;
+    if !shown.status.success() {
+        return Err(format!("{} does not exist at revision '{}'", relative, revision));
+    }
+    String::from_utf8(shown.stdout).map_err(|e| format!("Failed to decode {}: {}", relative, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=aidd", "-c", "user.email=aidd@example.com", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn reads_entity_content_at_previous_commit() {
+        let repo = std::env::temp_dir().join(format!("aidd-git-fixture-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&repo);
+        let rules = repo.join("content").join("rules");
+        fs::create_dir_all(&rules).unwrap();
+        let entity = rules.join("testing.md");
+
+        run_git(&repo, &["init", "-q"]);
+        fs::write(&entity, "# Testing v1\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-q", "-m", "v1"]);
+        fs::write(&entity, "# Testing v2\n").unwrap();
+        run_git(&repo, &["commit", "-q", "-am", "v2"]);
+
+        assert_eq!(read_file_at_revision(&entity, "HEAD~1").unwrap(), "# Testing v1\n");
+        assert_eq!(read_file_at_revision(&entity, "HEAD").unwrap(), "# Testing v2\n");
+        assert_eq!(
+            read_file_at_revision(&entity, "no-such-ref"),
+            Err("Revision 'no-such-ref' not found".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    fn scan(base: &Path, respect_gitignore: bool) -> Vec<String> {
+        let mut scan = Scan::new(base, &None, true, respect_gitignore);
+        collect_entries(base, 0, &mut scan).unwrap();
+        relative_paths(base, &scan.entries)
+    }
+
+    fn relative_paths(base: &Path, entries: &[FileEntry]) -> Vec<String> {
+        let mut paths: Vec<String> = entries
+            .iter()
+            .map(|e| Path::new(&e.path).strip_prefix(base).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn recursive_scan_skips_gitignored_and_denied_paths() {
+        let base = std::env::temp_dir().join(format!("aidd-gitignore-fixture-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        for dir in ["build", "docs/drafts", "node_modules/pkg"] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+        }
+        fs::write(base.join(".gitignore"), "build/\n*.log\n!keep.log\n").unwrap();
+        fs::write(base.join("docs/.gitignore"), "drafts/\n").unwrap();
+        for file in ["build/out.md", "docs/guide.md", "docs/debug.log", "docs/keep.log", "docs/drafts/wip.md", "node_modules/pkg/README.md"] {
+            fs::write(base.join(file), "# x\n").unwrap();
+        }
+
+        assert_eq!(scan(&base, true), vec!["docs", "docs/guide.md", "docs/keep.log"]);
+        assert!(scan(&base, false).contains(&"build/out.md".to_string()));
+        assert!(scan(&base, false).contains(&"node_modules/pkg/README.md".to_string()));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_cycles_and_escapes_are_not_followed() {
+        let root = std::env::temp_dir().join(format!("aidd-symlink-fixture-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (base, outside) = (root.join("base"), root.join("outside"));
+        fs::create_dir_all(base.join("docs")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(base.join("docs/guide.md"), "# x\n").unwrap();
+        fs::write(outside.join("secret.md"), "# x\n").unwrap();
+        std::os::unix::fs::symlink(base.join("docs"), base.join("docs/loop")).unwrap();
+        std::os::unix::fs::symlink(&outside, base.join("external")).unwrap();
+
+        let mut scan = Scan::new(&base, &None, true, false);
+        collect_entries(&base, 0, &mut scan).unwrap();
+        assert!(!scan.truncated);
+        assert_eq!(relative_paths(&base, &scan.entries), vec!["docs", "docs/guide.md", "docs/loop", "external"]);
+
+        let mut capped = Scan::new(&base, &None, true, false);
+        capped.max_entries = 2;
+        collect_entries(&base, 0, &mut capped).unwrap();
+        assert!(capped.truncated);
+        assert_eq!(capped.entries.len(), 2);
+
+        let mut shallow = Scan::new(&base, &None, true, false);
+        shallow.max_depth = 0;
+        collect_entries(&base, 0, &mut shallow).unwrap();
+        assert!(shallow.truncated);
+        assert!(shallow.entries.iter().all(|e| Path::new(&e.path).parent() == Some(base.as_path())));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,7 +1,12 @@
-use tauri::State;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, State};
 
 use crate::AppContext;
-use crate::domain::model::{FrameworkEntity, SyncInfo};
+use crate::application::{FrameworkService, DEFAULT_AUTO_SYNC_INTERVAL_SECS};
+use crate::domain::model::{EntitySearchResult, FrameworkEntity, ReleaseInfo, SyncInfo, SyncPreview};
+use crate::domain::AiddError;
 use crate::domain::ports::inbound::FrameworkPort;
 
 /// Get the resolved framework directory path (~/.aidd/framework/).
@@ -17,18 +22,34 @@ pub async fn get_framework_path(
 pub async fn get_framework_version(
     ctx: State<'_, AppContext>,
 ) -> Result<Option<String>, String> {
-    ctx.framework_service.get_version()
+    ctx.framework_service.get_version().map_err(String::from)
 }
 
-/// List all entities in a framework category, optionally merging project-level content.
+/// List all entities in a framework category, optionally merging project-level content
+/// and keeping only entities whose frontmatter matches every `filter` pair.
 #[tauri::command]
 pub async fn list_framework_entities(
     category: String,
     project_path: Option<String>,
+    filter: Option<HashMap<String, String>>,
     ctx: State<'_, AppContext>,
 ) -> Result<Vec<FrameworkEntity>, String> {
     ctx.framework_service
-        .list_entities_with_project(&category, project_path.as_deref())
+        .list_entities_with_project(&category, project_path.as_deref(), filter.as_ref())
+        .map_err(String::from)
+}
+
+/// Search entity frontmatter and bodies (global + project) for `query`.
+#[tauri::command]
+pub async fn search_framework_entities(
+    query: String,
+    categories: Option<Vec<String>>,
+    project_path: Option<String>,
+    ctx: State<'_, AppContext>,
+) -> Result<EntitySearchResult, String> {
+    ctx.framework_service
+        .search_entities(&query, categories.as_deref(), project_path.as_deref())
+        .map_err(String::from)
 }
 
 /// Read a specific framework entity by category and name.
@@ -38,7 +59,7 @@ pub async fn read_framework_entity(
     name: String,
     ctx: State<'_, AppContext>,
 ) -> Result<FrameworkEntity, String> {
-    ctx.framework_service.read_entity(&category, &name)
+    ctx.framework_service.read_entity(&category, &name).map_err(String::from)
 }
 
 /// Write (create or update) a framework entity.
@@ -49,17 +70,63 @@ pub async fn write_framework_entity(
     content: String,
     ctx: State<'_, AppContext>,
 ) -> Result<(), String> {
-    ctx.framework_service.write_entity(&category, &name, &content)
+    ctx.framework_service.write_entity(&category, &name, &content).map_err(String::from)
 }
 
-/// Delete a framework entity.
+/// Write a framework entity unless it changed since `expected_last_modified`.
+/// A conflict fails with code `conflict` and the file's `current_content`.
+#[tauri::command]
+pub async fn write_framework_entity_if_unchanged(
+    category: String,
+    name: String,
+    content: String,
+    expected_last_modified: Option<String>,
+    ctx: State<'_, AppContext>,
+) -> Result<(), AiddError> {
+    ctx.framework_service
+        .write_entity_if_unchanged(&category, &name, &content, expected_last_modified.as_deref())
+}
+
+/// Write a framework entity from its parsed `frontmatter` object and `body`,
+/// keeping the existing YAML lines for keys that didn't change.
+#[tauri::command]
+pub async fn write_framework_entity_parts(
+    category: String,
+    name: String,
+    frontmatter: serde_json::Value,
+    body: String,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.framework_service
+        .write_entity_parts(&category, &name, frontmatter, &body)
+        .map_err(String::from)
+}
+
+/// Delete a framework entity; pass `project_path` to delete a project-scoped one.
 #[tauri::command]
 pub async fn delete_framework_entity(
     category: String,
     name: String,
+    project_path: Option<String>,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.framework_service
+        .delete_entity_with_project(&category, &name, project_path.as_deref())
+        .map_err(String::from)
+}
+
+/// Copy a framework entity under a new name as a starting point for a variation.
+#[tauri::command]
+pub async fn duplicate_framework_entity(
+    category: String,
+    src_name: String,
+    dst_name: String,
+    overwrite: Option<bool>,
     ctx: State<'_, AppContext>,
 ) -> Result<(), String> {
-    ctx.framework_service.delete_entity(&category, &name)
+    ctx.framework_service
+        .duplicate_entity(&category, &src_name, &dst_name, overwrite.unwrap_or(false))
+        .map_err(String::from)
 }
 
 // ── Framework sync commands ─────────────────────────────────────────────
@@ -69,24 +136,64 @@ pub async fn delete_framework_entity(
 pub async fn get_sync_status(
     ctx: State<'_, AppContext>,
 ) -> Result<SyncInfo, String> {
-    ctx.framework_service.get_sync_status()
+    ctx.framework_service.get_sync_status().map_err(String::from)
 }
 
-/// Check for framework updates (hits GitHub API).
+/// Check for framework updates (hits GitHub API). Errors keep their `code`
+/// (`offline`, `timeout`, `rate_limited`, `server_error`, ...) for the UI.
 #[tauri::command]
 pub async fn check_for_updates(
     ctx: State<'_, AppContext>,
-) -> Result<SyncInfo, String> {
+) -> Result<SyncInfo, AiddError> {
     ctx.framework_service.check_for_updates().await
 }
 
+/// List published framework releases (prereleases only when asked for).
+#[tauri::command]
+pub async fn list_framework_versions(
+    include_prereleases: Option<bool>,
+    ctx: State<'_, AppContext>,
+) -> Result<Vec<ReleaseInfo>, String> {
+    ctx.framework_service
+        .list_available_versions(include_prereleases.unwrap_or(false))
+        .await
+        .map_err(String::from)
+}
+
 /// Download and install a framework version (or latest if None).
+/// Emits throttled `SYNC_PROGRESS` events while downloading and extracting.
+/// With `prune` (default false), files removed upstream are moved to
+/// `framework.orphaned/`.
 #[tauri::command]
 pub async fn sync_framework(
     version: Option<String>,
+    prune: Option<bool>,
+    app: AppHandle,
+    ctx: State<'_, AppContext>,
+) -> Result<SyncInfo, String> {
+    ctx.framework_service
+        .sync_framework(version, prune.unwrap_or(false), &move |progress| {
+            let _ = app.emit("SYNC_PROGRESS", progress);
+        })
+        .await
+        .map_err(String::from)
+}
+
+/// List the files a sync to `version` (or latest if None) would change, without installing it.
+#[tauri::command]
+pub async fn preview_framework_sync(
+    version: Option<String>,
+    ctx: State<'_, AppContext>,
+) -> Result<SyncPreview, String> {
+    ctx.framework_service.preview_sync(version).await.map_err(String::from)
+}
+
+/// Restore the previous framework version from the sync backup.
+#[tauri::command]
+pub async fn rollback_framework(
     ctx: State<'_, AppContext>,
 ) -> Result<SyncInfo, String> {
-    ctx.framework_service.sync_framework(version).await
+    ctx.framework_service.rollback_framework().map_err(String::from)
 }
 
 /// Set auto-sync preference.
@@ -95,5 +202,44 @@ pub async fn set_auto_sync(
     enabled: bool,
     ctx: State<'_, AppContext>,
 ) -> Result<(), String> {
-    ctx.framework_service.set_auto_sync(enabled)
+    ctx.framework_service.set_auto_sync(enabled).map_err(String::from)
+}
+
+/// Set whether the auto-sync scheduler installs the updates it finds.
+#[tauri::command]
+pub async fn set_auto_install(
+    enabled: bool,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.framework_service.set_auto_install(enabled).map_err(String::from)
+}
+
+/// (Re)start the background update checker. Emits `UPDATE_AVAILABLE` with the
+/// `SyncInfo` when a newer release is found, and again once it's installed.
+#[tauri::command]
+pub fn start_auto_sync_scheduler(
+    app: AppHandle,
+    ctx: State<'_, AppContext>,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    start_auto_sync(&app, &ctx.framework_service, interval_secs.unwrap_or(DEFAULT_AUTO_SYNC_INTERVAL_SECS))
+}
+
+/// Returns `false` when no scheduler was running.
+#[tauri::command]
+pub fn stop_auto_sync_scheduler(ctx: State<'_, AppContext>) -> Result<bool, String> {
+    ctx.framework_service.stop_auto_sync().map_err(String::from)
+}
+
+/// Start the auto-sync scheduler, forwarding its findings as `UPDATE_AVAILABLE` events.
+pub fn start_auto_sync(app: &AppHandle, framework_service: &Arc<FrameworkService>, interval_secs: u64) -> Result<(), String> {
+    let app = app.clone();
+    framework_service
+        .start_auto_sync(
+            interval_secs,
+            Arc::new(move |info| {
+                let _ = app.emit("UPDATE_AVAILABLE", info);
+            }),
+        )
+        .map_err(String::from)
 }
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
 use tauri::State;
 use crate::AppContext;
-use crate::domain::model::{IntegrationConfig, IntegrationResult, IntegrationType};
+use crate::domain::model::{
+    AgentsDocument, IntegrationBundle, IntegrationConfig, IntegrationRemovalSummary, IntegrationResult, IntegrationType,
+};
 use crate::domain::ports::inbound::IntegrationPort;
 
 #[tauri::command]
@@ -9,9 +12,10 @@ pub fn integrate_tool(
     project_path: String,
     tool: String,
     dev_mode: bool,
+    env: Option<BTreeMap<String, String>>,
 ) -> Result<IntegrationResult, String> {
     let tool_type = IntegrationType::from_str(&tool)?;
-    ctx.integration_service.integrate(&project_path, tool_type, dev_mode)
+    ctx.integration_service.integrate(&project_path, tool_type, dev_mode, &env.unwrap_or_default())
 }
 
 #[tauri::command]
@@ -24,6 +28,25 @@ pub fn remove_integration(
     ctx.integration_service.remove_integration(&project_path, tool_type)
 }
 
+#[tauri::command]
+pub fn repair_integration(
+    ctx: State<'_, AppContext>,
+    project_path: String,
+    tool: String,
+) -> Result<IntegrationResult, String> {
+    let tool_type = IntegrationType::from_str(&tool)?;
+    ctx.integration_service.repair_integration(&project_path, tool_type)
+}
+
+#[tauri::command]
+pub fn remove_all_integrations(
+    ctx: State<'_, AppContext>,
+    project_path: String,
+    purge: Option<bool>,
+) -> Result<IntegrationRemovalSummary, String> {
+    ctx.integration_service.remove_all_integrations(&project_path, purge.unwrap_or(false))
+}
+
 #[tauri::command]
 pub fn check_integrations(
     ctx: State<'_, AppContext>,
@@ -38,3 +61,28 @@ pub fn list_integration_types(
 ) -> Vec<IntegrationType> {
     ctx.integration_service.list_available()
 }
+
+#[tauri::command]
+pub fn export_integrations(
+    ctx: State<'_, AppContext>,
+    project_path: String,
+) -> Result<IntegrationBundle, String> {
+    ctx.integration_service.export_bundle(&project_path)
+}
+
+#[tauri::command]
+pub fn import_integrations(
+    ctx: State<'_, AppContext>,
+    project_path: String,
+    bundle: IntegrationBundle,
+) -> Result<Vec<IntegrationResult>, String> {
+    ctx.integration_service.import_bundle(&project_path, &bundle)
+}
+
+#[tauri::command]
+pub fn parse_agents_md(
+    ctx: State<'_, AppContext>,
+    path: String,
+) -> Result<AgentsDocument, String> {
+    ctx.integration_service.parse_agents_md(&path)
+}
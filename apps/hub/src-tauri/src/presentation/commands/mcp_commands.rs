@@ -1,12 +1,19 @@
-use tauri::State;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
 use serde_json::json;
 use serde_json::Value;
 use tauri::async_runtime;
 use tokio::time::{timeout, Duration};
 
 use crate::AppContext;
-use crate::domain::model::{McpServer, McpServerMode};
-use crate::domain::ports::inbound::McpPort;
+use crate::domain::model::{
+    McpConnectInfo, McpResetSummary, McpServer, McpServerMode, McpServerSpec, McpSpecValidation, RestartPolicy,
+};
+use crate::domain::ports::inbound::{McpPort, ProjectPort};
+use crate::infrastructure::mcp::tool_result_json;
+use crate::infrastructure::process::{McpProcessManager, STOP_GRACE_PERIOD};
 
 fn is_major_mutation(tool_name: &str) -> bool {
     matches!(
@@ -18,23 +25,32 @@ fn is_major_mutation(tool_name: &str) -> bool {
     )
 }
 
-fn parse_text_result_json(result: &Value) -> Option<Value> {
-    if let Some(structured) = result.get("structuredContent") {
-        return Some(structured.clone());
-    }
+/// Which major-mutation guards are enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GuardFlags {
+    require_docs_checksum: bool,
+    require_adr: bool,
+}
 
-    let content = result.get("content")?.as_array()?;
-    for block in content {
-        if block.get("type").and_then(|v| v.as_str()) == Some("text") {
-            if let Some(text) = block.get("text").and_then(|v| v.as_str()) {
-                if let Ok(parsed) = serde_json::from_str::<Value>(text) {
-                    return Some(parsed);
-                }
-            }
-        }
+/// Resolve guard flags from `guards.*`: the project's `.aidd/config.json` wins,
+/// then the governance config, then enforcement by default.
+fn resolve_guard_flags(project_config: Option<&Value>, governance: Option<&Value>) -> GuardFlags {
+    let flag = |key: &str| {
+        [project_config, governance]
+            .into_iter()
+            .flatten()
+            .find_map(|cfg| cfg.get("guards")?.get(key)?.as_bool())
+            .unwrap_or(true)
+    };
+    GuardFlags {
+        require_docs_checksum: flag("requireDocsChecksum"),
+        require_adr: flag("requireAdr"),
     }
+}
 
-    None
+fn read_project_config(project_root: &Path) -> Option<Value> {
+    let raw = std::fs::read_to_string(project_root.join(".aidd").join("config.json")).ok()?;
+    serde_json::from_str(&raw).ok()
 }
 
 fn docs_checksum_status(report: &Value) -> Option<String> {
@@ -45,29 +61,156 @@ fn docs_checksum_status(report: &Value) -> Option<String> {
         .map(|v| v.to_string())
 }
 
+/// Error returned by `call_mcp_tool`: a plain message for ordinary failures,
+/// or a structured report when a mutation guard blocked the call.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum CallToolError {
+    Blocked(MutationBlocked),
+    Message(String),
+}
+
+impl From<String> for CallToolError {
+    fn from(message: String) -> Self {
+        CallToolError::Message(message)
+    }
+}
+
+/// Why a major mutation was refused. `message` keeps the legacy error text.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationBlocked {
+    /// Always `mutation_blocked`.
+    pub code: &'static str,
+    pub message: String,
+    /// The guard that tripped: `docsChecksum` or `adr`.
+    pub guard: &'static str,
+    /// `docsChecksum.status` from `aidd_ci_report`; `None` if the guard is disabled.
+    pub docs_checksum_status: Option<String>,
+    /// Whether an ADR artifact exists; `None` if that guard didn't run.
+    pub adr_found: Option<bool>,
+    pub tools_invoked: Vec<GuardToolCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct GuardToolCall {
+    pub tool: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Run the enabled major-mutation guards. `call_engine` invokes an engine tool;
+/// `adr_exists` checks for a registered ADR artifact.
+fn run_mutation_guards(
+    guards: GuardFlags,
+    call_engine: impl Fn(&str, Value) -> Result<Value, String>,
+    adr_exists: impl Fn() -> bool,
+) -> Result<(), CallToolError> {
+    let mut tools_invoked = Vec::new();
+    let mut record = |tool: &str, result: &Result<Value, String>| {
+        tools_invoked.push(GuardToolCall {
+            tool: tool.to_string(),
+            ok: result.is_ok(),
+            error: result.as_ref().err().cloned(),
+        });
+    };
+
+    // Asymmetric enforcement: auto-sync context before high-impact mutations.
+    let optimize = call_engine("aidd_optimize_context", json!({ "budget": 2000 }));
+    record("aidd_optimize_context", &optimize);
+
+    // Guard 1: S2D checksum must be healthy before core mutations.
+    let mut checksum_status = None;
+    if guards.require_docs_checksum {
+        let report_result = call_engine("aidd_ci_report", json!({ "format": "json" }));
+        record("aidd_ci_report", &report_result);
+        let report_result = report_result?;
+        let parsed_report = tool_result_json(&report_result).unwrap_or(report_result);
+        let status = docs_checksum_status(&parsed_report).unwrap_or_else(|| "UNKNOWN".to_string());
+        if status != "FOUND" {
+            return Err(CallToolError::Blocked(MutationBlocked {
+                code: "mutation_blocked",
+                message: "Synchronizing Architecture... blocked mutation because docs checksum is stale or missing. Run `pnpm mcp:docs --check`.".to_string(),
+                guard: "docsChecksum",
+                docs_checksum_status: Some(status),
+                adr_found: None,
+                tools_invoked,
+            }));
+        }
+        checksum_status = Some(status);
+    }
+
+    // Guard 2: Require at least one ADR before core mutation promotion paths.
+    if guards.require_adr && !adr_exists() {
+        return Err(CallToolError::Blocked(MutationBlocked {
+            code: "mutation_blocked",
+            message: "Synchronizing Architecture... blocked mutation because no ADR artifact is registered. Create ADR first.".to_string(),
+            guard: "adr",
+            docs_checksum_status: checksum_status,
+            adr_found: Some(false),
+            tools_invoked,
+        }));
+    }
+
+    Ok(())
+}
+
+/// `env` is added to the server's environment (e.g. API keys, `AIDD_LOG_LEVEL`)
+/// and `extra_args` appended to its command; restarts reuse both.
 #[tauri::command]
 pub fn start_mcp_server(
     ctx: State<'_, AppContext>,
     package: String,
     mode: String,
+    env: Option<HashMap<String, String>>,
+    extra_args: Option<Vec<String>>,
 ) -> Result<McpServer, String> {
     let mode = McpServerMode::from_str(&mode)?;
-    ctx.mcp_service.start_server(&package, mode)
+    ctx.mcp_service
+        .start_server(&package, mode, env.unwrap_or_default(), extra_args.unwrap_or_default())
 }
 
+/// Longest grace period a caller can ask `stop_mcp_server` for.
+const MAX_STOP_GRACE: Duration = Duration::from_secs(30);
+
+/// Stop a Hub server. It gets `grace_ms` (default `STOP_GRACE_PERIOD`, at most
+/// `MAX_STOP_GRACE`) to exit after SIGTERM before it is killed.
 #[tauri::command]
-pub fn stop_mcp_server(
+pub async fn stop_mcp_server(
     ctx: State<'_, AppContext>,
     server_id: String,
+    grace_ms: Option<u64>,
 ) -> Result<(), String> {
-    ctx.mcp_service.stop_server(&server_id)
+    let grace = grace_ms.map(Duration::from_millis).unwrap_or(STOP_GRACE_PERIOD).min(MAX_STOP_GRACE);
+    let service = ctx.mcp_service.clone();
+    async_runtime::spawn_blocking(move || service.stop_server(&server_id, grace))
+        .await
+        .map_err(|e| format!("stop_mcp_server task failed: {}", e))?
 }
 
 #[tauri::command]
-pub fn stop_all_mcp_servers(
+pub async fn stop_all_mcp_servers(
     ctx: State<'_, AppContext>,
 ) -> Result<(), String> {
-    ctx.mcp_service.stop_all()
+    let service = ctx.mcp_service.clone();
+    async_runtime::spawn_blocking(move || service.stop_all())
+        .await
+        .map_err(|e| format!("stop_all_mcp_servers task failed: {}", e))?
+}
+
+/// Recovery action: stop every Hub server, shut down pooled and one-off
+/// clients (failing their in-flight calls) and drop the cached health report.
+#[tauri::command]
+pub async fn reset_mcp_state(
+    ctx: State<'_, AppContext>,
+) -> Result<McpResetSummary, String> {
+    let service = ctx.mcp_service.clone();
+    let mut summary = async_runtime::spawn_blocking(move || service.reset_state())
+        .await
+        .map_err(|e| format!("reset_mcp_state task failed: {}", e))??;
+    summary.health_cache_cleared = ctx.mcp_health_service.clear_cached_report();
+    Ok(summary)
 }
 
 #[tauri::command]
@@ -77,6 +220,59 @@ pub fn get_mcp_servers(
     ctx.mcp_service.get_servers()
 }
 
+#[tauri::command]
+pub fn get_mcp_server_logs(
+    ctx: State<'_, AppContext>,
+    server_id: String,
+) -> Result<String, String> {
+    ctx.mcp_service.get_server_logs(&server_id)
+}
+
+#[tauri::command]
+pub fn set_restart_policy(
+    ctx: State<'_, AppContext>,
+    server_id: String,
+    policy: RestartPolicy,
+) -> Result<(), String> {
+    ctx.mcp_service.set_restart_policy(&server_id, policy)
+}
+
+/// Check that a running server answers, not just that its process is alive.
+/// Returns the round-trip latency in milliseconds.
+#[tauri::command]
+pub async fn ping_server(
+    ctx: State<'_, AppContext>,
+    server_id: String,
+) -> Result<u64, String> {
+    // The ping itself is bounded by `PING_TIMEOUT`
+    let service = ctx.mcp_service.clone();
+    async_runtime::spawn_blocking(move || service.ping_server(&server_id))
+        .await
+        .map_err(|e| format!("ping_server task failed: {}", e))?
+}
+
+/// Payload of the `mcp-server-error` event.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct McpServerErrorEvent {
+    pub server_id: String,
+    pub line: String,
+}
+
+/// Emit `mcp-server-error` to the frontend whenever a Hub-started server
+/// writes an error-looking line to stderr.
+pub fn forward_server_errors(app: &AppHandle, process_manager: &McpProcessManager) {
+    let app = app.clone();
+    process_manager.set_error_listener(Arc::new(move |server_id, line| {
+        let _ = app.emit(
+            "mcp-server-error",
+            McpServerErrorEvent {
+                server_id: server_id.to_string(),
+                line: line.to_string(),
+            },
+        );
+    }));
+}
+
 #[tauri::command]
 pub async fn list_mcp_tools(
     ctx: State<'_, AppContext>,
@@ -92,47 +288,143 @@ pub async fn list_mcp_tools(
     .map_err(|e| format!("list_mcp_tools task failed: {}", e))?
 }
 
+/// Connect to a package and report handshake details (protocol, server info, latency).
+#[tauri::command]
+pub async fn get_mcp_connect_info(
+    ctx: State<'_, AppContext>,
+    package: String,
+) -> Result<McpConnectInfo, String> {
+    let service = ctx.mcp_service.clone();
+    timeout(
+        Duration::from_secs(20),
+        async_runtime::spawn_blocking(move || service.connect_info(&package)),
+    )
+    .await
+    .map_err(|_| "get_mcp_connect_info timed out after 20s".to_string())?
+    .map_err(|e| format!("get_mcp_connect_info task failed: {}", e))?
+}
+
+/// Check a custom MCP server spec before it is persisted: command resolves on
+/// PATH, args look well-formed and, with `probe`, the server completes an
+/// initialize handshake.
+#[tauri::command]
+pub async fn validate_mcp_spec(
+    ctx: State<'_, AppContext>,
+    spec: McpServerSpec,
+    probe: Option<bool>,
+) -> Result<McpSpecValidation, String> {
+    let service = ctx.mcp_service.clone();
+    let probe = probe.unwrap_or(false);
+    async_runtime::spawn_blocking(move || service.validate_spec(&spec, probe))
+        .await
+        .map_err(|e| format!("validate_mcp_spec task failed: {}", e))
+}
+
 #[tauri::command]
 pub async fn call_mcp_tool(
     ctx: State<'_, AppContext>,
     package: String,
     tool_name: String,
     arguments: Value,
-) -> Result<Value, String> {
+) -> Result<Value, CallToolError> {
     let service = ctx.mcp_service.clone();
     let memory = ctx.memory_service.clone();
+    let active_project = ctx.project_service.get_active_path().ok().flatten();
     timeout(
         Duration::from_secs(20),
         async_runtime::spawn_blocking(move || {
             if is_major_mutation(&tool_name) {
-                // Asymmetric enforcement: auto-sync context before high-impact mutations.
-                let _ = service.call_tool("engine", "aidd_optimize_context", json!({ "budget": 2000 }));
-
-                // Guard 1: S2D checksum must be healthy before core mutations.
-                let report_result = service.call_tool("engine", "aidd_ci_report", json!({ "format": "json" }))?;
-                let parsed_report = parse_text_result_json(&report_result).unwrap_or(report_result);
-                let checksum_status = docs_checksum_status(&parsed_report).unwrap_or_else(|| "UNKNOWN".to_string());
-                if checksum_status != "FOUND" {
-                    return Err(
-                        "Synchronizing Architecture... blocked mutation because docs checksum is stale or missing. Run `pnpm mcp:docs --check`.".to_string(),
-                    );
-                }
-
-                // Guard 2: Require at least one ADR before core mutation promotion paths.
-                let adrs = memory
-                    .list_artifacts(Some("adr"), None, Some(1))
-                    .unwrap_or_default();
-                if adrs.is_empty() {
-                    return Err(
-                        "Synchronizing Architecture... blocked mutation because no ADR artifact is registered. Create ADR first.".to_string(),
-                    );
-                }
+                // Resolved once per call; project config overrides governance defaults.
+                let project_config = active_project
+                    .as_deref()
+                    .and_then(|root| read_project_config(Path::new(root)));
+                let governance = memory.get_governance_config().ok();
+                let guards = resolve_guard_flags(project_config.as_ref(), governance.as_ref());
+
+                run_mutation_guards(
+                    guards,
+                    |tool, args| service.call_tool("engine", tool, args),
+                    || {
+                        !memory
+                            .list_artifacts(Some("adr"), None, Some(1), None, None)
+                            .unwrap_or_default()
+                            .is_empty()
+                    },
+                )?;
             }
 
-            service.call_tool(&package, &tool_name, arguments)
+            Ok(service.call_tool(&package, &tool_name, arguments)?)
         }),
     )
     .await
-    .map_err(|_| "call_mcp_tool timed out after 20s".to_string())?
-    .map_err(|e| format!("call_mcp_tool task failed: {}", e))?
+    .map_err(|_| CallToolError::from("call_mcp_tool timed out after 20s".to_string()))?
+    .map_err(|e| CallToolError::from(format!("call_mcp_tool task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_with_config(label: &str, config: Value) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("aidd-guards-{}-{}", label, std::process::id()));
+        std::fs::create_dir_all(root.join(".aidd")).unwrap();
+        std::fs::write(root.join(".aidd").join("config.json"), config.to_string()).unwrap();
+        root
+    }
+
+    #[test]
+    fn project_config_overrides_governance_guards() {
+        let strict = project_with_config("strict", json!({ "guards": { "requireAdr": true } }));
+        let relaxed = project_with_config("relaxed", json!({ "guards": { "requireAdr": false } }));
+        let governance = json!({ "guards": { "requireDocsChecksum": false, "requireAdr": false } });
+
+        let strict_flags = resolve_guard_flags(read_project_config(&strict).as_ref(), Some(&governance));
+        assert!(strict_flags.require_adr);
+        // Not set by the project, so the governance value applies
+        assert!(!strict_flags.require_docs_checksum);
+
+        let relaxed_flags = resolve_guard_flags(read_project_config(&relaxed).as_ref(), None);
+        assert_eq!(
+            relaxed_flags,
+            GuardFlags { require_docs_checksum: true, require_adr: false }
+        );
+
+        let _ = std::fs::remove_dir_all(&strict);
+        let _ = std::fs::remove_dir_all(&relaxed);
+    }
+
+    #[test]
+    fn stale_checksum_block_reports_status_and_tools() {
+        let guards = GuardFlags { require_docs_checksum: true, require_adr: true };
+        let stale_report = json!({
+            "content": [{ "type": "text", "text": "{\"docsChecksum\":{\"status\":\"STALE\"}}" }]
+        });
+        let result = run_mutation_guards(
+            guards,
+            |tool, _| match tool {
+                "aidd_ci_report" => Ok(stale_report.clone()),
+                _ => Err("context budget exceeded".to_string()),
+            },
+            || panic!("ADR guard must not run once the checksum guard trips"),
+        );
+
+        let Err(CallToolError::Blocked(blocked)) = result else {
+            panic!("expected a structured block, got {:?}", result);
+        };
+        assert_eq!(blocked.guard, "docsChecksum");
+        assert_eq!(blocked.docs_checksum_status.as_deref(), Some("STALE"));
+        assert_eq!(blocked.adr_found, None);
+        assert!(blocked.message.contains("docs checksum is stale or missing"));
+
+        let serialized = serde_json::to_value(&blocked).unwrap();
+        assert_eq!(serialized["code"], "mutation_blocked");
+        assert_eq!(serialized["docsChecksumStatus"], "STALE");
+        assert_eq!(
+            serialized["toolsInvoked"],
+            json!([
+                { "tool": "aidd_optimize_context", "ok": false, "error": "context budget exceeded" },
+                { "tool": "aidd_ci_report", "ok": true }
+            ])
+        );
+    }
 }
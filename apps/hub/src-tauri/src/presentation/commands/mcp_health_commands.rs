@@ -1,5 +1,8 @@
-use tauri::State;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, State};
 use crate::AppContext;
+use crate::application::DEFAULT_HEALTH_INTERVAL_SECS;
 use crate::domain::model::McpHealthReport;
 use crate::domain::ports::inbound::McpHealthPort;
 
@@ -10,3 +13,27 @@ pub fn scan_mcp_health(
 ) -> Result<McpHealthReport, String> {
     ctx.mcp_health_service.scan_health(project_path.as_deref())
 }
+
+/// Rescan in the background (default every 30s) and emit `MCP_HEALTH_CHANGED`
+/// with the new report whenever the result differs from the previous scan.
+#[tauri::command]
+pub fn start_mcp_health_monitor(
+    app: AppHandle,
+    ctx: State<'_, AppContext>,
+    interval_secs: Option<u64>,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    ctx.mcp_health_service.start_health_monitor(
+        interval_secs.unwrap_or(DEFAULT_HEALTH_INTERVAL_SECS),
+        project_path,
+        Arc::new(move |report| {
+            let _ = app.emit("MCP_HEALTH_CHANGED", report);
+        }),
+    )
+}
+
+/// Returns `false` when no monitor was running.
+#[tauri::command]
+pub fn stop_mcp_health_monitor(ctx: State<'_, AppContext>) -> Result<bool, String> {
+    ctx.mcp_health_service.stop_health_monitor()
+}
@@ -1,52 +1,149 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, State};
 use crate::AppContext;
-use crate::application::MemorySnapshot;
-use crate::domain::ports::inbound::ProjectPort;
+use crate::application::{MemoryService, MemorySnapshot};
+use crate::domain::AiddError;
+use crate::domain::ports::inbound::{
+    ActivityEntry, ConfigChange, DailyCount, EvolutionStatus, ImportMode, ImportSummary, Loaded, MemoryPage, MemorySource,
+    ObservationEntry, PatternStats, PermanentMemoryPage, ProjectPort, SessionStats, SessionSummary,
+};
+
+/// Run a read query against `project_path` (any registered project, read-only
+/// unless active) or, when omitted, the primary active project.
+fn scoped<T>(
+    ctx: &AppContext,
+    project_path: Option<String>,
+    query: impl FnOnce(&MemoryService) -> Result<T, AiddError>,
+) -> Result<T, String> {
+    match project_path {
+        Some(path) => query(&ctx.memory_service.for_project(&path)?),
+        None => query(&ctx.memory_service),
+    }
+    .map_err(String::from)
+}
+
+/// Like `scoped`, but reads strictly and returns a failure next to empty data,
+/// so the UI can tell "failed to load" from "nothing here". No active project
+/// or no database yet is "nothing here", not a failure.
+fn loaded<T: Default>(
+    ctx: &AppContext,
+    project_path: Option<String>,
+    query: impl FnOnce(&MemoryService) -> Result<T, AiddError>,
+) -> Loaded<T> {
+    let result = match project_path {
+        Some(path) => ctx.memory_service.for_project(&path).and_then(|memory| query(&memory.strict())),
+        None => query(&ctx.memory_service.strict()),
+    };
+    match result {
+        Err(AiddError::NoActiveProject | AiddError::DbNotFound { .. }) => Loaded::from(Ok(T::default())),
+        other => Loaded::from(other),
+    }
+}
 
 /// Get complete memory snapshot (sessions, observations, evolution, patterns)
 #[tauri::command]
 pub fn get_memory_snapshot(
     ctx: State<'_, AppContext>,
-) -> Result<MemorySnapshot, String> {
-    ctx.memory_service.get_memory_snapshot()
+    project_path: Option<String>,
+) -> Loaded<MemorySnapshot> {
+    loaded(&ctx, project_path, |memory| memory.get_memory_snapshot())
 }
 
 /// Get session summary and recent sessions
 #[tauri::command]
 pub fn get_sessions(
     ctx: State<'_, AppContext>,
-) -> Result<serde_json::Value, String> {
-    let summary = ctx.memory_service.get_session_summary()?;
-    Ok(serde_json::to_value(summary).map_err(|e| e.to_string())?)
+    project_path: Option<String>,
+) -> Loaded<SessionSummary> {
+    loaded(&ctx, project_path, |memory| memory.get_session_summary())
+}
+
+/// Get session rollups (counts, average/median duration, discovery tokens, observation types)
+#[tauri::command]
+pub fn get_session_stats(
+    ctx: State<'_, AppContext>,
+    project_path: Option<String>,
+) -> Loaded<SessionStats> {
+    loaded(&ctx, project_path, |memory| memory.get_session_stats())
 }
 
 /// Get evolution status (pending, approved, rejected, auto-applied)
 #[tauri::command]
 pub fn get_evolution_status(
     ctx: State<'_, AppContext>,
-) -> Result<serde_json::Value, String> {
-    let status = ctx.memory_service.get_evolution_status()?;
-    Ok(serde_json::to_value(status).map_err(|e| e.to_string())?)
+    project_path: Option<String>,
+) -> Loaded<EvolutionStatus> {
+    loaded(&ctx, project_path, |memory| memory.get_evolution_status())
 }
 
 /// Get pattern statistics (total, active, detections, false positives)
 #[tauri::command]
 pub fn get_pattern_stats(
     ctx: State<'_, AppContext>,
+    project_path: Option<String>,
+) -> Loaded<PatternStats> {
+    loaded(&ctx, project_path, |memory| memory.get_pattern_stats())
+}
+
+/// Get a chronological feed of recent observations, artifacts, evolution log and audits
+#[tauri::command]
+pub fn get_activity_feed(
+    ctx: State<'_, AppContext>,
+    limit: Option<usize>,
+    project_path: Option<String>,
+) -> Loaded<Vec<ActivityEntry>> {
+    loaded(&ctx, project_path, |memory| memory.get_activity_feed(limit))
+}
+
+/// Count observations per day within an optional epoch-ms range, for timeline views
+#[tauri::command]
+pub fn observation_daily_counts(
+    ctx: State<'_, AppContext>,
+    since_ts: Option<i64>,
+    until_ts: Option<i64>,
+    project_path: Option<String>,
+) -> Loaded<Vec<DailyCount>> {
+    loaded(&ctx, project_path, |memory| memory.observation_daily_counts(since_ts, until_ts))
+}
+
+/// Which backend (`sqlite` or `engine`) serves memory reads for the project
+#[tauri::command]
+pub fn get_memory_source(
+    ctx: State<'_, AppContext>,
+    project_path: Option<String>,
+) -> Result<MemorySource, String> {
+    scoped(&ctx, project_path, |memory| Ok(memory.source()))
+}
+
+/// Get database storage diagnostics (journal mode, WAL/SHM sidecar presence)
+#[tauri::command]
+pub fn get_memory_storage_info(
+    ctx: State<'_, AppContext>,
 ) -> Result<serde_json::Value, String> {
-    let stats = ctx.memory_service.get_pattern_stats()?;
-    Ok(serde_json::to_value(stats).map_err(|e| e.to_string())?)
+    let info = ctx.memory_service.get_storage_info()?;
+    serde_json::to_value(info).map_err(|e| e.to_string())
 }
 
-/// List all observations
+/// List observations newest first; pass `cursor` from the previous page to continue.
+/// `types` restricts the listing to those observation types (empty = all).
 #[tauri::command]
 pub fn list_all_observations(
     ctx: State<'_, AppContext>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let observations = ctx.memory_service.list_all_observations(limit)?;
-    Ok(serde_json::Value::Array(observations))
+    cursor: Option<String>,
+    types: Option<Vec<String>>,
+    project_path: Option<String>,
+) -> Loaded<MemoryPage> {
+    loaded(&ctx, project_path, |memory| memory.list_all_observations(limit, cursor.as_deref(), types.as_deref()))
+}
+
+/// Count observations per type, most frequent first, for the filter sidebar
+#[tauri::command]
+pub fn get_observation_type_counts(
+    ctx: State<'_, AppContext>,
+    project_path: Option<String>,
+) -> Loaded<Vec<(String, usize)>> {
+    loaded(&ctx, project_path, |memory| memory.get_observation_type_counts())
 }
 
 /// List observations for a specific session
@@ -55,11 +152,9 @@ pub fn list_observations_by_session(
     ctx: State<'_, AppContext>,
     session_id: String,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let observations = ctx
-        .memory_service
-        .list_observations_by_session(&session_id, limit)?;
-    Ok(serde_json::Value::Array(observations))
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_observations_by_session(&session_id, limit))
 }
 
 /// Search observations
@@ -68,28 +163,32 @@ pub fn search_observations(
     ctx: State<'_, AppContext>,
     query: String,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let observations = ctx.memory_service.search_observations(&query, limit)?;
-    Ok(serde_json::to_value(observations).map_err(|e| e.to_string())?)
+    project_path: Option<String>,
+) -> Loaded<Vec<ObservationEntry>> {
+    loaded(&ctx, project_path, |memory| memory.search_observations(&query, limit))
 }
 
-/// List all sessions with full detail (JSON blobs from SQLite data column)
+/// List sessions with full detail (JSON blobs from SQLite data column), cursor-paginated,
+/// optionally bounded to a start-time range (epoch ms, inclusive)
 #[tauri::command]
 pub fn list_all_sessions(
     ctx: State<'_, AppContext>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let sessions = ctx.memory_service.list_all_sessions(limit)?;
-    Ok(serde_json::Value::Array(sessions))
+    cursor: Option<String>,
+    project_path: Option<String>,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+) -> Loaded<MemoryPage> {
+    loaded(&ctx, project_path, |memory| memory.list_all_sessions(limit, cursor.as_deref(), from_ms, to_ms))
 }
 
 /// List evolution candidates with full detail
 #[tauri::command]
 pub fn list_evolution_candidates(
     ctx: State<'_, AppContext>,
-) -> Result<serde_json::Value, String> {
-    let candidates = ctx.memory_service.list_evolution_candidates()?;
-    Ok(serde_json::Value::Array(candidates))
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_evolution_candidates())
 }
 
 /// List evolution log entries
@@ -97,19 +196,32 @@ pub fn list_evolution_candidates(
 pub fn list_evolution_log(
     ctx: State<'_, AppContext>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let entries = ctx.memory_service.list_evolution_log(limit)?;
-    Ok(serde_json::Value::Array(entries))
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_evolution_log(limit))
 }
 
-/// List permanent memory entries by type (decision, mistake, convention)
+/// List one page of permanent memory entries by type (decision, mistake, convention)
 #[tauri::command]
 pub fn list_permanent_memory(
     ctx: State<'_, AppContext>,
     memory_type: String,
-) -> Result<serde_json::Value, String> {
-    let entries = ctx.memory_service.list_permanent_memory(&memory_type)?;
-    Ok(serde_json::Value::Array(entries))
+    limit: Option<usize>,
+    offset: Option<usize>,
+    project_path: Option<String>,
+) -> Loaded<PermanentMemoryPage> {
+    loaded(&ctx, project_path, |memory| memory.list_permanent_memory(&memory_type, limit, offset))
+}
+
+/// Search permanent memory entries by title and content
+#[tauri::command]
+pub fn search_permanent_memory(
+    ctx: State<'_, AppContext>,
+    query: String,
+    limit: Option<usize>,
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.search_permanent_memory(&query, limit))
 }
 
 /// Delete a permanent memory entry by type and id
@@ -119,16 +231,16 @@ pub fn delete_permanent_memory(
     memory_type: String,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_permanent_memory(&memory_type, &id)
+    ctx.memory_service.delete_permanent_memory(&memory_type, &id).map_err(String::from)
 }
 
 /// List all draft entries
 #[tauri::command]
 pub fn list_drafts(
     ctx: State<'_, AppContext>,
-) -> Result<serde_json::Value, String> {
-    let drafts = ctx.memory_service.list_drafts()?;
-    Ok(serde_json::Value::Array(drafts))
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_drafts())
 }
 
 /// List artifacts with optional filters
@@ -138,13 +250,13 @@ pub fn list_artifacts(
     artifact_type: Option<String>,
     status: Option<String>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let artifacts = ctx.memory_service.list_artifacts(
-        artifact_type.as_deref(),
-        status.as_deref(),
-        limit,
-    )?;
-    Ok(serde_json::Value::Array(artifacts))
+    project_path: Option<String>,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| {
+        memory.list_artifacts(artifact_type.as_deref(), status.as_deref(), limit, from_ms, to_ms)
+    })
 }
 
 /// List recent pattern audit scores.
@@ -152,9 +264,58 @@ pub fn list_artifacts(
 pub fn list_audit_scores(
     ctx: State<'_, AppContext>,
     limit: Option<usize>,
-) -> Result<serde_json::Value, String> {
-    let entries = ctx.memory_service.list_audit_scores(limit)?;
-    Ok(serde_json::Value::Array(entries))
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_audit_scores(limit))
+}
+
+/// List recent pattern detections, each with the banned pattern it matched.
+#[tauri::command]
+pub fn list_pattern_detections(
+    ctx: State<'_, AppContext>,
+    limit: Option<usize>,
+    project_path: Option<String>,
+) -> Loaded<Vec<serde_json::Value>> {
+    loaded(&ctx, project_path, |memory| memory.list_pattern_detections(limit))
+}
+
+/// Export every memory table as one versioned JSON bundle (backup / sharing),
+/// streamed to `destination`. The file only appears once the export succeeded.
+#[tauri::command]
+pub fn export_memory_bundle(
+    ctx: State<'_, AppContext>,
+    destination: String,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let destination = PathBuf::from(destination);
+    let mut partial = destination.clone().into_os_string();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let file = std::fs::File::create(&partial)
+        .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    let mut out = std::io::BufWriter::new(file);
+    if let Err(e) = scoped(&ctx, project_path, |memory| memory.export_bundle(&mut out)) {
+        drop(out);
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+    drop(out);
+    std::fs::rename(&partial, &destination)
+        .map_err(|e| format!("Failed to write {}: {}", destination.display(), e))
+}
+
+/// Restore a bundle from `export_memory_bundle` into a project (default: the active one).
+/// Registered projects other than the active one are read-only, so importing there fails.
+#[tauri::command]
+pub fn import_memory_bundle(
+    ctx: State<'_, AppContext>,
+    bundle: serde_json::Value,
+    mode: ImportMode,
+    project_path: Option<String>,
+) -> Result<ImportSummary, String> {
+    let payload = serde_json::to_string(&bundle).map_err(|e| e.to_string())?;
+    scoped(&ctx, project_path, |memory| memory.import_bundle(&payload, mode))
 }
 
 /// Get governance configuration from project-local SQLite.
@@ -162,18 +323,19 @@ pub fn list_audit_scores(
 pub fn get_governance_config(
     ctx: State<'_, AppContext>,
 ) -> Result<serde_json::Value, String> {
-    ctx.memory_service.get_governance_config()
+    ctx.memory_service.get_governance_config().map_err(String::from)
 }
 
 /// Upsert governance configuration in project-local SQLite and sync .aidd/config.json.
+/// Returns the changed keys; `CONFIG_UPDATED` carries them next to the new config.
 #[tauri::command]
 pub fn upsert_governance_config(
     ctx: State<'_, AppContext>,
     app: AppHandle,
     config: serde_json::Value,
-) -> Result<(), String> {
+) -> Result<Vec<ConfigChange>, String> {
     let payload = serde_json::to_string(&config).map_err(|e| e.to_string())?;
-    ctx.memory_service.upsert_governance_config(&payload)?;
+    let changes = ctx.memory_service.upsert_governance_config(&payload)?;
 
     let normalized = ctx.memory_service.get_governance_config()?;
     let active_path = ctx
@@ -182,16 +344,11 @@ pub fn upsert_governance_config(
         .map_err(|e| format!("Failed to resolve active project: {}", e))?;
     if let Some(project_root) = active_path {
         let config_path = PathBuf::from(project_root).join(".aidd").join("config.json");
-        if let Some(parent) = config_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create .aidd directory: {}", e))?;
-        }
-        let pretty = serde_json::to_string_pretty(&normalized).map_err(|e| e.to_string())?;
-        std::fs::write(&config_path, pretty)
-            .map_err(|e| format!("Failed to write {}: {}", config_path.display(), e))?;
+        write_governance_to_config_file(&config_path, &normalized)?;
     }
 
-    let _ = app.emit("CONFIG_UPDATED", normalized);
-    Ok(())
+    let _ = app.emit("CONFIG_UPDATED", serde_json::json!({ "config": normalized, "changes": changes }));
+    Ok(changes)
 }
 
 // --- Write commands ---
@@ -204,7 +361,7 @@ pub fn create_permanent_memory(
     title: String,
     content: String,
 ) -> Result<String, String> {
-    ctx.memory_service.create_permanent_memory(&memory_type, &title, &content)
+    ctx.memory_service.create_permanent_memory(&memory_type, &title, &content).map_err(String::from)
 }
 
 /// Update a permanent memory entry by ID.
@@ -215,7 +372,7 @@ pub fn update_permanent_memory(
     title: String,
     content: String,
 ) -> Result<(), String> {
-    ctx.memory_service.update_permanent_memory(&id, &title, &content)
+    ctx.memory_service.update_permanent_memory(&id, &title, &content).map_err(String::from)
 }
 
 /// Create an artifact. Returns the new artifact ID.
@@ -228,7 +385,7 @@ pub fn create_artifact(
     description: String,
     content: String,
 ) -> Result<String, String> {
-    ctx.memory_service.create_artifact(&artifact_type, &feature, &title, &description, &content)
+    ctx.memory_service.create_artifact(&artifact_type, &feature, &title, &description, &content).map_err(String::from)
 }
 
 /// Update an artifact by ID.
@@ -243,7 +400,7 @@ pub fn update_artifact(
     content: String,
     status: String,
 ) -> Result<(), String> {
-    ctx.memory_service.update_artifact(&id, &artifact_type, &feature, &title, &description, &content, &status)
+    ctx.memory_service.update_artifact(&id, &artifact_type, &feature, &title, &description, &content, &status).map_err(String::from)
 }
 
 /// Archive an artifact (set status to 'done').
@@ -252,7 +409,7 @@ pub fn archive_artifact(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.archive_artifact(&id)
+    ctx.memory_service.archive_artifact(&id).map_err(String::from)
 }
 
 /// Delete an artifact by ID.
@@ -261,7 +418,7 @@ pub fn delete_artifact(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_artifact(&id)
+    ctx.memory_service.delete_artifact(&id).map_err(String::from)
 }
 
 /// Approve an evolution candidate.
@@ -270,7 +427,7 @@ pub fn approve_evolution_candidate(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.approve_evolution_candidate(&id)
+    ctx.memory_service.approve_evolution_candidate(&id).map_err(String::from)
 }
 
 /// Reject an evolution candidate with a reason.
@@ -280,7 +437,7 @@ pub fn reject_evolution_candidate(
     id: String,
     reason: String,
 ) -> Result<(), String> {
-    ctx.memory_service.reject_evolution_candidate(&id, &reason)
+    ctx.memory_service.reject_evolution_candidate(&id, &reason).map_err(String::from)
 }
 
 /// Approve a draft entry.
@@ -289,7 +446,7 @@ pub fn approve_draft(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.approve_draft(&id)
+    ctx.memory_service.approve_draft(&id).map_err(String::from)
 }
 
 /// Reject a draft entry with a reason.
@@ -299,7 +456,7 @@ pub fn reject_draft(
     id: String,
     reason: String,
 ) -> Result<(), String> {
-    ctx.memory_service.reject_draft(&id, &reason)
+    ctx.memory_service.reject_draft(&id, &reason).map_err(String::from)
 }
 
 /// Delete a session and its associated observations.
@@ -308,7 +465,7 @@ pub fn delete_session(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_session(&id)
+    ctx.memory_service.delete_session(&id).map_err(String::from)
 }
 
 /// Update a session's editable fields.
@@ -320,7 +477,7 @@ pub fn update_session(
     input: Option<String>,
     output: Option<String>,
 ) -> Result<(), String> {
-    ctx.memory_service.update_session(&id, branch.as_deref(), input.as_deref(), output.as_deref())
+    ctx.memory_service.update_session(&id, branch.as_deref(), input.as_deref(), output.as_deref()).map_err(String::from)
 }
 
 /// Update a session's full data via JSON merge.
@@ -330,7 +487,16 @@ pub fn update_session_full(
     id: String,
     updates_json: String,
 ) -> Result<(), String> {
-    ctx.memory_service.update_session_full(&id, &updates_json)
+    ctx.memory_service.update_session_full(&id, &updates_json).map_err(String::from)
+}
+
+/// Reopen an ended session so it counts as active again.
+#[tauri::command]
+pub fn reopen_session(
+    ctx: State<'_, AppContext>,
+    id: String,
+) -> Result<(), String> {
+    ctx.memory_service.reopen_session(&id).map_err(String::from)
 }
 
 // --- Observation CRUD ---
@@ -348,6 +514,7 @@ pub fn create_observation(
     files_read: Option<String>,
     files_modified: Option<String>,
     discovery_tokens: Option<i64>,
+    created_at: Option<String>,
 ) -> Result<String, String> {
     ctx.memory_service.create_observation(
         &session_id,
@@ -359,7 +526,9 @@ pub fn create_observation(
         files_read.as_deref(),
         files_modified.as_deref(),
         discovery_tokens,
+        created_at.as_deref(),
     )
+    .map_err(String::from)
 }
 
 /// Update an observation by ID.
@@ -387,6 +556,7 @@ pub fn update_observation(
         files_modified.as_deref(),
         discovery_tokens,
     )
+    .map_err(String::from)
 }
 
 /// Delete an observation by ID.
@@ -395,7 +565,7 @@ pub fn delete_observation(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_observation(&id)
+    ctx.memory_service.delete_observation(&id).map_err(String::from)
 }
 
 // --- Evolution Candidate CRUD ---
@@ -409,7 +579,7 @@ pub fn create_evolution_candidate_entry(
     confidence: f64,
     data: String,
 ) -> Result<String, String> {
-    ctx.memory_service.create_evolution_candidate_entry(&evo_type, &title, confidence, &data)
+    ctx.memory_service.create_evolution_candidate_entry(&evo_type, &title, confidence, &data).map_err(String::from)
 }
 
 /// Update an evolution candidate by ID.
@@ -422,16 +592,20 @@ pub fn update_evolution_candidate_entry(
     confidence: f64,
     data: String,
 ) -> Result<(), String> {
-    ctx.memory_service.update_evolution_candidate_entry(&id, &evo_type, &title, confidence, &data)
+    ctx.memory_service.update_evolution_candidate_entry(&id, &evo_type, &title, confidence, &data).map_err(String::from)
 }
 
-/// Delete an evolution candidate by ID.
+/// Delete an evolution candidate by ID. Its evolution log entries go with it
+/// unless `preserve_log` is set.
 #[tauri::command]
 pub fn delete_evolution_candidate(
     ctx: State<'_, AppContext>,
     id: String,
+    preserve_log: Option<bool>,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_evolution_candidate(&id)
+    ctx.memory_service
+        .delete_evolution_candidate(&id, preserve_log.unwrap_or(false))
+        .map_err(String::from)
 }
 
 // --- Draft CRUD ---
@@ -447,7 +621,7 @@ pub fn create_draft(
     confidence: f64,
     source: String,
 ) -> Result<String, String> {
-    ctx.memory_service.create_draft(&category, &title, &filename, &content, confidence, &source)
+    ctx.memory_service.create_draft(&category, &title, &filename, &content, confidence, &source).map_err(String::from)
 }
 
 /// Update a draft by ID.
@@ -461,7 +635,7 @@ pub fn update_draft(
     confidence: Option<f64>,
     filename: Option<String>,
 ) -> Result<(), String> {
-    ctx.memory_service.update_draft(&id, &title, &content, &category, confidence, filename.as_deref())
+    ctx.memory_service.update_draft(&id, &title, &content, &category, confidence, filename.as_deref()).map_err(String::from)
 }
 
 /// Delete a draft by ID.
@@ -470,5 +644,183 @@ pub fn delete_draft(
     ctx: State<'_, AppContext>,
     id: String,
 ) -> Result<(), String> {
-    ctx.memory_service.delete_draft(&id)
+    ctx.memory_service.delete_draft(&id).map_err(String::from)
+}
+
+/// Delete every draft with the given status; returns how many were removed.
+#[tauri::command]
+pub fn delete_drafts_by_status(
+    ctx: State<'_, AppContext>,
+    status: String,
+) -> Result<usize, String> {
+    ctx.memory_service.delete_drafts_by_status(&status).map_err(String::from)
+}
+
+/// Create a banned pattern; regex patterns must compile. Returns the new ID.
+#[tauri::command]
+pub fn create_banned_pattern(
+    ctx: State<'_, AppContext>,
+    category: String,
+    pattern: String,
+    pattern_type: String,
+    severity: String,
+    hint: Option<String>,
+) -> Result<String, String> {
+    ctx.memory_service
+        .create_banned_pattern(&category, &pattern, &pattern_type, &severity, hint.as_deref())
+        .map_err(String::from)
+}
+
+/// Update a banned pattern; regex patterns must compile.
+#[tauri::command]
+pub fn update_banned_pattern(
+    ctx: State<'_, AppContext>,
+    id: String,
+    category: String,
+    pattern: String,
+    pattern_type: String,
+    severity: String,
+    hint: Option<String>,
+    active: bool,
+) -> Result<(), String> {
+    ctx.memory_service
+        .update_banned_pattern(&id, &category, &pattern, &pattern_type, &severity, hint.as_deref(), active)
+        .map_err(String::from)
+}
+
+/// Mark a pattern detection as a false positive (counted in pattern stats).
+#[tauri::command]
+pub fn mark_detection_false_positive(
+    ctx: State<'_, AppContext>,
+    id: i64,
+) -> Result<(), String> {
+    ctx.memory_service.mark_detection_false_positive(id).map_err(String::from)
+}
+
+/// Delete several drafts at once; returns how many were removed.
+#[tauri::command]
+pub fn delete_drafts(
+    ctx: State<'_, AppContext>,
+    ids: Vec<String>,
+) -> Result<usize, String> {
+    ctx.memory_service.delete_drafts(&ids).map_err(String::from)
+}
+
+/// Reject several drafts with one reason; returns how many were updated.
+#[tauri::command]
+pub fn reject_drafts(
+    ctx: State<'_, AppContext>,
+    ids: Vec<String>,
+    reason: String,
+) -> Result<usize, String> {
+    ctx.memory_service.reject_drafts(&ids, &reason).map_err(String::from)
+}
+
+/// Archive several artifacts at once; returns how many were updated.
+#[tauri::command]
+pub fn archive_artifacts(
+    ctx: State<'_, AppContext>,
+    ids: Vec<String>,
+) -> Result<usize, String> {
+    ctx.memory_service.archive_artifacts(&ids).map_err(String::from)
+}
+
+/// Merge the governance sections into `.aidd/config.json`, keeping any keys
+/// governance does not own (e.g. `content.paths`).
+fn write_governance_to_config_file(
+    config_path: &Path,
+    governance: &serde_json::Value,
+) -> Result<(), String> {
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create .aidd directory: {}", e))?;
+    }
+
+    // A missing file starts empty; one that can't be read or parsed is left alone
+    // rather than overwritten, since it may hold keys governance doesn't own.
+    let mut merged = match std::fs::read_to_string(config_path) {
+        Ok(raw) => serde_json::from_str::<serde_json::Value>(&raw)
+            .ok()
+            .filter(|v| v.is_object())
+            .ok_or_else(|| format!("{} is not a valid JSON object; fix it before saving governance", config_path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(format!("Failed to read {}: {}", config_path.display(), e)),
+    };
+    merge_json(&mut merged, governance);
+
+    let pretty = serde_json::to_string_pretty(&merged).map_err(|e| e.to_string())?;
+    std::fs::write(config_path, pretty)
+        .map_err(|e| format!("Failed to write {}: {}", config_path.display(), e))
+}
+
+/// Recursively overlay `patch` onto `target`: objects merge key by key, anything else replaces.
+fn merge_json(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(target_map), serde_json::Value::Object(patch_map)) => {
+            for (key, value) in patch_map {
+                match target_map.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn governance_write_preserves_content_paths() {
+        let dir = std::env::temp_dir().join(format!("aidd-config-merge-{}", std::process::id()));
+        let config_path = dir.join(".aidd").join("config.json");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &config_path,
+            serde_json::json!({
+                "evolution": { "enabled": true, "autoApplyThreshold": 90 },
+                "content": {
+                    "overrideMode": "merge",
+                    "paths": { "rules": "docs/rules" }
+                },
+                "custom": { "keep": true }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let governance = serde_json::json!({
+            "evolution": { "enabled": false, "autoApplyThreshold": 75 },
+            "content": { "overrideMode": "project_only", "slimStartEnabled": true }
+        });
+        write_governance_to_config_file(&config_path, &governance).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(written["evolution"]["enabled"], false);
+        assert_eq!(written["evolution"]["autoApplyThreshold"], 75);
+        assert_eq!(written["content"]["overrideMode"], "project_only");
+        assert_eq!(written["content"]["slimStartEnabled"], true);
+        assert_eq!(written["content"]["paths"]["rules"], "docs/rules");
+        assert_eq!(written["custom"]["keep"], true);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn governance_write_refuses_to_replace_an_invalid_config() {
+        let dir = std::env::temp_dir().join(format!("aidd-config-invalid-{}", std::process::id()));
+        let config_path = dir.join(".aidd").join("config.json");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "{ \"content\": { \"paths\": ").unwrap();
+
+        let governance = serde_json::json!({ "evolution": { "enabled": false } });
+        assert!(write_governance_to_config_file(&config_path, &governance).is_err());
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "{ \"content\": { \"paths\": ");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
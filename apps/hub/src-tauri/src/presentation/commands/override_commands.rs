@@ -1,7 +1,7 @@
 use tauri::State;
 
 use crate::AppContext;
-use crate::domain::model::{EffectiveEntity, FrameworkEntity, ProjectOverrides};
+use crate::domain::model::{BudgetReport, EffectiveEntity, EntityDiff, FrameworkEntity, ProjectOverrides};
 
 /// Get project overrides summary.
 #[tauri::command]
@@ -24,6 +24,18 @@ pub fn set_agent_override(
         .set_agent_override(&project_path, &agent, enabled)
 }
 
+/// Enable or disable a skill for a project (separate from agent overrides).
+#[tauri::command]
+pub fn set_skill_override(
+    project_path: String,
+    skill: String,
+    enabled: bool,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.override_service
+        .set_skill_override(&project_path, &skill, enabled)
+}
+
 /// Add a project-specific rule.
 #[tauri::command]
 pub fn add_project_rule(
@@ -66,3 +78,23 @@ pub fn get_effective_entities(
     ctx.override_service
         .get_effective_entities(&project_path, &category)
 }
+
+/// Unified diff of a project override against its global base.
+#[tauri::command]
+pub fn diff_override(
+    project_path: String,
+    category: String,
+    name: String,
+    ctx: State<'_, AppContext>,
+) -> Result<EntityDiff, String> {
+    ctx.override_service.diff_override(&project_path, &category, &name)
+}
+
+/// Size (bytes and estimated tokens) of the effective framework content for a project.
+#[tauri::command]
+pub fn framework_content_budget(
+    project_path: String,
+    ctx: State<'_, AppContext>,
+) -> Result<BudgetReport, String> {
+    ctx.override_service.content_budget(&project_path)
+}
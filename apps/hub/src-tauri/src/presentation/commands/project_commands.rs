@@ -1,10 +1,10 @@
 use tauri::State;
 
 use crate::AppContext;
-use crate::domain::model::{Project, ProjectEntry};
+use crate::domain::model::{DetectionPolicy, Project, ProjectDiagnostics, ProjectEntry};
 use crate::domain::ports::inbound::ProjectPort;
 
-/// Detect AIDD markers in a project directory.
+/// Detect AIDD markers in a project directory without registering it.
 #[tauri::command]
 pub async fn detect_project(
     path: String,
@@ -39,6 +39,14 @@ pub async fn list_projects(
     ctx.project_service.list()
 }
 
+/// List registered projects, most recently used first (for the project switcher).
+#[tauri::command]
+pub async fn list_projects_by_recent(
+    ctx: State<'_, AppContext>,
+) -> Result<Vec<ProjectEntry>, String> {
+    ctx.project_service.list_by_recent()
+}
+
 /// Get the currently active project path.
 #[tauri::command]
 pub async fn get_active_project(
@@ -55,3 +63,63 @@ pub async fn set_active_project(
 ) -> Result<(), String> {
     ctx.project_service.switch(&path)
 }
+
+/// List every active project (primary first, then workspace-mode extras).
+#[tauri::command]
+pub async fn list_active_projects(
+    ctx: State<'_, AppContext>,
+) -> Result<Vec<String>, String> {
+    ctx.project_service.list_active_projects()
+}
+
+/// Add a project to the active set (workspace mode).
+#[tauri::command]
+pub async fn add_active_project(
+    path: String,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.project_service.add_active_project(&path)
+}
+
+/// Remove a project from the active set (workspace mode).
+#[tauri::command]
+pub async fn remove_active_project(
+    path: String,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.project_service.remove_active_project(&path)
+}
+
+/// Get the policy deciding which directories count as aidd projects.
+#[tauri::command]
+pub async fn get_detection_policy(
+    ctx: State<'_, AppContext>,
+) -> Result<DetectionPolicy, String> {
+    ctx.project_service.detection_policy()
+}
+
+/// Set the project detection policy.
+#[tauri::command]
+pub async fn set_detection_policy(
+    policy: DetectionPolicy,
+    ctx: State<'_, AppContext>,
+) -> Result<(), String> {
+    ctx.project_service.set_detection_policy(policy)
+}
+
+/// Check one project directory for setup problems.
+#[tauri::command]
+pub async fn diagnose_project(
+    path: String,
+    ctx: State<'_, AppContext>,
+) -> Result<ProjectDiagnostics, String> {
+    Ok(ctx.project_service.diagnose(&path))
+}
+
+/// Diagnose every registered project; missing directories are flagged, not skipped.
+#[tauri::command]
+pub async fn verify_all_projects(
+    ctx: State<'_, AppContext>,
+) -> Result<Vec<ProjectDiagnostics>, String> {
+    ctx.project_service.verify_all()
+}
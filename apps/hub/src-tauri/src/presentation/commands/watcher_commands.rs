@@ -1,79 +1,358 @@
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use std::sync::mpsc;
-use tauri::{AppHandle, Emitter};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileChangeEvent {
     pub event_type: String,
     pub paths: Vec<String>,
+    /// Watched root the change happened under, as passed when watching started.
+    pub root: Option<String>,
+}
+
+/// Debounce window used when `start_watching` is not given one.
+const DEFAULT_DEBOUNCE_MS: u64 = 300;
+
+/// Paths skipped when `start_watching` is not given `ignore_globs`.
+const DEFAULT_IGNORE_GLOBS: [&str; 3] = ["node_modules", ".git", "dist"];
+
+/// Active watchers keyed by `watcher_id` — injected as Tauri managed state.
+#[derive(Default)]
+pub struct WatcherRegistry {
+    watchers: Mutex<HashMap<String, WatcherHandle>>,
+}
+
+struct WatcherHandle {
+    roots: Vec<String>,
+    signal: mpsc::Sender<WatchMessage>,
+}
+
+/// Everything the watcher thread waits on: notify events and the stop signal
+/// share one channel so a single `recv` covers both.
+enum WatchMessage {
+    Event(Result<Event, notify::Error>),
+    Stop,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherInfo {
+    pub watcher_id: String,
+    /// First watched root; see `roots` for watchers started with several.
+    pub path: String,
+    pub roots: Vec<String>,
+}
+
+impl WatcherRegistry {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, WatcherHandle>> {
+        self.watchers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn insert(&self, watcher_id: String, roots: Vec<String>, signal: mpsc::Sender<WatchMessage>) {
+        self.lock().insert(watcher_id, WatcherHandle { roots, signal });
+    }
+
+    /// Signal the watcher's thread to exit and forget it. Returns `false` for
+    /// unknown ids.
+    fn stop(&self, watcher_id: &str) -> bool {
+        match self.lock().remove(watcher_id) {
+            Some(handle) => {
+                let _ = handle.signal.send(WatchMessage::Stop);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn list(&self) -> Vec<WatcherInfo> {
+        let mut watchers: Vec<WatcherInfo> = self
+            .lock()
+            .iter()
+            .map(|(id, handle)| WatcherInfo {
+                watcher_id: id.clone(),
+                path: handle.roots.first().cloned().unwrap_or_default(),
+                roots: handle.roots.clone(),
+            })
+            .collect();
+        watchers.sort_by(|a, b| a.watcher_id.cmp(&b.watcher_id));
+        watchers
+    }
 }
 
 /// Start watching a directory for file changes.
-/// Emits "file-changed" events to the frontend.
+/// Emits "file-changed" events to the frontend, at most one per path per
+/// `debounce_ms` window (default 300ms). Paths matching `ignore_globs`
+/// (default `node_modules`, `.git`, `dist`) are skipped. Returns the id to
+/// pass to `stop_watching`.
 #[tauri::command]
 pub async fn start_watching(
     path: String,
     recursive: bool,
+    debounce_ms: Option<u64>,
+    ignore_globs: Option<Vec<String>>,
+    app: AppHandle,
+    registry: State<'_, WatcherRegistry>,
+) -> Result<String, String> {
+    spawn_watcher(vec![path], recursive, debounce_ms, ignore_globs, app, &registry)
+}
+
+/// Watch several directories under one watcher id, e.g. the global framework
+/// and a project's `.aidd`. Behaves like `start_watching`; each
+/// "file-changed" event's `root` names the directory it came from.
+#[tauri::command]
+pub async fn start_watching_many(
+    paths: Vec<String>,
+    recursive: bool,
+    debounce_ms: Option<u64>,
+    ignore_globs: Option<Vec<String>>,
     app: AppHandle,
+    registry: State<'_, WatcherRegistry>,
+) -> Result<String, String> {
+    if paths.is_empty() {
+        return Err("No paths to watch".to_string());
+    }
+    spawn_watcher(paths, recursive, debounce_ms, ignore_globs, app, &registry)
+}
+
+fn spawn_watcher(
+    roots: Vec<String>,
+    recursive: bool,
+    debounce_ms: Option<u64>,
+    ignore_globs: Option<Vec<String>>,
+    app: AppHandle,
+    registry: &WatcherRegistry,
 ) -> Result<String, String> {
-    let watch_path = path.clone();
     let watcher_id = format!("watcher-{}", uuid_simple());
+    let window = Duration::from_millis(debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS));
+    let ignore_globs = ignore_globs
+        .unwrap_or_else(|| DEFAULT_IGNORE_GLOBS.iter().map(|g| g.to_string()).collect());
+
+    let (watcher, tx, rx) = watch_roots(&roots, recursive)?;
+    let tagger = WatchRoots::new(&roots);
+    registry.insert(watcher_id.clone(), roots, tx);
+
+    std::thread::spawn(move || {
+        // The watcher lives as long as this thread; dropping it stops notify.
+        let _watcher = watcher;
+        run_watch_loop(&rx, window, &ignore_globs, &tagger, |change| {
+            let _ = app.emit("file-changed", change);
+        });
+    });
+
+    Ok(watcher_id)
+}
 
+/// One notify watcher over every root, feeding a single channel. The sender is
+/// returned so the registry can deliver the stop signal on the same channel.
+fn watch_roots(
+    roots: &[String],
+    recursive: bool,
+) -> Result<(RecommendedWatcher, mpsc::Sender<WatchMessage>, mpsc::Receiver<WatchMessage>), String> {
     let mode = if recursive {
         RecursiveMode::Recursive
     } else {
         RecursiveMode::NonRecursive
     };
 
-    std::thread::spawn(move || {
-        let (tx, rx) = mpsc::channel::<Result<Event, notify::Error>>();
+    let (tx, rx) = mpsc::channel::<WatchMessage>();
+    let event_tx = tx.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |event| {
+            let _ = event_tx.send(WatchMessage::Event(event));
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+    for root in roots {
+        watcher
+            .watch(Path::new(root), mode)
+            .map_err(|e| format!("Failed to start watching {}: {}", root, e))?;
+    }
 
-        let mut watcher = RecommendedWatcher::new(tx, Config::default())
-            .expect("Failed to create file watcher");
+    Ok((watcher, tx, rx))
+}
 
-        watcher
-            .watch(std::path::Path::new(&watch_path), mode)
-            .expect("Failed to start watching");
-
-        // Keep watcher alive and relay events
-        loop {
-            match rx.recv() {
-                Ok(Ok(event)) => {
-                    let event_type = match event.kind {
-                        notify::EventKind::Create(_) => "created",
-                        notify::EventKind::Modify(_) => "modified",
-                        notify::EventKind::Remove(_) => "deleted",
-                        _ => continue,
-                    };
-
-                    let paths: Vec<String> = event
-                        .paths
-                        .iter()
-                        .map(|p| p.to_string_lossy().to_string())
-                        .collect();
-
-                    let _ = app.emit(
-                        "file-changed",
-                        FileChangeEvent {
-                            event_type: event_type.to_string(),
-                            paths,
-                        },
-                    );
+/// Roots of one watcher, each with its canonical form too, since notify may
+/// report resolved paths (e.g. `/private/var` for `/var` on macOS).
+#[derive(Default)]
+struct WatchRoots {
+    roots: Vec<(String, Vec<PathBuf>)>,
+}
+
+impl WatchRoots {
+    fn new(roots: &[String]) -> Self {
+        let roots = roots
+            .iter()
+            .map(|root| {
+                let given = PathBuf::from(root);
+                let mut forms = vec![given.clone()];
+                if let Ok(canonical) = given.canonicalize() {
+                    if canonical != given {
+                        forms.push(canonical);
+                    }
                 }
-                Ok(Err(_)) => continue,
-                Err(_) => break,
+                (root.clone(), forms)
+            })
+            .collect();
+        Self { roots }
+    }
+
+    /// The deepest root containing `path`, so nested roots claim their own changes.
+    fn root_of(&self, path: &str) -> Option<String> {
+        self.deepest_form(Path::new(path)).map(|(root, _)| root.clone())
+    }
+
+    /// `path` relative to its root, `/`-separated, for matching ignore globs;
+    /// paths outside every root are returned whole.
+    fn relative_path(&self, path: &str) -> String {
+        let full = Path::new(path);
+        let relative = self
+            .deepest_form(full)
+            .and_then(|(_, form)| full.strip_prefix(form).ok())
+            .unwrap_or(full);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// The root containing `path` and the form of it that matched, deepest first.
+    fn deepest_form(&self, path: &Path) -> Option<(&String, &PathBuf)> {
+        self.roots
+            .iter()
+            .flat_map(|(root, forms)| forms.iter().map(move |form| (root, form)))
+            .filter(|(_, form)| path.starts_with(form))
+            .max_by_key(|(_, form)| form.components().count())
+    }
+}
+
+/// Collect events and emit them once per debounce window until stopped.
+fn run_watch_loop(
+    rx: &mpsc::Receiver<WatchMessage>,
+    window: Duration,
+    ignore_globs: &[String],
+    roots: &WatchRoots,
+    mut emit: impl FnMut(FileChangeEvent),
+) {
+    let mut batch = EventBatch::default();
+    let mut flush_at: Option<Instant> = None;
+    loop {
+        let received = match flush_at {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(WatchMessage::Event(Ok(event))) => {
+                let event_type = match event.kind {
+                    notify::EventKind::Create(_) => "created",
+                    notify::EventKind::Modify(_) => "modified",
+                    notify::EventKind::Remove(_) => "deleted",
+                    _ => continue,
+                };
+
+                for changed in &event.paths {
+                    let changed = changed.to_string_lossy();
+                    // Match below the root, so a root inside e.g. `build/` isn't ignored wholesale
+                    if !is_ignored(&roots.relative_path(&changed), ignore_globs) {
+                        batch.record(&changed, event_type);
+                    }
+                }
+                if flush_at.is_none() && !batch.is_empty() {
+                    flush_at = Some(Instant::now() + window);
+                }
+            }
+            Ok(WatchMessage::Event(Err(_))) => continue,
+            Ok(WatchMessage::Stop) | Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                for change in batch.drain(roots) {
+                    emit(change);
+                }
+                flush_at = None;
             }
         }
-    });
+    }
+}
 
-    Ok(watcher_id)
+/// Changes collected during one debounce window, one entry per path.
+#[derive(Default)]
+struct EventBatch {
+    changes: BTreeMap<String, &'static str>,
+}
+
+impl EventBatch {
+    /// Merge an event into the path's pending change. A create or delete
+    /// outranks a modify; between create and delete the later one wins, so an
+    /// atomic save (delete + create) still reads as the file existing.
+    fn record(&mut self, path: &str, event_type: &'static str) {
+        self.changes
+            .entry(path.to_string())
+            .and_modify(|current| {
+                if event_type != "modified" || *current == "modified" {
+                    *current = event_type;
+                }
+            })
+            .or_insert(event_type);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn drain(&mut self, roots: &WatchRoots) -> Vec<FileChangeEvent> {
+        std::mem::take(&mut self.changes)
+            .into_iter()
+            .map(|(path, event_type)| FileChangeEvent {
+                event_type: event_type.to_string(),
+                root: roots.root_of(&path),
+                paths: vec![path],
+            })
+            .collect()
+    }
+}
+
+/// Whether `path` (`/`-separated) matches any ignore glob. A glob without `/`
+/// is tested against each path component; one with `/` against the whole path.
+fn is_ignored(path: &str, globs: &[String]) -> bool {
+    globs.iter().any(|glob| {
+        if glob.contains('/') {
+            glob_match(glob.as_bytes(), path.as_bytes())
+        } else {
+            path.split('/').any(|part| glob_match(glob.as_bytes(), part.as_bytes()))
+        }
+    })
+}
+
+/// Minimal glob matcher: `?` is one non-`/` char, `*` any run of non-`/`
+/// chars, `**` any run including `/`.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            let rest = rest.strip_prefix(b"/").unwrap_or(rest);
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => matches!(text, [c, tail @ ..] if *c != b'/' && glob_match(rest, tail)),
+        [c, rest @ ..] => matches!(text, [t, tail @ ..] if t == c && glob_match(rest, tail)),
+    }
+}
+
+/// Stop a watcher started by `start_watching`.
+#[tauri::command]
+pub async fn stop_watching(watcher_id: String, registry: State<'_, WatcherRegistry>) -> Result<(), String> {
+    if registry.stop(&watcher_id) {
+        Ok(())
+    } else {
+        Err(format!("Watcher not found: {}", watcher_id))
+    }
 }
 
-/// Stop watching (placeholder — full implementation needs watcher handle storage).
+/// List active watchers and the paths they watch.
 #[tauri::command]
-pub async fn stop_watching(_watcher_id: String) -> Result<(), String> {
-    Ok(())
+pub async fn list_watchers(registry: State<'_, WatcherRegistry>) -> Result<Vec<WatcherInfo>, String> {
+    Ok(registry.list())
 }
 
 fn uuid_simple() -> String {
@@ -84,3 +363,131 @@ fn uuid_simple() -> String {
         .as_nanos();
     format!("{:x}", ts)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_coalesces_events_per_path() {
+        let mut batch = EventBatch::default();
+        batch.record("/p/a.md", "modified");
+        batch.record("/p/a.md", "created");
+        batch.record("/p/a.md", "modified");
+        batch.record("/p/b.md", "deleted");
+        batch.record("/p/b.md", "created");
+
+        let events = batch.drain(&WatchRoots::new(&["/p".to_string()]));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].root.as_deref(), Some("/p"));
+        assert_eq!((events[0].paths[0].as_str(), events[0].event_type.as_str()), ("/p/a.md", "created"));
+        assert_eq!((events[1].paths[0].as_str(), events[1].event_type.as_str()), ("/p/b.md", "created"));
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn ignore_globs_match_components_and_paths() {
+        let globs: Vec<String> = DEFAULT_IGNORE_GLOBS.iter().map(|g| g.to_string()).collect();
+        assert!(is_ignored("/repo/node_modules/x/index.js", &globs));
+        assert!(is_ignored("/repo/.git/HEAD", &globs));
+        assert!(!is_ignored("/repo/content/distribution.md", &globs));
+
+        let custom = vec!["*.tmp".to_string(), "**/build/**".to_string()];
+        assert!(is_ignored("/repo/notes.tmp", &custom));
+        assert!(is_ignored("/repo/apps/build/out.js", &custom));
+        assert!(!is_ignored("/repo/apps/builder.js", &custom));
+    }
+
+    #[test]
+    fn ignore_globs_only_match_below_the_watched_root() {
+        let root = "/home/dev/build/myproj";
+        let (tx, rx) = mpsc::channel();
+        let (emitted_tx, emitted) = mpsc::channel();
+        let watch_thread = std::thread::spawn(move || {
+            let globs = vec!["build".to_string(), "node_modules".to_string()];
+            run_watch_loop(&rx, Duration::from_millis(10), &globs, &WatchRoots::new(&[root.to_string()]), |change| {
+                let _ = emitted_tx.send(change);
+            });
+        });
+
+        let created = |path: &str| {
+            let kind = notify::EventKind::Create(notify::event::CreateKind::File);
+            WatchMessage::Event(Ok(Event::new(kind).add_path(PathBuf::from(path))))
+        };
+        tx.send(created("/home/dev/build/myproj/node_modules/x/index.js")).unwrap();
+        tx.send(created("/home/dev/build/myproj/src/main.rs")).unwrap();
+
+        let change = emitted.recv_timeout(Duration::from_secs(5)).expect("expected the change under src/");
+        assert_eq!(change.paths, ["/home/dev/build/myproj/src/main.rs"]);
+        assert!(emitted.recv_timeout(Duration::from_millis(100)).is_err());
+
+        tx.send(WatchMessage::Stop).unwrap();
+        watch_thread.join().unwrap();
+    }
+
+    #[test]
+    fn stopping_one_watcher_leaves_the_other_running() {
+        let registry = WatcherRegistry::default();
+        let mut threads = vec![];
+        for (id, path) in [("watcher-a", "/repo/a"), ("watcher-b", "/repo/b")] {
+            let (tx, rx) = mpsc::channel();
+            registry.insert(id.to_string(), vec![path.to_string()], tx);
+            threads.push(std::thread::spawn(move || {
+                run_watch_loop(&rx, Duration::from_millis(10), &[], &WatchRoots::default(), |_| {});
+            }));
+        }
+
+        assert!(registry.stop("watcher-a"));
+        assert!(!registry.stop("watcher-a"));
+        threads.remove(0).join().unwrap();
+
+        let active = registry.list();
+        assert_eq!(active.len(), 1);
+        assert_eq!((active[0].watcher_id.as_str(), active[0].path.as_str()), ("watcher-b", "/repo/b"));
+        assert!(!threads[0].is_finished());
+
+        assert!(registry.stop("watcher-b"));
+        threads.remove(0).join().unwrap();
+    }
+
+    #[test]
+    fn one_watcher_tags_changes_with_their_root() {
+        let base = std::env::temp_dir().join(format!("aidd-watch-many-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let (framework, project) = (base.join("framework"), base.join("project").join(".aidd"));
+        std::fs::create_dir_all(&framework).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+        let roots = vec![framework.to_string_lossy().to_string(), project.to_string_lossy().to_string()];
+
+        let (watcher, tx, rx) = watch_roots(&roots, true).unwrap();
+        let tagger = WatchRoots::new(&roots);
+        let (emitted_tx, emitted) = mpsc::channel();
+        let watch_thread = std::thread::spawn(move || {
+            let _watcher = watcher;
+            run_watch_loop(&rx, Duration::from_millis(50), &[], &tagger, |change| {
+                let _ = emitted_tx.send(change);
+            });
+        });
+
+        std::fs::write(framework.join("rule.md"), "# Rule\n").unwrap();
+        std::fs::write(project.join("override.md"), "# Override\n").unwrap();
+
+        let mut tagged = BTreeMap::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while tagged.len() < 2 {
+            let change = emitted
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .expect("expected change events from both roots");
+            for path in &change.paths {
+                let name = Path::new(path).file_name().unwrap().to_string_lossy().to_string();
+                tagged.insert(name, change.root.clone());
+            }
+        }
+        assert_eq!(tagged["rule.md"].as_deref(), Some(roots[0].as_str()));
+        assert_eq!(tagged["override.md"].as_deref(), Some(roots[1].as_str()));
+
+        let _ = tx.send(WatchMessage::Stop);
+        watch_thread.join().unwrap();
+        let _ = std::fs::remove_dir_all(&base);
+    }
+}
@@ -51,7 +51,7 @@ function createEmptyCounts(): Record<FrameworkCategory, CategoryCount> {
 async function countMarkdownFiles(path: string | null): Promise<number> {
   if (!path) return 0;
   try {
-    const entries = await listDirectory(path, ['md'], true);
+    const { entries } = await listDirectory(path, ['md'], true);
     return entries.filter((entry) => !entry.is_dir).length;
   } catch {
     return 0;
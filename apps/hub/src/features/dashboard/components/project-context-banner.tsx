@@ -55,7 +55,7 @@ export function ProjectContextBanner() {
       <div className="flex items-center gap-2">
         <div className="flex flex-wrap gap-1.5">
           {MARKER_KEYS.map((m) => {
-            const active = activeProject.markers[m.key as keyof typeof activeProject.markers];
+            const active = activeProject.markers[m.key];
             return (
               <Chip
                 key={m.key}
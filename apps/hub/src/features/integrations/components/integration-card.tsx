@@ -32,6 +32,16 @@ const TOOL_META = {
     descriptionKey: 'page.integrations.tool.windsurfDesc',
     hasMcp: true,
   },
+  cline: {
+    nameKey: 'page.integrations.tool.cline',
+    descriptionKey: 'page.integrations.tool.clineDesc',
+    hasMcp: true,
+  },
+  continue: {
+    nameKey: 'page.integrations.tool.continue',
+    descriptionKey: 'page.integrations.tool.continueDesc',
+    hasMcp: true,
+  },
 } as const satisfies Record<IntegrationTool, { nameKey: string; descriptionKey: string; hasMcp: boolean }>;
 
 interface IntegrationCardProps {
@@ -1,6 +1,6 @@
 import { Card } from '@/components/ui/card';
 import { Chip } from '@/components/ui/chip';
-import { Bot, MousePointer2, Code, Sparkles, Wind } from 'lucide-react';
+import { Bot, MousePointer2, Code, Sparkles, Wind, Terminal, FastForward } from 'lucide-react';
 import type { DiscoveredMcp, McpToolSource, McpServer } from '../../../lib/tauri';
 import { truncate } from '../../../lib/utils';
 
@@ -10,6 +10,8 @@ const TOOL_CONFIG: Record<McpToolSource, { label: string; icon: typeof Bot }> =
   vscode: { label: 'VS Code', icon: Code },
   gemini: { label: 'Gemini', icon: Sparkles },
   windsurf: { label: 'Windsurf', icon: Wind },
+  cline: { label: 'Cline', icon: Terminal },
+  continue: { label: 'Continue', icon: FastForward },
 };
 
 const STATUS_COLOR = {
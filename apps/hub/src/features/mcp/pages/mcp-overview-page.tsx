@@ -17,6 +17,8 @@ import {
   Code,
   Sparkles,
   Wind,
+  Terminal,
+  FastForward,
   StopCircle,
 } from "lucide-react";
 import { PageHeader } from "../../../components/layout/page-header";
@@ -42,6 +44,8 @@ const TOOL_META: Record<McpToolSource, { label: string; icon: typeof Bot }> = {
   vscode: { label: "VS Code", icon: Code },
   gemini: { label: "Gemini", icon: Sparkles },
   windsurf: { label: "Windsurf", icon: Wind },
+  cline: { label: "Cline", icon: Terminal },
+  continue: { label: "Continue", icon: FastForward },
 };
 
 const TOOL_ORDER: McpToolSource[] = [
@@ -50,6 +54,8 @@ const TOOL_ORDER: McpToolSource[] = [
   "vscode",
   "gemini",
   "windsurf",
+  "cline",
+  "continue",
 ];
 
 /** Maps package dir to the server id used by the Rust backend. */
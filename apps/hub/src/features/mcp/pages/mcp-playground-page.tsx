@@ -17,7 +17,7 @@ import { PageHeader } from '../../../components/layout/page-header';
 import { ToolExplorer } from '../components/tool-explorer';
 import { ResourceDetail, PromptDetail } from '../components/tool-detail';
 import { getAllResources, getAllPrompts, getCatalogStats, getAllTools } from '../lib/mcp-catalog';
-import { callMcpTool, listMcpTools } from '../../../lib/tauri';
+import { callMcpTool, isMutationBlocked, listMcpTools } from '../../../lib/tauri';
 import type { McpToolInfo } from '../lib/mcp-catalog';
 
 export function McpPlaygroundPage() {
@@ -124,6 +124,11 @@ export function McpPlaygroundPage() {
       );
       setToolOutput(JSON.stringify(result, null, 2));
     } catch (error) {
+      if (isMutationBlocked(error)) {
+        setRunnerError(error.message);
+        setToolOutput(JSON.stringify(error, null, 2));
+        return;
+      }
       const message = error instanceof Error ? error.message : t('page.mcpPlayground.executionFailed');
       setRunnerError(message);
     } finally {
@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
+import type { Loaded } from '../../../lib/tauri';
 import type { SessionSummary, ObservationEntry, EvolutionStatus, PatternStats } from '../types';
 
 interface MemoryStoreState {
@@ -52,18 +53,31 @@ export const useMemoryStore = create<MemoryStoreState>((set, get) => ({
       // Call Tauri commands — collect errors instead of swallowing them
       const errors: string[] = [];
 
+      // A failed read still carries empty data, so keep it and record the error
+      const load = <T>(command: string, empty: T) =>
+        invoke<Loaded<T>>(command)
+          .then((loaded) => {
+            if (loaded.error) errors.push(loaded.error.message);
+            return loaded.data;
+          })
+          .catch((e) => {
+            errors.push(String(e));
+            return empty;
+          });
+
       const [sessionSummary, evolutionStatus, patternStats] = await Promise.all([
-        invoke<SessionSummary>('get_sessions').catch((e) => {
-          errors.push(String(e));
-          return { total: 0, active: 0, completed: 0, recent_sessions: [] } as SessionSummary;
-        }),
-        invoke<EvolutionStatus>('get_evolution_status').catch((e) => {
-          errors.push(String(e));
-          return { pending_count: 0, approved_count: 0, rejected_count: 0, auto_applied_count: 0 } as EvolutionStatus;
+        load<SessionSummary>('get_sessions', { total: 0, active: 0, completed: 0, recent_sessions: [] }),
+        load<EvolutionStatus>('get_evolution_status', {
+          pending_count: 0,
+          approved_count: 0,
+          rejected_count: 0,
+          auto_applied_count: 0,
         }),
-        invoke<PatternStats>('get_pattern_stats').catch((e) => {
-          errors.push(String(e));
-          return { total_patterns: 0, active_patterns: 0, total_detections: 0, false_positives: 0 } as PatternStats;
+        load<PatternStats>('get_pattern_stats', {
+          total_patterns: 0,
+          active_patterns: 0,
+          total_detections: 0,
+          false_positives: 0,
         }),
       ]);
 
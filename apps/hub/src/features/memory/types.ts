@@ -18,6 +18,9 @@ export interface ObservationEntry {
   title: string;
   type: string;
   created_at: string;
+  score?: number | null;
+  /** FTS excerpt with matches wrapped in `<mark>`/`</mark>`; absent for LIKE-based search. */
+  snippet?: string | null;
 }
 
 export interface EvolutionStatus {
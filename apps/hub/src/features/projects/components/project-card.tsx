@@ -47,7 +47,7 @@ export function ProjectCard({
       <CardContent>
         <div className="flex flex-wrap gap-1">
           {MARKER_KEYS.map(({ key, label }) => {
-            const active = project.markers[key as keyof ProjectInfo['markers']];
+            const active = project.markers[key];
             return (
               <Chip
                 key={key}
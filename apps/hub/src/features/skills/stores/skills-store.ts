@@ -11,7 +11,7 @@ export const useSkillsStore = createEntityStore<SkillEntity>({
   transform: () => null, // Not used — customFetch overrides
   customFetch: async (projectRoot: string) => {
     const basePath = contentDir(projectRoot, 'skills');
-    const entries = await listDirectory(basePath);
+    const { entries } = await listDirectory(basePath);
     const skills: SkillEntity[] = [];
 
     for (const entry of entries) {
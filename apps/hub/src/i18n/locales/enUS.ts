@@ -762,6 +762,10 @@ export default {
   'page.integrations.tool.geminiDesc': 'AGENTS.md + .gemini/settings.json',
   'page.integrations.tool.windsurf': 'Windsurf',
   'page.integrations.tool.windsurfDesc': 'Global MCP config + .windsurfrules + AGENTS.md',
+  'page.integrations.tool.cline': 'Cline',
+  'page.integrations.tool.clineDesc': 'Global MCP settings + .clinerules + AGENTS.md',
+  'page.integrations.tool.continue': 'Continue',
+  'page.integrations.tool.continueDesc': '~/.continue/config.json MCP + .continue/rules + AGENTS.md',
 
   'page.artifacts.title': 'Artifacts',
   'page.artifacts.description': 'Workflow-produced documents and plans',
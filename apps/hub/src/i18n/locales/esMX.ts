@@ -766,6 +766,10 @@ const esMX: DeepStringify<typeof enUS> = {
   'page.integrations.tool.geminiDesc': 'AGENTS.md + .gemini/settings.json',
   'page.integrations.tool.windsurf': 'Windsurf',
   'page.integrations.tool.windsurfDesc': 'MCP global + .windsurfrules + AGENTS.md',
+  'page.integrations.tool.cline': 'Cline',
+  'page.integrations.tool.clineDesc': 'Ajustes MCP globales + .clinerules + AGENTS.md',
+  'page.integrations.tool.continue': 'Continue',
+  'page.integrations.tool.continueDesc': 'MCP en ~/.continue/config.json + .continue/rules + AGENTS.md',
 
   'page.artifacts.title': 'Artefactos',
   'page.artifacts.description': 'Documentos y planes producidos por flujos de trabajo',
@@ -1,4 +1,5 @@
 import { normalizePath } from './utils';
+import type { ProjectInfo } from './tauri';
 
 // ---------------------------------------------------------------------------
 // AIDD Framework Constants — SSOT for Hub frontend
@@ -76,8 +77,8 @@ export const WATCHER_PREFIXES = [
   `/${AIDD_DIR}/data.db`,
 ] as const;
 
-/** Marker keys for project detection display */
-export const MARKER_KEYS: Array<{ key: string; label: string }> = [
+/** Marker keys for project detection display — the Rust `AiddMarkers` field names */
+export const MARKER_KEYS: Array<{ key: keyof ProjectInfo['markers']; label: string }> = [
   ...CONTENT_CATEGORIES.map((c) => ({
     key: c,
     label: c.charAt(0).toUpperCase() + c.slice(1),
@@ -26,11 +26,29 @@ export interface FileEntry {
   size: number;
 }
 
+export interface DirectoryListing {
+  entries: FileEntry[];
+  /** True when `maxDepth` or `maxEntries` stopped the scan early. */
+  truncated: boolean;
+}
+
+/** `respectGitignore` defaults to `recursive` on the Rust side. */
 export const listDirectory = (
   path: string,
   extensions?: string[],
   recursive = false,
-) => invoke<FileEntry[]>('list_directory', { path, extensions, recursive });
+  respectGitignore?: boolean,
+  maxDepth?: number,
+  maxEntries?: number,
+) =>
+  invoke<DirectoryListing>('list_directory', {
+    path,
+    extensions,
+    recursive,
+    respectGitignore,
+    maxDepth,
+    maxEntries,
+  });
 
 export interface MarkdownEntity {
   path: string;
@@ -40,10 +58,15 @@ export interface MarkdownEntity {
   last_modified: string;
 }
 
-export const listMarkdownEntities = (basePath: string, recursive = false) =>
+export const listMarkdownEntities = (
+  basePath: string,
+  recursive = false,
+  respectGitignore?: boolean,
+) =>
   invoke<MarkdownEntity[]>('list_markdown_entities', {
     basePath,
     recursive,
+    respectGitignore,
   });
 
 export const readJsonFile = (path: string) =>
@@ -52,6 +75,10 @@ export const readJsonFile = (path: string) =>
 export const writeJsonFile = (path: string, data: unknown) =>
   invoke<void>('write_json_file', { path, data });
 
+/** File content at a git revision (e.g. `HEAD~1`), for history/diff views. */
+export const readEntityAtRevision = (path: string, revision: string) =>
+  invoke<string>('read_entity_at_revision', { path, revision });
+
 // Project management
 export interface ProjectInfo {
   name: string;
@@ -68,12 +95,20 @@ export interface ProjectInfo {
     aidd_dir: boolean;
     memory: boolean;
   };
+  /** Nearest ancestor with `.aidd/` or `AGENTS.md` when the project has neither. */
+  aidd_root: string | null;
+  /** Workspace member directories (pnpm / package.json workspaces). */
+  workspaces: string[];
+  /** Where the markers were found: `path` itself, or its `ai/` subfolder. */
+  content_root: string;
 }
 
 export interface ProjectEntry {
   name: string;
   path: string;
   detected: boolean;
+  /** Unix ms of the last switch to this project; 0 if never opened. */
+  last_accessed: number;
 }
 
 export const detectProject = (path: string) =>
@@ -88,12 +123,56 @@ export const removeProject = (path: string) =>
 export const listProjects = () =>
   invoke<ProjectEntry[]>('list_projects');
 
+export const listProjectsByRecent = () =>
+  invoke<ProjectEntry[]>('list_projects_by_recent');
+
 export const getActiveProject = () =>
   invoke<string | null>('get_active_project');
 
 export const setActiveProject = (path: string) =>
   invoke<void>('set_active_project', { path });
 
+// Workspace mode: several active projects at once (primary first)
+export const listActiveProjects = () =>
+  invoke<string[]>('list_active_projects');
+
+export const addActiveProject = (path: string) =>
+  invoke<void>('add_active_project', { path });
+
+export const removeActiveProject = (path: string) =>
+  invoke<void>('remove_active_project', { path });
+
+/**
+ * What `detectProject` counts as an aidd project: `any_content_dir` (default) is any of
+ * agents/rules/skills; `require_aidd_dir` needs `.aidd/` plus a content dir;
+ * `require_agents_md` needs a root `AGENTS.md`.
+ */
+export type DetectionPolicy = 'any_content_dir' | 'require_aidd_dir' | 'require_agents_md';
+
+export const getDetectionPolicy = () =>
+  invoke<DetectionPolicy>('get_detection_policy');
+
+export const setDetectionPolicy = (policy: DetectionPolicy) =>
+  invoke<void>('set_detection_policy', { policy });
+
+export interface ProjectDiagnostics {
+  path: string;
+  name: string;
+  /** False when the registered directory no longer exists. */
+  exists: boolean;
+  detected: boolean;
+  markers: ProjectInfo['markers'] | null;
+  /** Empty for a healthy project. */
+  issues: string[];
+}
+
+export const diagnoseProject = (path: string) =>
+  invoke<ProjectDiagnostics>('diagnose_project', { path });
+
+/** Health roll-up across every registered project, in registration order. */
+export const verifyAllProjects = () =>
+  invoke<ProjectDiagnostics[]>('verify_all_projects');
+
 // Framework management
 export type FrameworkCategory = 'agents' | 'rules' | 'skills' | 'knowledge' | 'workflows' | 'templates' | 'specs';
 
@@ -113,8 +192,42 @@ export const getFrameworkPath = () =>
 export const getFrameworkVersion = () =>
   invoke<string | null>('get_framework_version');
 
-export const listFrameworkEntities = (category: FrameworkCategory, projectPath?: string) =>
-  invoke<FrameworkEntity[]>('list_framework_entities', { category, projectPath: projectPath ?? null });
+/** `filter` keeps entities whose frontmatter matches every pair (list values match any element). */
+export const listFrameworkEntities = (
+  category: FrameworkCategory,
+  projectPath?: string,
+  filter?: Record<string, string>,
+) => invoke<FrameworkEntity[]>('list_framework_entities', { category, projectPath: projectPath ?? null, filter: filter ?? null });
+
+export interface EntityMatch {
+  /** Frontmatter key for a frontmatter match; null for a body line. */
+  field: string | null;
+  /** 1-based line within the body; null for a frontmatter match. */
+  line: number | null;
+  snippet: string;
+}
+
+export interface EntitySearchHit {
+  name: string;
+  category: string;
+  path: string;
+  source: string;
+  frontmatter: Record<string, string>;
+  matches: EntityMatch[];
+}
+
+export interface EntitySearchResult {
+  hits: EntitySearchHit[];
+  /** True when more entities matched than the hit cap. */
+  truncated: boolean;
+}
+
+export const searchFrameworkEntities = (query: string, categories?: FrameworkCategory[], projectPath?: string) =>
+  invoke<EntitySearchResult>('search_framework_entities', {
+    query,
+    categories: categories ?? null,
+    projectPath: projectPath ?? null,
+  });
 
 export const readFrameworkEntity = (category: FrameworkCategory, name: string) =>
   invoke<FrameworkEntity>('read_framework_entity', { category, name });
@@ -122,8 +235,35 @@ export const readFrameworkEntity = (category: FrameworkCategory, name: string) =
 export const writeFrameworkEntity = (category: FrameworkCategory, name: string, content: string) =>
   invoke<void>('write_framework_entity', { category, name, content });
 
-export const deleteFrameworkEntity = (category: FrameworkCategory, name: string) =>
-  invoke<void>('delete_framework_entity', { category, name });
+/**
+ * Write unless the file changed since it was loaded. Rejects with
+ * `{ code: 'conflict', message, current_content }` on a conflict.
+ */
+export const writeFrameworkEntityIfUnchanged = (
+  category: FrameworkCategory,
+  name: string,
+  content: string,
+  expectedLastModified: string | null,
+) => invoke<void>('write_framework_entity_if_unchanged', { category, name, content, expectedLastModified });
+
+/** Write `readFrameworkEntity`'s split form back; unchanged frontmatter lines are kept as-is. */
+export const writeFrameworkEntityParts = (
+  category: FrameworkCategory,
+  name: string,
+  frontmatter: Record<string, unknown>,
+  body: string,
+) => invoke<void>('write_framework_entity_parts', { category, name, frontmatter, body });
+
+/** Pass `projectPath` to delete a `source: 'project'` entity from that project. */
+export const deleteFrameworkEntity = (category: FrameworkCategory, name: string, projectPath?: string) =>
+  invoke<void>('delete_framework_entity', { category, name, projectPath: projectPath ?? null });
+
+export const duplicateFrameworkEntity = (
+  category: FrameworkCategory,
+  srcName: string,
+  dstName: string,
+  overwrite?: boolean,
+) => invoke<void>('duplicate_framework_entity', { category, srcName, dstName, overwrite: overwrite ?? null });
 
 // Framework sync
 export interface SyncInfo {
@@ -133,23 +273,94 @@ export interface SyncInfo {
   auto_sync: boolean;
   last_check: string | null;
   changelog: string | null;
+  /** Files the last sync moved to `framework.orphaned/` (only with `prune`). */
+  pruned: string[];
 }
 
 export const getSyncStatus = () =>
   invoke<SyncInfo>('get_sync_status');
 
+/** Why an update check failed; `offline`, `timeout`, `rate_limited` and `server_error` are worth retrying later. */
+export type UpdateCheckErrorCode =
+  | 'offline'
+  | 'timeout'
+  | 'rate_limited'
+  | 'server_error'
+  | 'network'
+  | 'io'
+  | 'other';
+
+/** Rejects with `{ code: UpdateCheckErrorCode, message }`. */
 export const checkForUpdates = () =>
   invoke<SyncInfo>('check_for_updates');
 
-export const syncFramework = (version?: string) =>
-  invoke<SyncInfo>('sync_framework', { version: version ?? null });
+export interface ReleaseInfo {
+  tag: string;
+  /** Tag without the leading `v`; pass this to `syncFramework`. */
+  version: string;
+  published_at: string | null;
+  prerelease: boolean;
+  changelog: string | null;
+}
+
+export const listFrameworkVersions = (includePrereleases = false) =>
+  invoke<ReleaseInfo[]>('list_framework_versions', { includePrereleases });
+
+/** `prune` moves files removed upstream to `framework.orphaned/`. */
+export const syncFramework = (version?: string, prune = false) =>
+  invoke<SyncInfo>('sync_framework', { version: version ?? null, prune });
+
+export interface SyncProgress {
+  phase: 'download' | 'extract';
+  /** Bytes received, or archive entries processed while extracting. */
+  current: number;
+  /** Content-Length (null if unknown), or archive entry count while extracting. */
+  total: number | null;
+}
+
+/** Throttled progress emitted while `syncFramework` runs. */
+export const onSyncProgress = (
+  callback: (progress: SyncProgress) => void,
+): Promise<UnlistenFn> =>
+  listen<SyncProgress>('SYNC_PROGRESS', (e) => callback(e.payload));
+
+export interface SyncPreview {
+  target_version: string;
+  added: string[];
+  modified: string[];
+  /** Local files the release no longer ships; kept unless a `prune` sync moves the previously shipped ones to `framework.orphaned/`. */
+  removed: string[];
+}
+
+export const previewFrameworkSync = (version?: string) =>
+  invoke<SyncPreview>('preview_framework_sync', { version: version ?? null });
+
+export const rollbackFramework = () =>
+  invoke<SyncInfo>('rollback_framework');
 
 export const setAutoSync = (enabled: boolean) =>
   invoke<void>('set_auto_sync', { enabled });
 
+export const setAutoInstall = (enabled: boolean) =>
+  invoke<void>('set_auto_install', { enabled });
+
+/** Background update checks (default daily); findings arrive via `onUpdateAvailable`. */
+export const startAutoSyncScheduler = (intervalSecs?: number) =>
+  invoke<void>('start_auto_sync_scheduler', { intervalSecs: intervalSecs ?? null });
+
+export const stopAutoSyncScheduler = () =>
+  invoke<boolean>('stop_auto_sync_scheduler');
+
+export const onUpdateAvailable = (
+  callback: (info: SyncInfo) => void,
+): Promise<UnlistenFn> =>
+  listen<SyncInfo>('UPDATE_AVAILABLE', (e) => callback(e.payload));
+
 // Project overrides
 export interface AgentOverrides {
   disabled: string[];
+  /** Absent until the first override write; skills then fall back to `disabled`. */
+  disabled_skills?: string[];
 }
 
 export interface ProjectOverrides {
@@ -165,6 +376,8 @@ export interface EffectiveEntity {
   source: 'global' | 'override';
   enabled: boolean;
   content: string | null;
+  /** How a project override was applied; null for global-only entities. */
+  merge_mode: 'replace' | 'append' | 'prepend' | null;
 }
 
 export const getProjectOverrides = (projectPath: string) =>
@@ -173,6 +386,9 @@ export const getProjectOverrides = (projectPath: string) =>
 export const setAgentOverride = (projectPath: string, agent: string, enabled: boolean) =>
   invoke<void>('set_agent_override', { projectPath, agent, enabled });
 
+export const setSkillOverride = (projectPath: string, skill: string, enabled: boolean) =>
+  invoke<void>('set_skill_override', { projectPath, skill, enabled });
+
 export const addProjectRule = (projectPath: string, name: string, content: string) =>
   invoke<void>('add_project_rule', { projectPath, name, content });
 
@@ -185,8 +401,39 @@ export const listProjectRules = (projectPath: string) =>
 export const getEffectiveEntities = (projectPath: string, category: FrameworkCategory) =>
   invoke<EffectiveEntity[]>('get_effective_entities', { projectPath, category });
 
+export interface EntityDiff {
+  name: string;
+  category: string;
+  /** `added`: override only; `removed`: global only. */
+  status: 'modified' | 'unchanged' | 'added' | 'removed';
+  global_content: string | null;
+  override_content: string | null;
+  unified_diff: string;
+}
+
+export const diffOverride = (projectPath: string, category: FrameworkCategory, name: string) =>
+  invoke<EntityDiff>('diff_override', { projectPath, category, name });
+
+export interface CategoryBudget {
+  category: string;
+  entity_count: number;
+  bytes: number;
+  estimated_tokens: number;
+}
+
+/** Token figures are a bytes/4 estimate. */
+export interface BudgetReport {
+  project_path: string;
+  categories: CategoryBudget[];
+  total_bytes: number;
+  total_estimated_tokens: number;
+}
+
+export const frameworkContentBudget = (projectPath: string) =>
+  invoke<BudgetReport>('framework_content_budget', { projectPath });
+
 // Integration management
-export type IntegrationTool = 'claude_code' | 'cursor' | 'vscode' | 'gemini' | 'windsurf';
+export type IntegrationTool = 'claude_code' | 'cursor' | 'vscode' | 'gemini' | 'windsurf' | 'cline' | 'continue';
 
 export type IntegrationStatusValue = 'not_configured' | 'configured' | 'needs_update';
 
@@ -204,21 +451,91 @@ export interface IntegrationResult {
   messages: string[];
 }
 
-export const integrateTool = (projectPath: string, tool: IntegrationTool, devMode = false) =>
-  invoke<IntegrationResult>('integrate_tool', { projectPath, tool, devMode });
+export const integrateTool = (
+  projectPath: string,
+  tool: IntegrationTool,
+  devMode = false,
+  env?: Record<string, string>,
+) => invoke<IntegrationResult>('integrate_tool', { projectPath, tool, devMode, env });
 
 export const removeIntegration = (projectPath: string, tool: IntegrationTool) =>
   invoke<IntegrationResult>('remove_integration', { projectPath, tool });
 
+/** Recreate missing integration files and MCP entry; existing ones are left as is. */
+export const repairIntegration = (projectPath: string, tool: IntegrationTool) =>
+  invoke<IntegrationResult>('repair_integration', { projectPath, tool });
+
+export interface IntegrationRemovalFailure {
+  tool: IntegrationTool;
+  error: string;
+}
+
+export interface IntegrationRemovalSummary {
+  removed: IntegrationResult[];
+  /** Tools whose removal failed; `.aidd/` is never purged while any remain. */
+  failed: IntegrationRemovalFailure[];
+  purged: boolean;
+}
+
+/** Remove every tool's integration; `purge` also deletes the project's `.aidd/`. */
+export const removeAllIntegrations = (projectPath: string, purge?: boolean) =>
+  invoke<IntegrationRemovalSummary>('remove_all_integrations', { projectPath, purge: purge ?? null });
+
 export const checkIntegrations = (projectPath: string) =>
   invoke<IntegrationConfig[]>('check_integrations', { projectPath });
 
 export const listIntegrationTypes = () =>
   invoke<IntegrationTool[]>('list_integration_types');
 
+export interface IntegrationBundleEntry {
+  tool: IntegrationTool;
+  dev_mode: boolean;
+  /** Project root is written as `${projectPath}`. */
+  mcp_server: Record<string, unknown> | null;
+  files: string[];
+}
+
+export interface IntegrationBundle {
+  version: number;
+  integrations: IntegrationBundleEntry[];
+}
+
+export const exportIntegrations = (projectPath: string) =>
+  invoke<IntegrationBundle>('export_integrations', { projectPath });
+
+export const importIntegrations = (projectPath: string, bundle: IntegrationBundle) =>
+  invoke<IntegrationResult[]>('import_integrations', { projectPath, bundle });
+
+export interface AgentDefinition {
+  name: string;
+  /** Tagline after a dash in the heading. */
+  role: string | null;
+  purpose: string | null;
+  skills: string | null;
+  triggers: string[];
+  capabilities: string[];
+}
+
+export interface RoutingRule {
+  trigger: string;
+  agent: string;
+}
+
+export interface AgentsDocument {
+  title: string | null;
+  orchestrator: AgentDefinition | null;
+  agents: AgentDefinition[];
+  /** Explicit routing, or derived from agent triggers when the document has none. */
+  routing: RoutingRule[];
+}
+
+/** Structured agent roster and routing parsed from an AGENTS.md-style file. */
+export const parseAgentsMd = (path: string) =>
+  invoke<AgentsDocument>('parse_agents_md', { path });
+
 // MCP server management
 export type McpServerMode = 'tool_launched' | 'hub_hosted';
-export type McpServerStatus = 'stopped' | 'running' | 'error';
+export type McpServerStatus = 'stopped' | 'running' | 'unresponsive' | 'error';
 
 export interface McpServer {
   id: string;
@@ -228,20 +545,60 @@ export interface McpServer {
   pid: number | null;
   started_at: string | null;
   error: string | null;
+  restart_count: number;
+  last_exit_code: number | null;
 }
 
-export const startMcpServer = (pkg: string, mode: McpServerMode) =>
-  invoke<McpServer>('start_mcp_server', { package: pkg, mode });
+export type RestartPolicy =
+  | { kind: 'off' }
+  | { kind: 'on_failure'; max_retries: number; backoff_ms: number };
+
+export const setRestartPolicy = (serverId: string, policy: RestartPolicy) =>
+  invoke<void>('set_restart_policy', { serverId, policy });
 
-export const stopMcpServer = (serverId: string) =>
-  invoke<void>('stop_mcp_server', { serverId });
+/** Round-trip latency (ms) of an MCP ping; a miss marks the server `unresponsive`. */
+export const pingServer = (serverId: string) =>
+  invoke<number>('ping_server', { serverId });
+
+/** `env` extends the server's environment; `extraArgs` are appended to its command. */
+export const startMcpServer = (
+  pkg: string,
+  mode: McpServerMode,
+  env?: Record<string, string>,
+  extraArgs?: string[],
+) =>
+  invoke<McpServer>('start_mcp_server', { package: pkg, mode, env: env ?? null, extraArgs: extraArgs ?? null });
+
+/** `graceMs` is how long the server may take to exit before it is killed (default 3s, at most 30s). */
+export const stopMcpServer = (serverId: string, graceMs?: number) =>
+  invoke<void>('stop_mcp_server', { serverId, graceMs: graceMs ?? null });
 
 export const stopAllMcpServers = () =>
   invoke<void>('stop_all_mcp_servers');
 
+export interface McpResetSummary {
+  stopped_servers: string[];
+  clients_closed: number;
+  cancelled_requests: number;
+  health_cache_cleared: boolean;
+}
+
+/** Stop all Hub servers, close MCP clients (failing in-flight calls) and clear cached health. */
+export const resetMcpState = () =>
+  invoke<McpResetSummary>('reset_mcp_state');
+
 export const getMcpServers = () =>
   invoke<McpServer[]>('get_mcp_servers');
 
+export const getMcpServerLogs = (serverId: string) =>
+  invoke<string>('get_mcp_server_logs', { serverId });
+
+/** Payload of the `mcp-server-error` event. */
+export interface McpServerErrorEvent {
+  server_id: string;
+  line: string;
+}
+
 export interface McpRuntimeTool {
   name: string;
   description?: string;
@@ -252,6 +609,49 @@ export interface McpRuntimeTool {
 export const listMcpTools = (pkg = 'engine') =>
   invoke<McpRuntimeTool[]>('list_mcp_tools', { package: pkg });
 
+export interface McpConnectInfo {
+  package: string;
+  protocol_version: string | null;
+  server_info: Record<string, unknown> | null;
+  init_duration_ms: number;
+}
+
+export const getMcpConnectInfo = (pkg = 'engine') =>
+  invoke<McpConnectInfo>('get_mcp_connect_info', { package: pkg });
+
+export interface McpServerSpec {
+  command: string;
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string | null;
+}
+
+export interface McpSpecValidation {
+  ok: boolean;
+  resolved_command: string | null;
+  errors: string[];
+  warnings: string[];
+  handshake: McpConnectInfo | null;
+}
+
+/** Check a custom MCP command before saving it; `probe` also runs the initialize handshake. */
+export const validateMcpSpec = (spec: McpServerSpec, probe = false) =>
+  invoke<McpSpecValidation>('validate_mcp_spec', { spec, probe });
+
+/** Rejection value of `callMcpTool` when a mutation guard refuses the call. */
+export interface MutationBlockedError {
+  code: 'mutation_blocked';
+  message: string;
+  guard: 'docsChecksum' | 'adr';
+  docsChecksumStatus: string | null;
+  adrFound: boolean | null;
+  toolsInvoked: { tool: string; ok: boolean; error?: string }[];
+}
+
+export const isMutationBlocked = (err: unknown): err is MutationBlockedError =>
+  typeof err === 'object' && err !== null && (err as { code?: unknown }).code === 'mutation_blocked';
+
+/** Rejects with a string, or a `MutationBlockedError` for guarded mutations. */
 export const callMcpTool = <T = unknown>(
   pkg: string,
   toolName: string,
@@ -264,8 +664,9 @@ export const callMcpTool = <T = unknown>(
   });
 
 // MCP health scanning
-export type McpToolSource = 'claude_code' | 'cursor' | 'vscode' | 'gemini' | 'windsurf';
+export type McpToolSource = 'claude_code' | 'cursor' | 'vscode' | 'gemini' | 'windsurf' | 'cline' | 'continue';
 export type McpConfigScope = 'global' | 'project';
+export type McpValidation = 'ok' | 'missing_command' | 'command_not_found' | 'invalid_url';
 
 export interface DiscoveredMcp {
   name: string;
@@ -277,6 +678,11 @@ export interface DiscoveredMcp {
   url: string | null;
   transport_type: string | null;
   is_aidd: boolean;
+  /** Written by the Hub (canonical name + launch command), not a user's own entry. */
+  installed_by_aidd: boolean;
+  /** Other tools configuring the same server (same url, or same command + args). */
+  also_in: McpToolSource[];
+  validation: McpValidation;
 }
 
 export interface McpHealthSummary {
@@ -287,57 +693,246 @@ export interface McpHealthSummary {
   hub_running: number;
   hub_stopped: number;
   hub_error: number;
+  hub_unresponsive: number;
+  invalid_count: number;
+}
+
+export interface McpNameConflict {
+  name: string;
+  entries: DiscoveredMcp[];
+  suggestion: string;
 }
 
 export interface McpHealthReport {
   discovered: DiscoveredMcp[];
   hub_servers: McpServer[];
   summary: McpHealthSummary;
+  conflicts: McpNameConflict[];
 }
 
 export const scanMcpHealth = (projectPath?: string) =>
   invoke<McpHealthReport>('scan_mcp_health', { projectPath: projectPath ?? null });
 
+/** Background rescans (default 30s); changes arrive via `onMcpHealthChanged`. */
+export const startMcpHealthMonitor = (intervalSecs?: number, projectPath?: string) =>
+  invoke<void>('start_mcp_health_monitor', {
+    intervalSecs: intervalSecs ?? null,
+    projectPath: projectPath ?? null,
+  });
+
+export const stopMcpHealthMonitor = () =>
+  invoke<boolean>('stop_mcp_health_monitor');
+
+export const onMcpHealthChanged = (
+  callback: (report: McpHealthReport) => void,
+): Promise<UnlistenFn> =>
+  listen<McpHealthReport>('MCP_HEALTH_CHANGED', (e) => callback(e.payload));
+
 // Memory data (detailed queries from SQLite)
-export const listAllSessions = (limit?: number) =>
-  invoke<unknown[]>('list_all_sessions', { limit: limit ?? null });
+/** A read that separates "failed to load" (`error` set, `data` empty) from "nothing here". */
+export interface Loaded<T> {
+  data: T;
+  error: { code: string; message: string } | null;
+}
+
+/** The data of a `Loaded` read, or its error thrown so callers' `catch` sees the failure. */
+export const unwrapLoaded = <T>(loaded: Loaded<T>): T => {
+  if (loaded.error) throw new Error(loaded.error.message);
+  return loaded.data;
+};
+
+export interface MemoryPage {
+  items: unknown[];
+  nextCursor: string | null;
+}
+
+// Read queries take an optional `projectPath` (any registered project; ones that
+// aren't active are browsed read-only); omitted, they target the primary active project.
 
-export const listEvolutionCandidates = () =>
-  invoke<unknown[]>('list_evolution_candidates');
+/** Inclusive epoch-ms bounds; leave either end undefined for an open range. */
+export interface TimeRange {
+  fromMs?: number;
+  toMs?: number;
+}
+
+export const listAllSessionsPage = (limit?: number, cursor?: string, projectPath?: string, range?: TimeRange) =>
+  invoke<Loaded<MemoryPage>>('list_all_sessions', {
+    limit: limit ?? null,
+    cursor: cursor ?? null,
+    projectPath: projectPath ?? null,
+    fromMs: range?.fromMs ?? null,
+    toMs: range?.toMs ?? null,
+  }).then(unwrapLoaded);
+
+export const listAllSessions = (limit?: number, projectPath?: string, range?: TimeRange) =>
+  listAllSessionsPage(limit, undefined, projectPath, range).then((page) => page.items);
+
+export const listEvolutionCandidates = (projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_evolution_candidates', { projectPath: projectPath ?? null }).then(unwrapLoaded);
+
+export const listEvolutionLog = (limit?: number, projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_evolution_log', { limit: limit ?? null, projectPath: projectPath ?? null }).then(unwrapLoaded);
+
+export interface PermanentMemoryPage {
+  items: unknown[];
+  /** Entries of this type across all pages. */
+  total: number;
+}
+
+export const listPermanentMemoryPage = (memoryType: string, limit?: number, offset?: number, projectPath?: string) =>
+  invoke<Loaded<PermanentMemoryPage>>('list_permanent_memory', {
+    memoryType,
+    limit: limit ?? null,
+    offset: offset ?? null,
+    projectPath: projectPath ?? null,
+  }).then(unwrapLoaded);
 
-export const listEvolutionLog = (limit?: number) =>
-  invoke<unknown[]>('list_evolution_log', { limit: limit ?? null });
+export const listPermanentMemory = (memoryType: string, projectPath?: string) =>
+  listPermanentMemoryPage(memoryType, undefined, undefined, projectPath).then((page) => page.items);
 
-export const listPermanentMemory = (memoryType: string) =>
-  invoke<unknown[]>('list_permanent_memory', { memoryType });
+export const searchPermanentMemory = (query: string, limit?: number, projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('search_permanent_memory', { query, limit: limit ?? null, projectPath: projectPath ?? null }).then(
+    unwrapLoaded,
+  );
 
 export const deletePermanentMemory = (memoryType: string, id: string) =>
   invoke<void>('delete_permanent_memory', { memoryType, id });
 
-export const listAllObservations = (limit?: number) =>
-  invoke<unknown[]>('list_all_observations', { limit: limit ?? null });
+export interface ActivityEntry {
+  kind: 'observation' | 'artifact' | 'evolution' | 'audit';
+  id: string;
+  title: string;
+  timestamp: number;
+}
+
+export const getActivityFeed = (limit?: number, projectPath?: string) =>
+  invoke<Loaded<ActivityEntry[]>>('get_activity_feed', { limit: limit ?? null, projectPath: projectPath ?? null }).then(
+    unwrapLoaded,
+  );
+
+export interface SessionStats {
+  total: number;
+  active: number;
+  completed: number;
+  /** Over ended sessions, in ms; null when none has ended. */
+  avg_duration_ms: number | null;
+  median_duration_ms: number | null;
+  total_discovery_tokens: number;
+  observations_by_type: Record<string, number>;
+}
+
+export const getSessionStats = (projectPath?: string) =>
+  invoke<Loaded<SessionStats>>('get_session_stats', { projectPath: projectPath ?? null });
+
+/** Backend answering memory reads: the project's SQLite file or an MCP engine. */
+export type MemorySource = 'sqlite' | 'engine';
+
+export const getMemorySource = (projectPath?: string) =>
+  invoke<MemorySource>('get_memory_source', { projectPath: projectPath ?? null });
+
+export interface DailyCount {
+  date: string;
+  count: number;
+}
+
+/** `sinceTs`/`untilTs` are epoch ms, both inclusive. */
+export const observationDailyCounts = (sinceTs?: number, untilTs?: number, projectPath?: string) =>
+  invoke<Loaded<DailyCount[]>>('observation_daily_counts', {
+    sinceTs: sinceTs ?? null,
+    untilTs: untilTs ?? null,
+    projectPath: projectPath ?? null,
+  }).then(unwrapLoaded);
+
+/** `types` lists only observations of those types; omit or pass `[]` for all. */
+export const listAllObservationsPage = (limit?: number, cursor?: string, projectPath?: string, types?: string[]) =>
+  invoke<Loaded<MemoryPage>>('list_all_observations', {
+    limit: limit ?? null,
+    cursor: cursor ?? null,
+    types: types ?? null,
+    projectPath: projectPath ?? null,
+  }).then(unwrapLoaded);
 
-export const listObservationsBySession = (sessionId: string, limit?: number) =>
-  invoke<unknown[]>('list_observations_by_session', { sessionId, limit: limit ?? null });
+export const listAllObservations = (limit?: number, projectPath?: string, types?: string[]) =>
+  listAllObservationsPage(limit, undefined, projectPath, types).then((page) => page.items);
 
-export const listDrafts = () =>
-  invoke<unknown[]>('list_drafts');
+/** `[type, count]` pairs, most frequent first. */
+export const getObservationTypeCounts = (projectPath?: string) =>
+  invoke<Loaded<Array<[string, number]>>>('get_observation_type_counts', { projectPath: projectPath ?? null });
 
-export const listArtifacts = (artifactType?: string, status?: string, limit?: number) =>
-  invoke<unknown[]>('list_artifacts', {
+export const listObservationsBySession = (sessionId: string, limit?: number, projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_observations_by_session', {
+    sessionId,
+    limit: limit ?? null,
+    projectPath: projectPath ?? null,
+  }).then(unwrapLoaded);
+
+export const listDrafts = (projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_drafts', { projectPath: projectPath ?? null }).then(unwrapLoaded);
+
+export const listArtifacts = (
+  artifactType?: string,
+  status?: string,
+  limit?: number,
+  projectPath?: string,
+  range?: TimeRange,
+) =>
+  invoke<Loaded<unknown[]>>('list_artifacts', {
     artifactType: artifactType ?? null,
     status: status ?? null,
     limit: limit ?? null,
-  });
+    projectPath: projectPath ?? null,
+    fromMs: range?.fromMs ?? null,
+    toMs: range?.toMs ?? null,
+  }).then(unwrapLoaded);
+
+export const listAuditScores = (limit?: number, projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_audit_scores', { limit: limit ?? null, projectPath: projectPath ?? null }).then(unwrapLoaded);
+
+export const listPatternDetections = (limit?: number, projectPath?: string) =>
+  invoke<Loaded<unknown[]>>('list_pattern_detections', { limit: limit ?? null, projectPath: projectPath ?? null }).then(
+    unwrapLoaded,
+  );
+
+/** Raw table rows keyed by column name, as stored in the project database. */
+export interface MemoryBundle {
+  schemaVersion: number;
+  exportedAt: string;
+  sessions: unknown[];
+  observations: unknown[];
+  evolutionCandidates: unknown[];
+  evolutionLog: unknown[];
+  permanentMemory: unknown[];
+  drafts: unknown[];
+  artifacts: unknown[];
+}
+
+/** Writes the bundle to `destination`; read it back as a `MemoryBundle` to import it. */
+export const exportMemoryBundle = (destination: string, projectPath?: string) =>
+  invoke<void>('export_memory_bundle', { destination, projectPath: projectPath ?? null });
 
-export const listAuditScores = (limit?: number) =>
-  invoke<unknown[]>('list_audit_scores', { limit: limit ?? null });
+/** `replace` wipes the memory tables first; `merge` skips rows whose id already exists. */
+export type ImportMode = 'replace' | 'merge';
+
+export interface ImportSummary {
+  imported: number;
+  skipped: number;
+}
+
+export const importMemoryBundle = (bundle: MemoryBundle, mode: ImportMode, projectPath?: string) =>
+  invoke<ImportSummary>('import_memory_bundle', { bundle, mode, projectPath: projectPath ?? null });
 
 export const getGovernanceConfig = () =>
   invoke<unknown>('get_governance_config');
 
+/** One governance setting changed by an upsert; `key` is a dotted path. */
+export interface ConfigChange {
+  key: string;
+  old: unknown;
+  new: unknown;
+}
+
 export const upsertGovernanceConfig = (config: unknown) =>
-  invoke<void>('upsert_governance_config', { config });
+  invoke<ConfigChange[]>('upsert_governance_config', { config });
 
 // Memory write operations
 export const createPermanentMemory = (memoryType: string, title: string, content: string) =>
@@ -390,6 +985,7 @@ export const createObservation = (
   filesRead?: string,
   filesModified?: string,
   discoveryTokens?: number,
+  createdAt?: string,
 ) =>
   invoke<string>('create_observation', {
     sessionId,
@@ -401,6 +997,7 @@ export const createObservation = (
     filesRead: filesRead ?? null,
     filesModified: filesModified ?? null,
     discoveryTokens: discoveryTokens ?? null,
+    createdAt: createdAt ?? null,
   });
 
 export const updateObservation = (
@@ -436,8 +1033,9 @@ export const createEvolutionCandidateEntry = (evoType: string, title: string, co
 export const updateEvolutionCandidateEntry = (id: string, evoType: string, title: string, confidence: number, data: string) =>
   invoke<void>('update_evolution_candidate_entry', { id, evoType, title, confidence, data });
 
-export const deleteEvolutionCandidate = (id: string) =>
-  invoke<void>('delete_evolution_candidate', { id });
+/** Also removes the candidate's evolution log entries unless `preserveLog`. */
+export const deleteEvolutionCandidate = (id: string, preserveLog = false) =>
+  invoke<void>('delete_evolution_candidate', { id, preserveLog });
 
 // Draft CRUD
 export const createDraft = (category: string, title: string, filename: string, content: string, confidence: number, source: string) =>
@@ -449,18 +1047,87 @@ export const updateDraft = (id: string, title: string, content: string, category
 export const deleteDraft = (id: string) =>
   invoke<void>('delete_draft', { id });
 
+export const deleteDraftsByStatus = (status: 'pending' | 'approved' | 'rejected') =>
+  invoke<number>('delete_drafts_by_status', { status });
+
+export type BannedPatternType = 'exact' | 'regex';
+
+export const createBannedPattern = (
+  category: string,
+  pattern: string,
+  patternType: BannedPatternType,
+  severity: string,
+  hint?: string,
+) => invoke<string>('create_banned_pattern', { category, pattern, patternType, severity, hint: hint ?? null });
+
+export const updateBannedPattern = (
+  id: string,
+  category: string,
+  pattern: string,
+  patternType: BannedPatternType,
+  severity: string,
+  hint: string | undefined,
+  active: boolean,
+) => invoke<void>('update_banned_pattern', { id, category, pattern, patternType, severity, hint: hint ?? null, active });
+
+export const markDetectionFalsePositive = (id: number) =>
+  invoke<void>('mark_detection_false_positive', { id });
+
+// Bulk operations — each resolves to the number of rows affected
+export const deleteDrafts = (ids: string[]) =>
+  invoke<number>('delete_drafts', { ids });
+
+export const rejectDrafts = (ids: string[], reason: string) =>
+  invoke<number>('reject_drafts', { ids, reason });
+
+export const archiveArtifacts = (ids: string[]) =>
+  invoke<number>('archive_artifacts', { ids });
+
 // File watcher
 export interface FileChangeEvent {
   event_type: 'created' | 'modified' | 'deleted';
   paths: string[];
+  /** Watched root the change happened under. */
+  root: string | null;
 }
 
-export const startWatching = (path: string, recursive = true) =>
-  invoke<string>('start_watching', { path, recursive });
+export const startWatching = (
+  path: string,
+  recursive = true,
+  options: { debounceMs?: number; ignoreGlobs?: string[] } = {},
+) =>
+  invoke<string>('start_watching', {
+    path,
+    recursive,
+    debounceMs: options.debounceMs ?? null,
+    ignoreGlobs: options.ignoreGlobs ?? null,
+  });
+
+/** One watcher id covering several roots; events carry the `root` they came from. */
+export const startWatchingMany = (
+  paths: string[],
+  recursive = true,
+  options: { debounceMs?: number; ignoreGlobs?: string[] } = {},
+) =>
+  invoke<string>('start_watching_many', {
+    paths,
+    recursive,
+    debounceMs: options.debounceMs ?? null,
+    ignoreGlobs: options.ignoreGlobs ?? null,
+  });
 
 export const stopWatching = (watcherId: string) =>
   invoke<void>('stop_watching', { watcherId });
 
+export interface WatcherInfo {
+  watcher_id: string;
+  path: string;
+  roots: string[];
+}
+
+export const listWatchers = () =>
+  invoke<WatcherInfo[]>('list_watchers');
+
 export const onFileChanged = (
   callback: (event: FileChangeEvent) => void,
 ): Promise<UnlistenFn> =>
@@ -222,6 +222,8 @@ export interface ArtifactEntry {
   date: string | number;
   createdAt: string | number;
   updatedAt: string | number;
+  /** Set when the stored `date` was in an unrecognized format; `date` then falls back to `createdAt`. */
+  dateWarning?: string;
 }
 
 // ---------------------------------------------------------------------------